@@ -0,0 +1,12 @@
+-- Add migration script here
+DROP INDEX tasks_tags_idx;
+DROP INDEX tasks_project_idx;
+
+ALTER TABLE tasks
+    ALTER COLUMN created_at SET DEFAULT (now() AT TIME ZONE 'Asia/Tokyo'),
+    ALTER COLUMN updated_at SET DEFAULT (now() AT TIME ZONE 'Asia/Tokyo');
+
+ALTER TABLE tasks
+    DROP COLUMN tags,
+    DROP COLUMN project,
+    DROP COLUMN due_at;
@@ -0,0 +1,13 @@
+-- Add migration script here
+ALTER TABLE tasks
+    ADD COLUMN due_at TIMESTAMP WITH TIME ZONE,
+    ADD COLUMN project TEXT,
+    ADD COLUMN tags TEXT[] NOT NULL DEFAULT '{}';
+
+-- セッションのタイムゾーンに依存せず現在時刻を保存する
+ALTER TABLE tasks
+    ALTER COLUMN created_at SET DEFAULT now(),
+    ALTER COLUMN updated_at SET DEFAULT now();
+
+CREATE INDEX tasks_project_idx ON tasks (project);
+CREATE INDEX tasks_tags_idx ON tasks USING GIN (tags);
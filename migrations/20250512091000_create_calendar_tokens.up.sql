@@ -0,0 +1,6 @@
+-- Add migration script here
+CREATE TABLE calendar_tokens (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid (),
+    token_hash TEXT NOT NULL UNIQUE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT now() NOT NULL
+);
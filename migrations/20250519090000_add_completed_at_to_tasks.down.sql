@@ -0,0 +1,3 @@
+-- Add migration script here
+DROP INDEX tasks_completed_at_idx;
+ALTER TABLE tasks DROP COLUMN completed_at;
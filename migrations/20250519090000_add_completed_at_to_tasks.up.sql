@@ -0,0 +1,7 @@
+-- Add migration script here
+ALTER TABLE tasks ADD COLUMN completed_at TIMESTAMP WITH TIME ZONE;
+
+-- 既存の完了済みタスクは最終更新日時を完了日時とみなす
+UPDATE tasks SET completed_at = updated_at WHERE completed;
+
+CREATE INDEX tasks_completed_at_idx ON tasks (completed_at);
@@ -0,0 +1,19 @@
+-- Add migration script here
+CREATE TABLE time_entries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid (),
+    task_id UUID NOT NULL REFERENCES tasks (id) ON DELETE CASCADE,
+    user_id UUID,
+    started_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    ended_at TIMESTAMP WITH TIME ZONE,
+    note TEXT,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT now() NOT NULL,
+    CHECK (ended_at IS NULL OR ended_at > started_at)
+);
+
+CREATE INDEX time_entries_task_id_idx ON time_entries (task_id);
+CREATE INDEX time_entries_started_at_idx ON time_entries (started_at);
+
+-- 計測中（ended_at が NULL）のタイマーはユーザーごとに1つまで
+CREATE UNIQUE INDEX time_entries_one_running_per_user_idx
+    ON time_entries (user_id) NULLS NOT DISTINCT
+    WHERE ended_at IS NULL;
@@ -0,0 +1,11 @@
+-- ローカルのユーザーアカウント
+CREATE TABLE users (
+    id UUID PRIMARY KEY,
+    email TEXT NOT NULL,
+    password_hash TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+-- メールアドレスは大文字・小文字を区別せずに一意にする
+CREATE UNIQUE INDEX users_email_key ON users (lower(email));
@@ -0,0 +1,3 @@
+ALTER TABLE calendar_tokens DROP COLUMN IF EXISTS user_id;
+DROP INDEX IF EXISTS tasks_owner_id_idx;
+ALTER TABLE tasks DROP COLUMN IF EXISTS owner_id;
@@ -0,0 +1,6 @@
+-- タスクの所有者（所有者のいない既存のタスクはどのユーザーからも見えない）
+ALTER TABLE tasks ADD COLUMN owner_id UUID REFERENCES users (id) ON DELETE CASCADE;
+CREATE INDEX tasks_owner_id_idx ON tasks (owner_id, id);
+
+-- カレンダーフィードはトークンを発行したユーザーのタスクを出力する
+ALTER TABLE calendar_tokens ADD COLUMN user_id UUID REFERENCES users (id) ON DELETE CASCADE;
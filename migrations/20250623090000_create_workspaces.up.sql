@@ -0,0 +1,31 @@
+-- ワークスペース内のロール
+CREATE TYPE workspace_role AS ENUM ('admin', 'member', 'viewer');
+
+CREATE TABLE workspaces (
+    id UUID PRIMARY KEY,
+    name TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE TABLE workspace_members (
+    workspace_id UUID NOT NULL REFERENCES workspaces (id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    role workspace_role NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (workspace_id, user_id)
+);
+
+CREATE INDEX workspace_members_user_id_idx ON workspace_members (user_id);
+
+-- 既存のユーザーには個人ワークスペース（id はユーザーIDと同じ）を作成し、管理者にする
+INSERT INTO workspaces (id, name) SELECT id, 'Personal' FROM users;
+INSERT INTO workspace_members (workspace_id, user_id, role) SELECT id, id, 'admin' FROM users;
+
+-- タスクはワークスペースに属する（既存のタスクは所有者の個人ワークスペースに移す）
+ALTER TABLE tasks ADD COLUMN workspace_id UUID REFERENCES workspaces (id) ON DELETE CASCADE;
+UPDATE tasks SET workspace_id = owner_id WHERE owner_id IS NOT NULL;
+CREATE INDEX tasks_workspace_id_idx ON tasks (workspace_id, id);
+
+-- カレンダーフィードはトークンを発行したワークスペースのタスクを出力する
+ALTER TABLE calendar_tokens ADD COLUMN workspace_id UUID REFERENCES workspaces (id) ON DELETE CASCADE;
+UPDATE calendar_tokens SET workspace_id = user_id WHERE user_id IS NOT NULL;
@@ -0,0 +1,13 @@
+DROP POLICY IF EXISTS time_entries_tenant_isolation ON time_entries;
+ALTER TABLE time_entries NO FORCE ROW LEVEL SECURITY;
+ALTER TABLE time_entries DISABLE ROW LEVEL SECURITY;
+
+DROP POLICY IF EXISTS tasks_tenant_isolation ON tasks;
+ALTER TABLE tasks NO FORCE ROW LEVEL SECURITY;
+ALTER TABLE tasks DISABLE ROW LEVEL SECURITY;
+
+REVOKE ALL ON tasks, time_entries FROM app_tenant;
+DROP ROLE IF EXISTS app_tenant;
+
+DROP INDEX IF EXISTS time_entries_workspace_id_idx;
+ALTER TABLE time_entries DROP COLUMN IF EXISTS workspace_id;
@@ -0,0 +1,33 @@
+-- 作業時間もワークスペースに属する（タスクのワークスペースを引き継ぐ）
+ALTER TABLE time_entries ADD COLUMN workspace_id UUID REFERENCES workspaces (id) ON DELETE CASCADE;
+UPDATE time_entries te SET workspace_id = t.workspace_id FROM tasks t WHERE t.id = te.task_id;
+CREATE INDEX time_entries_workspace_id_idx ON time_entries (workspace_id);
+
+-- テナントのトランザクションで切り替えるロール
+-- 行レベルセキュリティはスーパーユーザーには適用されないため、接続ユーザーに関係なくこのロールで実行する
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT FROM pg_roles WHERE rolname = 'app_tenant') THEN
+        CREATE ROLE app_tenant NOLOGIN;
+    END IF;
+END
+$$;
+GRANT app_tenant TO CURRENT_USER;
+GRANT SELECT, INSERT, UPDATE, DELETE ON tasks, time_entries TO app_tenant;
+
+-- app.workspace_id（トランザクションごとに設定する）のワークスペースの行だけを読み書きできる
+-- 設定されていなければどの行も見えない
+ALTER TABLE tasks ENABLE ROW LEVEL SECURITY;
+ALTER TABLE tasks FORCE ROW LEVEL SECURITY;
+CREATE POLICY tasks_tenant_isolation ON tasks
+    USING (workspace_id = NULLIF(current_setting('app.workspace_id', true), '')::uuid)
+    WITH CHECK (workspace_id = NULLIF(current_setting('app.workspace_id', true), '')::uuid);
+
+ALTER TABLE time_entries ENABLE ROW LEVEL SECURITY;
+ALTER TABLE time_entries FORCE ROW LEVEL SECURITY;
+CREATE POLICY time_entries_tenant_isolation ON time_entries
+    USING (workspace_id = NULLIF(current_setting('app.workspace_id', true), '')::uuid)
+    WITH CHECK (workspace_id = NULLIF(current_setting('app.workspace_id', true), '')::uuid);
+
+-- calendar_tokens はワークスペースが決まる前にトークンのハッシュで検索するため、
+-- users / refresh_tokens と同じく行レベルセキュリティの対象外にする
@@ -0,0 +1,19 @@
+-- APIキーで許可する操作
+CREATE TYPE api_key_scope AS ENUM ('tasks:read', 'tasks:write');
+
+-- マシン間連携用のAPIキー（平文は保存せず、prefix で検索して SHA-256 のハッシュで照合する）
+-- リクエストのワークスペースはキーから決まるため、行レベルセキュリティの対象外にする
+CREATE TABLE api_keys (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    workspace_id UUID NOT NULL REFERENCES workspaces (id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    prefix TEXT NOT NULL UNIQUE,
+    key_hash TEXT NOT NULL,
+    scopes api_key_scope[] NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    last_used_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX api_keys_user_id_idx ON api_keys (user_id);
@@ -0,0 +1,27 @@
+-- 共有された相手ができる操作
+CREATE TYPE grant_permission AS ENUM ('view', 'edit');
+
+-- タスクまたはプロジェクトの共有
+-- grantee_id があればユーザーへの共有、token_hash があれば共有リンク（平文のトークンは保存しない）
+-- 共有された側は自分のワークスペースの外から参照するため、行レベルセキュリティの対象外にする
+CREATE TABLE grants (
+    id UUID PRIMARY KEY,
+    workspace_id UUID NOT NULL REFERENCES workspaces (id) ON DELETE CASCADE,
+    task_id UUID REFERENCES tasks (id) ON DELETE CASCADE,
+    project TEXT,
+    grantee_id UUID REFERENCES users (id) ON DELETE CASCADE,
+    token_hash TEXT UNIQUE,
+    permission grant_permission NOT NULL,
+    expires_at TIMESTAMPTZ,
+    created_by UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT grants_target_check CHECK ((task_id IS NULL) <> (project IS NULL)),
+    CONSTRAINT grants_grantee_check CHECK ((grantee_id IS NULL) <> (token_hash IS NULL)),
+    -- 共有リンクは閲覧のみで、必ず期限を付ける
+    CONSTRAINT grants_link_check CHECK (
+        token_hash IS NULL OR (permission = 'view' AND expires_at IS NOT NULL)
+    )
+);
+
+CREATE INDEX grants_grantee_id_idx ON grants (grantee_id) WHERE grantee_id IS NOT NULL;
+CREATE INDEX grants_workspace_id_idx ON grants (workspace_id);
@@ -0,0 +1,20 @@
+-- OIDCプロバイダーのアカウントとローカルユーザーの紐付け（issuer と sub の組でアカウントを識別する）
+CREATE TABLE oidc_identities (
+    issuer TEXT NOT NULL,
+    subject TEXT NOT NULL,
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (issuer, subject)
+);
+
+CREATE INDEX oidc_identities_user_id_idx ON oidc_identities (user_id);
+
+-- 認可リクエストからコールバックまでの間だけ保持するログイン状態
+-- state は平文を保存せず、SHA-256 のハッシュで照合する
+CREATE TABLE oidc_login_states (
+    state_hash TEXT PRIMARY KEY,
+    nonce TEXT NOT NULL,
+    code_verifier TEXT NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
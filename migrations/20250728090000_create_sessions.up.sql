@@ -0,0 +1,14 @@
+-- ブラウザ向けのサーバー側セッション（トークンと CSRF トークンは平文を保存せず、SHA-256 のハッシュのみ保存する）
+CREATE TABLE sessions (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    token_hash TEXT NOT NULL UNIQUE,
+    csrf_token_hash TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    -- アイドルタイムアウトの判定に使う最終アクセス日時
+    last_seen_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    -- 絶対タイムアウト（アクセスがあっても延長しない）
+    expires_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX sessions_user_id_idx ON sessions (user_id);
@@ -0,0 +1,15 @@
+-- Idempotency-Key ヘッダー付きのリクエストと、その最初のレスポンス
+-- キーはユーザーごとに一意で、expires_at を過ぎたものは同じキーで新しいリクエストとして扱う
+CREATE TABLE idempotency_keys (
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    key TEXT NOT NULL,
+    -- メソッド・パス・ワークスペース・本文の SHA-256（同じキーで別のリクエストが送られたかの判定に使う）
+    request_hash TEXT NOT NULL,
+    -- 処理中は NULL
+    response_status SMALLINT,
+    response_headers JSONB,
+    response_body BYTEA,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (user_id, key)
+);
@@ -0,0 +1,4 @@
+-- 処理中のキーを確保した試行ごとの識別子
+-- 引き継いだ後に元の試行が complete / release しても、新しい試行の行を書き換えないようにする
+ALTER TABLE idempotency_keys ADD COLUMN attempt_id UUID NOT NULL DEFAULT gen_random_uuid();
+ALTER TABLE idempotency_keys ALTER COLUMN attempt_id DROP DEFAULT;
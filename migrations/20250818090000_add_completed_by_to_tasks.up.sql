@@ -0,0 +1,4 @@
+-- タスクを完了にしたユーザー（未完了に戻すと消去する）
+-- ユーザーを削除してもタスクは残すので、NULL にする
+-- 既存の完了済みタスクは誰が完了にしたか分からないため NULL のままにする
+ALTER TABLE tasks ADD COLUMN completed_by UUID REFERENCES users(id) ON DELETE SET NULL;
@@ -0,0 +1,23 @@
+DROP POLICY IF EXISTS sessions_tenant_isolation ON sessions;
+ALTER TABLE sessions NO FORCE ROW LEVEL SECURITY;
+ALTER TABLE sessions DISABLE ROW LEVEL SECURITY;
+
+DROP POLICY IF EXISTS workspace_members_tenant_isolation ON workspace_members;
+ALTER TABLE workspace_members NO FORCE ROW LEVEL SECURITY;
+ALTER TABLE workspace_members DISABLE ROW LEVEL SECURITY;
+
+DROP POLICY IF EXISTS calendar_tokens_tenant_isolation ON calendar_tokens;
+ALTER TABLE calendar_tokens NO FORCE ROW LEVEL SECURITY;
+ALTER TABLE calendar_tokens DISABLE ROW LEVEL SECURITY;
+
+DROP POLICY IF EXISTS api_keys_tenant_isolation ON api_keys;
+ALTER TABLE api_keys NO FORCE ROW LEVEL SECURITY;
+ALTER TABLE api_keys DISABLE ROW LEVEL SECURITY;
+
+DROP POLICY IF EXISTS grants_tenant_isolation ON grants;
+ALTER TABLE grants NO FORCE ROW LEVEL SECURITY;
+ALTER TABLE grants DISABLE ROW LEVEL SECURITY;
+
+REVOKE ALL ON grants, api_keys, calendar_tokens, sessions, workspace_members FROM app_tenant;
+
+ALTER TABLE time_entries ALTER COLUMN workspace_id DROP NOT NULL;
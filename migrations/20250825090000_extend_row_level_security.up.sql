@@ -0,0 +1,59 @@
+-- 行レベルセキュリティを tasks / time_entries 以外のワークスペースのデータにも広げる
+-- 20250630090000_enable_row_level_security と同じく、ロールの作成と付与には CREATE ROLE の権限が必要
+-- （serve --migrate-on-start で適用する場合は、アプリの接続ユーザーにその権限が必要になる）
+
+-- 作業時間は必ずワークスペースに属する
+-- ワークスペースのないタスクの作業時間は行レベルセキュリティでどこからも見えないため削除する
+UPDATE time_entries te SET workspace_id = t.workspace_id
+    FROM tasks t
+    WHERE t.id = te.task_id AND te.workspace_id IS NULL;
+DELETE FROM time_entries WHERE workspace_id IS NULL;
+ALTER TABLE time_entries ALTER COLUMN workspace_id SET NOT NULL;
+
+-- 以下のテーブルは、認証の前にトークンのハッシュや prefix で検索するため、接続ユーザーには全体を見せる
+-- テナントのトランザクション（app_tenant に切り替えたロール）だけを app.workspace_id のワークスペースの行に限る
+GRANT SELECT, INSERT, UPDATE, DELETE
+    ON grants, api_keys, calendar_tokens, sessions, workspace_members
+    TO app_tenant;
+
+ALTER TABLE grants ENABLE ROW LEVEL SECURITY;
+ALTER TABLE grants FORCE ROW LEVEL SECURITY;
+CREATE POLICY grants_tenant_isolation ON grants
+    USING (current_user <> 'app_tenant'
+        OR workspace_id = NULLIF(current_setting('app.workspace_id', true), '')::uuid)
+    WITH CHECK (current_user <> 'app_tenant'
+        OR workspace_id = NULLIF(current_setting('app.workspace_id', true), '')::uuid);
+
+ALTER TABLE api_keys ENABLE ROW LEVEL SECURITY;
+ALTER TABLE api_keys FORCE ROW LEVEL SECURITY;
+CREATE POLICY api_keys_tenant_isolation ON api_keys
+    USING (current_user <> 'app_tenant'
+        OR workspace_id = NULLIF(current_setting('app.workspace_id', true), '')::uuid)
+    WITH CHECK (current_user <> 'app_tenant'
+        OR workspace_id = NULLIF(current_setting('app.workspace_id', true), '')::uuid);
+
+ALTER TABLE calendar_tokens ENABLE ROW LEVEL SECURITY;
+ALTER TABLE calendar_tokens FORCE ROW LEVEL SECURITY;
+CREATE POLICY calendar_tokens_tenant_isolation ON calendar_tokens
+    USING (current_user <> 'app_tenant'
+        OR workspace_id = NULLIF(current_setting('app.workspace_id', true), '')::uuid)
+    WITH CHECK (current_user <> 'app_tenant'
+        OR workspace_id = NULLIF(current_setting('app.workspace_id', true), '')::uuid);
+
+ALTER TABLE workspace_members ENABLE ROW LEVEL SECURITY;
+ALTER TABLE workspace_members FORCE ROW LEVEL SECURITY;
+CREATE POLICY workspace_members_tenant_isolation ON workspace_members
+    USING (current_user <> 'app_tenant'
+        OR workspace_id = NULLIF(current_setting('app.workspace_id', true), '')::uuid)
+    WITH CHECK (current_user <> 'app_tenant'
+        OR workspace_id = NULLIF(current_setting('app.workspace_id', true), '')::uuid);
+
+-- セッションはワークスペースではなくユーザーに属する
+-- テナントのトランザクションは app.user_id を設定しないため、どのセッションも見えない
+ALTER TABLE sessions ENABLE ROW LEVEL SECURITY;
+ALTER TABLE sessions FORCE ROW LEVEL SECURITY;
+CREATE POLICY sessions_tenant_isolation ON sessions
+    USING (current_user <> 'app_tenant'
+        OR user_id = NULLIF(current_setting('app.user_id', true), '')::uuid)
+    WITH CHECK (current_user <> 'app_tenant'
+        OR user_id = NULLIF(current_setting('app.user_id', true), '')::uuid);
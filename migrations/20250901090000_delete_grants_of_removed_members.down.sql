@@ -0,0 +1,2 @@
+-- 削除した共有は元に戻せない
+SELECT 1;
@@ -0,0 +1,8 @@
+-- ワークスペースから外されたメンバーが作成した共有を削除する
+-- （以降はメンバーを外すときに同じトランザクションで削除する）
+DELETE FROM grants g
+WHERE NOT EXISTS (
+    SELECT 1
+    FROM workspace_members m
+    WHERE m.workspace_id = g.workspace_id AND m.user_id = g.created_by
+);
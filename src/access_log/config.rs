@@ -0,0 +1,37 @@
+use crate::config::settings::AccessLogSettings;
+use anyhow::Context;
+use axum::http::HeaderName;
+use std::collections::HashSet;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccessLogConfig {
+    pub headers: bool,
+    pub redact_headers: HashSet<HeaderName>,
+    // 小文字にして比べる
+    pub redact_query_params: HashSet<String>,
+    pub redact_body_fields: HashSet<String>,
+    pub body_sample_ratio: f64,
+    pub max_body_bytes: usize,
+}
+
+impl AccessLogConfig {
+    pub fn from_settings(settings: &AccessLogSettings) -> anyhow::Result<Self> {
+        let redact_headers = settings
+            .redact_headers
+            .iter()
+            .map(|name| {
+                HeaderName::from_bytes(name.as_bytes())
+                    .with_context(|| format!("invalid header name {:?}", name))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        let lowercase = |names: &[String]| names.iter().map(|name| name.to_lowercase()).collect();
+        Ok(Self {
+            headers: settings.headers,
+            redact_headers,
+            redact_query_params: lowercase(&settings.redact_query_params),
+            redact_body_fields: lowercase(&settings.redact_body_fields),
+            body_sample_ratio: settings.body_sample_ratio,
+            max_body_bytes: settings.max_body_bytes,
+        })
+    }
+}
@@ -0,0 +1,131 @@
+use crate::access_log::config::AccessLogConfig;
+use crate::access_log::redact::{redact_body, redact_headers, redact_query};
+use crate::error::AppError;
+use axum::{
+    body::{to_bytes, Body, HttpBody},
+    extract::{MatchedPath, Request, State},
+    http::{header, HeaderMap},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{info, warn};
+
+// アクセスログの target（RUST_LOG=access_log=off などで出力を切り替えられる）
+pub const ACCESS_LOG_TARGET: &str = "access_log";
+
+// 本文を出力するために読み込む本文の大きさの上限（大きさが分からない本文やこれより大きい本文は出力しない）
+const MAX_CAPTURED_BODY_SIZE: u64 = 1024 * 1024;
+
+// リクエストごとに、メソッド・ルート・ステータス・処理時間・本文の大きさを1行のログに出力する
+// ヘッダー・クエリパラメータ・本文の秘密の値は設定に従って伏せる
+// パスは /shared/:token のようにルートのテンプレートで出力する（パスに含まれる共有リンクのトークンなどを残さない）
+// ルートごとに出力するため、ルーティングの後に実行される layer で付ける
+pub async fn log_access(
+    State(config): State<Arc<AccessLogConfig>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let started = Instant::now();
+    let method = request.method().clone();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string());
+    // ルートがない（404 になる）リクエストはパスに値が埋め込まれていないため、そのまま出力する
+    let path = route.as_deref().unwrap_or(request.uri().path());
+    let uri = match request.uri().query() {
+        Some(query) => format!(
+            "{}?{}",
+            path,
+            redact_query(query, &config.redact_query_params)
+        ),
+        None => path.to_string(),
+    };
+    let request_bytes = request.body().size_hint().exact();
+    let request_headers = config
+        .headers
+        .then(|| redact_headers(request.headers(), &config.redact_headers));
+    let sample_body =
+        config.body_sample_ratio > 0.0 && rand::random::<f64>() < config.body_sample_ratio;
+
+    let (response, request_body) = if sample_body {
+        let (parts, body) = request.into_parts();
+        match capture_body(&parts.headers, body, &config).await {
+            Ok((body, captured)) => (next.run(Request::from_parts(parts, body)).await, captured),
+            // 接続が切れたなどで本文を読めなかった場合は、ハンドラーを呼ばずに 400 を返す
+            Err(e) => {
+                warn!("failed to read request body for access log: {}", e);
+                let error = AppError::BadRequest("Failed to read request body".to_string());
+                (error.into_response(), None)
+            }
+        }
+    } else {
+        (next.run(request).await, None)
+    };
+
+    let (response, response_body) = if sample_body {
+        let (parts, body) = response.into_parts();
+        match capture_body(&parts.headers, body, &config).await {
+            Ok((body, captured)) => (Response::from_parts(parts, body), captured),
+            // 読めた途中までの本文を元のステータスで返さず、サーバーエラーにする
+            Err(e) => {
+                warn!("failed to read response body for access log: {}", e);
+                (AppError::InternalError.into_response(), None)
+            }
+        }
+    } else {
+        (response, None)
+    };
+    let status = response.status().as_u16();
+    let response_bytes = response.body().size_hint().exact();
+    let response_headers = config
+        .headers
+        .then(|| redact_headers(response.headers(), &config.redact_headers));
+
+    info!(
+        target: ACCESS_LOG_TARGET,
+        method = %method,
+        route = route.as_deref(),
+        uri = %uri,
+        status,
+        latency_ms = started.elapsed().as_secs_f64() * 1000.0,
+        request_bytes,
+        response_bytes,
+        request_headers = request_headers.as_deref(),
+        response_headers = response_headers.as_deref(),
+        request_body = request_body.as_deref(),
+        response_body = response_body.as_deref(),
+        "request completed"
+    );
+    response
+}
+
+// 本文を読み込んで出力用の文字列にし、読み込んだ本文を同じ内容の本文に置き換える
+// 圧縮された本文（このミドルウェアは展開する layer より外側で動く）は読み込まずにそのまま渡す
+async fn capture_body(
+    headers: &HeaderMap,
+    body: Body,
+    config: &AccessLogConfig,
+) -> Result<(Body, Option<String>), axum::Error> {
+    if headers.contains_key(header::CONTENT_ENCODING)
+        || body
+            .size_hint()
+            .exact()
+            .is_none_or(|size| size > MAX_CAPTURED_BODY_SIZE)
+    {
+        return Ok((body, None));
+    }
+    let bytes = to_bytes(body, usize::MAX).await?;
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok());
+    let captured = redact_body(
+        content_type,
+        &bytes,
+        &config.redact_body_fields,
+        config.max_body_bytes,
+    );
+    Ok((Body::from(bytes), captured))
+}
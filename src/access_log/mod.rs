@@ -0,0 +1,5 @@
+pub mod config;
+pub mod middleware;
+pub mod redact;
+#[cfg(test)]
+pub mod tests;
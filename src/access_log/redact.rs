@@ -0,0 +1,99 @@
+use axum::http::{HeaderMap, HeaderName};
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+
+// 伏せた値の代わりに出力する文字列
+pub const REDACTED: &str = "[REDACTED]";
+
+// 本文を切り詰めたことを示す印
+const TRUNCATED: &str = "...(truncated)";
+
+// クエリ文字列の指定したパラメータの値を伏せる（パラメータの順序や形式はそのまま）
+pub fn redact_query(query: &str, names: &HashSet<String>) -> String {
+    query
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((name, _)) if names.contains(&name.to_lowercase()) => {
+                format!("{}={}", name, REDACTED)
+            }
+            _ => pair.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+// ヘッダーを JSON のオブジェクトにして出力する（同じ名前のヘッダーはカンマで繋げる）
+pub fn redact_headers(headers: &HeaderMap, names: &HashSet<HeaderName>) -> String {
+    let mut redacted = Map::new();
+    for name in headers.keys() {
+        let value = if names.contains(name) {
+            REDACTED.to_string()
+        } else {
+            headers
+                .get_all(name)
+                .iter()
+                .map(|value| value.to_str().unwrap_or("[non-ascii]"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        redacted.insert(name.to_string(), Value::String(value));
+    }
+    Value::Object(redacted).to_string()
+}
+
+// 本文を出力用の文字列にする（JSON・フォーム・テキスト以外は出力しない）
+// JSON のキーやフォームの項目が fields に含まれる場合は値を伏せ、max_bytes で切り詰める
+pub fn redact_body(
+    content_type: Option<&str>,
+    body: &[u8],
+    fields: &HashSet<String>,
+    max_bytes: usize,
+) -> Option<String> {
+    let content_type = content_type.unwrap_or_default().to_ascii_lowercase();
+    let text = if content_type.starts_with("application/json") {
+        match serde_json::from_slice::<Value>(body) {
+            Ok(mut json) => {
+                redact_json(&mut json, fields);
+                json.to_string()
+            }
+            Err(_) => String::from_utf8_lossy(body).into_owned(),
+        }
+    } else if content_type.starts_with("application/x-www-form-urlencoded") {
+        redact_query(&String::from_utf8_lossy(body), fields)
+    } else if content_type.starts_with("text/") {
+        String::from_utf8_lossy(body).into_owned()
+    } else {
+        return None;
+    };
+    Some(truncate(text, max_bytes))
+}
+
+fn redact_json(value: &mut Value, fields: &HashSet<String>) {
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object.iter_mut() {
+                if fields.contains(&key.to_lowercase()) {
+                    *value = Value::String(REDACTED.to_string());
+                } else {
+                    redact_json(value, fields);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| redact_json(item, fields)),
+        _ => {}
+    }
+}
+
+// 文字の途中で切らないよう、max_bytes 以下の文字の境界で切り詰める
+fn truncate(mut text: String, max_bytes: usize) -> String {
+    if text.len() <= max_bytes {
+        return text;
+    }
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    text.truncate(end);
+    text.push_str(TRUNCATED);
+    text
+}
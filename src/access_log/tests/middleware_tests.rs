@@ -0,0 +1,228 @@
+use crate::access_log::config::AccessLogConfig;
+use crate::access_log::middleware::{log_access, ACCESS_LOG_TARGET};
+use crate::access_log::redact::REDACTED;
+use crate::config::settings::AccessLogSettings;
+use crate::logger::tests::log_capture::LogCapture;
+use axum::{
+    body::{Body, Bytes, HttpBody},
+    http::{header, Request, StatusCode},
+    middleware,
+    routing::{get, post},
+    Json, Router,
+};
+use serde_json::{json, Value};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower::ServiceExt;
+
+fn router(settings: AccessLogSettings) -> Router {
+    let config = Arc::new(AccessLogConfig::from_settings(&settings).unwrap());
+    Router::new()
+        .route("/items/:id", get(|| async { "item" }))
+        .route("/shared/:token", get(|| async { "shared" }))
+        .route("/echo", post(|body: Bytes| async move { body }))
+        .route(
+            "/login",
+            post(|Json(body): Json<Value>| async move {
+                Json(json!({"email": body["email"], "token": "issued-token"}))
+            }),
+        )
+        .layer(middleware::from_fn_with_state(config, log_access))
+}
+
+// 大きさは分かるが、読み込みの途中で接続が切れる本文
+struct BrokenBody;
+
+impl HttpBody for BrokenBody {
+    type Data = Bytes;
+    type Error = std::io::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<http_body::Frame<Bytes>, Self::Error>>> {
+        Poll::Ready(Some(Err(std::io::Error::from(
+            std::io::ErrorKind::ConnectionReset,
+        ))))
+    }
+
+    fn size_hint(&self) -> http_body::SizeHint {
+        http_body::SizeHint::with_exact(16)
+    }
+}
+
+fn sampled() -> AccessLogSettings {
+    AccessLogSettings {
+        body_sample_ratio: 1.0,
+        ..AccessLogSettings::default()
+    }
+}
+
+// アクセスログの行（ほかのログは除く）
+fn access_logs(capture: &LogCapture) -> Vec<Value> {
+    capture
+        .lines()
+        .into_iter()
+        .filter(|line| line["target"] == ACCESS_LOG_TARGET)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_logs_request_summary() {
+        let capture = LogCapture::start();
+        let request = Request::get("/items/42?token=secret&from=2025-01-01")
+            .header(header::AUTHORIZATION, "Bearer secret")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = router(AccessLogSettings::default())
+            .oneshot(request)
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let logs = access_logs(&capture);
+        assert_eq!(logs.len(), 1);
+        let fields = &logs[0]["fields"];
+        assert_eq!(fields["method"], "GET");
+        assert_eq!(fields["route"], "/items/:id");
+        assert_eq!(
+            fields["uri"],
+            format!("/items/:id?token={}&from=2025-01-01", REDACTED)
+        );
+        assert_eq!(fields["status"], 200);
+        assert!(fields["latency_ms"].as_f64().unwrap() >= 0.0);
+        assert_eq!(fields["request_bytes"], 0);
+        assert_eq!(fields["response_bytes"], 4);
+        // 既定ではヘッダーと本文は出力しない
+        assert!(fields.get("request_headers").is_none());
+        assert!(fields.get("request_body").is_none());
+        assert!(!logs[0].to_string().contains("secret"));
+    }
+
+    #[tokio::test]
+    async fn test_logs_redacted_headers() {
+        let capture = LogCapture::start();
+        let request = Request::get("/items/1")
+            .header(header::AUTHORIZATION, "Bearer secret")
+            .header("x-api-key", "secret-key")
+            .header(header::ACCEPT, "text/plain")
+            .body(Body::empty())
+            .unwrap();
+
+        router(AccessLogSettings {
+            headers: true,
+            ..AccessLogSettings::default()
+        })
+        .oneshot(request)
+        .await
+        .unwrap();
+
+        let logs = access_logs(&capture);
+        let headers: Value =
+            serde_json::from_str(logs[0]["fields"]["request_headers"].as_str().unwrap()).unwrap();
+        assert_eq!(
+            headers,
+            json!({"authorization": REDACTED, "x-api-key": REDACTED, "accept": "text/plain"})
+        );
+        assert!(logs[0]["fields"]["response_headers"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_logs_sampled_bodies() {
+        let capture = LogCapture::start();
+        let request = Request::post("/login")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(
+                json!({"email": "a@example.com", "password": "hunter2"}).to_string(),
+            ))
+            .unwrap();
+
+        let response = router(AccessLogSettings {
+            body_sample_ratio: 1.0,
+            ..AccessLogSettings::default()
+        })
+        .oneshot(request)
+        .await
+        .unwrap();
+
+        // 読み込んだ本文はハンドラーと呼び出し元にそのまま渡る
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(
+            serde_json::from_slice::<Value>(&body).unwrap(),
+            json!({"email": "a@example.com", "token": "issued-token"})
+        );
+        let logs = access_logs(&capture);
+        let fields = &logs[0]["fields"];
+        assert_eq!(
+            serde_json::from_str::<Value>(fields["request_body"].as_str().unwrap()).unwrap(),
+            json!({"email": "a@example.com", "password": REDACTED})
+        );
+        assert_eq!(
+            serde_json::from_str::<Value>(fields["response_body"].as_str().unwrap()).unwrap(),
+            json!({"email": "a@example.com", "token": REDACTED})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_does_not_log_path_secrets() {
+        let capture = LogCapture::start();
+        let request = Request::get("/shared/s3cr3t-share-token")
+            .body(Body::empty())
+            .unwrap();
+
+        router(AccessLogSettings::default())
+            .oneshot(request)
+            .await
+            .unwrap();
+
+        // パスはルートのテンプレートで出力し、共有リンクのトークンは残さない
+        let logs = access_logs(&capture);
+        assert_eq!(logs[0]["fields"]["uri"], "/shared/:token");
+        assert!(!logs[0].to_string().contains("s3cr3t-share-token"));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_request_body_that_fails_to_read() {
+        let capture = LogCapture::start();
+        let request = Request::post("/echo")
+            .header(header::CONTENT_TYPE, "text/plain")
+            .body(Body::new(BrokenBody))
+            .unwrap();
+
+        let response = router(sampled()).oneshot(request).await.unwrap();
+
+        // 空の本文でハンドラーを呼ばず、400 を返す
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let logs = access_logs(&capture);
+        assert_eq!(logs[0]["fields"]["status"], 400);
+    }
+
+    #[tokio::test]
+    async fn test_does_not_capture_encoded_body() {
+        let capture = LogCapture::start();
+        let compressed = b"\x1f\x8b\x08\x00compressed".to_vec();
+        let request = Request::post("/echo")
+            .header(header::CONTENT_TYPE, "text/plain")
+            .header(header::CONTENT_ENCODING, "gzip")
+            .body(Body::from(compressed.clone()))
+            .unwrap();
+
+        let response = router(sampled()).oneshot(request).await.unwrap();
+
+        // 圧縮された本文は出力せず、そのままハンドラーに渡す
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body.to_vec(), compressed);
+        let logs = access_logs(&capture);
+        assert!(logs[0]["fields"].get("request_body").is_none());
+    }
+}
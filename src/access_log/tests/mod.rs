@@ -0,0 +1,2 @@
+pub mod middleware_tests;
+pub mod redact_tests;
@@ -0,0 +1,99 @@
+use crate::access_log::redact::{redact_body, redact_headers, redact_query, REDACTED};
+use axum::http::{header, HeaderMap, HeaderName, HeaderValue};
+use serde_json::{json, Value};
+use std::collections::HashSet;
+
+fn names(items: &[&str]) -> HashSet<String> {
+    items.iter().map(|item| item.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_query() {
+        let names = names(&["token", "code"]);
+
+        assert_eq!(
+            redact_query("token=abc&from=2025-01-01&Code=xyz&flag", &names),
+            format!("token={}&from=2025-01-01&Code={}&flag", REDACTED, REDACTED)
+        );
+        assert_eq!(redact_query("from=1", &names), "from=1");
+    }
+
+    #[test]
+    fn test_redact_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Bearer t"));
+        headers.insert(header::ACCEPT, HeaderValue::from_static("text/csv"));
+        headers.append(header::ACCEPT, HeaderValue::from_static("text/plain"));
+        let redacted: HashSet<HeaderName> = [header::AUTHORIZATION].into();
+
+        let logged: Value = serde_json::from_str(&redact_headers(&headers, &redacted)).unwrap();
+
+        assert_eq!(
+            logged,
+            json!({"authorization": REDACTED, "accept": "text/csv, text/plain"})
+        );
+    }
+
+    #[test]
+    fn test_redact_json_body() {
+        let body = json!({
+            "email": "a@example.com",
+            "Password": "secret",
+            "items": [{"token": "t", "title": "x"}],
+        });
+
+        let logged = redact_body(
+            Some("application/json; charset=utf-8"),
+            body.to_string().as_bytes(),
+            &names(&["password", "token"]),
+            4096,
+        )
+        .unwrap();
+
+        // 入れ子のオブジェクトのキーも伏せる
+        assert_eq!(
+            serde_json::from_str::<Value>(&logged).unwrap(),
+            json!({
+                "email": "a@example.com",
+                "Password": REDACTED,
+                "items": [{"token": REDACTED, "title": "x"}],
+            })
+        );
+    }
+
+    #[test]
+    fn test_redact_form_and_text_body() {
+        let fields = names(&["password"]);
+
+        assert_eq!(
+            redact_body(
+                Some("application/x-www-form-urlencoded"),
+                b"email=a%40example.com&password=secret",
+                &fields,
+                4096
+            ),
+            Some(format!("email=a%40example.com&password={}", REDACTED))
+        );
+        assert_eq!(
+            redact_body(Some("text/plain"), b"hello", &fields, 4096),
+            Some("hello".to_string())
+        );
+        // バイナリの本文は出力しない
+        assert_eq!(
+            redact_body(Some("image/png"), b"\x89PNG", &fields, 4096),
+            None
+        );
+        assert_eq!(redact_body(None, b"data", &fields, 4096), None);
+    }
+
+    #[test]
+    fn test_truncates_body_on_char_boundary() {
+        let logged = redact_body(Some("text/plain"), "あいう".as_bytes(), &names(&[]), 4).unwrap();
+
+        assert_eq!(logged, "あ...(truncated)");
+    }
+}
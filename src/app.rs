@@ -1,15 +1,196 @@
+use crate::access_log::config::AccessLogConfig;
+use crate::access_log::middleware::log_access;
+use crate::auth::jwt::JwtKeys;
+use crate::auth::middleware::{require_auth, AuthLayerState};
+use crate::metrics::middleware::track_http_metrics;
+use crate::rate_limit::middleware::{enforce_rate_limit, limit_failed_auth, RateLimitState};
+use crate::request_id::middleware::assign_request_id;
 use crate::routes;
+use crate::routes::metrics::MetricsState;
+use crate::server::layers::{
+    with_request_limits, with_response_layers, SWAGGER_UI_CONTENT_SECURITY_POLICY,
+};
+use crate::telemetry::middleware::trace_requests;
+use crate::usecase::api_key_usecase::ApiKeyService;
+use crate::usecase::auth_usecase::AuthService;
+use crate::usecase::calendar_usecase::CalendarService;
+use crate::usecase::health_usecase::HealthService;
+use crate::usecase::idempotency_usecase::IdempotencyService;
+use crate::usecase::oidc_usecase::OidcService;
+use crate::usecase::session_usecase::SessionService;
+use crate::usecase::sharing_usecase::SharingService;
 use crate::usecase::task_usecase::TaskService;
-use axum::Router;
+use crate::usecase::time_tracking_usecase::TimeTrackingService;
+use crate::usecase::workspace_usecase::WorkspaceService;
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::{middleware, Router};
+use std::sync::Arc;
+use std::time::Duration;
+use tower_http::set_header::SetResponseHeaderLayer;
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
 use crate::docs::api_doc::ApiDoc;
 
-pub fn create_app<T: TaskService + Send + Sync + 'static + Clone>(task_service: T) -> Router {
-    Router::new()
-        .merge(routes::hello::router())
+// アプリが使うユースケースの一式
+pub struct AppServices<T, C, S, A, W, K, G, O, E, I, H> {
+    pub task: T,
+    pub calendar: C,
+    pub time_tracking: S,
+    pub auth: A,
+    pub workspace: W,
+    pub api_key: K,
+    pub sharing: G,
+    // OIDC が設定されていない場合は None（OIDC ログインのルートを公開しない）
+    pub oidc: Option<O>,
+    pub session: E,
+    pub idempotency: I,
+    pub health: H,
+}
+
+// 設定で切り替えるアプリ全体の動作
+#[derive(Clone)]
+pub struct AppOptions {
+    // None の場合はレート制限をしない
+    pub rate_limit: Option<RateLimitState>,
+    pub swagger_ui: bool,
+    // None の場合は /metrics を公開せず、HTTP のメトリクスも記録しない
+    pub metrics: Option<MetricsState>,
+    // None の場合はアクセスログを出力しない
+    pub access_log: Option<Arc<AccessLogConfig>>,
+    // 空の場合は CORS ヘッダーを付けない
+    pub cors_allowed_origins: Vec<HeaderValue>,
+    pub request_timeout: Duration,
+    // タイムアウトしたリクエストに返すステータス（408 または 503）
+    pub timeout_status: StatusCode,
+    // リクエストの本文の大きさの上限（圧縮されている場合は展開後の大きさ）
+    pub max_body_bytes: usize,
+    // レスポンスを圧縮し、圧縮されたリクエストの本文を展開する
+    pub compression: bool,
+    // None の場合は Strict-Transport-Security を付けない
+    pub hsts_max_age: Option<Duration>,
+}
+
+pub fn create_app<T, C, S, A, W, K, G, O, E, I, H>(
+    services: AppServices<T, C, S, A, W, K, G, O, E, I, H>,
+    jwt_keys: JwtKeys,
+    options: AppOptions,
+) -> Router
+where
+    T: TaskService + Send + Sync + 'static + Clone,
+    C: CalendarService + Send + Sync + 'static + Clone,
+    S: TimeTrackingService + Send + Sync + 'static + Clone,
+    A: AuthService + Send + Sync + 'static + Clone,
+    W: WorkspaceService + Send + Sync + 'static + Clone,
+    K: ApiKeyService + Send + Sync + 'static + Clone,
+    G: SharingService + Send + Sync + 'static + Clone,
+    O: OidcService + Send + Sync + 'static + Clone,
+    E: SessionService + Send + Sync + 'static + Clone,
+    I: IdempotencyService + Send + Sync + 'static + Clone,
+    H: HealthService + Send + Sync + 'static + Clone,
+{
+    let AppServices {
+        task: task_service,
+        calendar: calendar_service,
+        time_tracking: time_tracking_service,
+        auth: auth_service,
+        workspace: workspace_service,
+        api_key: api_key_service,
+        sharing: sharing_service,
+        oidc: oidc_service,
+        session: session_service,
+        idempotency: idempotency_service,
+        health: health_service,
+    } = services;
+    let auth_layer_state = AuthLayerState {
+        keys: jwt_keys,
+        workspace_service: Arc::new(workspace_service.clone()),
+        api_key_service: Arc::new(api_key_service.clone()),
+        session_service: Arc::new(session_service.clone()),
+    };
+
+    // Authorization: Bearer のアクセストークン、X-API-Key のAPIキー、セッション Cookie のいずれかが必要なルート
+    // route_layer なので、存在しないパスは401ではなく404のままになる
+    // 後から追加した require_auth が先に実行され、レート制限は認証済みの呼び出し元ごとに数える
+    // 認証に失敗したリクエストは、さらに先に実行する limit_failed_auth が IP ごとに数える
+    let mut protected = Router::new()
         .merge(routes::users::router())
-        .merge(routes::tasks::router(task_service))
-        .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()))
+        .merge(routes::auth::account_router(auth_service.clone()))
+        .merge(routes::sessions::account_router(session_service.clone()))
+        .merge(routes::tasks::router(task_service, idempotency_service))
+        .merge(routes::calendar::router(calendar_service.clone()))
+        .merge(routes::time_tracking::router(time_tracking_service))
+        .merge(routes::workspaces::router(workspace_service))
+        .merge(routes::api_keys::router(api_key_service))
+        .merge(routes::sharing::router(sharing_service.clone()));
+    if let Some(rate_limit_state) = options.rate_limit.clone() {
+        protected = protected.route_layer(middleware::from_fn_with_state(
+            rate_limit_state,
+            enforce_rate_limit,
+        ));
+    }
+    let mut protected = protected.route_layer(middleware::from_fn_with_state(
+        auth_layer_state,
+        require_auth::<W, K, E>,
+    ));
+    if let Some(rate_limit_state) = options.rate_limit.clone() {
+        protected = protected.route_layer(middleware::from_fn_with_state(
+            rate_limit_state,
+            limit_failed_auth,
+        ));
+    }
+
+    // 認証なしで呼び出せるルート（レート制限はIPごとに数える）
+    let mut public = Router::new()
+        .merge(routes::hello::router())
+        .merge(routes::auth::router(auth_service))
+        .merge(routes::sessions::router(session_service))
+        .merge(routes::calendar::feed_router(calendar_service))
+        .merge(routes::sharing::link_router(sharing_service));
+    if let Some(oidc_service) = oidc_service {
+        public = public.merge(routes::oidc::router(oidc_service));
+    }
+    if let Some(rate_limit_state) = options.rate_limit.clone() {
+        public = public.route_layer(middleware::from_fn_with_state(
+            rate_limit_state,
+            enforce_rate_limit,
+        ));
+    }
+
+    // ヘルスチェックは頻繁に呼ばれるため、レート制限の対象にしない
+    let mut app = Router::new()
+        .merge(public)
+        .merge(protected)
+        .merge(routes::health::router(health_service));
+    if options.swagger_ui {
+        // Swagger UI の HTML が読み込むスクリプトやスタイルだけを許可する
+        let swagger_ui = Router::new()
+            .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()))
+            .layer(SetResponseHeaderLayer::overriding(
+                header::CONTENT_SECURITY_POLICY,
+                HeaderValue::from_static(SWAGGER_UI_CONTENT_SECURITY_POLICY),
+            ));
+        app = app.merge(swagger_ui);
+    }
+    app = with_request_limits(app, &options);
+    // ルートごとに記録するため、ルーティングの後に実行される layer で付ける（タイムアウトも記録する）
+    // merge した後に layer を付けないと、ルートがないリクエスト（fallback）が記録されない
+    if let Some(metrics_state) = options.metrics.clone() {
+        app = app
+            .merge(routes::metrics::router(metrics_state))
+            .layer(middleware::from_fn(track_http_metrics));
+    }
+    // タイムアウトしたリクエストも記録し、ログにリクエストのスパンの request_id が付くよう trace_requests の内側で付ける
+    if let Some(access_log_config) = options.access_log.clone() {
+        app = app.layer(middleware::from_fn_with_state(
+            access_log_config,
+            log_access,
+        ));
+    }
+    // メトリクスやアクセスログの外側の layer にして、タイムアウトやそれらの記録もリクエストのスパンに含める
+    app = app.layer(middleware::from_fn(trace_requests));
+    // アクセスログやメトリクスには圧縮する前の大きさを記録するため、それらの外側で圧縮する
+    app = with_response_layers(app, &options);
+    // 最も外側で付け、CORS のプリフライトやエラーのレスポンスにも X-Request-Id を返す
+    app.layer(middleware::from_fn(assign_request_id))
 }
@@ -0,0 +1,31 @@
+use axum::http::{header, HeaderMap};
+
+// Cookie ヘッダーから名前の一致する値を取り出す
+pub fn cookie_value<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+// HTTPS でだけ送られ、別サイトからの POST などには付かない Cookie の Set-Cookie ヘッダーの値
+// http_only が false の Cookie はスクリプトから読める（CSRF トークンを画面に渡すために使う）
+pub fn set_cookie(name: &str, value: &str, path: &str, max_age: i64, http_only: bool) -> String {
+    format!(
+        "{}={}; Path={}; Max-Age={}{}; Secure; SameSite=Lax",
+        name,
+        value,
+        path,
+        max_age,
+        if http_only { "; HttpOnly" } else { "" }
+    )
+}
+
+// Cookie を削除する Set-Cookie ヘッダーの値
+pub fn clear_cookie(name: &str, path: &str, http_only: bool) -> String {
+    set_cookie(name, "", path, 0, http_only)
+}
@@ -0,0 +1,280 @@
+use crate::config::settings::JwtSettings;
+use anyhow::{anyhow, Context};
+use chrono::{DateTime, Duration, Utc};
+use jsonwebtoken::errors::{Error, ErrorKind};
+use jsonwebtoken::jwk::{AlgorithmParameters, JwkSet, KeyAlgorithm};
+use jsonwebtoken::{
+    decode, decode_header, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard};
+use std::time::Instant;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+// アクセストークンの有効期間（失効の手段がないため短くする）
+pub const ACCESS_TOKEN_TTL: Duration = Duration::minutes(15);
+
+// JWKS ファイルを読み直す間隔（JWKS から外した鍵もこの時間が経てば使えなくなる）
+pub const JWKS_TTL: std::time::Duration = std::time::Duration::from_secs(300);
+// 未知の kid のトークンを受け取ったときに JWKS ファイルを読み直す最短の間隔
+// （不正な kid のトークンを送り続けられてもファイルを読み続けないようにする）
+pub const JWKS_MIN_REFETCH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+// アクセストークンのクレーム
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct Claims {
+    // ユーザーID
+    pub sub: Uuid,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+struct SigningKey {
+    algorithm: Algorithm,
+    kid: Option<String>,
+    key: EncodingKey,
+}
+
+type VerifyingKeys = HashMap<String, (Algorithm, DecodingKey)>;
+
+// JWKS の検証鍵と、それを読み込んだ（または読み込みを試みた）時刻
+struct JwksCache {
+    keys: VerifyingKeys,
+    loaded_at: Instant,
+}
+
+// JWTの署名鍵と検証鍵
+// kid 付きのトークンは JWKS の鍵で、kid なしのトークンは HS256 の共有鍵で検証する
+// JWKS をファイルから読み込んだ場合は、JWKS_TTL ごとと未知の kid を受け取ったときに読み直す
+#[derive(Clone)]
+pub struct JwtKeys {
+    signing: Arc<SigningKey>,
+    secret: Option<Arc<DecodingKey>>,
+    jwks: Arc<RwLock<JwksCache>>,
+    jwks_path: Option<Arc<PathBuf>>,
+    // JWKS ファイルを読み直している間に取るロック
+    jwks_reload: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl JwtKeys {
+    // 共有鍵（HS256）で署名・検証する
+    pub fn hs256(secret: &[u8]) -> Self {
+        Self {
+            signing: Arc::new(SigningKey {
+                algorithm: Algorithm::HS256,
+                kid: None,
+                key: EncodingKey::from_secret(secret),
+            }),
+            secret: Some(Arc::new(DecodingKey::from_secret(secret))),
+            jwks: Self::cache(HashMap::new()),
+            jwks_path: None,
+            jwks_reload: Arc::default(),
+        }
+    }
+
+    fn cache(keys: VerifyingKeys) -> Arc<RwLock<JwksCache>> {
+        Arc::new(RwLock::new(JwksCache {
+            keys,
+            loaded_at: Instant::now(),
+        }))
+    }
+
+    // RSA秘密鍵（RS256）で署名し、JWKS の公開鍵で検証する
+    // 鍵をローテーションする際は、新旧の公開鍵を JWKS に並べてから署名に使う kid を切り替える
+    pub fn rs256(private_key_pem: &[u8], kid: &str, jwks: &JwkSet) -> anyhow::Result<Self> {
+        let keys = Self {
+            signing: Arc::new(SigningKey {
+                algorithm: Algorithm::RS256,
+                kid: Some(kid.to_string()),
+                key: EncodingKey::from_rsa_pem(private_key_pem)
+                    .context("invalid RSA private key")?,
+            }),
+            secret: None,
+            jwks: Self::cache(HashMap::new()),
+            jwks_path: None,
+            jwks_reload: Arc::default(),
+        }
+        .with_jwks(jwks)?;
+        Ok(keys)
+    }
+
+    // JWKS の鍵を検証鍵に追加する（HS256 / RS256 以外の鍵はエラー）
+    pub fn with_jwks(mut self, jwks: &JwkSet) -> anyhow::Result<Self> {
+        let keys = self.verifying_keys(jwks)?;
+        self.jwks = Self::cache(keys);
+        Ok(self)
+    }
+
+    // JWKS から検証鍵を作る（RS256 で署名する場合は、署名に使う鍵が含まれていなければエラー）
+    fn verifying_keys(&self, jwks: &JwkSet) -> anyhow::Result<VerifyingKeys> {
+        let mut keys = HashMap::new();
+        for jwk in &jwks.keys {
+            let kid = jwk
+                .common
+                .key_id
+                .clone()
+                .ok_or_else(|| anyhow!("every key in the JWKS must have a `kid`"))?;
+            let algorithm = match (&jwk.algorithm, jwk.common.key_algorithm) {
+                (AlgorithmParameters::RSA(_), None | Some(KeyAlgorithm::RS256)) => Algorithm::RS256,
+                (AlgorithmParameters::OctetKey(_), None | Some(KeyAlgorithm::HS256)) => {
+                    Algorithm::HS256
+                }
+                _ => return Err(anyhow!("key `{}` is not an HS256 or RS256 key", kid)),
+            };
+            let key =
+                DecodingKey::from_jwk(jwk).with_context(|| format!("invalid key `{}`", kid))?;
+            keys.insert(kid, (algorithm, key));
+        }
+        if let Some(kid) = &self.signing.kid {
+            if !keys.contains_key(kid) {
+                return Err(anyhow!("signing key `{}` is not in the JWKS", kid));
+            }
+        }
+        Ok(keys)
+    }
+
+    fn read_jwks(path: &Path) -> anyhow::Result<JwkSet> {
+        let body = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read JWKS from {}", path.display()))?;
+        serde_json::from_str(&body).context("invalid JWKS")
+    }
+
+    // 必要なら JWKS ファイルを読み直す（読み込みに失敗した場合は今の鍵を使い続ける）
+    async fn refresh_jwks(&self, kid: &str, now: Instant) {
+        let Some(path) = &self.jwks_path else {
+            return;
+        };
+        // 読み直すのは1つのリクエストだけにし、待っていたリクエストは読み直した鍵を使う
+        let _reloading = self.jwks_reload.lock().await;
+        if !Self::needs_refresh(&self.read_cache(), kid, now) {
+            return;
+        }
+        // ファイルはロックを持たずにブロッキング用のスレッドで読み、鍵の差し替えの間だけ書き込みロックを取る
+        let reading = Arc::clone(path);
+        let jwks = tokio::task::spawn_blocking(move || Self::read_jwks(&reading))
+            .await
+            .unwrap_or_else(|e| Err(anyhow!("failed to read JWKS: {}", e)));
+        let keys = jwks.and_then(|jwks| self.verifying_keys(&jwks));
+        let mut cache = self.jwks.write().unwrap_or_else(PoisonError::into_inner);
+        cache.loaded_at = now;
+        match keys {
+            Ok(keys) => {
+                // 鍵の組み合わせが変わった場合だけ記録する
+                if keys.len() != cache.keys.len()
+                    || keys.keys().any(|k| !cache.keys.contains_key(k))
+                {
+                    info!(
+                        "reloaded JWKS from {} ({} keys)",
+                        path.display(),
+                        keys.len()
+                    );
+                }
+                cache.keys = keys;
+            }
+            Err(e) => warn!("failed to reload JWKS, keeping the current keys: {:#}", e),
+        }
+    }
+
+    fn read_cache(&self) -> RwLockReadGuard<'_, JwksCache> {
+        self.jwks.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn needs_refresh(cache: &JwksCache, kid: &str, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(cache.loaded_at);
+        elapsed >= JWKS_TTL
+            || (!cache.keys.contains_key(kid) && elapsed >= JWKS_MIN_REFETCH_INTERVAL)
+    }
+
+    async fn find_jwk(&self, kid: &str, now: Instant) -> Option<(Algorithm, DecodingKey)> {
+        let needs_refresh =
+            self.jwks_path.is_some() && Self::needs_refresh(&self.read_cache(), kid, now);
+        if needs_refresh {
+            self.refresh_jwks(kid, now).await;
+        }
+        self.read_cache().keys.get(kid).cloned()
+    }
+
+    // 設定から鍵を読み込む（必須の項目は Config::validate で確認済み）
+    // - private_key_path / key_id / jwks_path: RS256 で署名する
+    // - secret: HS256 で署名する（RS256 と併用した場合は kid なしの HS256 トークンも受け付ける）
+    pub fn from_settings(settings: &JwtSettings) -> anyhow::Result<Self> {
+        let secret = settings.secret.as_ref().map(|secret| secret.expose());
+        let jwks = settings
+            .jwks_path
+            .as_deref()
+            .map(Self::read_jwks)
+            .transpose()?;
+
+        let keys = match &settings.private_key_path {
+            Some(path) => {
+                let pem = std::fs::read(path).with_context(|| {
+                    format!("failed to read private key from {}", path.display())
+                })?;
+                let kid = settings
+                    .key_id
+                    .as_deref()
+                    .context("jwt.key_id must be set for RS256")?;
+                let jwks = jwks.context("jwt.jwks_path must be set for RS256")?;
+                let mut keys = Self::rs256(&pem, kid, &jwks)?;
+                if let Some(secret) = secret {
+                    keys.secret = Some(Arc::new(DecodingKey::from_secret(secret.as_bytes())));
+                }
+                keys
+            }
+            None => {
+                let secret = secret.context("jwt.secret or jwt.private_key_path must be set")?;
+                let keys = Self::hs256(secret.as_bytes());
+                match jwks {
+                    Some(jwks) => keys.with_jwks(&jwks)?,
+                    None => keys,
+                }
+            }
+        };
+        Ok(Self {
+            jwks_path: settings.jwks_path.clone().map(Arc::new),
+            ..keys
+        })
+    }
+
+    // アクセストークンを発行する
+    pub fn issue_access_token(&self, user_id: Uuid, now: DateTime<Utc>) -> Result<String, Error> {
+        let claims = Claims {
+            sub: user_id,
+            iat: now.timestamp(),
+            exp: (now + ACCESS_TOKEN_TTL).timestamp(),
+        };
+        let mut header = Header::new(self.signing.algorithm);
+        header.kid = self.signing.kid.clone();
+        encode(&header, &claims, &self.signing.key)
+    }
+
+    // アクセストークンの署名と有効期限を検証する
+    // 検証に使うアルゴリズムは鍵ごとに固定し、ヘッダーの alg は信用しない
+    pub async fn verify_access_token(&self, token: &str) -> Result<Claims, Error> {
+        self.verify_access_token_at(token, Instant::now()).await
+    }
+
+    // now の時点の JWKS で検証する（JWKS を読み直すかどうかの判断に使う）
+    pub async fn verify_access_token_at(&self, token: &str, now: Instant) -> Result<Claims, Error> {
+        let header = decode_header(token)?;
+        let (algorithm, key) = match &header.kid {
+            Some(kid) => self
+                .find_jwk(kid, now)
+                .await
+                .ok_or_else(|| Error::from(ErrorKind::InvalidKeyFormat))?,
+            None => (
+                Algorithm::HS256,
+                self.secret
+                    .as_deref()
+                    .cloned()
+                    .ok_or_else(|| Error::from(ErrorKind::InvalidKeyFormat))?,
+            ),
+        };
+        let mut validation = Validation::new(algorithm);
+        validation.set_required_spec_claims(&["exp", "sub"]);
+        Ok(decode::<Claims>(token, &key, &validation)?.claims)
+    }
+}
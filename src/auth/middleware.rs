@@ -0,0 +1,124 @@
+use crate::auth::cookie::cookie_value;
+use crate::auth::jwt::JwtKeys;
+use crate::error::AppError;
+use crate::models::caller::Caller;
+use crate::usecase::api_key_usecase::ApiKeyService;
+use crate::usecase::session_usecase::SessionService;
+use crate::usecase::workspace_usecase::WorkspaceService;
+use async_trait::async_trait;
+use axum::{
+    extract::{FromRequestParts, Request, State},
+    http::{header, request::Parts},
+    middleware::Next,
+    response::Response,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+// 操作対象のワークスペースを指定するヘッダー（省略時は個人ワークスペース）
+pub const WORKSPACE_ID_HEADER: &str = "x-workspace-id";
+
+// JWTの代わりに使えるAPIキーのヘッダー（ワークスペースはキーから決まる）
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+// ブラウザ向けのセッショントークンを保存する Cookie
+pub const SESSION_COOKIE: &str = "session";
+
+// セッション Cookie で状態を変更するリクエストに必要な CSRF トークンのヘッダー
+pub const CSRF_TOKEN_HEADER: &str = "x-csrf-token";
+
+#[derive(Clone)]
+pub struct AuthLayerState<W: WorkspaceService, K: ApiKeyService, S: SessionService> {
+    pub keys: JwtKeys,
+    pub workspace_service: Arc<W>,
+    pub api_key_service: Arc<K>,
+    pub session_service: Arc<S>,
+}
+
+// Authorization: Bearer のJWT、X-API-Key のAPIキー、セッション Cookie のいずれかを検証し、
+// ワークスペースでのロールを含む呼び出し元をリクエストに追加する
+pub async fn require_auth<W, K, S>(
+    State(state): State<AuthLayerState<W, K, S>>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, AppError>
+where
+    W: WorkspaceService + Send + Sync,
+    K: ApiKeyService + Send + Sync,
+    S: SessionService + Send + Sync,
+{
+    if let Some(value) = request.headers().get(API_KEY_HEADER) {
+        let key = value.to_str().map_err(|_| AppError::Unauthorized)?;
+        let caller = state.api_key_service.authenticate(key.trim()).await?;
+        request.extensions_mut().insert(caller);
+        return Ok(next.run(request).await);
+    }
+
+    let user_id = match request.headers().get(header::AUTHORIZATION) {
+        Some(value) => {
+            let token = value
+                .to_str()
+                .ok()
+                .and_then(|value| value.split_once(' '))
+                .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("Bearer"))
+                .map(|(_, token)| token.trim())
+                .ok_or(AppError::Unauthorized)?;
+            state
+                .keys
+                .verify_access_token(token)
+                .await
+                .map_err(|_| AppError::Unauthorized)?
+                .sub
+        }
+        None => {
+            let token =
+                cookie_value(request.headers(), SESSION_COOKIE).ok_or(AppError::Unauthorized)?;
+            let session = state.session_service.authenticate(token).await?;
+            // Cookie は別サイトからのリクエストにも付くため、状態を変更するリクエストには CSRF トークンを求める
+            if !request.method().is_safe() {
+                let verified = request
+                    .headers()
+                    .get(CSRF_TOKEN_HEADER)
+                    .and_then(|value| value.to_str().ok())
+                    .is_some_and(|csrf_token| session.verifies_csrf(csrf_token));
+                if !verified {
+                    return Err(AppError::Forbidden);
+                }
+            }
+            session.user_id
+        }
+    };
+
+    let workspace_id = request
+        .headers()
+        .get(WORKSPACE_ID_HEADER)
+        .map(|value| {
+            value
+                .to_str()
+                .ok()
+                .and_then(|value| Uuid::parse_str(value.trim()).ok())
+                .ok_or_else(|| AppError::BadRequest("Invalid X-Workspace-Id header".to_string()))
+        })
+        .transpose()?;
+    let caller = state
+        .workspace_service
+        .resolve_caller(user_id, workspace_id)
+        .await?;
+
+    request.extensions_mut().insert(caller);
+    Ok(next.run(request).await)
+}
+
+// require_auth を通ったハンドラーで呼び出し元を受け取る
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for Caller {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<Caller>()
+            .cloned()
+            .ok_or(AppError::Unauthorized)
+    }
+}
@@ -0,0 +1,6 @@
+pub mod cookie;
+pub mod jwt;
+pub mod middleware;
+pub mod oidc;
+#[cfg(test)]
+pub mod tests;
@@ -0,0 +1,43 @@
+use crate::config::settings::OidcSettings;
+use anyhow::Context;
+
+// OIDCプロバイダーの設定
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OidcConfig {
+    // discovery（{issuer}/.well-known/openid-configuration）の起点で、ID トークンの iss と一致する必要がある
+    pub issuer_url: String,
+    pub client_id: String,
+    // 公開クライアント（PKCE のみ）の場合は None
+    pub client_secret: Option<String>,
+    // プロバイダーに登録したコールバックURL（/auth/oidc/callback）
+    pub redirect_url: String,
+    pub scopes: String,
+}
+
+impl OidcConfig {
+    // 設定から読み込む（issuer_url がなければ OIDC ログインを無効にする）
+    // client_id と redirect_url は issuer_url を指定した場合に必須（Config::validate で確認済み）
+    pub fn from_settings(settings: &OidcSettings) -> anyhow::Result<Option<Self>> {
+        let Some(issuer_url) = settings.issuer_url.clone() else {
+            return Ok(None);
+        };
+        let client_id = settings
+            .client_id
+            .clone()
+            .context("oidc.client_id must be set for OIDC")?;
+        let redirect_url = settings
+            .redirect_url
+            .clone()
+            .context("oidc.redirect_url must be set for OIDC")?;
+        Ok(Some(Self {
+            issuer_url,
+            client_id,
+            client_secret: settings
+                .client_secret
+                .as_ref()
+                .map(|secret| secret.expose().to_string()),
+            redirect_url,
+            scopes: settings.scopes.clone(),
+        }))
+    }
+}
@@ -0,0 +1,230 @@
+use crate::auth::jwt::{Claims, JwtKeys, ACCESS_TOKEN_TTL, JWKS_MIN_REFETCH_INTERVAL, JWKS_TTL};
+use crate::config::settings::JwtSettings;
+use chrono::{Duration, Utc};
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{encode, EncodingKey, Header};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use uuid::Uuid;
+
+// テスト用の鍵（テスト以外では使わない）
+const JWKS: &str = include_str!("fixtures/jwks.json");
+const KEY_2025_05: &[u8] = include_bytes!("fixtures/key-2025-05.pem");
+const KEY_2025_06: &[u8] = include_bytes!("fixtures/key-2025-06.pem");
+
+fn jwks() -> JwkSet {
+    serde_json::from_str(JWKS).unwrap()
+}
+
+// 指定した kid の鍵だけを含む JWKS
+fn jwks_with(kids: &[&str]) -> JwkSet {
+    let mut jwks = jwks();
+    jwks.keys
+        .retain(|jwk| kids.contains(&jwk.common.key_id.as_deref().unwrap()));
+    jwks
+}
+
+fn write_jwks(path: &Path, jwks: &JwkSet) {
+    std::fs::write(path, serde_json::to_string(jwks).unwrap()).unwrap();
+}
+
+// 一時ディレクトリに置いた JWKS と 2025-05 の秘密鍵で署名する鍵を読み込む
+fn keys_from_files(jwks: &JwkSet) -> (JwtKeys, PathBuf) {
+    let dir = std::env::temp_dir().join(format!("jwks-{}", Uuid::now_v7()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let private_key_path = dir.join("key.pem");
+    std::fs::write(&private_key_path, KEY_2025_05).unwrap();
+    let jwks_path = dir.join("jwks.json");
+    write_jwks(&jwks_path, jwks);
+    let keys = JwtKeys::from_settings(&JwtSettings {
+        private_key_path: Some(private_key_path),
+        key_id: Some("2025-05".to_string()),
+        jwks_path: Some(jwks_path.clone()),
+        ..JwtSettings::default()
+    })
+    .unwrap();
+    (keys, jwks_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_hs256_round_trip() {
+        let keys = JwtKeys::hs256(b"secret");
+        let user_id = Uuid::now_v7();
+        let now = Utc::now();
+
+        let token = keys.issue_access_token(user_id, now).unwrap();
+        let claims = keys.verify_access_token(&token).await.unwrap();
+
+        // 検証
+        assert_eq!(claims.sub, user_id);
+        assert_eq!(claims.exp - claims.iat, ACCESS_TOKEN_TTL.num_seconds());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_expired_token() {
+        let keys = JwtKeys::hs256(b"secret");
+
+        // 有効期限（と検証時の猶予）を過ぎたトークン
+        let token = keys
+            .issue_access_token(Uuid::now_v7(), Utc::now() - Duration::hours(1))
+            .unwrap();
+
+        assert!(keys.verify_access_token(&token).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_token_signed_with_other_secret() {
+        let token = JwtKeys::hs256(b"other")
+            .issue_access_token(Uuid::now_v7(), Utc::now())
+            .unwrap();
+
+        assert!(JwtKeys::hs256(b"secret")
+            .verify_access_token(&token)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rs256_key_rotation() {
+        let user_id = Uuid::now_v7();
+        let now = Utc::now();
+
+        // 旧鍵で署名されたトークン
+        let old_keys = JwtKeys::rs256(KEY_2025_05, "2025-05", &jwks()).unwrap();
+        let old_token = old_keys.issue_access_token(user_id, now).unwrap();
+
+        // 署名に使う鍵を切り替えても、JWKS に旧鍵が残っていれば検証できる
+        let new_keys = JwtKeys::rs256(KEY_2025_06, "2025-06", &jwks()).unwrap();
+        let new_token = new_keys.issue_access_token(user_id, now).unwrap();
+        assert_eq!(
+            new_keys.verify_access_token(&old_token).await.unwrap().sub,
+            user_id
+        );
+        assert_eq!(
+            new_keys.verify_access_token(&new_token).await.unwrap().sub,
+            user_id
+        );
+    }
+
+    #[test]
+    fn test_rs256_requires_signing_key_in_jwks() {
+        assert!(JwtKeys::rs256(KEY_2025_05, "unknown", &jwks()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_unknown_kid() {
+        let keys = JwtKeys::rs256(KEY_2025_05, "2025-05", &jwks()).unwrap();
+        let now = Utc::now();
+        let claims = Claims {
+            sub: Uuid::now_v7(),
+            iat: now.timestamp(),
+            exp: (now + Duration::minutes(5)).timestamp(),
+        };
+        let mut header = Header::new(jsonwebtoken::Algorithm::RS256);
+        header.kid = Some("2024-01".to_string());
+        let token = encode(
+            &header,
+            &claims,
+            &EncodingKey::from_rsa_pem(KEY_2025_05).unwrap(),
+        )
+        .unwrap();
+
+        assert!(keys.verify_access_token(&token).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_algorithm_confusion() {
+        // RSA公開鍵の kid を指定した HS256 トークンは受け付けない
+        let keys = JwtKeys::rs256(KEY_2025_05, "2025-05", &jwks()).unwrap();
+        let now = Utc::now();
+        let claims = Claims {
+            sub: Uuid::now_v7(),
+            iat: now.timestamp(),
+            exp: (now + Duration::minutes(5)).timestamp(),
+        };
+        let mut header = Header::new(jsonwebtoken::Algorithm::HS256);
+        header.kid = Some("2025-05".to_string());
+        let token = encode(&header, &claims, &EncodingKey::from_secret(JWKS.as_bytes())).unwrap();
+
+        assert!(keys.verify_access_token(&token).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_reloads_jwks_for_unknown_kid() {
+        let (keys, jwks_path) = keys_from_files(&jwks_with(&["2025-05"]));
+        let started = Instant::now();
+
+        // 新しい鍵を JWKS に追加し、その鍵で署名されたトークンを受け取る
+        write_jwks(&jwks_path, &jwks());
+        let token = JwtKeys::rs256(KEY_2025_06, "2025-06", &jwks())
+            .unwrap()
+            .issue_access_token(Uuid::now_v7(), Utc::now())
+            .unwrap();
+
+        // 読み込んだ直後は読み直さない
+        assert!(keys.verify_access_token_at(&token, started).await.is_err());
+        // 最短の間隔を過ぎたら読み直して検証できる
+        assert!(keys
+            .verify_access_token_at(&token, started + JWKS_MIN_REFETCH_INTERVAL)
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_requests_share_jwks_reload() {
+        let (keys, jwks_path) = keys_from_files(&jwks_with(&["2025-05"]));
+        let later = Instant::now() + JWKS_MIN_REFETCH_INTERVAL;
+        write_jwks(&jwks_path, &jwks());
+        let token = JwtKeys::rs256(KEY_2025_06, "2025-06", &jwks())
+            .unwrap()
+            .issue_access_token(Uuid::now_v7(), Utc::now())
+            .unwrap();
+
+        // 読み直しを待っていたリクエストも、読み直した鍵で検証できる
+        let (first, second) = tokio::join!(
+            keys.verify_access_token_at(&token, later),
+            keys.verify_access_token_at(&token, later)
+        );
+        assert!(first.is_ok());
+        assert!(second.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_reloads_jwks_after_ttl() {
+        let (keys, jwks_path) = keys_from_files(&jwks());
+        let started = Instant::now();
+        let token = JwtKeys::rs256(KEY_2025_06, "2025-06", &jwks())
+            .unwrap()
+            .issue_access_token(Uuid::now_v7(), Utc::now())
+            .unwrap();
+        assert!(keys.verify_access_token_at(&token, started).await.is_ok());
+
+        // JWKS から外した鍵は TTL が過ぎると使えなくなる
+        write_jwks(&jwks_path, &jwks_with(&["2025-05"]));
+        assert!(keys.verify_access_token_at(&token, started).await.is_ok());
+        assert!(keys
+            .verify_access_token_at(&token, started + JWKS_TTL)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_keeps_jwks_when_reload_fails() {
+        let (keys, jwks_path) = keys_from_files(&jwks());
+        let token = keys.issue_access_token(Uuid::now_v7(), Utc::now()).unwrap();
+
+        // 署名に使う鍵が含まれない JWKS と、壊れた JWKS は読み込まない
+        write_jwks(&jwks_path, &jwks_with(&["2025-06"]));
+        let later = Instant::now() + JWKS_TTL;
+        assert!(keys.verify_access_token_at(&token, later).await.is_ok());
+        std::fs::write(&jwks_path, "not json").unwrap();
+        assert!(keys
+            .verify_access_token_at(&token, later + JWKS_TTL)
+            .await
+            .is_ok());
+    }
+}
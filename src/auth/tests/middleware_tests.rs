@@ -0,0 +1,472 @@
+use crate::auth::jwt::JwtKeys;
+use crate::auth::middleware::{
+    require_auth, AuthLayerState, API_KEY_HEADER, CSRF_TOKEN_HEADER, SESSION_COOKIE,
+    WORKSPACE_ID_HEADER,
+};
+use crate::models::api_key::{ApiKey, ApiKeyScope};
+use crate::models::caller::Caller;
+use crate::models::session::{Session, SESSION_IDLE_TIMEOUT};
+use crate::models::workspace::WorkspaceRole;
+use crate::repositories::api_key_repository::MockApiKeyRepository;
+use crate::repositories::refresh_token_repository::MockRefreshTokenRepository;
+use crate::repositories::session_repository::MockSessionRepository;
+use crate::repositories::user_repository::MockUserRepository;
+use crate::repositories::workspace_repository::MockWorkspaceRepository;
+use crate::secret::hash_token;
+use crate::usecase::api_key_usecase::ApiKeyUsecase;
+use crate::usecase::session_usecase::SessionUsecase;
+use crate::usecase::workspace_usecase::WorkspaceUsecase;
+use axum::{
+    body::Body,
+    http::{header, Method, Request, StatusCode},
+    middleware,
+    routing::get,
+    Router,
+};
+use chrono::{Duration, Utc};
+use mockall::predicate::*;
+use std::sync::Arc;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+type TestApiKeyService = ApiKeyUsecase<MockApiKeyRepository, MockWorkspaceRepository>;
+type TestSessionService =
+    SessionUsecase<MockSessionRepository, MockUserRepository, MockRefreshTokenRepository>;
+
+const SESSION_TOKEN: &str = "session-token";
+const CSRF_TOKEN: &str = "csrf-token";
+
+// 呼び出し元のユーザーIDを返すだけの保護されたルーター
+fn protected_router(keys: JwtKeys, workspace_repo: MockWorkspaceRepository) -> Router {
+    let api_key_service =
+        ApiKeyUsecase::new(MockApiKeyRepository::new(), MockWorkspaceRepository::new());
+    protected_router_with_api_keys(keys, workspace_repo, api_key_service)
+}
+
+fn protected_router_with_api_keys(
+    keys: JwtKeys,
+    workspace_repo: MockWorkspaceRepository,
+    api_key_service: TestApiKeyService,
+) -> Router {
+    protected_router_with(
+        keys,
+        workspace_repo,
+        api_key_service,
+        MockSessionRepository::new(),
+    )
+}
+
+fn protected_router_with(
+    keys: JwtKeys,
+    workspace_repo: MockWorkspaceRepository,
+    api_key_service: TestApiKeyService,
+    session_repo: MockSessionRepository,
+) -> Router {
+    let state = AuthLayerState {
+        keys,
+        workspace_service: Arc::new(WorkspaceUsecase::new(
+            workspace_repo,
+            MockUserRepository::new(),
+        )),
+        api_key_service: Arc::new(api_key_service),
+        session_service: Arc::new(SessionUsecase::new(
+            session_repo,
+            MockUserRepository::new(),
+            MockRefreshTokenRepository::new(),
+            JwtKeys::hs256(b"secret"),
+        )),
+    };
+    let whoami = |caller: Caller| async move {
+        format!(
+            "{} {} {:?}",
+            caller.user_id, caller.workspace_id, caller.role
+        )
+    };
+    Router::new()
+        .route("/whoami", get(whoami).post(whoami))
+        .route_layer(middleware::from_fn_with_state(
+            state,
+            require_auth::<
+                WorkspaceUsecase<MockWorkspaceRepository, MockUserRepository>,
+                TestApiKeyService,
+                TestSessionService,
+            >,
+        ))
+}
+
+// どのワークスペースでも member として扱うモック
+fn member_everywhere() -> MockWorkspaceRepository {
+    let mut workspace_repo = MockWorkspaceRepository::new();
+    workspace_repo
+        .expect_find_role()
+        .returning(|_, _| Ok(Some(WorkspaceRole::Member)));
+    workspace_repo
+}
+
+// SESSION_TOKEN のセッション（last_seen_at 以降アクセスがないもの）を返すモック
+fn session_repo_with(user_id: Uuid, last_seen_at: chrono::DateTime<Utc>) -> MockSessionRepository {
+    let mut session = Session::new(
+        user_id,
+        hash_token(SESSION_TOKEN),
+        hash_token(CSRF_TOKEN),
+        Utc::now() - Duration::hours(1),
+    );
+    session.last_seen_at = last_seen_at;
+    let mut session_repo = MockSessionRepository::new();
+    session_repo
+        .expect_find_by_token_hash()
+        .with(eq(hash_token(SESSION_TOKEN)))
+        .returning(move |_| Ok(Some(session.clone())));
+    session_repo
+}
+
+fn cookie_request(method: Method, csrf_token: Option<&str>) -> Request<Body> {
+    let mut builder = Request::builder().method(method).uri("/whoami").header(
+        header::COOKIE,
+        format!("theme=dark; {}={}", SESSION_COOKIE, SESSION_TOKEN),
+    );
+    if let Some(value) = csrf_token {
+        builder = builder.header(CSRF_TOKEN_HEADER, value);
+    }
+    builder.body(Body::empty()).unwrap()
+}
+
+fn request(authorization: Option<&str>) -> Request<Body> {
+    let mut builder = Request::builder().uri("/whoami");
+    if let Some(value) = authorization {
+        builder = builder.header(header::AUTHORIZATION, value);
+    }
+    builder.body(Body::empty()).unwrap()
+}
+
+async fn body_string(response: axum::response::Response) -> String {
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_accepts_valid_bearer_token() {
+        let keys = JwtKeys::hs256(b"secret");
+        let user_id = Uuid::now_v7();
+        let token = keys.issue_access_token(user_id, Utc::now()).unwrap();
+
+        // ヘッダーがなければ個人ワークスペース（id はユーザーIDと同じ）でのロールを調べる
+        let mut workspace_repo = MockWorkspaceRepository::new();
+        workspace_repo
+            .expect_find_role()
+            .with(eq(user_id), eq(user_id))
+            .times(1)
+            .returning(|_, _| Ok(Some(WorkspaceRole::Admin)));
+
+        let response = protected_router(keys, workspace_repo)
+            .oneshot(request(Some(&format!("Bearer {}", token))))
+            .await
+            .unwrap();
+
+        // 検証
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            body_string(response).await,
+            format!("{} {} Admin", user_id, user_id)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolves_workspace_from_header() {
+        let keys = JwtKeys::hs256(b"secret");
+        let user_id = Uuid::now_v7();
+        let workspace_id = Uuid::now_v7();
+        let token = keys.issue_access_token(user_id, Utc::now()).unwrap();
+
+        // モックリポジトリの作成
+        let mut workspace_repo = MockWorkspaceRepository::new();
+        workspace_repo
+            .expect_find_role()
+            .with(eq(workspace_id), eq(user_id))
+            .times(1)
+            .returning(|_, _| Ok(Some(WorkspaceRole::Viewer)));
+
+        let response = protected_router(keys, workspace_repo)
+            .oneshot(
+                Request::builder()
+                    .uri("/whoami")
+                    .header(header::AUTHORIZATION, format!("Bearer {}", token))
+                    .header(WORKSPACE_ID_HEADER, workspace_id.to_string())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // 検証
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            body_string(response).await,
+            format!("{} {} Viewer", user_id, workspace_id)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rejects_workspace_of_non_member() {
+        let keys = JwtKeys::hs256(b"secret");
+        let token = keys.issue_access_token(Uuid::now_v7(), Utc::now()).unwrap();
+
+        // メンバーでないワークスペースは存在しないものとして扱う
+        let mut workspace_repo = MockWorkspaceRepository::new();
+        workspace_repo
+            .expect_find_role()
+            .times(1)
+            .returning(|_, _| Ok(None));
+
+        let response = protected_router(keys.clone(), workspace_repo)
+            .oneshot(
+                Request::builder()
+                    .uri("/whoami")
+                    .header(header::AUTHORIZATION, format!("Bearer {}", token))
+                    .header(WORKSPACE_ID_HEADER, Uuid::now_v7().to_string())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        // UUIDでないヘッダーは400
+        let response = protected_router(keys, MockWorkspaceRepository::new())
+            .oneshot(
+                Request::builder()
+                    .uri("/whoami")
+                    .header(header::AUTHORIZATION, format!("Bearer {}", token))
+                    .header(WORKSPACE_ID_HEADER, "not-a-uuid")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_missing_or_invalid_token() {
+        let keys = JwtKeys::hs256(b"secret");
+        let token = keys.issue_access_token(Uuid::now_v7(), Utc::now()).unwrap();
+
+        for authorization in [
+            None,
+            Some("Bearer not-a-jwt".to_string()),
+            Some(format!("Basic {}", token)),
+        ] {
+            let response = protected_router(keys.clone(), member_everywhere())
+                .oneshot(request(authorization.as_deref()))
+                .await
+                .unwrap();
+
+            // 検証
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+            assert_eq!(response.headers()[header::WWW_AUTHENTICATE], "Bearer");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_accepts_valid_api_key() {
+        let user_id = Uuid::now_v7();
+        let workspace_id = Uuid::now_v7();
+        let key = "tk_0a1b2c3d4e5f_secret";
+
+        // モックリポジトリの作成
+        let mut api_key_repo = MockApiKeyRepository::new();
+        api_key_repo
+            .expect_find_by_prefix()
+            .with(eq("0a1b2c3d4e5f".to_string()))
+            .times(1)
+            .returning(move |prefix| {
+                Ok(Some(ApiKey::new(
+                    user_id,
+                    workspace_id,
+                    "ci".to_string(),
+                    prefix,
+                    hash_token(key),
+                    vec![ApiKeyScope::TasksWrite],
+                    Utc::now() + Duration::days(1),
+                )))
+            });
+        api_key_repo
+            .expect_touch()
+            .times(1)
+            .returning(|_, _| Ok(()));
+        let mut workspace_repo = MockWorkspaceRepository::new();
+        workspace_repo
+            .expect_find_role()
+            .with(eq(workspace_id), eq(user_id))
+            .times(1)
+            .returning(|_, _| Ok(Some(WorkspaceRole::Member)));
+
+        // ワークスペースはキーから決まるため、JWT用のワークスペースの解決は行わない
+        let response = protected_router_with_api_keys(
+            JwtKeys::hs256(b"secret"),
+            MockWorkspaceRepository::new(),
+            ApiKeyUsecase::new(api_key_repo, workspace_repo),
+        )
+        .oneshot(
+            Request::builder()
+                .uri("/whoami")
+                .header(API_KEY_HEADER, key)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+        // 検証
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            body_string(response).await,
+            format!("{} {} Member", user_id, workspace_id)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rejects_unknown_api_key() {
+        // モックリポジトリの作成
+        let mut api_key_repo = MockApiKeyRepository::new();
+        api_key_repo
+            .expect_find_by_prefix()
+            .times(1)
+            .returning(|_| Ok(None));
+
+        let response = protected_router_with_api_keys(
+            JwtKeys::hs256(b"secret"),
+            MockWorkspaceRepository::new(),
+            ApiKeyUsecase::new(api_key_repo, MockWorkspaceRepository::new()),
+        )
+        .oneshot(
+            Request::builder()
+                .uri("/whoami")
+                .header(API_KEY_HEADER, "tk_0a1b2c3d4e5f_secret")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+        // 検証
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_session_cookie_allows_safe_requests_without_csrf_token() {
+        let user_id = Uuid::now_v7();
+
+        // 直前にアクセスのあったセッション（最終アクセス日時は更新しない）
+        let response = protected_router_with(
+            JwtKeys::hs256(b"secret"),
+            member_everywhere(),
+            ApiKeyUsecase::new(MockApiKeyRepository::new(), MockWorkspaceRepository::new()),
+            session_repo_with(user_id, Utc::now()),
+        )
+        .oneshot(cookie_request(Method::GET, None))
+        .await
+        .unwrap();
+
+        // 検証
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            body_string(response).await,
+            format!("{} {} Member", user_id, user_id)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_session_cookie_requires_csrf_token_for_unsafe_requests() {
+        let user_id = Uuid::now_v7();
+
+        for (csrf_token, expected) in [
+            (None, StatusCode::FORBIDDEN),
+            (Some("wrong-token"), StatusCode::FORBIDDEN),
+            (Some(CSRF_TOKEN), StatusCode::OK),
+        ] {
+            let response = protected_router_with(
+                JwtKeys::hs256(b"secret"),
+                member_everywhere(),
+                ApiKeyUsecase::new(MockApiKeyRepository::new(), MockWorkspaceRepository::new()),
+                session_repo_with(user_id, Utc::now()),
+            )
+            .oneshot(cookie_request(Method::POST, csrf_token))
+            .await
+            .unwrap();
+
+            // 検証
+            assert_eq!(response.status(), expected, "{:?}", csrf_token);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rejects_expired_session_cookie() {
+        // アイドルタイムアウトを過ぎたセッションは破棄する
+        let mut session_repo = session_repo_with(
+            Uuid::now_v7(),
+            Utc::now() - SESSION_IDLE_TIMEOUT - Duration::minutes(1),
+        );
+        session_repo
+            .expect_delete_by_token_hash()
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let response = protected_router_with(
+            JwtKeys::hs256(b"secret"),
+            MockWorkspaceRepository::new(),
+            ApiKeyUsecase::new(MockApiKeyRepository::new(), MockWorkspaceRepository::new()),
+            session_repo,
+        )
+        .oneshot(cookie_request(Method::GET, None))
+        .await
+        .unwrap();
+
+        // 検証
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_bearer_token_does_not_fall_back_to_session_cookie() {
+        // Authorization ヘッダーがあればセッション Cookie は調べない
+        let response = protected_router(JwtKeys::hs256(b"secret"), MockWorkspaceRepository::new())
+            .oneshot(
+                Request::builder()
+                    .uri("/whoami")
+                    .header(header::AUTHORIZATION, "Bearer invalid")
+                    .header(
+                        header::COOKIE,
+                        format!("{}={}", SESSION_COOKIE, SESSION_TOKEN),
+                    )
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // 検証
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_path_is_not_found() {
+        // route_layer は存在しないパスには適用されない
+        let response = protected_router(JwtKeys::hs256(b"secret"), member_everywhere())
+            .oneshot(
+                Request::builder()
+                    .uri("/missing")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
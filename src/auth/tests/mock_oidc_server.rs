@@ -0,0 +1,233 @@
+use crate::auth::oidc::OidcConfig;
+use crate::models::oidc::code_challenge;
+use axum::{
+    extract::{Form, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Redirect},
+    routing::{get, post},
+    Json, Router,
+};
+use chrono::Utc;
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::net::TcpListener;
+
+// テスト用の鍵（テスト以外では使わない）
+const JWKS: &str = include_str!("fixtures/jwks.json");
+pub const KEY_2025_05: &[u8] = include_bytes!("fixtures/key-2025-05.pem");
+pub const KEY_2025_06: &[u8] = include_bytes!("fixtures/key-2025-06.pem");
+
+pub const CLIENT_ID: &str = "task-api";
+pub const CLIENT_SECRET: &str = "task-api-secret";
+pub const REDIRECT_URL: &str = "http://localhost:3000/auth/oidc/callback";
+
+pub fn jwks() -> JwkSet {
+    serde_json::from_str(JWKS).unwrap()
+}
+
+// 発行済みの認可コードに紐付く、PKCE の code_challenge と ID トークン
+struct PendingCode {
+    code_challenge: String,
+    id_token: String,
+}
+
+#[derive(Clone)]
+struct ServerState {
+    issuer: String,
+    jwks: Arc<Mutex<JwkSet>>,
+    codes: Arc<Mutex<HashMap<String, PendingCode>>>,
+    // /authorize でログインしたことにするユーザーのクレーム
+    login_claims: Arc<Mutex<Value>>,
+}
+
+// 統合テスト用のローカルのOIDCプロバイダー（discovery・認可・トークン・JWKS のエンドポイントを持つ）
+// 実際の IdP と同様に、トークンエンドポイントでは認可コードと PKCE のコード検証子を照合する
+pub struct MockOidcServer {
+    pub issuer: String,
+    state: ServerState,
+}
+
+impl MockOidcServer {
+    pub async fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let issuer = format!("http://{}", listener.local_addr().unwrap());
+        let state = ServerState {
+            issuer: issuer.clone(),
+            jwks: Arc::new(Mutex::new(jwks())),
+            codes: Arc::new(Mutex::new(HashMap::new())),
+            login_claims: Arc::new(Mutex::new(json!({
+                "sub": "user-1",
+                "email": "alice@example.com",
+                "email_verified": true
+            }))),
+        };
+        let app = Router::new()
+            .route("/.well-known/openid-configuration", get(discovery))
+            .route("/jwks", get(jwks_endpoint))
+            .route("/authorize", get(authorize))
+            .route("/token", post(token))
+            .with_state(state.clone());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        Self { issuer, state }
+    }
+
+    pub fn config(&self) -> OidcConfig {
+        OidcConfig {
+            issuer_url: self.issuer.clone(),
+            client_id: CLIENT_ID.to_string(),
+            client_secret: Some(CLIENT_SECRET.to_string()),
+            redirect_url: REDIRECT_URL.to_string(),
+            scopes: "openid email".to_string(),
+        }
+    }
+
+    // JWKS で公開する鍵を差し替える（鍵のローテーション）
+    pub fn set_jwks(&self, jwks: JwkSet) {
+        *self.state.jwks.lock().unwrap() = jwks;
+    }
+
+    // /authorize でログインしたことにするユーザーのクレームを差し替える
+    pub fn set_login_claims(&self, claims: Value) {
+        *self.state.login_claims.lock().unwrap() = claims;
+    }
+
+    // iss・aud・exp・iat を補った ID トークンのクレーム（claims で上書きできる）
+    pub fn claims(&self, claims: Value) -> Value {
+        default_claims(&self.issuer, claims)
+    }
+
+    // 認可コードを発行する（ユーザーがプロバイダーでログインを済ませたことにする）
+    pub fn issue_code(&self, code_challenge: &str, id_token: String) -> String {
+        issue_code(&self.state, code_challenge, id_token)
+    }
+}
+
+fn default_claims(issuer: &str, overrides: Value) -> Value {
+    let now = Utc::now().timestamp();
+    let mut claims = json!({
+        "iss": issuer,
+        "aud": CLIENT_ID,
+        "iat": now,
+        "exp": now + 300,
+    });
+    for (key, value) in overrides.as_object().unwrap() {
+        claims[key] = value.clone();
+    }
+    claims
+}
+
+// テスト用の鍵で ID トークンに署名する
+pub fn sign(algorithm: Algorithm, kid: &str, key: &[u8], claims: &Value) -> String {
+    let mut header = Header::new(algorithm);
+    header.kid = Some(kid.to_string());
+    let key = match algorithm {
+        Algorithm::HS256 => EncodingKey::from_secret(key),
+        _ => EncodingKey::from_rsa_pem(key).unwrap(),
+    };
+    encode(&header, claims, &key).unwrap()
+}
+
+fn issue_code(state: &ServerState, code_challenge: &str, id_token: String) -> String {
+    let code = uuid::Uuid::now_v7().to_string();
+    state.codes.lock().unwrap().insert(
+        code.clone(),
+        PendingCode {
+            code_challenge: code_challenge.to_string(),
+            id_token,
+        },
+    );
+    code
+}
+
+async fn discovery(State(state): State<ServerState>) -> impl IntoResponse {
+    Json(json!({
+        "issuer": state.issuer,
+        "authorization_endpoint": format!("{}/authorize", state.issuer),
+        "token_endpoint": format!("{}/token", state.issuer),
+        "jwks_uri": format!("{}/jwks", state.issuer),
+        "response_types_supported": ["code"],
+        "code_challenge_methods_supported": ["S256"]
+    }))
+}
+
+async fn jwks_endpoint(State(state): State<ServerState>) -> impl IntoResponse {
+    Json(state.jwks.lock().unwrap().clone())
+}
+
+#[derive(Deserialize)]
+struct AuthorizeQuery {
+    client_id: String,
+    redirect_uri: String,
+    state: String,
+    nonce: String,
+    code_challenge: String,
+    code_challenge_method: String,
+}
+
+// ログイン画面を省略し、すぐに認可コードを付けてコールバックへリダイレクトする
+async fn authorize(
+    State(state): State<ServerState>,
+    Query(query): Query<AuthorizeQuery>,
+) -> Result<impl IntoResponse, StatusCode> {
+    if query.client_id != CLIENT_ID || query.code_challenge_method != "S256" {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let mut claims = state.login_claims.lock().unwrap().clone();
+    claims["nonce"] = json!(query.nonce);
+    let id_token = sign(
+        Algorithm::RS256,
+        "2025-05",
+        KEY_2025_05,
+        &default_claims(&state.issuer, claims),
+    );
+    let code = issue_code(&state, &query.code_challenge, id_token);
+    Ok(Redirect::to(&format!(
+        "{}?code={}&state={}",
+        query.redirect_uri, code, query.state
+    )))
+}
+
+#[derive(Deserialize)]
+struct TokenRequest {
+    grant_type: String,
+    code: String,
+    client_id: String,
+    client_secret: Option<String>,
+    code_verifier: String,
+}
+
+async fn token(
+    State(state): State<ServerState>,
+    Form(request): Form<TokenRequest>,
+) -> impl IntoResponse {
+    let invalid_grant = (
+        StatusCode::BAD_REQUEST,
+        Json(json!({ "error": "invalid_grant" })),
+    );
+    if request.grant_type != "authorization_code"
+        || request.client_id != CLIENT_ID
+        || request.client_secret.as_deref() != Some(CLIENT_SECRET)
+    {
+        return invalid_grant;
+    }
+    // 認可コードは一度しか使えない
+    let Some(pending) = state.codes.lock().unwrap().remove(&request.code) else {
+        return invalid_grant;
+    };
+    if code_challenge(&request.code_verifier) != pending.code_challenge {
+        return invalid_grant;
+    }
+    (
+        StatusCode::OK,
+        Json(json!({
+            "access_token": "provider-access-token",
+            "token_type": "Bearer",
+            "expires_in": 300,
+            "id_token": pending.id_token
+        })),
+    )
+}
@@ -0,0 +1,4 @@
+pub mod jwt_tests;
+pub mod middleware_tests;
+pub mod mock_oidc_server;
+pub mod oidc_flow_tests;
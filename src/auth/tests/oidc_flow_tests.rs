@@ -0,0 +1,206 @@
+use crate::auth::jwt::JwtKeys;
+use crate::auth::tests::mock_oidc_server::{MockOidcServer, REDIRECT_URL};
+use crate::infrastructure::oidc_provider::OidcProviderImpl;
+use crate::models::oidc::OidcLoginState;
+use crate::repositories::oidc_repository::MockOidcRepository;
+use crate::repositories::refresh_token_repository::MockRefreshTokenRepository;
+use crate::repositories::user_repository::MockUserRepository;
+use crate::routes;
+use crate::usecase::oidc_usecase::OidcUsecase;
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    Router,
+};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tower::ServiceExt;
+
+const JWT_SECRET: &[u8] = b"secret";
+
+// ログイン状態をメモリに保存し、初回ログインのアカウントを作成するモック
+fn in_memory_oidc_repo() -> MockOidcRepository {
+    let states: Arc<Mutex<HashMap<String, OidcLoginState>>> = Arc::default();
+    let mut oidc_repo = MockOidcRepository::new();
+    let saved = states.clone();
+    oidc_repo.expect_save_login_state().returning(move |state| {
+        saved
+            .lock()
+            .unwrap()
+            .insert(state.state_hash.clone(), state);
+        Ok(())
+    });
+    oidc_repo
+        .expect_take_login_state()
+        .returning(move |state_hash| Ok(states.lock().unwrap().remove(&state_hash)));
+    oidc_repo.expect_find_identity().returning(|_, _| Ok(None));
+    oidc_repo.expect_create_identity().returning(|_| Ok(()));
+    oidc_repo
+}
+
+// テスト用のプロバイダーを使う OIDC ログインのルーター
+fn oidc_router(server: &MockOidcServer) -> Router {
+    let mut user_repo = MockUserRepository::new();
+    user_repo.expect_find_by_email().returning(|_| Ok(None));
+    user_repo.expect_create().returning(Ok);
+    let mut refresh_token_repo = MockRefreshTokenRepository::new();
+    refresh_token_repo.expect_create().returning(|_| Ok(()));
+    routes::oidc::router(OidcUsecase::new(
+        OidcProviderImpl::new(server.config()).unwrap(),
+        in_memory_oidc_repo(),
+        user_repo,
+        refresh_token_repo,
+        JwtKeys::hs256(JWT_SECRET),
+    ))
+}
+
+fn get(uri: &str, cookie: Option<&str>) -> Request<Body> {
+    let mut builder = Request::builder().uri(uri);
+    if let Some(cookie) = cookie {
+        builder = builder.header(header::COOKIE, cookie);
+    }
+    builder.body(Body::empty()).unwrap()
+}
+
+fn location(headers: &axum::http::HeaderMap) -> String {
+    headers[header::LOCATION].to_str().unwrap().to_string()
+}
+
+// ブラウザの代わりにプロバイダーの認可エンドポイントを開き、コールバックのパスとクエリを返す
+async fn authorize_at_provider(authorization_url: &str) -> String {
+    let client = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .unwrap();
+    let response = client.get(authorization_url).send().await.unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::SEE_OTHER);
+    let callback = response.headers()[reqwest::header::LOCATION]
+        .to_str()
+        .unwrap()
+        .to_string();
+    let query = callback
+        .strip_prefix(REDIRECT_URL)
+        .expect("redirects to the registered callback");
+    format!("/auth/oidc/callback{}", query)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_login_with_mock_provider() {
+        let server = MockOidcServer::start().await;
+        let router = oidc_router(&server);
+
+        // ログインを開始すると、state のクッキーを付けてプロバイダーへリダイレクトする
+        let response = router
+            .clone()
+            .oneshot(get("/auth/oidc/login", None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        let set_cookie = response.headers()[header::SET_COOKIE].to_str().unwrap();
+        assert!(set_cookie.contains("HttpOnly"));
+        let cookie = set_cookie.split(';').next().unwrap().to_string();
+        let authorization_url = location(response.headers());
+        assert!(authorization_url.starts_with(&format!("{}/authorize?", server.issuer)));
+
+        // プロバイダーでログインしてコールバックへ戻る
+        let callback = authorize_at_provider(&authorization_url).await;
+        let response = router
+            .clone()
+            .oneshot(get(&callback, Some(&cookie)))
+            .await
+            .unwrap();
+
+        // 検証
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers()[header::SET_COOKIE]
+            .to_str()
+            .unwrap()
+            .contains("Max-Age=0"));
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let tokens: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(tokens["token_type"], "Bearer");
+        assert!(JwtKeys::hs256(JWT_SECRET)
+            .verify_access_token(tokens["access_token"].as_str().unwrap())
+            .await
+            .is_ok());
+
+        // 同じコールバックは二度と使えない
+        let response = router.oneshot(get(&callback, Some(&cookie))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_callback_requires_state_cookie() {
+        let server = MockOidcServer::start().await;
+        let router = oidc_router(&server);
+
+        let response = router
+            .clone()
+            .oneshot(get("/auth/oidc/login", None))
+            .await
+            .unwrap();
+        let callback = authorize_at_provider(&location(response.headers())).await;
+
+        // ログインを開始したブラウザ以外（クッキーがない・一致しない）ではログインできない
+        for cookie in [None, Some("oidc_state=another-state")] {
+            let response = router
+                .clone()
+                .oneshot(get(&callback, cookie))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unverified_email_is_forbidden() {
+        let server = MockOidcServer::start().await;
+        server.set_login_claims(json!({
+            "sub": "user-2",
+            "email": "mallory@example.com",
+            "email_verified": false
+        }));
+        let router = oidc_router(&server);
+
+        let response = router
+            .clone()
+            .oneshot(get("/auth/oidc/login", None))
+            .await
+            .unwrap();
+        let cookie = response.headers()[header::SET_COOKIE]
+            .to_str()
+            .unwrap()
+            .split(';')
+            .next()
+            .unwrap()
+            .to_string();
+        let callback = authorize_at_provider(&location(response.headers())).await;
+        let response = router.oneshot(get(&callback, Some(&cookie))).await.unwrap();
+
+        // 検証
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_provider_error_is_unauthorized() {
+        let server = MockOidcServer::start().await;
+
+        // ユーザーがプロバイダーで同意を拒否した場合
+        let response = oidc_router(&server)
+            .oneshot(get(
+                "/auth/oidc/callback?error=access_denied&state=state-1",
+                Some("oidc_state=state-1"),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+}
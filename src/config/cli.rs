@@ -0,0 +1,119 @@
+use std::path::PathBuf;
+
+// コマンドライン引数
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CliArgs {
+    // --config <PATH>: TOML の設定ファイル（省略時は CONFIG_FILE 環境変数）
+    pub config_path: Option<PathBuf>,
+    // --print-config: 秘密の値を伏せた設定を出力して終了する
+    pub print_config: bool,
+    pub command: Command,
+}
+
+// サブコマンド（省略時は serve）
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Command {
+    // --migrate-on-start: 起動する前に未適用のマイグレーションを適用する
+    Serve { migrate_on_start: bool },
+    Migrate(MigrateCommand),
+    // 開発用のユーザーとタスクを作成する
+    Seed,
+    // 設定を検証して終了する
+    CheckConfig,
+}
+
+impl Default for Command {
+    fn default() -> Self {
+        Self::Serve {
+            migrate_on_start: false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MigrateCommand {
+    Up,
+    // --to <VERSION>: VERSION より新しいものをすべて戻す（省略時は最後に適用した1つだけ）
+    Down { to: Option<i64> },
+    Status,
+}
+
+pub const USAGE: &str = "\
+usage: rust-on-docker [--config <PATH>] [--print-config] [<COMMAND>]
+
+commands:
+  serve [--migrate-on-start]     start the server (default)
+  migrate up                     apply pending migrations
+  migrate down [--to <VERSION>]  revert the latest migration, or all migrations newer than VERSION
+  migrate status                 list migrations and whether they are applied
+  seed                           create a demo user and tasks for development
+  check-config                   validate the configuration and exit";
+
+impl CliArgs {
+    // プログラム名を除いた引数を解釈する
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut parsed = Self::default();
+        let mut words = Vec::new();
+        let mut migrate_on_start = false;
+        let mut down_to = None;
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--print-config" => parsed.print_config = true,
+                "--migrate-on-start" => migrate_on_start = true,
+                "--config" => {
+                    let path = args
+                        .next()
+                        .ok_or_else(|| format!("--config requires a path\n{}", USAGE))?;
+                    parsed.config_path = Some(PathBuf::from(path));
+                }
+                "--to" => {
+                    let version = args
+                        .next()
+                        .ok_or_else(|| format!("--to requires a version\n{}", USAGE))?;
+                    down_to = Some(parse_version(&version)?);
+                }
+                _ if !arg.starts_with('-') => words.push(arg),
+                _ => match arg.strip_prefix("--config=") {
+                    Some(path) if !path.is_empty() => {
+                        parsed.config_path = Some(PathBuf::from(path))
+                    }
+                    _ => return Err(format!("unknown argument: {}\n{}", arg, USAGE)),
+                },
+            }
+        }
+
+        let words: Vec<&str> = words.iter().map(String::as_str).collect();
+        parsed.command = match words.as_slice() {
+            [] | ["serve"] => Command::Serve { migrate_on_start },
+            ["migrate", "up"] => Command::Migrate(MigrateCommand::Up),
+            ["migrate", "down"] => Command::Migrate(MigrateCommand::Down { to: down_to }),
+            ["migrate", "status"] => Command::Migrate(MigrateCommand::Status),
+            ["seed"] => Command::Seed,
+            ["check-config"] => Command::CheckConfig,
+            _ => return Err(format!("unknown command: {}\n{}", words.join(" "), USAGE)),
+        };
+        // サブコマンドごとのオプションは、そのサブコマンドでだけ受け付ける
+        if migrate_on_start && !matches!(parsed.command, Command::Serve { .. }) {
+            return Err(format!("--migrate-on-start is only for serve\n{}", USAGE));
+        }
+        if down_to.is_some()
+            && !matches!(
+                parsed.command,
+                Command::Migrate(MigrateCommand::Down { .. })
+            )
+        {
+            return Err(format!("--to is only for migrate down\n{}", USAGE));
+        }
+        Ok(parsed)
+    }
+}
+
+// マイグレーションのバージョン（ファイル名の先頭の数字。0 はすべて戻す）
+fn parse_version(version: &str) -> Result<i64, String> {
+    version
+        .parse()
+        .ok()
+        .filter(|version| *version >= 0)
+        .ok_or_else(|| format!("invalid migration version: {}\n{}", version, USAGE))
+}
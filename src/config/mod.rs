@@ -0,0 +1,5 @@
+pub mod cli;
+pub mod settings;
+
+#[cfg(test)]
+pub mod tests;
@@ -0,0 +1,835 @@
+use axum::http::HeaderName;
+use reqwest::Url;
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::HashSet;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use thiserror::Error;
+use tracing_subscriber::EnvFilter;
+
+// --print-config やログで秘密の値の代わりに出力する文字列
+const REDACTED: &str = "[REDACTED]";
+
+// OIDC の既定のスコープ（openid は必須、メールアドレスは既存ユーザーとの紐付けに使う）
+const DEFAULT_OIDC_SCOPES: &str = "openid email profile";
+
+// アプリの設定（既定値 → TOML ファイル → 環境変数 の順に読み込み、後のものが優先される）
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub server: ServerSettings,
+    pub database: DatabaseSettings,
+    pub log: LogSettings,
+    pub cors: CorsSettings,
+    pub features: FeatureSettings,
+    pub jwt: JwtSettings,
+    pub oidc: OidcSettings,
+    pub rate_limit: RateLimitSettings,
+    pub health: HealthSettings,
+    pub tracing: TracingSettings,
+    pub access_log: AccessLogSettings,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerSettings {
+    pub host: String,
+    pub port: u16,
+    // リクエストの処理がこの秒数を超えたら timeout_status を返す
+    pub request_timeout_secs: u64,
+    // タイムアウトしたリクエストに返すステータス（408 または 503）
+    pub timeout_status: u16,
+    // 終了時に処理中のリクエストを待つ秒数の上限（超えた場合は接続を切る）
+    pub shutdown_timeout_secs: u64,
+    // 終了時に /readyz を 503 にしてから新しい接続を止めるまでの秒数（0 の場合はすぐに止める）
+    pub shutdown_delay_secs: u64,
+    // リクエストの本文の大きさの上限（圧縮されている場合は展開後の大きさ、超えた場合は 413 を返す）
+    pub max_body_bytes: usize,
+    // Strict-Transport-Security の max-age（0 の場合は付けない。HTTPS で公開する場合だけ設定する）
+    pub hsts_max_age_secs: u64,
+}
+
+impl Default for ServerSettings {
+    fn default() -> Self {
+        Self {
+            host: "0.0.0.0".to_string(),
+            port: 3000,
+            request_timeout_secs: 30,
+            timeout_status: 408,
+            shutdown_timeout_secs: 30,
+            shutdown_delay_secs: 5,
+            max_body_bytes: 2 * 1024 * 1024,
+            hsts_max_age_secs: 0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseSettings {
+    // 必須（既定値はない）
+    pub url: Option<SecretUrl>,
+    pub max_connections: u32,
+    pub min_connections: u32,
+    // プールから接続を取得するまでの待ち時間の上限
+    pub acquire_timeout_secs: u64,
+}
+
+impl Default for DatabaseSettings {
+    fn default() -> Self {
+        Self {
+            url: None,
+            max_connections: 10,
+            min_connections: 0,
+            acquire_timeout_secs: 5,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    // 人が読むための1行のテキスト
+    #[default]
+    Text,
+    // ログ基盤に取り込むための1行1件の JSON
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct LogSettings {
+    pub format: LogFormat,
+    // tracing の EnvFilter の書式（例: "info,sqlx=warn"）
+    pub filter: String,
+}
+
+impl Default for LogSettings {
+    fn default() -> Self {
+        Self {
+            format: LogFormat::Text,
+            filter: "info".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct CorsSettings {
+    // ブラウザから呼び出すことを許可するオリジン（例: "https://app.example.com"）
+    // 空の場合は CORS ヘッダーを付けない（同じオリジンからだけ呼び出せる）
+    pub allowed_origins: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct FeatureSettings {
+    // /swagger-ui と /api-docs/openapi.json を公開する
+    pub swagger_ui: bool,
+    pub rate_limit: bool,
+    // /metrics を公開する（認証なしのため、外部に公開しない場合は無効にするかプロキシで塞ぐ）
+    pub metrics: bool,
+    // リクエストごとにアクセスログを出力する
+    pub access_log: bool,
+    // レスポンスを圧縮し（gzip / br / zstd）、圧縮されたリクエストの本文を展開する
+    pub compression: bool,
+}
+
+impl Default for FeatureSettings {
+    fn default() -> Self {
+        Self {
+            swagger_ui: true,
+            rate_limit: true,
+            metrics: true,
+            access_log: true,
+            compression: true,
+        }
+    }
+}
+
+// private_key_path を指定した場合は RS256、secret だけの場合は HS256 で署名する
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct JwtSettings {
+    pub secret: Option<SecretString>,
+    pub private_key_path: Option<PathBuf>,
+    pub key_id: Option<String>,
+    pub jwks_path: Option<PathBuf>,
+}
+
+// issuer_url を指定した場合だけ OIDC ログインを有効にする
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct OidcSettings {
+    pub issuer_url: Option<String>,
+    pub client_id: Option<String>,
+    // 公開クライアント（PKCE のみ）の場合は指定しない
+    pub client_secret: Option<SecretString>,
+    pub redirect_url: Option<String>,
+    pub scopes: String,
+}
+
+impl Default for OidcSettings {
+    fn default() -> Self {
+        Self {
+            issuer_url: None,
+            client_id: None,
+            client_secret: None,
+            redirect_url: None,
+            scopes: DEFAULT_OIDC_SCOPES.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RateLimitStoreKind {
+    #[default]
+    Memory,
+    Redis,
+}
+
+impl FromStr for RateLimitStoreKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "memory" => Ok(Self::Memory),
+            "redis" => Ok(Self::Redis),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct RateLimitSettings {
+    // 複数のレプリカで上限を共有する場合は redis を使う
+    pub store: RateLimitStoreKind,
+    pub redis_url: Option<SecretUrl>,
+    // ルートごとの上限がないリクエストの上限
+    pub per_minute: u32,
+    // X-Forwarded-For の先頭をクライアントのIPとして使う（リバースプロキシの後ろで動かす場合だけ有効にする）
+    pub trust_forwarded_for: bool,
+    // ルートごとの上限（TOML で指定した場合は既定のルートの一覧をすべて置き換える）
+    pub routes: Vec<RouteQuotaSettings>,
+}
+
+impl Default for RateLimitSettings {
+    fn default() -> Self {
+        let route = |method: &str, path: &str, per_minute| RouteQuotaSettings {
+            method: method.to_string(),
+            path: path.to_string(),
+            per_minute,
+        };
+        Self {
+            store: RateLimitStoreKind::Memory,
+            redis_url: None,
+            per_minute: 120,
+            trust_forwarded_for: false,
+            routes: vec![
+                // パスワードの総当たりを防ぐ（認証前なのでIPごとに数える）
+                route("POST", "/auth/register", 10),
+                route("POST", "/auth/login", 10),
+                route("POST", "/auth/sessions", 10),
+                route("POST", "/auth/refresh", 30),
+                route("POST", "/tasks", 30),
+                route("POST", "/tasks/import", 5),
+                route("GET", "/tasks/export", 10),
+            ],
+        }
+    }
+}
+
+// 特定のルートの上限（ほかのルートとは別のバケットで数える）
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RouteQuotaSettings {
+    pub method: String,
+    // ルーターに登録したパス（例: "/tasks/:id"）
+    pub path: String,
+    pub per_minute: u32,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct HealthSettings {
+    // /readyz の確認ごとの待ち時間の上限
+    pub check_timeout_ms: u64,
+    // /readyz で users API も確認する（外部APIの障害で全レプリカが外れないよう既定では確認しない）
+    pub check_users_api: bool,
+}
+
+impl Default for HealthSettings {
+    fn default() -> Self {
+        Self {
+            check_timeout_ms: 2000,
+            check_users_api: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct TracingSettings {
+    // OTLP/HTTP でスパンを送る先（例: http://otel-collector:4318/v1/traces）
+    // 設定しない場合は送らないが、traceparent の伝播は行う
+    pub otlp_endpoint: Option<String>,
+    // 親スパンがないリクエストをサンプリングする割合（0.0〜1.0、親がある場合は親の判断に従う）
+    pub sampling_ratio: f64,
+    // service.name リソース属性
+    pub service_name: String,
+}
+
+impl Default for TracingSettings {
+    fn default() -> Self {
+        Self {
+            otlp_endpoint: None,
+            sampling_ratio: 1.0,
+            service_name: "rust-on-docker".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct AccessLogSettings {
+    // リクエストとレスポンスのヘッダーも出力する
+    pub headers: bool,
+    // 値を伏せるヘッダー（大文字・小文字は区別しない）
+    pub redact_headers: Vec<String>,
+    // 値を伏せるクエリパラメータ（大文字・小文字は区別しない）
+    pub redact_query_params: Vec<String>,
+    // 本文を出力する場合に値を伏せる JSON のキーとフォームの項目
+    pub redact_body_fields: Vec<String>,
+    // 本文も出力するリクエストの割合（0.0〜1.0、調査用で既定では出力しない）
+    pub body_sample_ratio: f64,
+    // 出力する本文の長さの上限（バイト）
+    pub max_body_bytes: usize,
+}
+
+impl Default for AccessLogSettings {
+    fn default() -> Self {
+        let list = |items: &[&str]| items.iter().map(|item| item.to_string()).collect();
+        Self {
+            headers: false,
+            redact_headers: list(&[
+                "authorization",
+                "cookie",
+                "set-cookie",
+                "x-api-key",
+                "x-csrf-token",
+            ]),
+            // カレンダーフィードのトークンと、OIDC のコールバックの認可コード・state
+            redact_query_params: list(&["token", "code", "state"]),
+            redact_body_fields: list(&[
+                "password",
+                "current_password",
+                "new_password",
+                "token",
+                "access_token",
+                "refresh_token",
+                "api_key",
+                "key",
+                "secret",
+                "client_secret",
+            ]),
+            body_sample_ratio: 0.0,
+            max_body_bytes: 4096,
+        }
+    }
+}
+
+// 出力しない秘密の値（JWT の共有鍵など）
+#[derive(Clone, Deserialize, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct SecretString(String);
+
+impl SecretString {
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for SecretString {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.to_string()))
+    }
+}
+
+impl fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl Serialize for SecretString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(REDACTED)
+    }
+}
+
+// パスワードを含むことがある接続URL（出力時はパスワードだけを伏せる）
+#[derive(Clone, Deserialize, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct SecretUrl(String);
+
+impl SecretUrl {
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    fn redacted(&self) -> String {
+        match Url::parse(&self.0) {
+            Ok(mut url) => {
+                if url.password().is_some() {
+                    let _ = url.set_password(Some("***"));
+                }
+                url.to_string()
+            }
+            Err(_) => REDACTED.to_string(),
+        }
+    }
+}
+
+impl FromStr for SecretUrl {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.to_string()))
+    }
+}
+
+impl fmt::Debug for SecretUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.redacted())
+    }
+}
+
+impl Serialize for SecretUrl {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.redacted())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("failed to read config file {path}: {source}")]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("invalid config file {path}: {message}")]
+    Parse { path: PathBuf, message: String },
+
+    // 見つかった問題をまとめて報告する
+    #[error("invalid configuration:\n  - {}", .0.join("\n  - "))]
+    Invalid(Vec<String>),
+}
+
+impl Config {
+    // 既定値に TOML ファイル（指定した場合）と環境変数を重ねる（検証は validate で行う）
+    pub fn load(
+        path: Option<&Path>,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self, ConfigError> {
+        let mut config = match path {
+            Some(path) => {
+                let content =
+                    std::fs::read_to_string(path).map_err(|source| ConfigError::Read {
+                        path: path.to_path_buf(),
+                        source,
+                    })?;
+                Self::from_toml(&content).map_err(|e| ConfigError::Parse {
+                    path: path.to_path_buf(),
+                    message: e.message().to_string(),
+                })?
+            }
+            None => Self::default(),
+        };
+        config.apply_env(&env)?;
+        Ok(config)
+    }
+
+    // TOML に書かれていない項目は既定値になる（知らない項目はエラーにする）
+    pub fn from_toml(content: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(content)
+    }
+
+    // 秘密の値を伏せた TOML（--print-config で出力する）
+    pub fn to_redacted_toml(&self) -> String {
+        toml::to_string_pretty(self).unwrap_or_default()
+    }
+
+    // 環境変数で上書きする（空の値は設定されていないものとして扱う）
+    fn apply_env(&mut self, env: &dyn Fn(&str) -> Option<String>) -> Result<(), ConfigError> {
+        let mut env = EnvOverrides {
+            lookup: env,
+            problems: Vec::new(),
+        };
+        env.parse("HOST", &mut self.server.host);
+        env.parse("PORT", &mut self.server.port);
+        env.parse(
+            "REQUEST_TIMEOUT_SECS",
+            &mut self.server.request_timeout_secs,
+        );
+        env.parse("REQUEST_TIMEOUT_STATUS", &mut self.server.timeout_status);
+        env.parse(
+            "SHUTDOWN_TIMEOUT_SECS",
+            &mut self.server.shutdown_timeout_secs,
+        );
+        env.parse("SHUTDOWN_DELAY_SECS", &mut self.server.shutdown_delay_secs);
+        env.parse("MAX_BODY_BYTES", &mut self.server.max_body_bytes);
+        env.parse("HSTS_MAX_AGE_SECS", &mut self.server.hsts_max_age_secs);
+
+        env.parse_optional("DATABASE_URL", &mut self.database.url);
+        env.parse(
+            "DATABASE_MAX_CONNECTIONS",
+            &mut self.database.max_connections,
+        );
+        env.parse(
+            "DATABASE_MIN_CONNECTIONS",
+            &mut self.database.min_connections,
+        );
+        env.parse(
+            "DATABASE_ACQUIRE_TIMEOUT_SECS",
+            &mut self.database.acquire_timeout_secs,
+        );
+
+        env.parse("LOG_FORMAT", &mut self.log.format);
+        env.parse("RUST_LOG", &mut self.log.filter);
+
+        env.parse_list("CORS_ALLOWED_ORIGINS", &mut self.cors.allowed_origins);
+
+        env.parse_bool("SWAGGER_UI_ENABLED", &mut self.features.swagger_ui);
+        env.parse_bool("RATE_LIMIT_ENABLED", &mut self.features.rate_limit);
+        env.parse_bool("METRICS_ENABLED", &mut self.features.metrics);
+        env.parse_bool("ACCESS_LOG_ENABLED", &mut self.features.access_log);
+        env.parse_bool("COMPRESSION_ENABLED", &mut self.features.compression);
+
+        env.parse_optional("JWT_SECRET", &mut self.jwt.secret);
+        env.parse_optional("JWT_PRIVATE_KEY_PATH", &mut self.jwt.private_key_path);
+        env.parse_optional("JWT_KEY_ID", &mut self.jwt.key_id);
+        env.parse_optional("JWT_JWKS_PATH", &mut self.jwt.jwks_path);
+
+        env.parse_optional("OIDC_ISSUER_URL", &mut self.oidc.issuer_url);
+        env.parse_optional("OIDC_CLIENT_ID", &mut self.oidc.client_id);
+        env.parse_optional("OIDC_CLIENT_SECRET", &mut self.oidc.client_secret);
+        env.parse_optional("OIDC_REDIRECT_URL", &mut self.oidc.redirect_url);
+        env.parse("OIDC_SCOPES", &mut self.oidc.scopes);
+
+        env.parse("RATE_LIMIT_STORE", &mut self.rate_limit.store);
+        env.parse_optional("REDIS_URL", &mut self.rate_limit.redis_url);
+        env.parse("RATE_LIMIT_PER_MINUTE", &mut self.rate_limit.per_minute);
+        env.parse_bool(
+            "RATE_LIMIT_TRUST_FORWARDED_FOR",
+            &mut self.rate_limit.trust_forwarded_for,
+        );
+
+        env.parse("HEALTH_CHECK_TIMEOUT_MS", &mut self.health.check_timeout_ms);
+        env.parse_bool("HEALTH_CHECK_USERS_API", &mut self.health.check_users_api);
+
+        // OpenTelemetry の標準の環境変数名を使う
+        env.parse_optional(
+            "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT",
+            &mut self.tracing.otlp_endpoint,
+        );
+        env.parse("OTEL_TRACES_SAMPLER_ARG", &mut self.tracing.sampling_ratio);
+        env.parse("OTEL_SERVICE_NAME", &mut self.tracing.service_name);
+
+        env.parse_bool("ACCESS_LOG_HEADERS", &mut self.access_log.headers);
+        env.parse_list(
+            "ACCESS_LOG_REDACT_HEADERS",
+            &mut self.access_log.redact_headers,
+        );
+        env.parse_list(
+            "ACCESS_LOG_REDACT_QUERY_PARAMS",
+            &mut self.access_log.redact_query_params,
+        );
+        env.parse_list(
+            "ACCESS_LOG_REDACT_BODY_FIELDS",
+            &mut self.access_log.redact_body_fields,
+        );
+        env.parse(
+            "ACCESS_LOG_BODY_SAMPLE_RATIO",
+            &mut self.access_log.body_sample_ratio,
+        );
+        env.parse(
+            "ACCESS_LOG_MAX_BODY_BYTES",
+            &mut self.access_log.max_body_bytes,
+        );
+
+        if env.problems.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::Invalid(env.problems))
+        }
+    }
+
+    // 起動前に設定の問題をすべて調べる（メッセージには TOML の項目名と環境変数名を含める）
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut problems = Vec::new();
+        let mut check = |ok: bool, message: &str| {
+            if !ok {
+                problems.push(message.to_string());
+            }
+        };
+
+        check(
+            !self.server.host.trim().is_empty(),
+            "server.host (HOST) must not be empty",
+        );
+        check(
+            self.server.request_timeout_secs > 0,
+            "server.request_timeout_secs (REQUEST_TIMEOUT_SECS) must be greater than 0",
+        );
+        check(
+            matches!(self.server.timeout_status, 408 | 503),
+            "server.timeout_status (REQUEST_TIMEOUT_STATUS) must be 408 or 503",
+        );
+        check(
+            self.server.shutdown_timeout_secs > 0,
+            "server.shutdown_timeout_secs (SHUTDOWN_TIMEOUT_SECS) must be greater than 0",
+        );
+        check(
+            self.server.max_body_bytes > 0,
+            "server.max_body_bytes (MAX_BODY_BYTES) must be greater than 0",
+        );
+
+        check(
+            self.database.url.as_ref().is_some_and(|url| {
+                url.expose().starts_with("postgres://") || url.expose().starts_with("postgresql://")
+            }),
+            "database.url (DATABASE_URL) must be set to a postgres:// URL",
+        );
+        check(
+            self.database.max_connections > 0,
+            "database.max_connections (DATABASE_MAX_CONNECTIONS) must be greater than 0",
+        );
+        check(
+            self.database.min_connections <= self.database.max_connections,
+            "database.min_connections (DATABASE_MIN_CONNECTIONS) must not exceed database.max_connections",
+        );
+        check(
+            self.database.acquire_timeout_secs > 0,
+            "database.acquire_timeout_secs (DATABASE_ACQUIRE_TIMEOUT_SECS) must be greater than 0",
+        );
+
+        check(
+            EnvFilter::try_new(&self.log.filter).is_ok(),
+            "log.filter (RUST_LOG) is not a valid tracing filter",
+        );
+
+        for origin in &self.cors.allowed_origins {
+            check(
+                is_origin(origin),
+                &format!(
+                    "cors.allowed_origins (CORS_ALLOWED_ORIGINS): {:?} is not an origin like \"https://app.example.com\"",
+                    origin
+                ),
+            );
+        }
+
+        check(
+            self.jwt.secret.is_some() || self.jwt.private_key_path.is_some(),
+            "jwt.secret (JWT_SECRET) or jwt.private_key_path (JWT_PRIVATE_KEY_PATH) must be set",
+        );
+        if self.jwt.private_key_path.is_some() {
+            check(
+                self.jwt.key_id.is_some(),
+                "jwt.key_id (JWT_KEY_ID) must be set for RS256",
+            );
+            check(
+                self.jwt.jwks_path.is_some(),
+                "jwt.jwks_path (JWT_JWKS_PATH) must be set for RS256",
+            );
+        }
+
+        if self.oidc.issuer_url.is_some() {
+            check(
+                self.oidc.client_id.is_some(),
+                "oidc.client_id (OIDC_CLIENT_ID) must be set for OIDC",
+            );
+            check(
+                self.oidc.redirect_url.is_some(),
+                "oidc.redirect_url (OIDC_REDIRECT_URL) must be set for OIDC",
+            );
+        }
+
+        check(
+            self.rate_limit.per_minute > 0,
+            "rate_limit.per_minute (RATE_LIMIT_PER_MINUTE) must be greater than 0",
+        );
+        check(
+            self.rate_limit.store != RateLimitStoreKind::Redis
+                || self.rate_limit.redis_url.is_some(),
+            "rate_limit.redis_url (REDIS_URL) must be set for the redis store",
+        );
+        let mut routes = HashSet::new();
+        for route in &self.rate_limit.routes {
+            let name = format!("{} {}", route.method, route.path);
+            check(
+                is_route_method(&route.method),
+                &format!(
+                    "rate_limit.routes: {:?} must use one of the methods {}",
+                    name,
+                    ROUTE_METHODS.join(", ")
+                ),
+            );
+            check(
+                route.path.starts_with('/'),
+                &format!(
+                    "rate_limit.routes: {:?} must have a path starting with /",
+                    name
+                ),
+            );
+            check(
+                route.per_minute > 0,
+                &format!(
+                    "rate_limit.routes: {:?} must have per_minute greater than 0",
+                    name
+                ),
+            );
+            check(
+                routes.insert((route.method.to_ascii_uppercase(), route.path.as_str())),
+                &format!("rate_limit.routes: {:?} is listed more than once", name),
+            );
+        }
+
+        check(
+            self.health.check_timeout_ms > 0,
+            "health.check_timeout_ms (HEALTH_CHECK_TIMEOUT_MS) must be greater than 0",
+        );
+
+        if let Some(endpoint) = &self.tracing.otlp_endpoint {
+            check(
+                Url::parse(endpoint).is_ok_and(|url| matches!(url.scheme(), "http" | "https")),
+                "tracing.otlp_endpoint (OTEL_EXPORTER_OTLP_TRACES_ENDPOINT) must be an http:// or https:// URL",
+            );
+        }
+        check(
+            (0.0..=1.0).contains(&self.tracing.sampling_ratio),
+            "tracing.sampling_ratio (OTEL_TRACES_SAMPLER_ARG) must be between 0.0 and 1.0",
+        );
+        check(
+            !self.tracing.service_name.trim().is_empty(),
+            "tracing.service_name (OTEL_SERVICE_NAME) must not be empty",
+        );
+
+        for name in &self.access_log.redact_headers {
+            check(
+                HeaderName::from_bytes(name.as_bytes()).is_ok(),
+                &format!(
+                    "access_log.redact_headers (ACCESS_LOG_REDACT_HEADERS): {:?} is not a header name",
+                    name
+                ),
+            );
+        }
+        check(
+            (0.0..=1.0).contains(&self.access_log.body_sample_ratio),
+            "access_log.body_sample_ratio (ACCESS_LOG_BODY_SAMPLE_RATIO) must be between 0.0 and 1.0",
+        );
+        check(
+            self.access_log.max_body_bytes > 0,
+            "access_log.max_body_bytes (ACCESS_LOG_MAX_BODY_BYTES) must be greater than 0",
+        );
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::Invalid(problems))
+        }
+    }
+}
+
+// rate_limit.routes に指定できるメソッド（大文字・小文字は区別しない）
+const ROUTE_METHODS: [&str; 5] = ["GET", "POST", "PUT", "PATCH", "DELETE"];
+
+fn is_route_method(method: &str) -> bool {
+    ROUTE_METHODS
+        .iter()
+        .any(|allowed| allowed.eq_ignore_ascii_case(method))
+}
+
+// scheme://host[:port] の形式で、パスなどを含まないか
+fn is_origin(origin: &str) -> bool {
+    Url::parse(origin).is_ok_and(|url| {
+        matches!(url.scheme(), "http" | "https") && url.origin().ascii_serialization() == origin
+    })
+}
+
+// 環境変数の値を読み取り、不正な値を problems に集める
+struct EnvOverrides<'a> {
+    lookup: &'a dyn Fn(&str) -> Option<String>,
+    problems: Vec<String>,
+}
+
+impl EnvOverrides<'_> {
+    fn get(&self, name: &str) -> Option<String> {
+        (self.lookup)(name)
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    }
+
+    fn parse<T: FromStr>(&mut self, name: &str, target: &mut T) {
+        if let Some(parsed) = self.parse_value(name) {
+            *target = parsed;
+        }
+    }
+
+    fn parse_optional<T: FromStr>(&mut self, name: &str, target: &mut Option<T>) {
+        if let Some(parsed) = self.parse_value(name) {
+            *target = Some(parsed);
+        }
+    }
+
+    fn parse_value<T: FromStr>(&mut self, name: &str) -> Option<T> {
+        let value = self.get(name)?;
+        let parsed = value.parse().ok();
+        if parsed.is_none() {
+            self.problems
+                .push(format!("{}: invalid value {:?}", name, value));
+        }
+        parsed
+    }
+
+    fn parse_bool(&mut self, name: &str, target: &mut bool) {
+        if let Some(value) = self.get(name) {
+            match value.to_ascii_lowercase().as_str() {
+                "true" | "1" | "yes" => *target = true,
+                "false" | "0" | "no" => *target = false,
+                _ => self.problems.push(format!(
+                    "{}: invalid value {:?} (expected true or false)",
+                    name, value
+                )),
+            }
+        }
+    }
+
+    // カンマ区切りのリスト
+    fn parse_list(&mut self, name: &str, target: &mut Vec<String>) {
+        if let Some(value) = self.get(name) {
+            *target = value
+                .split(',')
+                .map(|item| item.trim().to_string())
+                .filter(|item| !item.is_empty())
+                .collect();
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use crate::config::cli::{CliArgs, Command, MigrateCommand};
+use std::path::PathBuf;
+
+fn parse(args: &[&str]) -> Result<CliArgs, String> {
+    CliArgs::parse(args.iter().map(|arg| arg.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse(&[]).unwrap(), CliArgs::default());
+        assert_eq!(
+            parse(&["--config", "app.toml", "--print-config"]).unwrap(),
+            CliArgs {
+                config_path: Some(PathBuf::from("app.toml")),
+                print_config: true,
+                command: Command::Serve {
+                    migrate_on_start: false,
+                },
+            }
+        );
+        assert_eq!(
+            parse(&["--config=/etc/app.toml"]).unwrap().config_path,
+            Some(PathBuf::from("/etc/app.toml"))
+        );
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(
+            parse(&["serve", "--migrate-on-start"]).unwrap().command,
+            Command::Serve {
+                migrate_on_start: true
+            }
+        );
+        assert_eq!(
+            parse(&["migrate", "up"]).unwrap().command,
+            Command::Migrate(MigrateCommand::Up)
+        );
+        assert_eq!(
+            parse(&["migrate", "down"]).unwrap().command,
+            Command::Migrate(MigrateCommand::Down { to: None })
+        );
+        assert_eq!(
+            parse(&["migrate", "down", "--to", "20250707090000"])
+                .unwrap()
+                .command,
+            Command::Migrate(MigrateCommand::Down {
+                to: Some(20250707090000)
+            })
+        );
+        assert_eq!(
+            parse(&["migrate", "status"]).unwrap().command,
+            Command::Migrate(MigrateCommand::Status)
+        );
+        assert_eq!(parse(&["seed"]).unwrap().command, Command::Seed);
+
+        // グローバルなオプションはサブコマンドの前後どちらにも書ける
+        let args = parse(&["check-config", "--config", "app.toml"]).unwrap();
+        assert_eq!(args.command, Command::CheckConfig);
+        assert_eq!(args.config_path, Some(PathBuf::from("app.toml")));
+    }
+
+    #[test]
+    fn test_parse_invalid_args() {
+        assert!(parse(&["--config"]).is_err());
+        assert!(parse(&["--config="]).is_err());
+        assert!(parse(&["--verbose"]).is_err());
+        assert!(parse(&["migrate"]).is_err());
+        assert!(parse(&["migrate", "sideways"]).is_err());
+        assert!(parse(&["serve", "seed"]).is_err());
+        assert!(parse(&["migrate", "down", "--to", "latest"]).is_err());
+        assert!(parse(&["migrate", "up", "--to", "1"]).is_err());
+        assert!(parse(&["seed", "--migrate-on-start"]).is_err());
+    }
+}
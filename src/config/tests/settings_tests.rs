@@ -0,0 +1,338 @@
+use crate::config::settings::{Config, ConfigError, LogFormat, RateLimitStoreKind};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+const DATABASE_URL: &str = "postgres://postgres:p%40ss@db:5432/app";
+
+// 実際の環境変数の代わりに使う
+fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+    let vars: HashMap<String, String> = vars
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    move |name| vars.get(name).cloned()
+}
+
+fn valid_env() -> Vec<(&'static str, &'static str)> {
+    vec![("DATABASE_URL", DATABASE_URL), ("JWT_SECRET", "jwt-secret")]
+}
+
+fn write_config_file(content: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("config-{}.toml", Uuid::now_v7()));
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+fn problems(config: &Config) -> Vec<String> {
+    match config.validate() {
+        Err(ConfigError::Invalid(problems)) => problems,
+        other => panic!("expected validation errors, got {:?}", other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults() {
+        let config = Config::load(None, env(&valid_env())).unwrap();
+
+        assert_eq!(config.server.host, "0.0.0.0");
+        assert_eq!(config.server.port, 3000);
+        assert_eq!(config.database.max_connections, 10);
+        assert_eq!(config.log.format, LogFormat::Text);
+        assert!(config.cors.allowed_origins.is_empty());
+        assert!(config.features.swagger_ui);
+        assert_eq!(config.rate_limit.store, RateLimitStoreKind::Memory);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_env_overrides_file() {
+        let path = write_config_file(
+            r#"
+            [server]
+            port = 8080
+            request_timeout_secs = 10
+
+            [database]
+            max_connections = 20
+
+            [cors]
+            allowed_origins = ["https://file.example.com"]
+            "#,
+        );
+        let mut vars = valid_env();
+        vars.extend([
+            ("PORT", "9090"),
+            (
+                "CORS_ALLOWED_ORIGINS",
+                "https://a.example.com, http://localhost:5173",
+            ),
+            ("LOG_FORMAT", "json"),
+            ("SWAGGER_UI_ENABLED", "false"),
+            // 空の値は設定されていないものとして扱う
+            ("HOST", ""),
+        ]);
+
+        let config = Config::load(Some(&path), env(&vars)).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(config.server.port, 9090);
+        assert_eq!(config.server.request_timeout_secs, 10);
+        assert_eq!(config.server.host, "0.0.0.0");
+        assert_eq!(config.database.max_connections, 20);
+        assert_eq!(
+            config.cors.allowed_origins,
+            vec!["https://a.example.com", "http://localhost:5173"]
+        );
+        assert_eq!(config.log.format, LogFormat::Json);
+        assert!(!config.features.swagger_ui);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_invalid_env_values() {
+        let result = Config::load(
+            None,
+            env(&[("PORT", "http"), ("RATE_LIMIT_ENABLED", "maybe")]),
+        );
+
+        let Err(ConfigError::Invalid(problems)) = result else {
+            panic!("expected invalid env values to be rejected");
+        };
+        assert_eq!(problems.len(), 2);
+        assert!(problems[0].starts_with("PORT"));
+        assert!(problems[1].starts_with("RATE_LIMIT_ENABLED"));
+    }
+
+    #[test]
+    fn test_tracing_settings() {
+        let mut vars = valid_env();
+        vars.extend([
+            (
+                "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT",
+                "http://otel-collector:4318/v1/traces",
+            ),
+            ("OTEL_TRACES_SAMPLER_ARG", "0.25"),
+        ]);
+
+        let config = Config::load(None, env(&vars)).unwrap();
+
+        assert_eq!(
+            config.tracing.otlp_endpoint.as_deref(),
+            Some("http://otel-collector:4318/v1/traces")
+        );
+        assert_eq!(config.tracing.sampling_ratio, 0.25);
+        assert_eq!(config.tracing.service_name, "rust-on-docker");
+        assert!(config.validate().is_ok());
+
+        vars.extend([
+            ("OTEL_EXPORTER_OTLP_TRACES_ENDPOINT", "otel-collector:4318"),
+            ("OTEL_TRACES_SAMPLER_ARG", "1.5"),
+        ]);
+        let config = Config::load(None, env(&vars)).unwrap();
+
+        let problems = problems(&config);
+        assert_eq!(problems.len(), 2, "{:?}", problems);
+        assert!(
+            problems[0].starts_with("tracing.otlp_endpoint (OTEL_EXPORTER_OTLP_TRACES_ENDPOINT)")
+        );
+        assert!(problems[1].starts_with("tracing.sampling_ratio (OTEL_TRACES_SAMPLER_ARG)"));
+    }
+
+    #[test]
+    fn test_access_log_settings() {
+        let mut vars = valid_env();
+        vars.extend([
+            ("ACCESS_LOG_HEADERS", "true"),
+            (
+                "ACCESS_LOG_REDACT_HEADERS",
+                "authorization, x-tenant-secret",
+            ),
+            ("ACCESS_LOG_BODY_SAMPLE_RATIO", "0.1"),
+        ]);
+
+        let config = Config::load(None, env(&vars)).unwrap();
+
+        assert!(config.access_log.headers);
+        assert_eq!(
+            config.access_log.redact_headers,
+            vec!["authorization", "x-tenant-secret"]
+        );
+        assert_eq!(config.access_log.body_sample_ratio, 0.1);
+        assert!(config.validate().is_ok());
+
+        vars.extend([
+            ("ACCESS_LOG_REDACT_HEADERS", "bad header"),
+            ("ACCESS_LOG_MAX_BODY_BYTES", "0"),
+        ]);
+        let config = Config::load(None, env(&vars)).unwrap();
+
+        let problems = problems(&config);
+        assert_eq!(problems.len(), 2, "{:?}", problems);
+        assert!(problems[0].starts_with("access_log.redact_headers (ACCESS_LOG_REDACT_HEADERS)"));
+        assert!(problems[1].starts_with("access_log.max_body_bytes (ACCESS_LOG_MAX_BODY_BYTES)"));
+    }
+
+    #[test]
+    fn test_http_layer_settings() {
+        let mut vars = valid_env();
+        vars.extend([
+            ("REQUEST_TIMEOUT_STATUS", "503"),
+            ("MAX_BODY_BYTES", "65536"),
+            ("HSTS_MAX_AGE_SECS", "31536000"),
+            ("COMPRESSION_ENABLED", "false"),
+        ]);
+
+        let config = Config::load(None, env(&vars)).unwrap();
+
+        assert_eq!(config.server.timeout_status, 503);
+        assert_eq!(config.server.max_body_bytes, 65536);
+        assert_eq!(config.server.hsts_max_age_secs, 31_536_000);
+        assert!(!config.features.compression);
+        assert!(config.validate().is_ok());
+
+        vars.extend([("REQUEST_TIMEOUT_STATUS", "504"), ("MAX_BODY_BYTES", "0")]);
+        let config = Config::load(None, env(&vars)).unwrap();
+
+        let problems = problems(&config);
+        assert_eq!(problems.len(), 2, "{:?}", problems);
+        assert!(problems[0].starts_with("server.timeout_status (REQUEST_TIMEOUT_STATUS)"));
+        assert!(problems[1].starts_with("server.max_body_bytes (MAX_BODY_BYTES)"));
+    }
+
+    #[test]
+    fn test_rate_limit_routes_from_file() {
+        // ファイルで指定したルートの一覧が既定の一覧を置き換える
+        let path = write_config_file(
+            r#"
+            [[rate_limit.routes]]
+            method = "post"
+            path = "/tasks/import"
+            per_minute = 2
+
+            [[rate_limit.routes]]
+            method = "GET"
+            path = "/tasks/:id"
+            per_minute = 60
+            "#,
+        );
+
+        let config = Config::load(Some(&path), env(&valid_env())).unwrap();
+
+        let routes: Vec<_> = config
+            .rate_limit
+            .routes
+            .iter()
+            .map(|route| (route.method.as_str(), route.path.as_str(), route.per_minute))
+            .collect();
+        assert_eq!(
+            routes,
+            vec![("post", "/tasks/import", 2), ("GET", "/tasks/:id", 60)]
+        );
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_invalid_rate_limit_routes() {
+        let path = write_config_file(
+            r#"
+            [[rate_limit.routes]]
+            method = "FETCH"
+            path = "/tasks"
+            per_minute = 10
+
+            [[rate_limit.routes]]
+            method = "POST"
+            path = "tasks/import"
+            per_minute = 0
+
+            [[rate_limit.routes]]
+            method = "GET"
+            path = "/tasks/export"
+            per_minute = 10
+
+            [[rate_limit.routes]]
+            method = "get"
+            path = "/tasks/export"
+            per_minute = 5
+            "#,
+        );
+        let config = Config::load(Some(&path), env(&valid_env())).unwrap();
+
+        let problems = problems(&config);
+        assert_eq!(problems.len(), 4, "{:?}", problems);
+        assert!(problems[0].contains("\"FETCH /tasks\" must use one of the methods"));
+        assert!(problems[1].contains("\"POST tasks/import\" must have a path starting with /"));
+        assert!(problems[2].contains("\"POST tasks/import\" must have per_minute greater than 0"));
+        assert!(problems[3].contains("\"get /tasks/export\" is listed more than once"));
+        assert!(problems
+            .iter()
+            .all(|problem| problem.starts_with("rate_limit.routes")));
+    }
+
+    #[test]
+    fn test_unknown_file_keys_are_rejected() {
+        assert!(Config::from_toml("[server]\nprot = 8080\n").is_err());
+        assert!(Config::from_toml("[unknown]\n").is_err());
+    }
+
+    #[test]
+    fn test_validate_reports_all_problems() {
+        let config = Config::load(
+            None,
+            env(&[
+                ("DATABASE_MIN_CONNECTIONS", "20"),
+                ("CORS_ALLOWED_ORIGINS", "https://app.example.com/"),
+                ("JWT_PRIVATE_KEY_PATH", "private.pem"),
+                ("OIDC_ISSUER_URL", "https://idp.example.com"),
+                ("RATE_LIMIT_STORE", "redis"),
+            ]),
+        )
+        .unwrap();
+
+        let problems = problems(&config);
+
+        // 問題ごとに TOML の項目名と環境変数名を含める
+        let expected = [
+            "database.url (DATABASE_URL)",
+            "database.min_connections (DATABASE_MIN_CONNECTIONS)",
+            "cors.allowed_origins (CORS_ALLOWED_ORIGINS)",
+            "jwt.key_id (JWT_KEY_ID)",
+            "jwt.jwks_path (JWT_JWKS_PATH)",
+            "oidc.client_id (OIDC_CLIENT_ID)",
+            "oidc.redirect_url (OIDC_REDIRECT_URL)",
+            "rate_limit.redis_url (REDIS_URL)",
+        ];
+        assert_eq!(problems.len(), expected.len(), "{:?}", problems);
+        for (problem, key) in problems.iter().zip(expected) {
+            assert!(problem.starts_with(key), "{}", problem);
+        }
+    }
+
+    #[test]
+    fn test_redacted_toml_hides_secrets() {
+        let mut vars = valid_env();
+        vars.extend([
+            ("OIDC_CLIENT_SECRET", "oidc-secret"),
+            ("REDIS_URL", "redis://:redis-pass@redis:6379"),
+        ]);
+        let config = Config::load(None, env(&vars)).unwrap();
+
+        let printed = config.to_redacted_toml();
+
+        assert!(!printed.contains("jwt-secret"));
+        assert!(!printed.contains("oidc-secret"));
+        assert!(!printed.contains("p%40ss"));
+        assert!(!printed.contains("redis-pass"));
+        // パスワード以外は確認できるように残す
+        assert!(printed.contains("postgres://postgres:***@db:5432/app"));
+        assert!(printed.contains("secret = \"[REDACTED]\""));
+        assert!(!format!("{:?}", config).contains("jwt-secret"));
+        // 出力した設定はそのまま設定ファイルとして読み込める
+        assert!(Config::from_toml(&printed).is_ok());
+    }
+}
@@ -1,6 +1,20 @@
+use crate::models::api_key::ApiKeyScope;
+use crate::models::calendar::CalendarComponent;
+use crate::models::grant::GrantPermission;
+use crate::models::health::{CheckResult, HealthReport, HealthStatus};
+use crate::models::stats::{CompletionBucket, StatsBucket, TaskStats, TaskStatusCounts};
 use crate::models::task::Task;
-use crate::routes::tasks;
-use utoipa::OpenApi;
+use crate::models::task_transfer::{
+    ImportAction, ImportReport, ImportRowError, TaskRecord, TransferFormat,
+};
+use crate::models::time_entry::{TimeEntry, TimeReportRow};
+use crate::models::workspace::{Workspace, WorkspaceMember, WorkspaceMembership, WorkspaceRole};
+use crate::routes::{
+    api_keys, auth, calendar, health, oidc, sessions, sharing, stats, task_transfer, tasks,
+    time_tracking, workspaces,
+};
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
 
 #[derive(OpenApi)]
 #[openapi(
@@ -10,15 +24,121 @@ use utoipa::OpenApi;
         tasks::create_task,
         tasks::update_task,
         tasks::delete_task,
+        task_transfer::export_tasks,
+        task_transfer::import_tasks,
+        calendar::get_calendar,
+        calendar::create_calendar_token,
+        calendar::rotate_calendar_token,
+        calendar::revoke_calendar_tokens,
+        stats::get_task_stats,
+        time_tracking::start_timer,
+        time_tracking::stop_timer,
+        time_tracking::list_time_entries,
+        time_tracking::create_time_entry,
+        time_tracking::get_time_report,
+        auth::register,
+        auth::login,
+        auth::refresh,
+        auth::get_me,
+        oidc::login,
+        oidc::callback,
+        sessions::create_session,
+        sessions::delete_session,
+        sessions::delete_all_sessions,
+        workspaces::list_workspaces,
+        workspaces::create_workspace,
+        workspaces::list_members,
+        workspaces::set_member_role,
+        workspaces::remove_member,
+        api_keys::create_api_key,
+        api_keys::list_api_keys,
+        api_keys::revoke_api_key,
+        sharing::create_grant,
+        sharing::create_share_link,
+        sharing::list_grants,
+        sharing::revoke_grant,
+        sharing::get_shared_link,
+        tasks::get_shared_tasks,
+        health::liveness,
+        health::readiness,
     ),
     components(
         schemas(Task),
         schemas(tasks::CreateTaskRequest),
         schemas(tasks::UpdateTaskRequest),
         schemas(tasks::TaskResponse),
+        schemas(TransferFormat, TaskRecord, ImportAction, ImportRowError, ImportReport),
+        schemas(CalendarComponent, calendar::CalendarTokenResponse),
+        schemas(StatsBucket, TaskStatusCounts, CompletionBucket, TaskStats),
+        schemas(TimeEntry, TimeReportRow, time_tracking::CreateTimeEntryRequest),
+        schemas(
+            auth::CredentialsRequest,
+            auth::RefreshRequest,
+            auth::TokenResponse,
+            auth::UserResponse,
+            sessions::SessionResponse
+        ),
+        schemas(
+            WorkspaceRole,
+            Workspace,
+            WorkspaceMembership,
+            WorkspaceMember,
+            workspaces::CreateWorkspaceRequest,
+            workspaces::SetMemberRoleRequest
+        ),
+        schemas(
+            ApiKeyScope,
+            api_keys::CreateApiKeyRequest,
+            api_keys::ApiKeyResponse,
+            api_keys::CreatedApiKeyResponse
+        ),
+        schemas(
+            GrantPermission,
+            sharing::CreateGrantRequest,
+            sharing::CreateShareLinkRequest,
+            sharing::GrantResponse,
+            sharing::ShareLinkResponse,
+            tasks::SharedTaskResponse
+        ),
+        schemas(HealthStatus, CheckResult, HealthReport),
     ),
+    modifiers(&SecurityAddon),
     tags(
-        (name = "Tasks", description = "タスク管理API")
+        (name = "Tasks", description = "タスク管理API"),
+        (name = "Calendar", description = "タスクのiCalendarフィード"),
+        (name = "Stats", description = "タスクの統計・レポート"),
+        (name = "TimeTracking", description = "作業時間の記録・集計"),
+        (name = "Auth", description = "ユーザー登録・ログイン（パスワード・OIDC）"),
+        (name = "Workspaces", description = "ワークスペースとメンバーのロール"),
+        (name = "ApiKeys", description = "マシン間連携用のAPIキー"),
+        (name = "Sharing", description = "タスク・プロジェクトの共有と共有リンク"),
+        (name = "Health", description = "死活監視・受け付け可否の確認")
     )
 )]
 pub struct ApiDoc;
+
+// Authorization: Bearer のJWT、X-API-Key のAPIキー、セッション Cookie を認証方式として登録する
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer_auth",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .build(),
+            ),
+        );
+        components.add_security_scheme(
+            "api_key",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("X-API-Key"))),
+        );
+        components.add_security_scheme(
+            "session_cookie",
+            SecurityScheme::ApiKey(ApiKey::Cookie(ApiKeyValue::new("session"))),
+        );
+    }
+}
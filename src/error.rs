@@ -1,4 +1,5 @@
-use axum::http::StatusCode;
+use crate::request_id::middleware::current_request_id;
+use axum::http::{header, StatusCode};
 use axum::response::{IntoResponse, Response};
 use thiserror::Error;
 
@@ -11,17 +12,87 @@ pub enum AppError {
     #[error("User not found")]
     NotFound,
 
+    #[error("{0} not found")]
+    ResourceNotFound(&'static str),
+
+    #[error("{0}")]
+    BadRequest(String),
+
+    #[error("{0}")]
+    Conflict(String),
+
+    #[error("{0}")]
+    UnprocessableEntity(String),
+
+    #[error("Invalid email or password")]
+    InvalidCredentials,
+
+    #[error("Unauthorized")]
+    Unauthorized,
+
+    #[error("Forbidden")]
+    Forbidden,
+
+    // 次にリクエストできるまでの秒数
+    #[error("Too many requests")]
+    TooManyRequests(u64),
+
+    #[error("Database error")]
+    DatabaseError(#[from] sqlx::Error),
+
     #[error("Internal server error")]
     InternalError,
 }
 
+// PostgreSQL が不正なタイムゾーン名などに返すエラーコード（invalid_parameter_value）
+const INVALID_PARAMETER_VALUE: &str = "22023";
+
+impl AppError {
+    // 集計のタイムゾーン名を PostgreSQL が受け付けなかった場合は BadRequest にする
+    pub fn from_timezone_query(error: sqlx::Error) -> Self {
+        match &error {
+            sqlx::Error::Database(e) if e.code().as_deref() == Some(INVALID_PARAMETER_VALUE) => {
+                AppError::BadRequest("Invalid timezone".to_string())
+            }
+            _ => AppError::DatabaseError(error),
+        }
+    }
+}
+
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
+        // 問い合わせの際にログと突き合わせられるよう、リクエストのIDを本文に含める
+        let body = match current_request_id() {
+            Some(request_id) => format!("{} (request_id: {})", self, request_id),
+            None => self.to_string(),
+        };
         match self {
-            AppError::ExternalApiError(_) | AppError::InternalError => {
-                (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()).into_response()
+            AppError::ExternalApiError(_)
+            | AppError::DatabaseError(_)
+            | AppError::InternalError => (StatusCode::INTERNAL_SERVER_ERROR, body).into_response(),
+            AppError::NotFound | AppError::ResourceNotFound(_) => {
+                (StatusCode::NOT_FOUND, body).into_response()
+            }
+            AppError::BadRequest(_) => (StatusCode::BAD_REQUEST, body).into_response(),
+            AppError::Conflict(_) => (StatusCode::CONFLICT, body).into_response(),
+            AppError::UnprocessableEntity(_) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, body).into_response()
             }
-            AppError::NotFound => (StatusCode::NOT_FOUND, self.to_string()).into_response(),
+            AppError::InvalidCredentials => (StatusCode::UNAUTHORIZED, body).into_response(),
+            // RFC 6750 に従い、Bearer トークンが必要であることを示す
+            AppError::Unauthorized => (
+                StatusCode::UNAUTHORIZED,
+                [(header::WWW_AUTHENTICATE, "Bearer")],
+                body,
+            )
+                .into_response(),
+            AppError::Forbidden => (StatusCode::FORBIDDEN, body).into_response(),
+            AppError::TooManyRequests(retry_after) => (
+                StatusCode::TOO_MANY_REQUESTS,
+                [(header::RETRY_AFTER, retry_after.to_string())],
+                body,
+            )
+                .into_response(),
         }
     }
 }
@@ -0,0 +1,117 @@
+use crate::error::AppError;
+use crate::models::caller::Caller;
+use crate::models::idempotency::{
+    is_valid_key, request_fingerprint, IdempotencyStart, StoredResponse,
+};
+use crate::usecase::idempotency_usecase::IdempotencyService;
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Request, State},
+    http::{HeaderName, HeaderValue, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use std::sync::Arc;
+use tracing::warn;
+
+// 同じ操作の再送であることを示すヘッダー
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+// 保存したレスポンスを返した場合に付けるヘッダー
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+// 指紋の計算と保存のために読み込むリクエスト・レスポンスの本文の上限
+const MAX_BODY_SIZE: usize = 10 * 1024 * 1024;
+
+#[derive(Clone)]
+pub struct IdempotencyState<I: IdempotencyService> {
+    pub idempotency_service: Arc<I>,
+}
+
+// Idempotency-Key ヘッダー付きのリクエストの最初のレスポンスを保存し、再送には同じレスポンスを返す
+// ハンドラーに .layer で付けて使う（require_auth の後に実行され、キーはユーザーごとに区別する）
+pub async fn idempotent<I>(
+    State(state): State<IdempotencyState<I>>,
+    caller: Caller,
+    request: Request,
+    next: Next,
+) -> Result<Response, AppError>
+where
+    I: IdempotencyService + Send + Sync,
+{
+    let Some(value) = request.headers().get(IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(next.run(request).await);
+    };
+    let key = value
+        .to_str()
+        .ok()
+        .map(str::trim)
+        .filter(|key| is_valid_key(key))
+        .ok_or_else(|| AppError::BadRequest("Invalid Idempotency-Key header".to_string()))?
+        .to_string();
+
+    let (parts, body) = request.into_parts();
+    let body = to_bytes(body, MAX_BODY_SIZE)
+        .await
+        .map_err(|_| AppError::BadRequest("Request body is too large".to_string()))?;
+    let request_hash = request_fingerprint(
+        parts.method.as_str(),
+        &parts.uri.to_string(),
+        caller.workspace_id,
+        &body,
+    );
+    let service = &state.idempotency_service;
+    let attempt_id = match service.begin(&caller, key.clone(), request_hash).await? {
+        IdempotencyStart::Proceed { attempt_id } => attempt_id,
+        IdempotencyStart::Replay(stored) => return Ok(replay(stored)),
+    };
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+    // サーバーエラーは保存せず、同じキーで再試行できるようにする
+    if response.status().is_server_error() {
+        if let Err(e) = service.release(&caller, key, attempt_id).await {
+            warn!("failed to release Idempotency-Key: {}", e);
+        }
+        return Ok(response);
+    }
+
+    let (parts, body) = response.into_parts();
+    let body = match to_bytes(body, MAX_BODY_SIZE).await {
+        Ok(body) => body,
+        Err(e) => {
+            warn!("failed to read response for Idempotency-Key: {}", e);
+            if let Err(e) = service.release(&caller, key, attempt_id).await {
+                warn!("failed to release Idempotency-Key: {}", e);
+            }
+            return Err(AppError::InternalError);
+        }
+    };
+    let stored = StoredResponse {
+        status: parts.status.as_u16(),
+        headers: parts
+            .headers
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect(),
+        body: body.to_vec(),
+    };
+    // 処理は終わっているため、保存に失敗してもレスポンスはそのまま返す
+    if let Err(e) = service.complete(&caller, key, attempt_id, stored).await {
+        warn!("failed to store response for Idempotency-Key: {}", e);
+    }
+    Ok(Response::from_parts(parts, Body::from(body)))
+}
+
+fn replay(stored: StoredResponse) -> Response {
+    let mut response = Response::new(Body::from(stored.body));
+    *response.status_mut() =
+        StatusCode::from_u16(stored.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    let headers = response.headers_mut();
+    for (name, value) in stored.headers {
+        if let (Ok(name), Ok(value)) = (HeaderName::try_from(name), HeaderValue::try_from(value)) {
+            headers.append(name, value);
+        }
+    }
+    headers.insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+    response
+}
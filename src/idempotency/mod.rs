@@ -0,0 +1,3 @@
+pub mod middleware;
+#[cfg(test)]
+pub mod tests;
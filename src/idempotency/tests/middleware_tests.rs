@@ -0,0 +1,289 @@
+use crate::idempotency::middleware::{
+    idempotent, IdempotencyState, IDEMPOTENCY_KEY_HEADER, IDEMPOTENT_REPLAYED_HEADER,
+};
+use crate::models::caller::Caller;
+use crate::models::idempotency::{IdempotencyRecord, StoredResponse};
+use crate::models::workspace::WorkspaceRole;
+use crate::repositories::idempotency_repository::IdempotencyRepository;
+use crate::usecase::idempotency_usecase::IdempotencyUsecase;
+use async_trait::async_trait;
+use axum::{
+    body::{Body, Bytes},
+    extract::Request,
+    handler::Handler,
+    http::{header, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::post,
+    Router,
+};
+use chrono::{DateTime, Utc};
+use sqlx::types::Json;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tower::ServiceExt;
+use uuid::Uuid;
+
+// DB の代わりにメモリに保存するリポジトリ
+#[derive(Clone, Default)]
+struct InMemoryIdempotencyRepository {
+    records: Arc<Mutex<HashMap<(Uuid, String), IdempotencyRecord>>>,
+}
+
+#[async_trait]
+impl IdempotencyRepository for InMemoryIdempotencyRepository {
+    async fn reserve(&self, record: IdempotencyRecord) -> Result<bool, sqlx::Error> {
+        let mut records = self.records.lock().unwrap();
+        let key = (record.user_id, record.key.clone());
+        if records.contains_key(&key) {
+            return Ok(false);
+        }
+        records.insert(key, record);
+        Ok(true)
+    }
+
+    async fn take_over(
+        &self,
+        record: IdempotencyRecord,
+        abandoned_before: DateTime<Utc>,
+    ) -> Result<bool, sqlx::Error> {
+        let mut records = self.records.lock().unwrap();
+        match records.get_mut(&(record.user_id, record.key.clone())) {
+            Some(existing)
+                if existing.response_status.is_none() && existing.created_at < abandoned_before =>
+            {
+                *existing = record;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn find(
+        &self,
+        user_id: Uuid,
+        key: String,
+    ) -> Result<Option<IdempotencyRecord>, sqlx::Error> {
+        Ok(self.records.lock().unwrap().get(&(user_id, key)).cloned())
+    }
+
+    async fn complete(
+        &self,
+        user_id: Uuid,
+        key: String,
+        attempt_id: Uuid,
+        response: StoredResponse,
+    ) -> Result<(), sqlx::Error> {
+        if let Some(record) = self
+            .records
+            .lock()
+            .unwrap()
+            .get_mut(&(user_id, key))
+            .filter(|record| record.attempt_id == attempt_id)
+        {
+            record.response_status = Some(response.status as i16);
+            record.response_headers = Some(Json(response.headers));
+            record.response_body = Some(response.body);
+        }
+        Ok(())
+    }
+
+    async fn release(
+        &self,
+        user_id: Uuid,
+        key: String,
+        attempt_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        let mut records = self.records.lock().unwrap();
+        let key = (user_id, key);
+        if records
+            .get(&key)
+            .is_some_and(|record| record.attempt_id == attempt_id)
+        {
+            records.remove(&key);
+        }
+        Ok(())
+    }
+}
+
+type TestIdempotencyService = IdempotencyUsecase<InMemoryIdempotencyRepository>;
+
+// X-User-Id ヘッダーのユーザーを呼び出し元として扱うテスト用の認証
+async fn fake_auth(mut request: Request, next: Next) -> Response {
+    let user_id = request
+        .headers()
+        .get("x-user-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| Uuid::parse_str(value).ok())
+        .unwrap_or(Uuid::from_u128(1));
+    request
+        .extensions_mut()
+        .insert(Caller::new(user_id, user_id, WorkspaceRole::Member));
+    next.run(request).await
+}
+
+// 呼び出された回数を番号にして作成したことにするハンドラー
+// "fail" を送ると 500 を返す
+fn router(calls: Arc<AtomicUsize>) -> Router {
+    let state = IdempotencyState {
+        idempotency_service: Arc::new(TestIdempotencyService::new(
+            InMemoryIdempotencyRepository::default(),
+        )),
+    };
+    let handler = move |body: Bytes| async move {
+        let n = calls.fetch_add(1, Ordering::SeqCst) + 1;
+        if body.as_ref() == b"fail" {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+        (
+            StatusCode::CREATED,
+            [(header::LOCATION, format!("/items/{}", n))],
+            format!("item {}", n),
+        )
+            .into_response()
+    };
+    Router::new()
+        .route(
+            "/items",
+            post(handler.layer(middleware::from_fn_with_state(
+                state,
+                idempotent::<TestIdempotencyService>,
+            ))),
+        )
+        .route_layer(middleware::from_fn(fake_auth))
+}
+
+fn request(key: Option<&str>, body: &'static str) -> Request<Body> {
+    let mut builder = Request::builder().method("POST").uri("/items");
+    if let Some(key) = key {
+        builder = builder.header(IDEMPOTENCY_KEY_HEADER, key);
+    }
+    builder.body(Body::from(body)).unwrap()
+}
+
+async fn body_string(response: Response) -> String {
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_replays_first_response() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let router = router(calls.clone());
+
+        let first = router
+            .clone()
+            .oneshot(request(Some("key-1"), "{}"))
+            .await
+            .unwrap();
+        assert_eq!(first.status(), StatusCode::CREATED);
+        assert!(first.headers().get(IDEMPOTENT_REPLAYED_HEADER).is_none());
+        assert_eq!(body_string(first).await, "item 1");
+
+        // 再送にはハンドラーを呼ばずに、ステータス・ヘッダー・本文を同じにして返す
+        let replayed = router
+            .clone()
+            .oneshot(request(Some("key-1"), "{}"))
+            .await
+            .unwrap();
+        assert_eq!(replayed.status(), StatusCode::CREATED);
+        assert_eq!(replayed.headers()[header::LOCATION], "/items/1");
+        assert_eq!(replayed.headers()[IDEMPOTENT_REPLAYED_HEADER], "true");
+        assert_eq!(body_string(replayed).await, "item 1");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // 別のキーは新しいリクエストとして処理する
+        let other = router.oneshot(request(Some("key-2"), "{}")).await.unwrap();
+        assert_eq!(body_string(other).await, "item 2");
+    }
+
+    #[tokio::test]
+    async fn test_rejects_key_reused_with_different_body() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let router = router(calls.clone());
+
+        router
+            .clone()
+            .oneshot(request(Some("key-1"), "{\"title\":\"a\"}"))
+            .await
+            .unwrap();
+        let response = router
+            .oneshot(request(Some("key-1"), "{\"title\":\"b\"}"))
+            .await
+            .unwrap();
+
+        // 検証
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_keys_are_scoped_to_user() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let router = router(calls.clone());
+
+        // 別のユーザーが同じキーを使っても、保存したレスポンスは返さない
+        for user_id in [Uuid::from_u128(1), Uuid::from_u128(2)] {
+            let response = router
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .method("POST")
+                        .uri("/items")
+                        .header(IDEMPOTENCY_KEY_HEADER, "key-1")
+                        .header("x-user-id", user_id.to_string())
+                        .body(Body::from("{}"))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert!(response.headers().get(IDEMPOTENT_REPLAYED_HEADER).is_none());
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_server_error_is_not_stored() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let router = router(calls.clone());
+
+        // 500 の後は同じキーで再試行できる
+        for _ in 0..2 {
+            let response = router
+                .clone()
+                .oneshot(request(Some("key-1"), "fail"))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_without_key_or_with_invalid_key() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let router = router(calls.clone());
+
+        // キーがなければ毎回処理する
+        for _ in 0..2 {
+            let response = router.clone().oneshot(request(None, "{}")).await.unwrap();
+            assert_eq!(response.status(), StatusCode::CREATED);
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // 不正なキーは処理せずに 400 を返す
+        let response = router
+            .oneshot(request(Some("has space"), "{}"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1 @@
+pub mod middleware_tests;
@@ -0,0 +1,84 @@
+use crate::infrastructure::db::DbPool;
+use crate::models::calendar::CalendarTokenOwner;
+use crate::repositories::calendar_token_repository::CalendarTokenRepository;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct CalendarTokenRepositoryImpl {
+    pub pool: DbPool,
+}
+
+impl CalendarTokenRepositoryImpl {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl CalendarTokenRepository for CalendarTokenRepositoryImpl {
+    async fn create(
+        &self,
+        user_id: Uuid,
+        workspace_id: Uuid,
+        token_hash: String,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO calendar_tokens (user_id, workspace_id, token_hash) VALUES ($1, $2, $3)",
+        )
+        .bind(user_id)
+        .bind(workspace_id)
+        .bind(token_hash)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn replace(
+        &self,
+        user_id: Uuid,
+        workspace_id: Uuid,
+        token_hash: String,
+    ) -> Result<(), sqlx::Error> {
+        // 古いトークンの削除と新しいトークンの保存を同時に反映する
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM calendar_tokens WHERE user_id = $1 AND workspace_id = $2")
+            .bind(user_id)
+            .bind(workspace_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(
+            "INSERT INTO calendar_tokens (user_id, workspace_id, token_hash) VALUES ($1, $2, $3)",
+        )
+        .bind(user_id)
+        .bind(workspace_id)
+        .bind(token_hash)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await
+    }
+
+    async fn delete_for_user(&self, user_id: Uuid, workspace_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result =
+            sqlx::query("DELETE FROM calendar_tokens WHERE user_id = $1 AND workspace_id = $2")
+                .bind(user_id)
+                .bind(workspace_id)
+                .execute(&self.pool)
+                .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn find_owner(
+        &self,
+        token_hash: String,
+    ) -> Result<Option<CalendarTokenOwner>, sqlx::Error> {
+        // ユーザーやワークスペースに紐付かない（所有者を導入する前に発行された）トークンは無効
+        sqlx::query_as::<_, CalendarTokenOwner>(
+            "SELECT user_id, workspace_id FROM calendar_tokens
+             WHERE token_hash = $1 AND user_id IS NOT NULL AND workspace_id IS NOT NULL",
+        )
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await
+    }
+}
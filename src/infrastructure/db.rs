@@ -1,4 +1,54 @@
-use sqlx::PgPool;
+use crate::metrics::registry::METRICS;
+use sqlx::migrate::Migrator;
+use sqlx::{PgPool, Postgres, Transaction};
+use std::time::Instant;
+use uuid::Uuid;
 
 // PostgreSQLプールの型エイリアス
 pub type DbPool = PgPool;
+
+// バイナリに埋め込んだ migrations/ のマイグレーション
+pub static MIGRATOR: Migrator = sqlx::migrate!();
+
+// 実行する SQL を現在のスパンの db.statement 属性に記録する
+// （スパンは db.statement = Empty でフィールドを宣言しておく必要がある）
+pub fn record_statement(sql: &str) {
+    tracing::Span::current().record("db.statement", sql);
+}
+
+// テナント（ワークスペース）ごとのデータにアクセスするための DbPool のラッパー
+// 行レベルセキュリティで、WHERE 句に関係なく1つのワークスペースの行だけが読み書きできる
+#[derive(Clone)]
+pub struct TenantPool {
+    pool: DbPool,
+}
+
+impl TenantPool {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    // workspace_id をテナントとして設定したトランザクションを開始する
+    pub async fn begin(
+        &self,
+        workspace_id: Uuid,
+    ) -> Result<Transaction<'static, Postgres>, sqlx::Error> {
+        // プールが枯渇していると、ここで接続が空くまで待たされる
+        let started = Instant::now();
+        let tx = self.pool.begin().await;
+        METRICS
+            .db_pool_acquire_duration_seconds
+            .observe(started.elapsed().as_secs_f64());
+        let mut tx = tx?;
+        // スーパーユーザーには行レベルセキュリティが適用されないため、権限の少ないロールに切り替える
+        sqlx::query("SET LOCAL ROLE app_tenant")
+            .execute(&mut *tx)
+            .await?;
+        // SET LOCAL app.workspace_id と同じ（SET はバインド変数を使えないので set_config を使う）
+        sqlx::query("SELECT set_config('app.workspace_id', $1, true)")
+            .bind(workspace_id.to_string())
+            .execute(&mut *tx)
+            .await?;
+        Ok(tx)
+    }
+}
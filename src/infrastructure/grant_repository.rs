@@ -0,0 +1,113 @@
+use crate::infrastructure::db::DbPool;
+use crate::models::grant::Grant;
+use crate::repositories::grant_repository::GrantRepository;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+const GRANT_COLUMNS: &str = "id, workspace_id, task_id, project, grantee_id, token_hash, permission, expires_at, created_by, created_at";
+
+#[derive(Clone)]
+pub struct GrantRepositoryImpl {
+    pub pool: DbPool,
+}
+
+impl GrantRepositoryImpl {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl GrantRepository for GrantRepositoryImpl {
+    async fn create(&self, grant: Grant) -> Result<Grant, sqlx::Error> {
+        let grant = sqlx::query_as::<_, Grant>(&format!(
+            "INSERT INTO grants ({})
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+             RETURNING {}",
+            GRANT_COLUMNS, GRANT_COLUMNS
+        ))
+        .bind(grant.id)
+        .bind(grant.workspace_id)
+        .bind(grant.task_id)
+        .bind(grant.project)
+        .bind(grant.grantee_id)
+        .bind(grant.token_hash)
+        .bind(grant.permission)
+        .bind(grant.expires_at)
+        .bind(grant.created_by)
+        .bind(grant.created_at)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(grant)
+    }
+
+    async fn find_by_id(&self, workspace_id: Uuid, id: Uuid) -> Result<Option<Grant>, sqlx::Error> {
+        let grant = sqlx::query_as::<_, Grant>(&format!(
+            "SELECT {} FROM grants WHERE id = $1 AND workspace_id = $2",
+            GRANT_COLUMNS
+        ))
+        .bind(id)
+        .bind(workspace_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(grant)
+    }
+
+    async fn find_by_workspace(&self, workspace_id: Uuid) -> Result<Vec<Grant>, sqlx::Error> {
+        let grants = sqlx::query_as::<_, Grant>(&format!(
+            "SELECT {} FROM grants WHERE workspace_id = $1 ORDER BY created_at DESC, id DESC",
+            GRANT_COLUMNS
+        ))
+        .bind(workspace_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(grants)
+    }
+
+    async fn find_active_by_grantee(
+        &self,
+        grantee_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<Grant>, sqlx::Error> {
+        let grants = sqlx::query_as::<_, Grant>(&format!(
+            "SELECT {} FROM grants
+             WHERE grantee_id = $1 AND (expires_at IS NULL OR expires_at > $2)
+             ORDER BY created_at, id",
+            GRANT_COLUMNS
+        ))
+        .bind(grantee_id)
+        .bind(now)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(grants)
+    }
+
+    async fn find_active_by_token(
+        &self,
+        token_hash: String,
+        now: DateTime<Utc>,
+    ) -> Result<Option<Grant>, sqlx::Error> {
+        let grant = sqlx::query_as::<_, Grant>(&format!(
+            "SELECT {} FROM grants WHERE token_hash = $1 AND expires_at > $2",
+            GRANT_COLUMNS
+        ))
+        .bind(token_hash)
+        .bind(now)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(grant)
+    }
+
+    async fn delete(&self, workspace_id: Uuid, id: Uuid) -> Result<(), sqlx::Error> {
+        let result = sqlx::query("DELETE FROM grants WHERE id = $1 AND workspace_id = $2")
+            .bind(id)
+            .bind(workspace_id)
+            .execute(&self.pool)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        Ok(())
+    }
+}
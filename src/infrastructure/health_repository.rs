@@ -0,0 +1,48 @@
+use crate::error::AppError;
+use crate::infrastructure::db::{DbPool, MIGRATOR};
+use crate::repositories::health_repository::HealthRepository;
+use async_trait::async_trait;
+use std::collections::HashSet;
+
+// マイグレーションの履歴のテーブルがない場合に PostgreSQL が返すエラーコード（undefined_table）
+const UNDEFINED_TABLE: &str = "42P01";
+
+#[derive(Clone)]
+pub struct HealthRepositoryImpl {
+    pool: DbPool,
+}
+
+impl HealthRepositoryImpl {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl HealthRepository for HealthRepositoryImpl {
+    async fn ping(&self) -> Result<(), AppError> {
+        sqlx::query("SELECT 1").execute(&self.pool).await?;
+        Ok(())
+    }
+
+    async fn pending_migrations(&self) -> Result<Vec<i64>, AppError> {
+        // 失敗したマイグレーションは適用されていないものとして扱う
+        let applied: HashSet<i64> =
+            match sqlx::query_scalar("SELECT version FROM _sqlx_migrations WHERE success")
+                .fetch_all(&self.pool)
+                .await
+            {
+                Ok(versions) => versions.into_iter().collect(),
+                Err(sqlx::Error::Database(e)) if e.code().as_deref() == Some(UNDEFINED_TABLE) => {
+                    HashSet::new()
+                }
+                Err(e) => return Err(e.into()),
+            };
+        Ok(MIGRATOR
+            .iter()
+            .filter(|migration| !migration.migration_type.is_down_migration())
+            .map(|migration| migration.version)
+            .filter(|version| !applied.contains(version))
+            .collect())
+    }
+}
@@ -0,0 +1,129 @@
+use crate::infrastructure::db::DbPool;
+use crate::models::idempotency::{IdempotencyRecord, StoredResponse};
+use crate::repositories::idempotency_repository::IdempotencyRepository;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::types::Json;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct IdempotencyRepositoryImpl {
+    pub pool: DbPool,
+}
+
+impl IdempotencyRepositoryImpl {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl IdempotencyRepository for IdempotencyRepositoryImpl {
+    async fn reserve(&self, record: IdempotencyRecord) -> Result<bool, sqlx::Error> {
+        // 期限切れの同じユーザーのキーはここで片付ける（同じキーは新しいリクエストとして扱う）
+        sqlx::query("DELETE FROM idempotency_keys WHERE user_id = $1 AND expires_at <= $2")
+            .bind(record.user_id)
+            .bind(record.created_at)
+            .execute(&self.pool)
+            .await?;
+        // 同時に届いた同じキーのリクエストは、どちらか一方だけが保存できる
+        let result = sqlx::query(
+            "INSERT INTO idempotency_keys
+                 (user_id, key, request_hash, attempt_id, created_at, expires_at)
+             VALUES ($1, $2, $3, $4, $5, $6)
+             ON CONFLICT (user_id, key) DO NOTHING",
+        )
+        .bind(record.user_id)
+        .bind(record.key)
+        .bind(record.request_hash)
+        .bind(record.attempt_id)
+        .bind(record.created_at)
+        .bind(record.expires_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn take_over(
+        &self,
+        record: IdempotencyRecord,
+        abandoned_before: DateTime<Utc>,
+    ) -> Result<bool, sqlx::Error> {
+        // 確認と書き換えを1つの UPDATE で行い、同時に引き継いだ一方だけが行を更新できるようにする
+        // created_at も更新するため、引き継いだ試行は再び IN_PROGRESS_TIMEOUT の間は引き継がれない
+        let result = sqlx::query(
+            "UPDATE idempotency_keys
+             SET attempt_id = $4, created_at = $5
+             WHERE user_id = $1 AND key = $2 AND request_hash = $3
+               AND response_status IS NULL AND created_at < $6",
+        )
+        .bind(record.user_id)
+        .bind(record.key)
+        .bind(record.request_hash)
+        .bind(record.attempt_id)
+        .bind(record.created_at)
+        .bind(abandoned_before)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn find(
+        &self,
+        user_id: Uuid,
+        key: String,
+    ) -> Result<Option<IdempotencyRecord>, sqlx::Error> {
+        let record = sqlx::query_as::<_, IdempotencyRecord>(
+            "SELECT user_id, key, request_hash, attempt_id, response_status, response_headers, response_body,
+                    created_at, expires_at
+             FROM idempotency_keys
+             WHERE user_id = $1 AND key = $2",
+        )
+        .bind(user_id)
+        .bind(key)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(record)
+    }
+
+    async fn complete(
+        &self,
+        user_id: Uuid,
+        key: String,
+        attempt_id: Uuid,
+        response: StoredResponse,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE idempotency_keys
+             SET response_status = $4, response_headers = $5, response_body = $6
+             WHERE user_id = $1 AND key = $2 AND attempt_id = $3 AND response_status IS NULL",
+        )
+        .bind(user_id)
+        .bind(key)
+        .bind(attempt_id)
+        .bind(response.status as i16)
+        .bind(Json(response.headers))
+        .bind(response.body)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn release(
+        &self,
+        user_id: Uuid,
+        key: String,
+        attempt_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "DELETE FROM idempotency_keys
+             WHERE user_id = $1 AND key = $2 AND attempt_id = $3 AND response_status IS NULL",
+        )
+        .bind(user_id)
+        .bind(key)
+        .bind(attempt_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,149 @@
+use crate::infrastructure::db::MIGRATOR;
+use sqlx::migrate::{Migrate, MigrateError};
+use sqlx::PgConnection;
+use std::collections::HashMap;
+use std::fmt;
+
+// マイグレーションの適用状況
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MigrationState {
+    Applied,
+    Pending,
+    // 適用の途中で失敗した（履歴を確認して手で直すまで、他のマイグレーションは実行できない）
+    Failed,
+    // 適用した後に SQL ファイルが書き換えられた
+    ChecksumMismatch,
+    // 適用済みだが、このバイナリには含まれていない（新しいバージョンから戻した場合など）
+    Unknown,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub description: String,
+    pub state: MigrationState,
+}
+
+impl fmt::Display for MigrationStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = match self.state {
+            MigrationState::Applied => "applied",
+            MigrationState::Pending => "pending",
+            MigrationState::Failed => "failed",
+            MigrationState::ChecksumMismatch => "checksum mismatch",
+            MigrationState::Unknown => "unknown",
+        };
+        write!(f, "{:<14} {:<18} {}", self.version, state, self.description)
+    }
+}
+
+// バイナリに埋め込んだマイグレーションと、データベースの履歴を突き合わせる
+// 履歴のテーブルがない場合はすべて未適用として扱う（テーブルは作成しない）
+pub async fn migration_status(
+    conn: &mut PgConnection,
+) -> Result<Vec<MigrationStatus>, MigrateError> {
+    let has_history: bool =
+        sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+            .fetch_one(&mut *conn)
+            .await?;
+    let (mut applied, dirty) = if has_history {
+        let applied: HashMap<i64, Vec<u8>> = conn
+            .list_applied_migrations()
+            .await?
+            .into_iter()
+            .map(|migration| (migration.version, migration.checksum.into_owned()))
+            .collect();
+        (applied, conn.dirty_version().await?)
+    } else {
+        (HashMap::new(), None)
+    };
+
+    let mut statuses: Vec<MigrationStatus> = MIGRATOR
+        .iter()
+        .filter(|migration| !migration.migration_type.is_down_migration())
+        .map(|migration| {
+            let state = match applied.remove(&migration.version) {
+                _ if dirty == Some(migration.version) => MigrationState::Failed,
+                Some(checksum) if checksum != *migration.checksum => {
+                    MigrationState::ChecksumMismatch
+                }
+                Some(_) => MigrationState::Applied,
+                None => MigrationState::Pending,
+            };
+            MigrationStatus {
+                version: migration.version,
+                description: migration.description.to_string(),
+                state,
+            }
+        })
+        .collect();
+    statuses.extend(applied.into_keys().map(|version| MigrationStatus {
+        version,
+        description: String::new(),
+        state: if dirty == Some(version) {
+            MigrationState::Failed
+        } else {
+            MigrationState::Unknown
+        },
+    }));
+    statuses.sort_by_key(|status| status.version);
+    Ok(statuses)
+}
+
+// 未適用のマイグレーションを適用し、適用したバージョンを返す
+// 複数のレプリカが同時に起動しても1つずつ実行されるよう、advisory lock を取ってから確認する
+// （sqlx-cli と同じロックを使うので、sqlx migrate run とも同時には実行されない）
+pub async fn migrate_up(conn: &mut PgConnection) -> Result<Vec<i64>, MigrateError> {
+    conn.lock().await?;
+    let result = apply_pending(conn).await;
+    conn.unlock().await?;
+    result
+}
+
+async fn apply_pending(conn: &mut PgConnection) -> Result<Vec<i64>, MigrateError> {
+    let pending = versions_in_state(conn, MigrationState::Pending).await?;
+    // ロックは取得済みで、同じセッションなので run_direct の中で取り直しても待たされない
+    MIGRATOR.run_direct(conn).await?;
+    Ok(pending)
+}
+
+// target より新しい適用済みのマイグレーションを新しい順に戻し、戻したバージョンを返す
+// target が None の場合は最後に適用した1つだけを戻す
+pub async fn migrate_down(
+    conn: &mut PgConnection,
+    target: Option<i64>,
+) -> Result<Vec<i64>, MigrateError> {
+    conn.lock().await?;
+    let result = revert_applied(conn, target).await;
+    conn.unlock().await?;
+    result
+}
+
+async fn revert_applied(
+    conn: &mut PgConnection,
+    target: Option<i64>,
+) -> Result<Vec<i64>, MigrateError> {
+    let applied = versions_in_state(conn, MigrationState::Applied).await?;
+    let target = target.unwrap_or(match applied.as_slice() {
+        [.., previous, _] => *previous,
+        _ => 0,
+    });
+    MIGRATOR.undo(&mut *conn, target).await?;
+    Ok(applied
+        .into_iter()
+        .rev()
+        .filter(|version| *version > target)
+        .collect())
+}
+
+async fn versions_in_state(
+    conn: &mut PgConnection,
+    state: MigrationState,
+) -> Result<Vec<i64>, MigrateError> {
+    Ok(migration_status(conn)
+        .await?
+        .into_iter()
+        .filter(|status| status.state == state)
+        .map(|status| status.version)
+        .collect())
+}
@@ -1,4 +1,19 @@
+pub mod api_key_repository;
+pub mod calendar_token_repository;
 pub mod db;
+pub mod grant_repository;
+pub mod health_repository;
+pub mod idempotency_repository;
+pub mod migration;
+pub mod oidc_provider;
+pub mod oidc_repository;
+pub mod rate_limit_store;
+pub mod refresh_token_repository;
+pub mod session_repository;
 pub mod task_repository;
 #[cfg(test)]
 pub mod tests;
+pub mod time_entry_repository;
+pub mod user_repository;
+pub mod users_api_probe;
+pub mod workspace_repository;
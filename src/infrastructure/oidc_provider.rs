@@ -0,0 +1,221 @@
+use crate::auth::oidc::OidcConfig;
+use crate::error::AppError;
+use crate::models::oidc::{AuthorizationRequest, IdTokenClaims};
+use crate::repositories::oidc_provider::OidcProvider;
+use async_trait::async_trait;
+use jsonwebtoken::jwk::{AlgorithmParameters, Jwk, JwkSet};
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use reqwest::Url;
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{OnceCell, RwLock};
+use tracing::warn;
+
+// プロバイダーへのリクエストのタイムアウト
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+// discovery で取得するプロバイダーのメタデータ（使うものだけ）
+#[derive(Clone, Debug, Deserialize)]
+struct ProviderMetadata {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    jwks_uri: String,
+}
+
+#[derive(Deserialize)]
+struct TokenEndpointResponse {
+    id_token: String,
+}
+
+// discovery・JWKS を使ってプロバイダーと通信する
+// メタデータは最初に使うときに取得し、JWKS は未知の kid の ID トークンを受け取ったときに取得し直す
+#[derive(Clone)]
+pub struct OidcProviderImpl {
+    config: Arc<OidcConfig>,
+    http: reqwest::Client,
+    metadata: Arc<OnceCell<ProviderMetadata>>,
+    jwks: Arc<RwLock<JwkSet>>,
+}
+
+impl OidcProviderImpl {
+    pub fn new(config: OidcConfig) -> Result<Self, reqwest::Error> {
+        Ok(Self {
+            config: Arc::new(config),
+            http: reqwest::Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()?,
+            metadata: Arc::new(OnceCell::new()),
+            jwks: Arc::new(RwLock::new(JwkSet { keys: Vec::new() })),
+        })
+    }
+
+    async fn metadata(&self) -> Result<&ProviderMetadata, AppError> {
+        self.metadata.get_or_try_init(|| self.discover()).await
+    }
+
+    async fn discover(&self) -> Result<ProviderMetadata, AppError> {
+        let url = format!(
+            "{}/.well-known/openid-configuration",
+            self.config.issuer_url.trim_end_matches('/')
+        );
+        let metadata: ProviderMetadata = self
+            .http
+            .get(&url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        // 別のプロバイダーになりすましたメタデータを使わないよう、issuer の一致を確認する
+        if metadata.issuer != self.config.issuer_url {
+            warn!(
+                "OIDC discovery returned issuer `{}`, expected `{}`",
+                metadata.issuer, self.config.issuer_url
+            );
+            return Err(AppError::InternalError);
+        }
+        Ok(metadata)
+    }
+
+    // kid の鍵を探す（kid がない場合は鍵が1つだけのときに限りそれを使う）
+    fn find_key(jwks: &JwkSet, kid: Option<&str>) -> Option<Jwk> {
+        match kid {
+            Some(kid) => jwks.find(kid).cloned(),
+            None if jwks.keys.len() == 1 => jwks.keys.first().cloned(),
+            None => None,
+        }
+    }
+
+    async fn signing_key(&self, kid: Option<&str>) -> Result<Option<Jwk>, AppError> {
+        if let Some(jwk) = Self::find_key(&*self.jwks.read().await, kid) {
+            return Ok(Some(jwk));
+        }
+        // プロバイダーが鍵をローテーションした可能性があるため取得し直す
+        let jwks_uri = &self.metadata().await?.jwks_uri;
+        let jwks: JwkSet = self
+            .http
+            .get(jwks_uri)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let jwk = Self::find_key(&jwks, kid);
+        *self.jwks.write().await = jwks;
+        Ok(jwk)
+    }
+
+    // ID トークンの署名・issuer・audience・有効期限を検証する
+    async fn verify_id_token(&self, id_token: &str) -> Result<IdTokenClaims, AppError> {
+        let header = decode_header(id_token).map_err(|e| {
+            warn!("invalid ID token: {}", e);
+            AppError::Unauthorized
+        })?;
+        let jwk = self
+            .signing_key(header.kid.as_deref())
+            .await?
+            .ok_or_else(|| {
+                warn!("ID token is signed with an unknown key {:?}", header.kid);
+                AppError::Unauthorized
+            })?;
+
+        // alg は鍵の種類と一致する公開鍵方式に限る（HS256 などの共有鍵方式や none は受け付けない）
+        let allowed = match &jwk.algorithm {
+            AlgorithmParameters::RSA(_) => matches!(
+                header.alg,
+                Algorithm::RS256
+                    | Algorithm::RS384
+                    | Algorithm::RS512
+                    | Algorithm::PS256
+                    | Algorithm::PS384
+                    | Algorithm::PS512
+            ),
+            AlgorithmParameters::EllipticCurve(_) => {
+                matches!(header.alg, Algorithm::ES256 | Algorithm::ES384)
+            }
+            _ => false,
+        };
+        if !allowed {
+            warn!("ID token uses unsupported algorithm {:?}", header.alg);
+            return Err(AppError::Unauthorized);
+        }
+        let key = DecodingKey::from_jwk(&jwk).map_err(|e| {
+            warn!("invalid JWK from OIDC provider: {}", e);
+            AppError::Unauthorized
+        })?;
+
+        let metadata = self.metadata().await?;
+        let mut validation = Validation::new(header.alg);
+        validation.set_issuer(&[&metadata.issuer]);
+        validation.set_audience(&[&self.config.client_id]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+        let claims = decode::<IdTokenClaims>(id_token, &key, &validation)
+            .map_err(|e| {
+                warn!("invalid ID token: {}", e);
+                AppError::Unauthorized
+            })?
+            .claims;
+        Ok(claims)
+    }
+}
+
+#[async_trait]
+impl OidcProvider for OidcProviderImpl {
+    async fn authorization_url(&self, request: AuthorizationRequest) -> Result<String, AppError> {
+        let metadata = self.metadata().await?;
+        let url = Url::parse_with_params(
+            &metadata.authorization_endpoint,
+            [
+                ("response_type", "code"),
+                ("client_id", self.config.client_id.as_str()),
+                ("redirect_uri", self.config.redirect_url.as_str()),
+                ("scope", self.config.scopes.as_str()),
+                ("state", request.state.as_str()),
+                ("nonce", request.nonce.as_str()),
+                ("code_challenge", request.code_challenge.as_str()),
+                ("code_challenge_method", "S256"),
+            ],
+        )
+        .map_err(|e| {
+            warn!("invalid OIDC authorization endpoint: {}", e);
+            AppError::InternalError
+        })?;
+        Ok(url.to_string())
+    }
+
+    async fn exchange_code(
+        &self,
+        code: String,
+        code_verifier: String,
+    ) -> Result<IdTokenClaims, AppError> {
+        let metadata = self.metadata().await?;
+        let mut form = vec![
+            ("grant_type", "authorization_code"),
+            ("code", code.as_str()),
+            ("redirect_uri", self.config.redirect_url.as_str()),
+            ("client_id", self.config.client_id.as_str()),
+            ("code_verifier", code_verifier.as_str()),
+        ];
+        if let Some(secret) = &self.config.client_secret {
+            form.push(("client_secret", secret.as_str()));
+        }
+        let response = self
+            .http
+            .post(&metadata.token_endpoint)
+            .form(&form)
+            .send()
+            .await?;
+        // 認可コードが無効・期限切れ・検証子が一致しない場合は 400 が返る
+        if !response.status().is_success() {
+            warn!(
+                "OIDC token endpoint responded with {}",
+                response.status().as_u16()
+            );
+            return Err(AppError::Unauthorized);
+        }
+        let tokens: TokenEndpointResponse = response.json().await?;
+        self.verify_id_token(&tokens.id_token).await
+    }
+}
@@ -0,0 +1,83 @@
+use crate::infrastructure::db::DbPool;
+use crate::models::oidc::{OidcIdentity, OidcLoginState};
+use crate::repositories::oidc_repository::OidcRepository;
+use async_trait::async_trait;
+
+#[derive(Clone)]
+pub struct OidcRepositoryImpl {
+    pub pool: DbPool,
+}
+
+impl OidcRepositoryImpl {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl OidcRepository for OidcRepositoryImpl {
+    async fn save_login_state(&self, state: OidcLoginState) -> Result<(), sqlx::Error> {
+        // 期限切れのログイン状態はここで片付ける
+        sqlx::query("DELETE FROM oidc_login_states WHERE expires_at <= $1")
+            .bind(state.created_at)
+            .execute(&self.pool)
+            .await?;
+        sqlx::query(
+            "INSERT INTO oidc_login_states (state_hash, nonce, code_verifier, expires_at, created_at)
+             VALUES ($1, $2, $3, $4, $5)",
+        )
+        .bind(state.state_hash)
+        .bind(state.nonce)
+        .bind(state.code_verifier)
+        .bind(state.expires_at)
+        .bind(state.created_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn take_login_state(
+        &self,
+        state_hash: String,
+    ) -> Result<Option<OidcLoginState>, sqlx::Error> {
+        // DELETE ... RETURNING で取得と削除を1文で行い、同じコールバックを繰り返し使えないようにする
+        let state = sqlx::query_as::<_, OidcLoginState>(
+            "DELETE FROM oidc_login_states WHERE state_hash = $1
+             RETURNING state_hash, nonce, code_verifier, expires_at, created_at",
+        )
+        .bind(state_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(state)
+    }
+
+    async fn find_identity(
+        &self,
+        issuer: String,
+        subject: String,
+    ) -> Result<Option<OidcIdentity>, sqlx::Error> {
+        let identity = sqlx::query_as::<_, OidcIdentity>(
+            "SELECT issuer, subject, user_id, created_at FROM oidc_identities
+             WHERE issuer = $1 AND subject = $2",
+        )
+        .bind(issuer)
+        .bind(subject)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(identity)
+    }
+
+    async fn create_identity(&self, identity: OidcIdentity) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO oidc_identities (issuer, subject, user_id, created_at)
+             VALUES ($1, $2, $3, $4)",
+        )
+        .bind(identity.issuer)
+        .bind(identity.subject)
+        .bind(identity.user_id)
+        .bind(identity.created_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,163 @@
+use crate::error::AppError;
+use crate::models::rate_limit::{RateLimitDecision, RateLimitQuota, TokenBucket};
+use crate::repositories::rate_limit_store::RateLimitStore;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use redis::aio::ConnectionManager;
+use redis::Script;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tracing::warn;
+
+// 満杯に戻ったバケットを削除する間隔
+const PRUNE_INTERVAL: Duration = Duration::minutes(1);
+
+// プロセス内に保持するストア（レプリカごとに別々に数える）
+pub struct InMemoryRateLimitStore {
+    state: Mutex<InMemoryState>,
+}
+
+struct InMemoryState {
+    buckets: HashMap<String, (RateLimitQuota, TokenBucket)>,
+    pruned_at: DateTime<Utc>,
+}
+
+impl InMemoryRateLimitStore {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(InMemoryState {
+                buckets: HashMap::new(),
+                pruned_at: Utc::now(),
+            }),
+        }
+    }
+}
+
+impl Default for InMemoryRateLimitStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl RateLimitStore for InMemoryRateLimitStore {
+    async fn take(
+        &self,
+        key: &str,
+        quota: RateLimitQuota,
+        now: DateTime<Utc>,
+    ) -> Result<RateLimitDecision, AppError> {
+        let mut state = self.state.lock().map_err(|_| AppError::InternalError)?;
+        if now - state.pruned_at >= PRUNE_INTERVAL {
+            state
+                .buckets
+                .retain(|_, (quota, bucket)| !bucket.is_idle(*quota, now));
+            state.pruned_at = now;
+        }
+        let (_, bucket) = state
+            .buckets
+            .entry(key.to_string())
+            .or_insert_with(|| (quota, TokenBucket::full(quota, now)));
+        Ok(bucket.take(quota, now))
+    }
+
+    async fn peek(
+        &self,
+        key: &str,
+        quota: RateLimitQuota,
+        now: DateTime<Utc>,
+    ) -> Result<RateLimitDecision, AppError> {
+        let state = self.state.lock().map_err(|_| AppError::InternalError)?;
+        Ok(match state.buckets.get(key) {
+            Some((_, bucket)) => bucket.peek(quota, now),
+            None => TokenBucket::full(quota, now).peek(quota, now),
+        })
+    }
+}
+
+// TokenBucket::take と同じ計算を Redis 上でまとめて行う（レプリカ間で競合しないようにする）
+// KEYS[1]: バケットのキー / ARGV: limit, 1ミリ秒あたりの補充数, 現在時刻（ミリ秒）, 有効期限（ミリ秒）
+const TAKE_SCRIPT: &str = r#"
+local limit = tonumber(ARGV[1])
+local rate = tonumber(ARGV[2])
+local now = tonumber(ARGV[3])
+local bucket = redis.call('HMGET', KEYS[1], 'tokens', 'updated_at')
+local tokens = tonumber(bucket[1]) or limit
+local updated_at = tonumber(bucket[2]) or now
+tokens = math.min(limit, tokens + math.max(0, now - updated_at) * rate)
+local allowed = 0
+if tokens >= 1 then
+  tokens = tokens - 1
+  allowed = 1
+end
+redis.call('HSET', KEYS[1], 'tokens', tostring(tokens), 'updated_at', ARGV[3])
+redis.call('PEXPIRE', KEYS[1], ARGV[4])
+return {allowed, tostring(tokens)}
+"#;
+
+// Redis に保持するストア（すべてのレプリカで上限を共有する）
+#[derive(Clone)]
+pub struct RedisRateLimitStore {
+    connection: ConnectionManager,
+    script: Script,
+}
+
+impl RedisRateLimitStore {
+    pub async fn connect(redis_url: &str) -> Result<Self, redis::RedisError> {
+        let client = redis::Client::open(redis_url)?;
+        Ok(Self {
+            connection: client.get_connection_manager().await?,
+            script: Script::new(TAKE_SCRIPT),
+        })
+    }
+}
+
+#[async_trait]
+impl RateLimitStore for RedisRateLimitStore {
+    async fn take(
+        &self,
+        key: &str,
+        quota: RateLimitQuota,
+        now: DateTime<Utc>,
+    ) -> Result<RateLimitDecision, AppError> {
+        let (allowed, tokens): (i64, String) = self
+            .script
+            .key(format!("rate_limit:{}", key))
+            .arg(quota.limit)
+            .arg(quota.refill_per_milli())
+            .arg(now.timestamp_millis())
+            .arg(quota.window.num_milliseconds())
+            .invoke_async(&mut self.connection.clone())
+            .await
+            .map_err(|e| {
+                warn!("failed to update rate limit bucket in Redis: {}", e);
+                AppError::InternalError
+            })?;
+        let tokens = tokens.parse::<f64>().map_err(|_| AppError::InternalError)?;
+        Ok(RateLimitDecision::new(allowed == 1, quota, tokens))
+    }
+
+    async fn peek(
+        &self,
+        key: &str,
+        quota: RateLimitQuota,
+        now: DateTime<Utc>,
+    ) -> Result<RateLimitDecision, AppError> {
+        let (tokens, updated_at): (Option<f64>, Option<i64>) = redis::cmd("HMGET")
+            .arg(format!("rate_limit:{}", key))
+            .arg("tokens")
+            .arg("updated_at")
+            .query_async(&mut self.connection.clone())
+            .await
+            .map_err(|e| {
+                warn!("failed to read rate limit bucket from Redis: {}", e);
+                AppError::InternalError
+            })?;
+        // TAKE_SCRIPT と同じく、バケットがなければ満杯として扱う
+        let bucket = match (tokens, updated_at.and_then(DateTime::from_timestamp_millis)) {
+            (Some(tokens), Some(updated_at)) => TokenBucket { tokens, updated_at },
+            _ => TokenBucket::full(quota, now),
+        };
+        Ok(bucket.peek(quota, now))
+    }
+}
@@ -0,0 +1,54 @@
+use crate::infrastructure::db::DbPool;
+use crate::models::auth_token::RefreshToken;
+use crate::repositories::refresh_token_repository::RefreshTokenRepository;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct RefreshTokenRepositoryImpl {
+    pub pool: DbPool,
+}
+
+impl RefreshTokenRepositoryImpl {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl RefreshTokenRepository for RefreshTokenRepositoryImpl {
+    async fn create(&self, token: RefreshToken) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO refresh_tokens (id, user_id, token_hash, expires_at, created_at)
+             VALUES ($1, $2, $3, $4, $5)",
+        )
+        .bind(token.id)
+        .bind(token.user_id)
+        .bind(token.token_hash)
+        .bind(token.expires_at)
+        .bind(token.created_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn consume(&self, token_hash: String) -> Result<Option<RefreshToken>, sqlx::Error> {
+        // DELETE ... RETURNING で取得と削除を1文で行い、同時に使われても1回しか成功しないようにする
+        let token = sqlx::query_as::<_, RefreshToken>(
+            "DELETE FROM refresh_tokens WHERE token_hash = $1
+             RETURNING id, user_id, token_hash, expires_at, created_at",
+        )
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(token)
+    }
+
+    async fn delete_by_user(&self, user_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM refresh_tokens WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,84 @@
+use crate::infrastructure::db::DbPool;
+use crate::models::session::Session;
+use crate::repositories::session_repository::SessionRepository;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+// SELECT / RETURNING で取得するカラム
+const SESSION_COLUMNS: &str =
+    "id, user_id, token_hash, csrf_token_hash, created_at, last_seen_at, expires_at";
+
+#[derive(Clone)]
+pub struct SessionRepositoryImpl {
+    pub pool: DbPool,
+}
+
+impl SessionRepositoryImpl {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SessionRepository for SessionRepositoryImpl {
+    async fn create(&self, session: Session) -> Result<(), sqlx::Error> {
+        // 絶対タイムアウトを過ぎた同じユーザーのセッションはここで片付ける
+        sqlx::query("DELETE FROM sessions WHERE user_id = $1 AND expires_at <= $2")
+            .bind(session.user_id)
+            .bind(session.created_at)
+            .execute(&self.pool)
+            .await?;
+        sqlx::query(
+            "INSERT INTO sessions
+                 (id, user_id, token_hash, csrf_token_hash, created_at, last_seen_at, expires_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)",
+        )
+        .bind(session.id)
+        .bind(session.user_id)
+        .bind(session.token_hash)
+        .bind(session.csrf_token_hash)
+        .bind(session.created_at)
+        .bind(session.last_seen_at)
+        .bind(session.expires_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn find_by_token_hash(&self, token_hash: String) -> Result<Option<Session>, sqlx::Error> {
+        let session = sqlx::query_as::<_, Session>(&format!(
+            "SELECT {} FROM sessions WHERE token_hash = $1",
+            SESSION_COLUMNS
+        ))
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(session)
+    }
+
+    async fn touch(&self, id: Uuid, seen_at: DateTime<Utc>) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE sessions SET last_seen_at = $2 WHERE id = $1")
+            .bind(id)
+            .bind(seen_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_by_token_hash(&self, token_hash: String) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM sessions WHERE token_hash = $1")
+            .bind(token_hash)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_by_user(&self, user_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM sessions WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
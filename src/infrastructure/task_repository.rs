@@ -1,76 +1,348 @@
-use crate::infrastructure::db::DbPool;
-use crate::models::task::Task;
+use crate::infrastructure::db::{record_statement, DbPool, TenantPool};
+use crate::models::stats::{CompletionBucket, StatsRange, TaskStats, TaskStatusCounts};
+use crate::models::task::{Task, TaskFilter};
 use crate::repositories::task_repository::TaskRepository;
 use async_trait::async_trait;
+use sqlx::{Postgres, Transaction};
+use tracing::field::Empty;
+use tracing::instrument;
 use uuid::Uuid;
 
+// SELECT / RETURNING で取得するカラム（tracked_seconds は計測中のタイマーの経過時間も含む）
+const TASK_COLUMNS: &str = "id, workspace_id, owner_id, title, completed, created_at, updated_at, due_at, project, tags, completed_at, completed_by,
+     (SELECT COALESCE(sum(EXTRACT(EPOCH FROM (COALESCE(te.ended_at, NOW()) - te.started_at))), 0)::bigint
+      FROM time_entries te WHERE te.task_id = tasks.id) AS tracked_seconds";
+
+const DELETE_SQL: &str = "DELETE FROM tasks WHERE id = $1 AND workspace_id = $2";
+
+const STATS_STATUS_SQL: &str = "SELECT count(*) AS total,
+                count(*) FILTER (WHERE completed) AS completed,
+                count(*) FILTER (WHERE NOT completed) AS open,
+                count(*) FILTER (WHERE NOT completed AND due_at < NOW()) AS overdue
+         FROM tasks
+         WHERE workspace_id = $1";
+
+const STATS_AVG_SQL: &str = "SELECT EXTRACT(EPOCH FROM avg(completed_at - created_at))::float8
+         FROM tasks
+         WHERE workspace_id = $3 AND completed_at >= $1 AND completed_at < $2";
+
+// バケットはタイムゾーンのローカル時刻で区切り、空のバケットも0件として返す
+const STATS_COMPLETION_SQL: &str = "WITH buckets AS (
+             SELECT generate_series(
+                 date_trunc($1, $2 AT TIME ZONE $4),
+                 date_trunc($1, $3 AT TIME ZONE $4),
+                 ('1 ' || $1)::interval
+             ) AS bucket
+         ),
+         created AS (
+             SELECT date_trunc($1, created_at AT TIME ZONE $4) AS bucket,
+                    count(*) AS created,
+                    count(*) FILTER (WHERE completed) AS created_completed
+             FROM tasks
+             WHERE workspace_id = $5 AND created_at >= $2 AND created_at < $3
+             GROUP BY 1
+         ),
+         finished AS (
+             SELECT date_trunc($1, completed_at AT TIME ZONE $4) AS bucket,
+                    count(*) AS completed
+             FROM tasks
+             WHERE workspace_id = $5 AND completed_at >= $2 AND completed_at < $3
+             GROUP BY 1
+         )
+         SELECT b.bucket AT TIME ZONE $4 AS bucket_start,
+                COALESCE(c.created, 0) AS created,
+                COALESCE(f.completed, 0) AS completed,
+                c.created_completed::float8 / NULLIF(c.created, 0) AS completion_rate
+         FROM buckets b
+         LEFT JOIN created c ON c.bucket = b.bucket
+         LEFT JOIN finished f ON f.bucket = b.bucket
+         ORDER BY b.bucket";
+
 #[derive(Clone)]
 pub struct TaskRepositoryImpl {
-    pub pool: DbPool,
+    pub pool: TenantPool,
 }
 
 impl TaskRepositoryImpl {
     pub fn new(pool: DbPool) -> Self {
-        Self { pool }
+        Self {
+            pool: TenantPool::new(pool),
+        }
+    }
+
+    // create と try_create で同じ INSERT を使う
+    async fn insert(
+        tx: &mut Transaction<'static, Postgres>,
+        task: &Task,
+    ) -> Result<Task, sqlx::Error> {
+        let sql = format!(
+            "INSERT INTO tasks (id, title, completed, created_at, updated_at, due_at, project, tags, completed_at, completed_by, owner_id, workspace_id)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+             RETURNING {}",
+            TASK_COLUMNS
+        );
+        record_statement(&sql);
+        sqlx::query_as::<_, Task>(&sql)
+            .bind(task.id)
+            .bind(&task.title)
+            .bind(task.completed)
+            .bind(task.created_at)
+            .bind(task.updated_at)
+            .bind(task.due_at)
+            .bind(&task.project)
+            .bind(&task.tags)
+            .bind(task.completed_at)
+            .bind(task.completed_by)
+            .bind(task.owner_id)
+            .bind(task.workspace_id)
+            .fetch_one(&mut **tx)
+            .await
     }
 }
 
 #[async_trait]
 impl TaskRepository for TaskRepositoryImpl {
-    async fn find_all(&self) -> Result<Vec<Task>, sqlx::Error> {
-        let tasks = sqlx::query_as::<_, Task>(
-            "SELECT id, title, completed, created_at, updated_at FROM tasks",
-        )
-        .fetch_all(&self.pool)
-        .await?;
+    #[instrument(
+        name = "TaskRepository::find_all",
+        skip_all,
+        fields(db.system = "postgresql", db.statement = Empty, workspace_id = %workspace_id)
+    )]
+    async fn find_all(&self, workspace_id: Uuid) -> Result<Vec<Task>, sqlx::Error> {
+        let sql = format!("SELECT {} FROM tasks WHERE workspace_id = $1", TASK_COLUMNS);
+        record_statement(&sql);
+        let mut tx = self.pool.begin(workspace_id).await?;
+        let tasks = sqlx::query_as::<_, Task>(&sql)
+            .bind(workspace_id)
+            .fetch_all(&mut *tx)
+            .await?;
+        tx.commit().await?;
         Ok(tasks)
     }
 
-    async fn find_by_id(&self, id: Uuid) -> Result<Option<Task>, sqlx::Error> {
-        let task = sqlx::query_as::<_, Task>(
-            "SELECT id, title, completed, created_at, updated_at FROM tasks WHERE id = $1",
+    #[instrument(
+        name = "TaskRepository::find_by_id",
+        skip_all,
+        fields(
+            db.system = "postgresql",
+            db.statement = Empty,
+            workspace_id = %workspace_id,
+            task_id = %id
         )
-        .bind(id)
-        .fetch_optional(&self.pool)
-        .await?;
+    )]
+    async fn find_by_id(&self, workspace_id: Uuid, id: Uuid) -> Result<Option<Task>, sqlx::Error> {
+        let sql = format!(
+            "SELECT {} FROM tasks WHERE id = $1 AND workspace_id = $2",
+            TASK_COLUMNS
+        );
+        record_statement(&sql);
+        let mut tx = self.pool.begin(workspace_id).await?;
+        let task = sqlx::query_as::<_, Task>(&sql)
+            .bind(id)
+            .bind(workspace_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        tx.commit().await?;
         Ok(task)
     }
 
-    async fn create(&self, task: Task) -> Result<Task, sqlx::Error> {
-        let created_task = sqlx::query_as::<_, Task>(
-            "INSERT INTO tasks (id, title, completed, created_at, updated_at)
-             VALUES ($1, $2, $3, $4, $5)
-             RETURNING id, title, completed, created_at, updated_at",
+    #[instrument(
+        name = "TaskRepository::find_filtered",
+        skip_all,
+        fields(db.system = "postgresql", db.statement = Empty, workspace_id = %workspace_id)
+    )]
+    async fn find_filtered(
+        &self,
+        workspace_id: Uuid,
+        filter: TaskFilter,
+    ) -> Result<Vec<Task>, sqlx::Error> {
+        let sql = format!(
+            "SELECT {} FROM tasks
+             WHERE workspace_id = $4
+               AND ($1::text IS NULL OR project = $1)
+               AND ($2::text IS NULL OR $2 = ANY(tags))
+               AND ($3::timestamptz IS NULL OR completed_at >= $3)
+             ORDER BY id",
+            TASK_COLUMNS
+        );
+        record_statement(&sql);
+        let mut tx = self.pool.begin(workspace_id).await?;
+        let tasks = sqlx::query_as::<_, Task>(&sql)
+            .bind(filter.project)
+            .bind(filter.tag)
+            .bind(filter.completed_since)
+            .bind(workspace_id)
+            .fetch_all(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(tasks)
+    }
+
+    #[instrument(
+        name = "TaskRepository::find_page",
+        skip_all,
+        fields(db.system = "postgresql", db.statement = Empty, workspace_id = %workspace_id)
+    )]
+    async fn find_page(
+        &self,
+        workspace_id: Uuid,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<Task>, sqlx::Error> {
+        let sql = format!(
+            "SELECT {} FROM tasks
+             WHERE workspace_id = $3 AND ($1::uuid IS NULL OR id > $1)
+             ORDER BY id
+             LIMIT $2",
+            TASK_COLUMNS
+        );
+        record_statement(&sql);
+        let mut tx = self.pool.begin(workspace_id).await?;
+        let tasks = sqlx::query_as::<_, Task>(&sql)
+            .bind(after)
+            .bind(limit)
+            .bind(workspace_id)
+            .fetch_all(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(tasks)
+    }
+
+    #[instrument(
+        name = "TaskRepository::create",
+        skip_all,
+        fields(
+            db.system = "postgresql",
+            db.statement = Empty,
+            workspace_id = %task.workspace_id,
+            task_id = %task.id
         )
-        .bind(task.id)
-        .bind(&task.title)
-        .bind(task.completed)
-        .bind(task.created_at)
-        .bind(task.updated_at)
-        .fetch_one(&self.pool)
-        .await?;
+    )]
+    async fn create(&self, task: Task) -> Result<Task, sqlx::Error> {
+        let mut tx = self.pool.begin(task.workspace_id).await?;
+        let created_task = Self::insert(&mut tx, &task).await?;
+        tx.commit().await?;
         Ok(created_task)
     }
 
-    async fn update(&self, task: Task) -> Result<Task, sqlx::Error> {
-        let updated_task = sqlx::query_as::<_, Task>(
-            "UPDATE tasks SET title = $1, completed = $2, updated_at = (NOW() AT TIME ZONE 'Asia/Tokyo')
-             WHERE id = $3
-             RETURNING id, title, completed, created_at, updated_at"
+    #[instrument(
+        name = "TaskRepository::try_create",
+        skip_all,
+        fields(
+            db.system = "postgresql",
+            db.statement = Empty,
+            workspace_id = %task.workspace_id,
+            task_id = %task.id
         )
-        .bind(&task.title)
-        .bind(task.completed)
-        .bind(task.id)
-        .fetch_one(&self.pool)
-        .await?;
+    )]
+    async fn try_create(&self, task: Task) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin(task.workspace_id).await?;
+        Self::insert(&mut tx, &task).await?;
+        tx.rollback().await
+    }
+
+    #[instrument(
+        name = "TaskRepository::update",
+        skip_all,
+        fields(
+            db.system = "postgresql",
+            db.statement = Empty,
+            workspace_id = %task.workspace_id,
+            task_id = %task.id
+        )
+    )]
+    async fn update(&self, task: Task) -> Result<Task, sqlx::Error> {
+        let sql = format!(
+            "UPDATE tasks SET title = $1, completed = $2, due_at = $3, project = $4, tags = $5, completed_at = $6,
+                 completed_by = $7, updated_at = NOW()
+             WHERE id = $8 AND workspace_id = $9
+             RETURNING {}",
+            TASK_COLUMNS
+        );
+        record_statement(&sql);
+        let mut tx = self.pool.begin(task.workspace_id).await?;
+        let updated_task = sqlx::query_as::<_, Task>(&sql)
+            .bind(&task.title)
+            .bind(task.completed)
+            .bind(task.due_at)
+            .bind(&task.project)
+            .bind(&task.tags)
+            .bind(task.completed_at)
+            .bind(task.completed_by)
+            .bind(task.id)
+            .bind(task.workspace_id)
+            .fetch_one(&mut *tx)
+            .await?;
+        tx.commit().await?;
         Ok(updated_task)
     }
 
-    async fn delete(&self, id: Uuid) -> Result<(), sqlx::Error> {
-        sqlx::query("DELETE FROM tasks WHERE id = $1")
+    #[instrument(
+        name = "TaskRepository::delete",
+        skip_all,
+        fields(
+            db.system = "postgresql",
+            db.statement = Empty,
+            workspace_id = %workspace_id,
+            task_id = %id
+        )
+    )]
+    async fn delete(&self, workspace_id: Uuid, id: Uuid) -> Result<(), sqlx::Error> {
+        record_statement(DELETE_SQL);
+        let mut tx = self.pool.begin(workspace_id).await?;
+        let result = sqlx::query(DELETE_SQL)
             .bind(id)
-            .execute(&self.pool)
+            .bind(workspace_id)
+            .execute(&mut *tx)
             .await?;
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        tx.commit().await?;
         Ok(())
     }
+
+    #[instrument(
+        name = "TaskRepository::aggregate_stats",
+        skip_all,
+        fields(db.system = "postgresql", db.statement = Empty, workspace_id = %workspace_id)
+    )]
+    async fn aggregate_stats(
+        &self,
+        workspace_id: Uuid,
+        range: StatsRange,
+    ) -> Result<TaskStats, sqlx::Error> {
+        record_statement(&[STATS_STATUS_SQL, STATS_AVG_SQL, STATS_COMPLETION_SQL].join(";\n"));
+        let mut tx = self.pool.begin(workspace_id).await?;
+        let status = sqlx::query_as::<_, TaskStatusCounts>(STATS_STATUS_SQL)
+            .bind(workspace_id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        let avg_seconds_to_complete: Option<f64> = sqlx::query_scalar(STATS_AVG_SQL)
+            .bind(range.from)
+            .bind(range.to)
+            .bind(workspace_id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        let completion = sqlx::query_as::<_, CompletionBucket>(STATS_COMPLETION_SQL)
+            .bind(range.bucket.as_sql())
+            .bind(range.from)
+            .bind(range.to)
+            .bind(&range.timezone)
+            .bind(workspace_id)
+            .fetch_all(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(TaskStats {
+            bucket: range.bucket,
+            from: range.from,
+            to: range.to,
+            timezone: range.timezone,
+            status,
+            avg_seconds_to_complete,
+            completion,
+        })
+    }
 }
@@ -0,0 +1,54 @@
+use crate::infrastructure::api_key_repository::ApiKeyRepositoryImpl;
+use crate::infrastructure::tests::task_repository_tests::{create_test_owner, setup_test_db};
+use crate::models::api_key::{ApiKey, ApiKeyScope};
+use crate::repositories::api_key_repository::ApiKeyRepository;
+use crate::secret::{generate_key_prefix, hash_token};
+use chrono::{Duration, Utc};
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_create_find_touch_and_delete() {
+    let pool = setup_test_db().await;
+    let user_id = create_test_owner(&pool).await;
+    let other_user_id = create_test_owner(&pool).await;
+    let repo = ApiKeyRepositoryImpl::new(pool);
+
+    // 個人ワークスペースで使うキーを作成
+    let prefix = generate_key_prefix();
+    let created = repo
+        .create(ApiKey::new(
+            user_id,
+            user_id,
+            "ci".to_string(),
+            prefix.clone(),
+            hash_token("tk_secret"),
+            vec![ApiKeyScope::TasksRead, ApiKeyScope::TasksWrite],
+            Utc::now() + Duration::days(30),
+        ))
+        .await
+        .unwrap();
+
+    // prefix で検索でき、スコープの配列も読み書きできる
+    let found = repo.find_by_prefix(prefix.clone()).await.unwrap().unwrap();
+    assert_eq!(found.id, created.id);
+    assert_eq!(
+        found.scopes,
+        vec![ApiKeyScope::TasksRead, ApiKeyScope::TasksWrite]
+    );
+    assert!(found.last_used_at.is_none());
+    assert_eq!(repo.find_by_user(user_id).await.unwrap().len(), 1);
+    assert!(repo.find_by_user(other_user_id).await.unwrap().is_empty());
+
+    // 最終使用日時を記録する
+    repo.touch(created.id, Utc::now()).await.unwrap();
+    let touched = repo.find_by_prefix(prefix.clone()).await.unwrap().unwrap();
+    assert!(touched.last_used_at.is_some());
+
+    // 他のユーザーは削除できない
+    assert!(matches!(
+        repo.delete(other_user_id, created.id).await,
+        Err(sqlx::Error::RowNotFound)
+    ));
+    repo.delete(user_id, created.id).await.unwrap();
+    assert!(repo.find_by_prefix(prefix).await.unwrap().is_none());
+}
@@ -0,0 +1,76 @@
+use crate::infrastructure::calendar_token_repository::CalendarTokenRepositoryImpl;
+use crate::infrastructure::tests::task_repository_tests::{create_test_owner, setup_test_db};
+use crate::models::calendar::CalendarTokenOwner;
+use crate::repositories::calendar_token_repository::CalendarTokenRepository;
+use crate::secret::{generate_token, hash_token};
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_create_and_find_owner() {
+    let pool = setup_test_db().await;
+    let user_id = create_test_owner(&pool).await;
+    let repo = CalendarTokenRepositoryImpl::new(pool.clone());
+
+    // ユーザーと個人ワークスペースに紐付けてトークンのハッシュを保存
+    let token_hash = hash_token(&generate_token());
+    repo.create(user_id, user_id, token_hash.clone())
+        .await
+        .unwrap();
+
+    // 検証
+    assert_eq!(
+        repo.find_owner(token_hash.clone()).await.unwrap(),
+        Some(CalendarTokenOwner {
+            user_id,
+            workspace_id: user_id,
+        })
+    );
+    assert!(repo
+        .find_owner(hash_token("unknown"))
+        .await
+        .unwrap()
+        .is_none());
+
+    // 後処理：作成したトークンを削除
+    sqlx::query("DELETE FROM calendar_tokens WHERE token_hash = $1")
+        .bind(token_hash)
+        .execute(&pool)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_replace_and_delete_for_user() {
+    let pool = setup_test_db().await;
+    let user_id = create_test_owner(&pool).await;
+    let repo = CalendarTokenRepositoryImpl::new(pool.clone());
+
+    // 2つ発行したトークンを1つに置き換える
+    let first_hash = hash_token(&generate_token());
+    let second_hash = hash_token(&generate_token());
+    repo.create(user_id, user_id, first_hash.clone())
+        .await
+        .unwrap();
+    repo.create(user_id, user_id, second_hash.clone())
+        .await
+        .unwrap();
+    let rotated_hash = hash_token(&generate_token());
+    repo.replace(user_id, user_id, rotated_hash.clone())
+        .await
+        .unwrap();
+
+    // 検証：古いトークンは使えず、新しいトークンだけが使える
+    assert!(repo.find_owner(first_hash).await.unwrap().is_none());
+    assert!(repo.find_owner(second_hash).await.unwrap().is_none());
+    assert!(repo
+        .find_owner(rotated_hash.clone())
+        .await
+        .unwrap()
+        .is_some());
+
+    // 削除した件数を返し、削除後は使えない
+    assert_eq!(repo.delete_for_user(user_id, user_id).await.unwrap(), 1);
+    assert!(repo.find_owner(rotated_hash).await.unwrap().is_none());
+    assert_eq!(repo.delete_for_user(user_id, user_id).await.unwrap(), 0);
+}
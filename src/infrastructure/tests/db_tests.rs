@@ -0,0 +1,200 @@
+use crate::infrastructure::db::TenantPool;
+use crate::infrastructure::task_repository::TaskRepositoryImpl;
+use crate::infrastructure::tests::task_repository_tests::{create_test_owner, setup_test_db};
+use crate::metrics::registry::METRICS;
+use crate::models::task::Task;
+use crate::repositories::task_repository::TaskRepository;
+use uuid::Uuid;
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_tenant_transaction_isolates_rows_without_where_clause() {
+    let pool = setup_test_db().await;
+    let workspace_a = create_test_owner(&pool).await;
+    let workspace_b = create_test_owner(&pool).await;
+    let repo = TaskRepositoryImpl::new(pool.clone());
+
+    // それぞれのワークスペースにTaskを作成
+    let task_a = repo
+        .create(Task::new(workspace_a, workspace_a, "Aのタスク".to_string()))
+        .await
+        .unwrap();
+    repo.create(Task::new(workspace_b, workspace_b, "Bのタスク".to_string()))
+        .await
+        .unwrap();
+
+    // WHERE 句がなくても、テナントのワークスペースの行だけが見える
+    let tenant_pool = TenantPool::new(pool.clone());
+    let mut tx = tenant_pool.begin(workspace_a).await.unwrap();
+    let ids: Vec<Uuid> = sqlx::query_scalar("SELECT id FROM tasks")
+        .fetch_all(&mut *tx)
+        .await
+        .unwrap();
+    assert_eq!(ids, vec![task_a.id]);
+
+    // 他のワークスペースの行は更新・削除の対象にならない
+    let updated = sqlx::query("UPDATE tasks SET title = '乗っ取り'")
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+    assert_eq!(updated.rows_affected(), 1);
+    let deleted = sqlx::query("DELETE FROM tasks WHERE workspace_id = $1")
+        .bind(workspace_b)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+    assert_eq!(deleted.rows_affected(), 0);
+
+    // 他のワークスペースの行は作成できない
+    let insert = sqlx::query(
+        "INSERT INTO tasks (id, title, workspace_id, owner_id) VALUES ($1, 'x', $2, $2)",
+    )
+    .bind(Uuid::now_v7())
+    .bind(workspace_b)
+    .execute(&mut *tx)
+    .await;
+    assert!(insert.is_err());
+    tx.rollback().await.unwrap();
+
+    // テナントを設定しないトランザクションでも、切り替えたロールではどの行も見えない
+    let mut tx = pool.begin().await.unwrap();
+    sqlx::query("SET LOCAL ROLE app_tenant")
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+    let count: i64 = sqlx::query_scalar("SELECT count(*) FROM tasks")
+        .fetch_one(&mut *tx)
+        .await
+        .unwrap();
+    assert_eq!(count, 0);
+    tx.rollback().await.unwrap();
+
+    // 後処理：作成したユーザーを削除（Taskはカスケード削除される）
+    sqlx::query("DELETE FROM users WHERE id = ANY($1)")
+        .bind(vec![workspace_a, workspace_b])
+        .execute(&pool)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_tenant_transaction_records_acquire_duration() {
+    let pool = setup_test_db().await;
+    let tenant_pool = TenantPool::new(pool);
+    let before = METRICS.db_pool_acquire_duration_seconds.get_sample_count();
+
+    let tx = tenant_pool.begin(Uuid::now_v7()).await.unwrap();
+    tx.rollback().await.unwrap();
+
+    // 他のテストも並行して記録するため、増えたことだけを確認する
+    assert!(METRICS.db_pool_acquire_duration_seconds.get_sample_count() > before);
+}
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_tenant_transaction_isolates_workspace_tables() {
+    let pool = setup_test_db().await;
+    let workspace_a = create_test_owner(&pool).await;
+    let workspace_b = create_test_owner(&pool).await;
+
+    // それぞれのワークスペースに共有・APIキー・カレンダーのトークン・セッションを作成
+    for workspace_id in [workspace_a, workspace_b] {
+        let task = TaskRepositoryImpl::new(pool.clone())
+            .create(Task::new(workspace_id, workspace_id, "タスク".to_string()))
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO grants (id, workspace_id, task_id, grantee_id, permission, created_by)
+             VALUES ($1, $2, $3, $2, 'view', $2)",
+        )
+        .bind(Uuid::now_v7())
+        .bind(workspace_id)
+        .bind(task.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO api_keys (id, user_id, workspace_id, name, prefix, key_hash, scopes, expires_at)
+             VALUES ($1, $2, $2, 'key', $3, 'hash', '{tasks:read}', now() + interval '1 day')",
+        )
+        .bind(Uuid::now_v7())
+        .bind(workspace_id)
+        .bind(Uuid::now_v7().to_string())
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO calendar_tokens (token_hash, workspace_id) VALUES ($1, $2)")
+            .bind(Uuid::now_v7().to_string())
+            .bind(workspace_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO sessions (id, user_id, token_hash, csrf_token_hash, expires_at)
+             VALUES ($1, $2, $3, 'csrf', now() + interval '1 day')",
+        )
+        .bind(Uuid::now_v7())
+        .bind(workspace_id)
+        .bind(Uuid::now_v7().to_string())
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+    let workspaces = vec![workspace_a, workspace_b];
+    let count = |table: &str, column: &str| {
+        format!("SELECT count(*) FROM {} WHERE {} = ANY($1)", table, column)
+    };
+    let tables = [
+        ("grants", "workspace_id"),
+        ("api_keys", "workspace_id"),
+        ("calendar_tokens", "workspace_id"),
+        ("workspace_members", "workspace_id"),
+    ];
+
+    // テナントのトランザクションでは、テナントのワークスペースの行だけが見え、セッションは見えない
+    let tenant_pool = TenantPool::new(pool.clone());
+    let mut tx = tenant_pool.begin(workspace_a).await.unwrap();
+    for (table, column) in tables {
+        let visible: i64 = sqlx::query_scalar(&count(table, column))
+            .bind(&workspaces)
+            .fetch_one(&mut *tx)
+            .await
+            .unwrap();
+        assert_eq!(visible, 1, "{}", table);
+    }
+    let sessions: i64 = sqlx::query_scalar(&count("sessions", "user_id"))
+        .bind(&workspaces)
+        .fetch_one(&mut *tx)
+        .await
+        .unwrap();
+    assert_eq!(sessions, 0);
+
+    // 他のワークスペースの行は作成できない
+    let insert = sqlx::query(
+        "INSERT INTO workspace_members (workspace_id, user_id, role) VALUES ($1, $2, 'admin')",
+    )
+    .bind(workspace_b)
+    .bind(workspace_a)
+    .execute(&mut *tx)
+    .await;
+    assert!(insert.is_err());
+    tx.rollback().await.unwrap();
+
+    // ロールを切り替えない接続では、認証の前の検索のためにすべての行が見える
+    for (table, column) in tables.into_iter().chain([("sessions", "user_id")]) {
+        let visible: i64 = sqlx::query_scalar(&count(table, column))
+            .bind(&workspaces)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(visible, 2, "{}", table);
+    }
+
+    // 後処理：作成したユーザーを削除（行はカスケード削除される）
+    sqlx::query("DELETE FROM users WHERE id = ANY($1)")
+        .bind(workspaces)
+        .execute(&pool)
+        .await
+        .unwrap();
+}
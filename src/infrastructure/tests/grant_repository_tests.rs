@@ -0,0 +1,120 @@
+use crate::infrastructure::grant_repository::GrantRepositoryImpl;
+use crate::infrastructure::task_repository::TaskRepositoryImpl;
+use crate::infrastructure::tests::task_repository_tests::{create_test_owner, setup_test_db};
+use crate::models::grant::{Grant, GrantPermission, ShareTarget};
+use crate::models::task::Task;
+use crate::repositories::grant_repository::GrantRepository;
+use crate::repositories::task_repository::TaskRepository;
+use crate::secret::{generate_token, hash_token};
+use chrono::{Duration, Utc};
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_find_active_grants() {
+    let pool = setup_test_db().await;
+    let owner_id = create_test_owner(&pool).await;
+    let grantee_id = create_test_owner(&pool).await;
+    let task_repo = TaskRepositoryImpl::new(pool.clone());
+    let repo = GrantRepositoryImpl::new(pool);
+
+    // 所有者の個人ワークスペースのタスクを共有する
+    let task = task_repo
+        .create(Task::new(owner_id, owner_id, "共有するタスク".to_string()))
+        .await
+        .unwrap();
+    let now = Utc::now();
+    let active = repo
+        .create(Grant::for_user(
+            owner_id,
+            ShareTarget::Task(task.id),
+            grantee_id,
+            GrantPermission::Edit,
+            None,
+            owner_id,
+        ))
+        .await
+        .unwrap();
+    repo.create(Grant::for_user(
+        owner_id,
+        ShareTarget::Project("release".to_string()),
+        grantee_id,
+        GrantPermission::View,
+        Some(now - Duration::hours(1)),
+        owner_id,
+    ))
+    .await
+    .unwrap();
+
+    // 期限切れの共有は含まれない
+    let grants = repo.find_active_by_grantee(grantee_id, now).await.unwrap();
+    assert_eq!(grants, vec![active.clone()]);
+    assert_eq!(repo.find_by_workspace(owner_id).await.unwrap().len(), 2);
+
+    // タスクを削除すると共有も削除される
+    task_repo.delete(owner_id, task.id).await.unwrap();
+    assert!(repo
+        .find_by_id(owner_id, active.id)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_share_link_lookup_and_revoke() {
+    let pool = setup_test_db().await;
+    let owner_id = create_test_owner(&pool).await;
+    let repo = GrantRepositoryImpl::new(pool.clone());
+
+    // 共有リンクを作成
+    let token = generate_token();
+    let now = Utc::now();
+    let link = repo
+        .create(Grant::for_link(
+            owner_id,
+            ShareTarget::Project("release".to_string()),
+            hash_token(&token),
+            now + Duration::hours(1),
+            owner_id,
+        ))
+        .await
+        .unwrap();
+
+    // 期限内だけ見つかる
+    assert_eq!(
+        repo.find_active_by_token(hash_token(&token), now)
+            .await
+            .unwrap()
+            .map(|grant| grant.id),
+        Some(link.id)
+    );
+    assert!(repo
+        .find_active_by_token(hash_token(&token), now + Duration::hours(2))
+        .await
+        .unwrap()
+        .is_none());
+
+    // 編集できる共有リンクは作れない
+    let mut editable = Grant::for_link(
+        owner_id,
+        ShareTarget::Project("release".to_string()),
+        hash_token(&generate_token()),
+        now + Duration::hours(1),
+        owner_id,
+    );
+    editable.permission = GrantPermission::Edit;
+    assert!(repo.create(editable).await.is_err());
+
+    // 他のワークスペースからは取り消せない
+    let other_id = create_test_owner(&pool).await;
+    assert!(matches!(
+        repo.delete(other_id, link.id).await,
+        Err(sqlx::Error::RowNotFound)
+    ));
+    repo.delete(owner_id, link.id).await.unwrap();
+    assert!(repo
+        .find_active_by_token(hash_token(&token), now)
+        .await
+        .unwrap()
+        .is_none());
+}
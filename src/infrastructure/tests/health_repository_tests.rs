@@ -0,0 +1,18 @@
+use crate::infrastructure::db::MIGRATOR;
+use crate::infrastructure::health_repository::HealthRepositoryImpl;
+use crate::infrastructure::tests::task_repository_tests::setup_test_db;
+use crate::repositories::health_repository::HealthRepository;
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_health_checks() {
+    let pool = setup_test_db().await;
+    let repo = HealthRepositoryImpl::new(pool);
+
+    repo.ping().await.unwrap();
+
+    // マイグレーションの履歴がなくてもエラーにせず、未適用のバージョンを返す
+    let pending = repo.pending_migrations().await.unwrap();
+    let known: Vec<i64> = MIGRATOR.iter().map(|migration| migration.version).collect();
+    assert!(pending.iter().all(|version| known.contains(version)));
+}
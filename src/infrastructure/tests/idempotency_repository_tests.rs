@@ -0,0 +1,163 @@
+use crate::infrastructure::idempotency_repository::IdempotencyRepositoryImpl;
+use crate::infrastructure::tests::task_repository_tests::setup_test_db;
+use crate::infrastructure::user_repository::UserRepositoryImpl;
+use crate::models::idempotency::{
+    IdempotencyRecord, StoredResponse, IDEMPOTENCY_KEY_TTL, IN_PROGRESS_TIMEOUT,
+};
+use crate::models::user_account::UserAccount;
+use crate::repositories::idempotency_repository::IdempotencyRepository;
+use crate::repositories::user_repository::UserRepository;
+use chrono::{Duration, Utc};
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_reserve_complete_and_release() {
+    let pool = setup_test_db().await;
+    let user_repo = UserRepositoryImpl::new(pool.clone());
+    let repo = IdempotencyRepositoryImpl::new(pool.clone());
+    let user = user_repo
+        .create(UserAccount::new(
+            "idempotency-test@example.com".to_string(),
+            "hash".to_string(),
+        ))
+        .await
+        .unwrap();
+    let now = Utc::now();
+    let record =
+        |key: &str, at| IdempotencyRecord::new(user.id, key.to_string(), "hash".to_string(), at);
+
+    // 同じキーは1回だけ確保できる
+    let first = record("first", now);
+    assert!(repo.reserve(first.clone()).await.unwrap());
+    assert!(!repo.reserve(record("first", now)).await.unwrap());
+
+    // 処理済みのキーは解放されない
+    let response = StoredResponse {
+        status: 201,
+        headers: vec![("content-type".to_string(), "application/json".to_string())],
+        body: b"{\"id\":1}".to_vec(),
+    };
+    repo.complete(
+        user.id,
+        "first".to_string(),
+        first.attempt_id,
+        response.clone(),
+    )
+    .await
+    .unwrap();
+    repo.release(user.id, "first".to_string(), first.attempt_id)
+        .await
+        .unwrap();
+    let found = repo
+        .find(user.id, "first".to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.response(), Some(response));
+
+    // 処理中のキーは解放すると再び確保できる
+    let second = record("second", now);
+    assert!(repo.reserve(second.clone()).await.unwrap());
+    repo.release(user.id, "second".to_string(), second.attempt_id)
+        .await
+        .unwrap();
+    assert!(repo
+        .find(user.id, "second".to_string())
+        .await
+        .unwrap()
+        .is_none());
+
+    // 期限切れのキーは新しいリクエストとして確保し直せる
+    let later = now + IDEMPOTENCY_KEY_TTL + Duration::seconds(1);
+    assert!(repo.reserve(record("first", later)).await.unwrap());
+    let found = repo
+        .find(user.id, "first".to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(found.response().is_none());
+
+    // 後処理：作成したユーザーを削除（idempotency_keysはカスケード削除される）
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(user.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_concurrent_take_over_succeeds_once() {
+    let pool = setup_test_db().await;
+    let user_repo = UserRepositoryImpl::new(pool.clone());
+    let repo = IdempotencyRepositoryImpl::new(pool.clone());
+    let user = user_repo
+        .create(UserAccount::new(
+            "idempotency-takeover-test@example.com".to_string(),
+            "hash".to_string(),
+        ))
+        .await
+        .unwrap();
+    let now = Utc::now();
+    let record = |at| IdempotencyRecord::new(user.id, "key".to_string(), "hash".to_string(), at);
+
+    // 処理中のまま残ったキー
+    let abandoned = record(now - IN_PROGRESS_TIMEOUT - Duration::seconds(1));
+    assert!(repo.reserve(abandoned.clone()).await.unwrap());
+
+    // 同時に2つの再送が引き継ごうとしても、確保し直せるのは一方だけ
+    let (retry_a, retry_b) = (record(now), record(now));
+    let cutoff = now - IN_PROGRESS_TIMEOUT;
+    let (taken_a, taken_b) = tokio::join!(
+        repo.take_over(retry_a.clone(), cutoff),
+        repo.take_over(retry_b.clone(), cutoff)
+    );
+    let (taken_a, taken_b) = (taken_a.unwrap(), taken_b.unwrap());
+    assert!(taken_a != taken_b);
+    let (owner, loser) = if taken_a {
+        (retry_a, retry_b)
+    } else {
+        (retry_b, retry_a)
+    };
+
+    // 引き継いだ直後のキーは、もう一度は引き継げない
+    assert!(!repo.take_over(record(now), cutoff).await.unwrap());
+
+    // 元の試行や引き継げなかった試行は、引き継いだ試行の行を削除・上書きできない
+    let response = |status| StoredResponse {
+        status,
+        headers: vec![],
+        body: vec![],
+    };
+    repo.release(user.id, "key".to_string(), abandoned.attempt_id)
+        .await
+        .unwrap();
+    repo.complete(user.id, "key".to_string(), loser.attempt_id, response(500))
+        .await
+        .unwrap();
+    let found = repo
+        .find(user.id, "key".to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.attempt_id, owner.attempt_id);
+    assert!(found.response().is_none());
+
+    // 引き継いだ試行はレスポンスを保存できる
+    repo.complete(user.id, "key".to_string(), owner.attempt_id, response(201))
+        .await
+        .unwrap();
+    let found = repo
+        .find(user.id, "key".to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.response(), Some(response(201)));
+
+    // 後処理：作成したユーザーを削除（idempotency_keysはカスケード削除される）
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(user.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+}
@@ -0,0 +1,83 @@
+use crate::infrastructure::db::MIGRATOR;
+use crate::infrastructure::migration::{
+    migrate_down, migrate_up, migration_status, MigrationState,
+};
+use dotenvy::dotenv;
+use sqlx::migrate::Migrate;
+use sqlx::{Connection, PgConnection};
+use std::time::Duration;
+
+// 他のテストとプールを共有しないよう、専用の接続を作る（ロックはセッションごとに取られる）
+async fn connect() -> PgConnection {
+    dotenv().ok();
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set for tests");
+    PgConnection::connect(&database_url)
+        .await
+        .expect("Failed to connect")
+}
+
+fn latest_version() -> i64 {
+    MIGRATOR
+        .iter()
+        .map(|migration| migration.version)
+        .max()
+        .unwrap()
+}
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_migrate_up_applies_all_migrations() {
+    let mut conn = connect().await;
+
+    migrate_up(&mut conn).await.unwrap();
+
+    // 2回目は何も適用しない
+    assert!(migrate_up(&mut conn).await.unwrap().is_empty());
+    let statuses = migration_status(&mut conn).await.unwrap();
+    assert!(!statuses.is_empty());
+    assert!(statuses
+        .iter()
+        .all(|status| status.state == MigrationState::Applied));
+}
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_migrate_down_reverts_latest_migration() {
+    let mut conn = connect().await;
+    migrate_up(&mut conn).await.unwrap();
+    let latest = latest_version();
+
+    assert_eq!(migrate_down(&mut conn, None).await.unwrap(), vec![latest]);
+    let statuses = migration_status(&mut conn).await.unwrap();
+    let pending: Vec<i64> = statuses
+        .iter()
+        .filter(|status| status.state == MigrationState::Pending)
+        .map(|status| status.version)
+        .collect();
+    assert_eq!(pending, vec![latest]);
+
+    // 戻したマイグレーションだけが適用し直される
+    assert_eq!(migrate_up(&mut conn).await.unwrap(), vec![latest]);
+}
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_migrate_up_waits_for_lock() {
+    let mut holder = connect().await;
+    let mut conn = connect().await;
+    migrate_up(&mut conn).await.unwrap();
+
+    // 別のレプリカがマイグレーション中の間は待たされる
+    holder.lock().await.unwrap();
+    let waiting = tokio::spawn(async move { migrate_up(&mut conn).await });
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert!(!waiting.is_finished());
+
+    holder.unlock().await.unwrap();
+    let applied = tokio::time::timeout(Duration::from_secs(5), waiting)
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert!(applied.is_empty());
+}
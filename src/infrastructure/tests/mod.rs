@@ -1 +1,16 @@
-pub mod task_repository_tests;
\ No newline at end of file
+pub mod api_key_repository_tests;
+pub mod calendar_token_repository_tests;
+pub mod db_tests;
+pub mod grant_repository_tests;
+pub mod health_repository_tests;
+pub mod idempotency_repository_tests;
+pub mod migration_tests;
+pub mod oidc_provider_tests;
+pub mod oidc_repository_tests;
+pub mod rate_limit_store_tests;
+pub mod refresh_token_repository_tests;
+pub mod session_repository_tests;
+pub mod task_repository_tests;
+pub mod time_entry_repository_tests;
+pub mod user_repository_tests;
+pub mod workspace_repository_tests;
@@ -0,0 +1,225 @@
+use crate::auth::tests::mock_oidc_server::{
+    jwks, sign, MockOidcServer, CLIENT_ID, KEY_2025_05, KEY_2025_06, REDIRECT_URL,
+};
+use crate::error::AppError;
+use crate::infrastructure::oidc_provider::OidcProviderImpl;
+use crate::models::oidc::{code_challenge, AuthorizationRequest, IdTokenClaims};
+use crate::repositories::oidc_provider::OidcProvider;
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::Algorithm;
+use reqwest::Url;
+use serde_json::json;
+use std::collections::HashMap;
+
+const CODE_VERIFIER: &str = "verifier-0123456789-0123456789-0123456789";
+
+// テスト用のプロバイダーに code_challenge 付きで認可コードを発行させ、交換する
+async fn exchange(
+    server: &MockOidcServer,
+    provider: &OidcProviderImpl,
+    id_token: String,
+) -> Result<IdTokenClaims, AppError> {
+    let code = server.issue_code(&code_challenge(CODE_VERIFIER), id_token);
+    provider
+        .exchange_code(code, CODE_VERIFIER.to_string())
+        .await
+}
+
+#[tokio::test]
+async fn test_authorization_url_uses_discovered_endpoint() {
+    let server = MockOidcServer::start().await;
+    let provider = OidcProviderImpl::new(server.config()).unwrap();
+
+    let url = provider
+        .authorization_url(AuthorizationRequest {
+            state: "state-1".to_string(),
+            nonce: "nonce-1".to_string(),
+            code_challenge: "challenge-1".to_string(),
+        })
+        .await
+        .unwrap();
+
+    // 検証
+    let url = Url::parse(&url).unwrap();
+    assert_eq!(
+        url.as_str().split('?').next().unwrap(),
+        format!("{}/authorize", server.issuer)
+    );
+    let params: HashMap<_, _> = url.query_pairs().into_owned().collect();
+    assert_eq!(params["response_type"], "code");
+    assert_eq!(params["client_id"], CLIENT_ID);
+    assert_eq!(params["redirect_uri"], REDIRECT_URL);
+    assert_eq!(params["scope"], "openid email");
+    assert_eq!(params["state"], "state-1");
+    assert_eq!(params["nonce"], "nonce-1");
+    assert_eq!(params["code_challenge"], "challenge-1");
+    assert_eq!(params["code_challenge_method"], "S256");
+}
+
+#[tokio::test]
+async fn test_exchange_code_returns_verified_claims() {
+    let server = MockOidcServer::start().await;
+    let provider = OidcProviderImpl::new(server.config()).unwrap();
+    let id_token = sign(
+        Algorithm::RS256,
+        "2025-05",
+        KEY_2025_05,
+        &server.claims(json!({
+            "sub": "user-1",
+            "email": "alice@example.com",
+            "email_verified": true,
+            "nonce": "nonce-1"
+        })),
+    );
+
+    let claims = exchange(&server, &provider, id_token).await.unwrap();
+
+    // 検証
+    assert_eq!(claims.iss, server.issuer);
+    assert_eq!(claims.sub, "user-1");
+    assert_eq!(claims.verified_email(), Some("alice@example.com"));
+    assert_eq!(claims.nonce.as_deref(), Some("nonce-1"));
+}
+
+#[tokio::test]
+async fn test_exchange_code_rejects_wrong_code_verifier() {
+    let server = MockOidcServer::start().await;
+    let provider = OidcProviderImpl::new(server.config()).unwrap();
+    let id_token = sign(
+        Algorithm::RS256,
+        "2025-05",
+        KEY_2025_05,
+        &server.claims(json!({ "sub": "user-1" })),
+    );
+    let code = server.issue_code(&code_challenge(CODE_VERIFIER), id_token);
+
+    // 認可リクエストと異なるコード検証子ではトークンエンドポイントが拒否する
+    let result = provider
+        .exchange_code(code, "another-verifier".to_string())
+        .await;
+
+    assert!(matches!(result, Err(AppError::Unauthorized)));
+}
+
+#[tokio::test]
+async fn test_exchange_code_rejects_invalid_id_tokens() {
+    let server = MockOidcServer::start().await;
+    let provider = OidcProviderImpl::new(server.config()).unwrap();
+
+    let invalid_tokens = [
+        // 別のクライアント向け
+        sign(
+            Algorithm::RS256,
+            "2025-05",
+            KEY_2025_05,
+            &server.claims(json!({ "sub": "user-1", "aud": "another-client" })),
+        ),
+        // 別の issuer
+        sign(
+            Algorithm::RS256,
+            "2025-05",
+            KEY_2025_05,
+            &server.claims(json!({ "sub": "user-1", "iss": "https://evil.example.com" })),
+        ),
+        // 期限切れ（検証時の猶予を過ぎたもの）
+        sign(
+            Algorithm::RS256,
+            "2025-05",
+            KEY_2025_05,
+            &server.claims(json!({ "sub": "user-1", "exp": 1_000_000_000 })),
+        ),
+        // kid と異なる鍵で署名
+        sign(
+            Algorithm::RS256,
+            "2025-05",
+            KEY_2025_06,
+            &server.claims(json!({ "sub": "user-1" })),
+        ),
+        // 共有鍵方式（公開鍵を共有鍵として使わせる攻撃）
+        sign(
+            Algorithm::HS256,
+            "2025-05",
+            b"secret",
+            &server.claims(json!({ "sub": "user-1" })),
+        ),
+        // sub がない
+        sign(
+            Algorithm::RS256,
+            "2025-05",
+            KEY_2025_05,
+            &server.claims(json!({})),
+        ),
+    ];
+
+    for id_token in invalid_tokens {
+        let result = exchange(&server, &provider, id_token).await;
+        assert!(
+            matches!(result, Err(AppError::Unauthorized)),
+            "{:?} should be rejected",
+            result
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_refetches_jwks_after_key_rotation() {
+    let server = MockOidcServer::start().await;
+    let provider = OidcProviderImpl::new(server.config()).unwrap();
+
+    // 最初は 2025-05 の鍵だけを公開している
+    let mut old_jwks = jwks();
+    old_jwks
+        .keys
+        .retain(|jwk| jwk.common.key_id.as_deref() == Some("2025-05"));
+    server.set_jwks(old_jwks);
+    let id_token = sign(
+        Algorithm::RS256,
+        "2025-05",
+        KEY_2025_05,
+        &server.claims(json!({ "sub": "user-1" })),
+    );
+    exchange(&server, &provider, id_token).await.unwrap();
+
+    // 2025-06 の鍵を追加して署名を切り替えても、取得し直して検証できる
+    server.set_jwks(jwks());
+    let id_token = sign(
+        Algorithm::RS256,
+        "2025-06",
+        KEY_2025_06,
+        &server.claims(json!({ "sub": "user-1" })),
+    );
+    let claims = exchange(&server, &provider, id_token).await.unwrap();
+    assert_eq!(claims.sub, "user-1");
+
+    // JWKS にない鍵は拒否する
+    server.set_jwks(JwkSet { keys: Vec::new() });
+    let id_token = sign(
+        Algorithm::RS256,
+        "2025-07",
+        KEY_2025_06,
+        &server.claims(json!({ "sub": "user-1" })),
+    );
+    assert!(matches!(
+        exchange(&server, &provider, id_token).await,
+        Err(AppError::Unauthorized)
+    ));
+}
+
+#[tokio::test]
+async fn test_rejects_discovery_with_other_issuer() {
+    let server = MockOidcServer::start().await;
+    // discovery の issuer と設定が一致しない
+    let mut config = server.config();
+    config.issuer_url = format!("{}/", server.issuer);
+    let provider = OidcProviderImpl::new(config).unwrap();
+
+    let result = provider
+        .authorization_url(AuthorizationRequest {
+            state: "state-1".to_string(),
+            nonce: "nonce-1".to_string(),
+            code_challenge: "challenge-1".to_string(),
+        })
+        .await;
+
+    assert!(matches!(result, Err(AppError::InternalError)));
+}
@@ -0,0 +1,85 @@
+use crate::infrastructure::oidc_repository::OidcRepositoryImpl;
+use crate::infrastructure::tests::task_repository_tests::{create_test_owner, setup_test_db};
+use crate::models::oidc::{OidcIdentity, OidcLoginState};
+use crate::repositories::oidc_repository::OidcRepository;
+use crate::secret::{generate_token, hash_token};
+use chrono::{Duration, Utc};
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_save_and_take_login_state() {
+    let pool = setup_test_db().await;
+    let repo = OidcRepositoryImpl::new(pool);
+
+    let state_hash = hash_token(&generate_token());
+    repo.save_login_state(OidcLoginState::new(
+        state_hash.clone(),
+        "nonce".to_string(),
+        "verifier".to_string(),
+        Utc::now(),
+    ))
+    .await
+    .unwrap();
+
+    // 1回目は取得でき、2回目は取得できない
+    let taken = repo.take_login_state(state_hash.clone()).await.unwrap();
+    assert_eq!(
+        taken.map(|s| (s.nonce, s.code_verifier)),
+        Some(("nonce".to_string(), "verifier".to_string()))
+    );
+    assert!(repo.take_login_state(state_hash).await.unwrap().is_none());
+
+    // 期限切れのログイン状態は次の保存時に削除される
+    let expired_hash = hash_token(&generate_token());
+    repo.save_login_state(OidcLoginState::new(
+        expired_hash.clone(),
+        "nonce".to_string(),
+        "verifier".to_string(),
+        Utc::now() - Duration::hours(1),
+    ))
+    .await
+    .unwrap();
+    let fresh_hash = hash_token(&generate_token());
+    repo.save_login_state(OidcLoginState::new(
+        fresh_hash.clone(),
+        "nonce".to_string(),
+        "verifier".to_string(),
+        Utc::now(),
+    ))
+    .await
+    .unwrap();
+    assert!(repo.take_login_state(expired_hash).await.unwrap().is_none());
+    assert!(repo.take_login_state(fresh_hash).await.unwrap().is_some());
+}
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_create_and_find_identity() {
+    let pool = setup_test_db().await;
+    let user_id = create_test_owner(&pool).await;
+    let repo = OidcRepositoryImpl::new(pool);
+    let issuer = "https://idp.example.com".to_string();
+    let subject = generate_token();
+
+    assert!(repo
+        .find_identity(issuer.clone(), subject.clone())
+        .await
+        .unwrap()
+        .is_none());
+    repo.create_identity(OidcIdentity::new(issuer.clone(), subject.clone(), user_id))
+        .await
+        .unwrap();
+
+    // 検証
+    let found = repo
+        .find_identity(issuer.clone(), subject.clone())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.user_id, user_id);
+    // 同じアカウントは二重に紐付けられない
+    assert!(matches!(
+        repo.create_identity(OidcIdentity::new(issuer, subject, user_id)).await,
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation()
+    ));
+}
@@ -0,0 +1,48 @@
+use crate::infrastructure::rate_limit_store::{InMemoryRateLimitStore, RedisRateLimitStore};
+use crate::models::rate_limit::RateLimitQuota;
+use crate::repositories::rate_limit_store::RateLimitStore;
+use crate::secret::generate_token;
+use chrono::{Duration, Utc};
+
+// 同じ上限を2つのキーで数え、キーごとに別々のバケットになることを確かめる
+async fn assert_counts_each_key_separately(store: &impl RateLimitStore, prefix: &str) {
+    let now = Utc::now();
+    let quota = RateLimitQuota::per_minute(2);
+    let first = format!("{}:first", prefix);
+    let second = format!("{}:second", prefix);
+
+    // peek ではトークンは減らない
+    assert_eq!(store.peek(&first, quota, now).await.unwrap().remaining, 2);
+    assert!(store.take(&first, quota, now).await.unwrap().allowed);
+    assert!(store.take(&first, quota, now).await.unwrap().allowed);
+    assert!(!store.peek(&first, quota, now).await.unwrap().allowed);
+    let decision = store.take(&first, quota, now).await.unwrap();
+    assert!(!decision.allowed);
+    assert_eq!(decision.retry_after, 30);
+
+    // 別のキーには影響しない
+    let decision = store.take(&second, quota, now).await.unwrap();
+    assert!(decision.allowed);
+    assert_eq!(decision.remaining, 1);
+
+    // 時間が経てば補充される
+    let decision = store
+        .take(&first, quota, now + Duration::seconds(30))
+        .await
+        .unwrap();
+    assert!(decision.allowed);
+}
+
+#[tokio::test]
+async fn test_in_memory_store() {
+    assert_counts_each_key_separately(&InMemoryRateLimitStore::new(), "memory").await;
+}
+
+#[tokio::test]
+#[ignore = "Requires REDIS_URL to be set"]
+async fn test_redis_store() {
+    let redis_url = std::env::var("REDIS_URL").expect("REDIS_URL must be set");
+    let store = RedisRateLimitStore::connect(&redis_url).await.unwrap();
+    // 実行ごとに別のキーを使う（バケットは window の経過後に期限切れになる）
+    assert_counts_each_key_separately(&store, &format!("test:{}", generate_token())).await;
+}
@@ -0,0 +1,56 @@
+use crate::infrastructure::refresh_token_repository::RefreshTokenRepositoryImpl;
+use crate::infrastructure::tests::task_repository_tests::setup_test_db;
+use crate::infrastructure::user_repository::UserRepositoryImpl;
+use crate::models::auth_token::RefreshToken;
+use crate::models::user_account::UserAccount;
+use crate::repositories::refresh_token_repository::RefreshTokenRepository;
+use crate::repositories::user_repository::UserRepository;
+use crate::secret::{generate_token, hash_token};
+use chrono::{Duration, Utc};
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_create_and_consume() {
+    let pool = setup_test_db().await;
+    let user_repo = UserRepositoryImpl::new(pool.clone());
+    let repo = RefreshTokenRepositoryImpl::new(pool.clone());
+
+    // ユーザーとリフレッシュトークンを作成
+    let user = user_repo
+        .create(UserAccount::new(
+            "refresh-test@example.com".to_string(),
+            "hash".to_string(),
+        ))
+        .await
+        .unwrap();
+    let token_hash = hash_token(&generate_token());
+    repo.create(RefreshToken::new(
+        user.id,
+        token_hash.clone(),
+        Utc::now() + Duration::days(1),
+    ))
+    .await
+    .unwrap();
+
+    // 1回目は取得でき、2回目は取得できない
+    let consumed = repo.consume(token_hash.clone()).await.unwrap();
+    assert_eq!(consumed.map(|t| t.user_id), Some(user.id));
+    assert!(repo.consume(token_hash).await.unwrap().is_none());
+
+    // ユーザーの残りのトークンはまとめて削除できる
+    repo.create(RefreshToken::new(
+        user.id,
+        hash_token(&generate_token()),
+        Utc::now() + Duration::days(1),
+    ))
+    .await
+    .unwrap();
+    assert_eq!(repo.delete_by_user(user.id).await.unwrap(), 1);
+
+    // 後処理：作成したユーザーを削除（refresh_tokensはカスケード削除される）
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(user.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+}
@@ -0,0 +1,75 @@
+use crate::infrastructure::session_repository::SessionRepositoryImpl;
+use crate::infrastructure::tests::task_repository_tests::setup_test_db;
+use crate::infrastructure::user_repository::UserRepositoryImpl;
+use crate::models::session::Session;
+use crate::models::user_account::UserAccount;
+use crate::repositories::session_repository::SessionRepository;
+use crate::repositories::user_repository::UserRepository;
+use crate::secret::{generate_token, hash_token};
+use chrono::{Duration, Utc};
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_session_lifecycle() {
+    let pool = setup_test_db().await;
+    let user_repo = UserRepositoryImpl::new(pool.clone());
+    let repo = SessionRepositoryImpl::new(pool.clone());
+
+    // ユーザーと2つのセッションを作成
+    let user = user_repo
+        .create(UserAccount::new(
+            "session-test@example.com".to_string(),
+            "hash".to_string(),
+        ))
+        .await
+        .unwrap();
+    let first_hash = hash_token(&generate_token());
+    let second_hash = hash_token(&generate_token());
+    let now = Utc::now();
+    for token_hash in [&first_hash, &second_hash] {
+        repo.create(Session::new(
+            user.id,
+            token_hash.clone(),
+            hash_token(&generate_token()),
+            now,
+        ))
+        .await
+        .unwrap();
+    }
+
+    // 最終アクセス日時を更新できる
+    let session = repo
+        .find_by_token_hash(first_hash.clone())
+        .await
+        .unwrap()
+        .unwrap();
+    let seen_at = now + Duration::minutes(5);
+    repo.touch(session.id, seen_at).await.unwrap();
+    let touched = repo
+        .find_by_token_hash(first_hash.clone())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(touched.user_id, user.id);
+    assert_eq!(
+        touched.last_seen_at.timestamp_micros(),
+        seen_at.timestamp_micros()
+    );
+
+    // 1つだけ削除した後、残りをまとめて削除する
+    repo.delete_by_token_hash(first_hash.clone()).await.unwrap();
+    assert!(repo.find_by_token_hash(first_hash).await.unwrap().is_none());
+    assert_eq!(repo.delete_by_user(user.id).await.unwrap(), 1);
+    assert!(repo
+        .find_by_token_hash(second_hash)
+        .await
+        .unwrap()
+        .is_none());
+
+    // 後処理：作成したユーザーを削除
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(user.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+}
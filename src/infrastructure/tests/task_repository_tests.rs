@@ -1,9 +1,16 @@
 use crate::infrastructure::db::DbPool;
 use crate::infrastructure::task_repository::TaskRepositoryImpl;
-use crate::models::task::Task;
+use crate::infrastructure::user_repository::UserRepositoryImpl;
+use crate::models::stats::{StatsBucket, StatsRange};
+use crate::models::task::{Task, TaskFilter};
+use crate::models::user_account::UserAccount;
 use crate::repositories::task_repository::TaskRepository;
+use crate::repositories::user_repository::UserRepository;
+use crate::telemetry::tests::span_recorder::{attribute, SpanRecorder};
+use chrono::{Duration, Utc};
 use dotenvy::dotenv;
 use sqlx::{Error, PgPool};
+use uuid::Uuid;
 
 pub async fn setup_test_db() -> DbPool {
     // .envファイルを読み込む
@@ -24,11 +31,36 @@ pub async fn setup_test_db() -> DbPool {
     pool
 }
 
+// タスクの所有者になるテスト用のユーザーを作成する
+// 個人ワークスペース（id はユーザーIDと同じ）も作成されるので、返したIDはワークスペースIDとしても使える
+pub async fn create_test_owner(pool: &DbPool) -> Uuid {
+    let user = UserAccount::new(
+        format!("owner-{}@example.com", Uuid::now_v7()),
+        "hash".to_string(),
+    );
+    UserRepositoryImpl::new(pool.clone())
+        .create(user)
+        .await
+        .expect("Failed to create test owner")
+        .id
+}
+
 // テスト用にデータベースをリセットする関数
 async fn reset_test_db(pool: &DbPool) -> Result<(), Error> {
     // すべてのテーブルのデータを削除する（必要に応じてテーブル名を変更）
     // DELETE文でタスクテーブルをリセット
     sqlx::query!("DELETE FROM tasks").execute(pool).await?;
+    // create_test_owner で作成したユーザーを削除
+    sqlx::query!("DELETE FROM users WHERE email LIKE 'owner-%@example.com'")
+        .execute(pool)
+        .await?;
+    // メンバーがいなくなった個人ワークスペースを削除
+    sqlx::query!(
+        "DELETE FROM workspaces w
+         WHERE NOT EXISTS (SELECT 1 FROM workspace_members m WHERE m.workspace_id = w.id)"
+    )
+    .execute(pool)
+    .await?;
 
     Ok(())
 }
@@ -37,17 +69,18 @@ async fn reset_test_db(pool: &DbPool) -> Result<(), Error> {
 #[ignore = "Requires DATABASE_URL to be set"]
 async fn test_create_and_find_by_id() {
     let pool = setup_test_db().await;
+    let owner_id = create_test_owner(&pool).await;
     let repo = TaskRepositoryImpl::new(pool);
 
     // 新しいTaskを作成
     let title = "テストタスク".to_string();
-    let task = Task::new(title.clone());
+    let task = Task::new(owner_id, owner_id, title.clone());
 
     // 作成したTaskをデータベースに保存
     let created_task = repo.create(task).await.unwrap();
 
     // IDで検索
-    let found_task = repo.find_by_id(created_task.id).await.unwrap();
+    let found_task = repo.find_by_id(owner_id, created_task.id).await.unwrap();
 
     // 検証
     assert!(found_task.is_some());
@@ -57,27 +90,28 @@ async fn test_create_and_find_by_id() {
     assert!(!found_task.completed);
 
     // 後処理：作成したTaskを削除
-    repo.delete(created_task.id).await.unwrap();
+    repo.delete(owner_id, created_task.id).await.unwrap();
 }
 
 #[tokio::test]
 #[ignore = "Requires DATABASE_URL to be set"]
 async fn test_find_all() {
     let pool = setup_test_db().await;
+    let owner_id = create_test_owner(&pool).await;
     let repo = TaskRepositoryImpl::new(pool);
 
     // 新しいTaskを作成
     let title1 = "テストタスク1".to_string();
-    let task1 = Task::new(title1.clone());
+    let task1 = Task::new(owner_id, owner_id, title1.clone());
     let title2 = "テストタスク2".to_string();
-    let task2 = Task::new(title2.clone());
+    let task2 = Task::new(owner_id, owner_id, title2.clone());
 
     // 作成したTaskをデータベースに保存
     let created_task1 = repo.create(task1).await.unwrap();
     let created_task2 = repo.create(task2).await.unwrap();
 
     // 全件取得
-    let tasks = repo.find_all().await.unwrap();
+    let tasks = repo.find_all(owner_id).await.unwrap();
 
     // 検証
     assert_eq!(tasks.len(), 2);
@@ -89,19 +123,20 @@ async fn test_find_all() {
     assert!(!tasks[1].completed);
 
     // 後処理：作成したTaskを削除
-    repo.delete(created_task1.id).await.unwrap();
-    repo.delete(created_task2.id).await.unwrap();
+    repo.delete(owner_id, created_task1.id).await.unwrap();
+    repo.delete(owner_id, created_task2.id).await.unwrap();
 }
 
 #[tokio::test]
 #[ignore = "Requires DATABASE_URL to be set"]
 async fn test_update() {
     let pool = setup_test_db().await;
+    let owner_id = create_test_owner(&pool).await;
     let repo = TaskRepositoryImpl::new(pool);
 
     // 新しいTaskを作成
     let title = "テストタスク".to_string();
-    let task = Task::new(title.clone());
+    let task = Task::new(owner_id, owner_id, title.clone());
 
     // 作成したTaskをデータベースに保存
     let created_task = repo.create(task).await.unwrap();
@@ -109,10 +144,18 @@ async fn test_update() {
     // Taskを更新
     let update_task = Task {
         id: created_task.id,
+        workspace_id: created_task.workspace_id,
+        owner_id: created_task.owner_id,
         title: "更新後のタスク".to_string(),
         completed: true,
         created_at: created_task.created_at,
         updated_at: created_task.updated_at,
+        due_at: None,
+        project: None,
+        tags: Vec::new(),
+        completed_at: None,
+        completed_by: Some(created_task.owner_id),
+        tracked_seconds: 0,
     };
     let updated_task = repo.update(update_task).await.unwrap();
 
@@ -120,28 +163,356 @@ async fn test_update() {
     assert_eq!(updated_task.id, created_task.id);
     assert_eq!(updated_task.title, "更新後のタスク");
     assert!(updated_task.completed);
+    assert_eq!(updated_task.completed_by, Some(created_task.owner_id));
 
     // 後処理：作成したTaskを削除
-    repo.delete(created_task.id).await.unwrap();
+    repo.delete(owner_id, created_task.id).await.unwrap();
 }
 
 #[tokio::test]
 #[ignore = "Requires DATABASE_URL to be set"]
 async fn test_delete() {
     let pool = setup_test_db().await;
+    let owner_id = create_test_owner(&pool).await;
     let repo = TaskRepositoryImpl::new(pool);
 
     // 新しいTaskを作成
     let title = "テストタスク".to_string();
-    let task = Task::new(title.clone());
+    let task = Task::new(owner_id, owner_id, title.clone());
 
     // 作成したTaskをデータベースに保存
     let created_task = repo.create(task).await.unwrap();
 
     // Taskを削除
-    repo.delete(created_task.id).await.unwrap();
+    repo.delete(owner_id, created_task.id).await.unwrap();
 
     // 検証
-    let found_task = repo.find_by_id(created_task.id).await.unwrap();
+    let found_task = repo.find_by_id(owner_id, created_task.id).await.unwrap();
     assert!(found_task.is_none());
 }
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_find_page() {
+    let pool = setup_test_db().await;
+    let owner_id = create_test_owner(&pool).await;
+    let repo = TaskRepositoryImpl::new(pool);
+
+    // 新しいTaskを3件作成
+    let mut created_ids = Vec::new();
+    for i in 0..3 {
+        let task = Task::new(owner_id, owner_id, format!("テストタスク{}", i));
+        created_ids.push(repo.create(task).await.unwrap().id);
+    }
+
+    // 2件ずつページング
+    let first_page = repo.find_page(owner_id, None, 2).await.unwrap();
+    let second_page = repo
+        .find_page(owner_id, first_page.last().map(|t| t.id), 2)
+        .await
+        .unwrap();
+
+    // 検証（idの昇順で重複なく取得できる）
+    let paged_ids: Vec<_> = first_page
+        .iter()
+        .chain(&second_page)
+        .map(|t| t.id)
+        .collect();
+    assert_eq!(first_page.len(), 2);
+    assert_eq!(second_page.len(), 1);
+    assert_eq!(paged_ids, created_ids);
+
+    // 後処理：作成したTaskを削除
+    for id in created_ids {
+        repo.delete(owner_id, id).await.unwrap();
+    }
+}
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_create_with_existing_id_fails() {
+    let pool = setup_test_db().await;
+    let owner_id = create_test_owner(&pool).await;
+    let repo = TaskRepositoryImpl::new(pool);
+
+    // インポートでは指定されたidのまま作成される
+    let task = Task::new(owner_id, owner_id, "インポートタスク".to_string());
+    let inserted = repo.create(task.clone()).await.unwrap();
+    assert_eq!(inserted.id, task.id);
+
+    // 同じidでは作成できない（上書きされない）
+    let duplicate = repo
+        .create(Task {
+            title: "重複したタスク".to_string(),
+            ..task.clone()
+        })
+        .await;
+
+    // 検証
+    assert!(matches!(duplicate, Err(sqlx::Error::Database(e)) if e.is_unique_violation()));
+    let found = repo.find_by_id(owner_id, task.id).await.unwrap().unwrap();
+    assert_eq!(found.title, "インポートタスク");
+    assert_eq!(repo.find_all(owner_id).await.unwrap().len(), 1);
+
+    // 後処理：作成したTaskを削除
+    repo.delete(owner_id, task.id).await.unwrap();
+}
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_try_create_does_not_write() {
+    let pool = setup_test_db().await;
+    let owner_id = create_test_owner(&pool).await;
+    let other_owner_id = create_test_owner(&pool).await;
+    let repo = TaskRepositoryImpl::new(pool);
+
+    // 作成できるタスクでも書き込まない
+    let task = Task::new(owner_id, owner_id, "インポートタスク".to_string());
+    repo.try_create(task.clone()).await.unwrap();
+    assert!(repo.find_by_id(owner_id, task.id).await.unwrap().is_none());
+
+    // 他のワークスペースのタスクと同じidは create と同じく一意制約違反になる
+    let other = repo
+        .create(Task::new(
+            other_owner_id,
+            other_owner_id,
+            "他のワークスペースのタスク".to_string(),
+        ))
+        .await
+        .unwrap();
+    let result = repo
+        .try_create(Task {
+            id: other.id,
+            ..task.clone()
+        })
+        .await;
+
+    // 検証
+    assert!(matches!(result, Err(sqlx::Error::Database(e)) if e.is_unique_violation()));
+    assert!(repo.find_all(owner_id).await.unwrap().is_empty());
+
+    // 後処理：作成したTaskを削除
+    repo.delete(other_owner_id, other.id).await.unwrap();
+}
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_find_filtered() {
+    let pool = setup_test_db().await;
+    let owner_id = create_test_owner(&pool).await;
+    let repo = TaskRepositoryImpl::new(pool);
+
+    // プロジェクトとタグの異なるTaskを作成
+    let mut tagged = Task::new(owner_id, owner_id, "タグ付き".to_string());
+    tagged.project = Some("website".to_string());
+    tagged.tags = vec!["urgent".to_string(), "design".to_string()];
+    let mut other = Task::new(owner_id, owner_id, "別プロジェクト".to_string());
+    other.project = Some("backend".to_string());
+    let tagged = repo.create(tagged).await.unwrap();
+    let other = repo.create(other).await.unwrap();
+
+    // プロジェクト・タグで絞り込み
+    let by_project = repo
+        .find_filtered(
+            owner_id,
+            TaskFilter {
+                project: Some("website".to_string()),
+                tag: None,
+                completed_since: None,
+            },
+        )
+        .await
+        .unwrap();
+    let by_tag = repo
+        .find_filtered(
+            owner_id,
+            TaskFilter {
+                project: None,
+                tag: Some("design".to_string()),
+                completed_since: None,
+            },
+        )
+        .await
+        .unwrap();
+    let all = repo
+        .find_filtered(owner_id, TaskFilter::default())
+        .await
+        .unwrap();
+
+    // 検証
+    assert_eq!(by_project, vec![tagged.clone()]);
+    assert_eq!(by_tag, vec![tagged.clone()]);
+    assert_eq!(all.len(), 2);
+
+    // 後処理：作成したTaskを削除
+    repo.delete(owner_id, tagged.id).await.unwrap();
+    repo.delete(owner_id, other.id).await.unwrap();
+}
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_aggregate_stats() {
+    let pool = setup_test_db().await;
+    let owner_id = create_test_owner(&pool).await;
+    let repo = TaskRepositoryImpl::new(pool);
+
+    // 完了済み・期限切れ・未完了のTaskを作成
+    let done = repo
+        .create(Task::new(owner_id, owner_id, "完了".to_string()))
+        .await
+        .unwrap();
+    let done = repo
+        .update(Task {
+            completed: true,
+            completed_at: Some(Utc::now()),
+            ..done
+        })
+        .await
+        .unwrap();
+    let mut overdue = Task::new(owner_id, owner_id, "期限切れ".to_string());
+    overdue.due_at = Some(Utc::now() - Duration::days(1));
+    let overdue = repo.create(overdue).await.unwrap();
+    let open = repo
+        .create(Task::new(owner_id, owner_id, "未完了".to_string()))
+        .await
+        .unwrap();
+
+    // 直近3日間を日単位で集計
+    let now = Utc::now();
+    let stats = repo
+        .aggregate_stats(
+            owner_id,
+            StatsRange {
+                bucket: StatsBucket::Day,
+                from: now - Duration::days(2),
+                to: now + Duration::seconds(1),
+                timezone: "Asia/Tokyo".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+
+    // 検証
+    assert_eq!(stats.status.total, 3);
+    assert_eq!(stats.status.completed, 1);
+    assert_eq!(stats.status.open, 2);
+    assert_eq!(stats.status.overdue, 1);
+    assert!(stats.avg_seconds_to_complete.is_some());
+    assert!(stats.completion.len() >= 3);
+    assert_eq!(stats.completion.iter().map(|b| b.created).sum::<i64>(), 3);
+    assert_eq!(stats.completion.iter().map(|b| b.completed).sum::<i64>(), 1);
+
+    // 後処理：作成したTaskを削除
+    for id in [done.id, overdue.id, open.id] {
+        repo.delete(owner_id, id).await.unwrap();
+    }
+}
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_find_filtered_completed_since() {
+    let pool = setup_test_db().await;
+    let owner_id = create_test_owner(&pool).await;
+    let repo = TaskRepositoryImpl::new(pool);
+
+    // 2日前に完了したTaskと、たった今完了したTaskを作成
+    let mut old = Task::new(owner_id, owner_id, "以前に完了".to_string());
+    old.set_completed(true, owner_id, Utc::now() - Duration::days(2));
+    let mut recent = Task::new(owner_id, owner_id, "最近完了".to_string());
+    recent.set_completed(true, owner_id, Utc::now());
+    let old = repo.create(old).await.unwrap();
+    let recent = repo.create(recent).await.unwrap();
+
+    // 1日前以降に完了したTaskに絞り込み
+    let tasks = repo
+        .find_filtered(
+            owner_id,
+            TaskFilter {
+                completed_since: Some(Utc::now() - Duration::days(1)),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    // 検証
+    assert_eq!(tasks, vec![recent.clone()]);
+
+    // 後処理：作成したTaskを削除
+    repo.delete(owner_id, old.id).await.unwrap();
+    repo.delete(owner_id, recent.id).await.unwrap();
+}
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_other_workspaces_tasks_are_invisible() {
+    let pool = setup_test_db().await;
+    let owner_id = create_test_owner(&pool).await;
+    let other_id = create_test_owner(&pool).await;
+    let repo = TaskRepositoryImpl::new(pool);
+
+    // 他のユーザーの個人ワークスペースにTaskを作成
+    let task = repo
+        .create(Task::new(other_id, other_id, "他人のタスク".to_string()))
+        .await
+        .unwrap();
+
+    // 一覧・取得の対象にならない
+    assert!(repo.find_all(owner_id).await.unwrap().is_empty());
+    assert!(repo.find_by_id(owner_id, task.id).await.unwrap().is_none());
+    assert!(repo.find_page(owner_id, None, 10).await.unwrap().is_empty());
+
+    // 更新・削除・同じidでの作成もできない
+    let hijacked = Task {
+        workspace_id: owner_id,
+        owner_id,
+        title: "乗っ取り".to_string(),
+        ..task.clone()
+    };
+    assert!(matches!(
+        repo.update(hijacked.clone()).await,
+        Err(sqlx::Error::RowNotFound)
+    ));
+    assert!(matches!(
+        repo.create(hijacked).await,
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation()
+    ));
+    assert!(matches!(
+        repo.delete(owner_id, task.id).await,
+        Err(sqlx::Error::RowNotFound)
+    ));
+
+    // 元のワークスペースからは変更されていないTaskが見える
+    let found = repo.find_by_id(other_id, task.id).await.unwrap().unwrap();
+    assert_eq!(found.title, "他人のタスク");
+
+    // 後処理：作成したTaskを削除
+    repo.delete(other_id, task.id).await.unwrap();
+}
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_records_statement_on_span() {
+    let pool = setup_test_db().await;
+    let owner_id = create_test_owner(&pool).await;
+    let repo = TaskRepositoryImpl::new(pool);
+    let recorder = SpanRecorder::start();
+
+    let task = repo
+        .create(Task::new(owner_id, owner_id, "テストタスク".to_string()))
+        .await
+        .unwrap();
+
+    // 実行した SQL と対象のワークスペースをスパンの属性に記録する
+    let span = recorder.span("TaskRepository::create");
+    let statement = attribute(&span, "db.statement").unwrap().to_string();
+    assert!(statement.starts_with("INSERT INTO tasks"));
+    assert_eq!(
+        attribute(&span, "db.system").unwrap().to_string(),
+        "postgresql"
+    );
+    assert_eq!(
+        attribute(&span, "task_id").unwrap().to_string(),
+        task.id.to_string()
+    );
+}
@@ -0,0 +1,129 @@
+use crate::infrastructure::task_repository::TaskRepositoryImpl;
+use crate::infrastructure::tests::task_repository_tests::{create_test_owner, setup_test_db};
+use crate::infrastructure::time_entry_repository::TimeEntryRepositoryImpl;
+use crate::models::task::Task;
+use crate::models::time_entry::{TimeEntry, TimeReportRange};
+use crate::repositories::task_repository::TaskRepository;
+use crate::repositories::time_entry_repository::TimeEntryRepository;
+use chrono::{Duration, Utc};
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_start_and_stop_timer() {
+    let pool = setup_test_db().await;
+    let owner_id = create_test_owner(&pool).await;
+    let task_repo = TaskRepositoryImpl::new(pool.clone());
+    let repo = TimeEntryRepositoryImpl::new(pool);
+
+    // Taskとタイマーを作成
+    let task = task_repo
+        .create(Task::new(owner_id, owner_id, "計測タスク".to_string()))
+        .await
+        .unwrap();
+    let started_at = Utc::now() - Duration::minutes(30);
+    let running = repo
+        .create(TimeEntry::new(
+            owner_id,
+            task.id,
+            Some(owner_id),
+            started_at,
+            None,
+            None,
+        ))
+        .await
+        .unwrap();
+
+    // 同じユーザーで2つ目のタイマーは作成できない
+    let second = repo
+        .create(TimeEntry::new(
+            owner_id,
+            task.id,
+            Some(owner_id),
+            Utc::now(),
+            None,
+            None,
+        ))
+        .await;
+    assert!(matches!(second, Err(sqlx::Error::Database(e)) if e.is_unique_violation()));
+
+    // 計測中のタイマーを取得して停止
+    let found = repo.find_running(owner_id, owner_id).await.unwrap();
+    assert_eq!(found.map(|e| e.id), Some(running.id));
+    let stopped = repo.stop(owner_id, running.id, Utc::now()).await.unwrap();
+    assert!(stopped.ended_at.is_some());
+    assert!(repo
+        .find_running(owner_id, owner_id)
+        .await
+        .unwrap()
+        .is_none());
+
+    // タスクの合計作業時間に反映される
+    let task = task_repo
+        .find_by_id(owner_id, task.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(task.tracked_seconds >= 30 * 60);
+
+    // 後処理：作成したTaskを削除（time_entriesはカスケード削除される）
+    task_repo.delete(owner_id, task.id).await.unwrap();
+}
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_report() {
+    let pool = setup_test_db().await;
+    let owner_id = create_test_owner(&pool).await;
+    let task_repo = TaskRepositoryImpl::new(pool.clone());
+    let repo = TimeEntryRepositoryImpl::new(pool);
+
+    // プロジェクトの異なるTaskに作業時間を記録
+    let mut web = Task::new(owner_id, owner_id, "Web".to_string());
+    web.project = Some("website".to_string());
+    let web = task_repo.create(web).await.unwrap();
+    let misc = task_repo
+        .create(Task::new(owner_id, owner_id, "その他".to_string()))
+        .await
+        .unwrap();
+    let start = Utc::now() - Duration::hours(3);
+    for (task_id, minutes) in [(web.id, 60), (web.id, 30), (misc.id, 15)] {
+        repo.create(TimeEntry::new(
+            owner_id,
+            task_id,
+            Some(owner_id),
+            start,
+            Some(start + Duration::minutes(minutes)),
+            None,
+        ))
+        .await
+        .unwrap();
+    }
+
+    // 集計
+    let rows = repo
+        .report(
+            owner_id,
+            owner_id,
+            TimeReportRange {
+                from: start - Duration::minutes(1),
+                to: Utc::now(),
+                timezone: "UTC".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+
+    // 検証（プロジェクトごとに合算される）
+    let seconds_for = |project: Option<&str>| -> i64 {
+        rows.iter()
+            .filter(|r| r.project.as_deref() == project)
+            .map(|r| r.seconds)
+            .sum()
+    };
+    assert_eq!(seconds_for(Some("website")), 90 * 60);
+    assert_eq!(seconds_for(None), 15 * 60);
+
+    // 後処理：作成したTaskを削除
+    task_repo.delete(owner_id, web.id).await.unwrap();
+    task_repo.delete(owner_id, misc.id).await.unwrap();
+}
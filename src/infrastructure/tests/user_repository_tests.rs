@@ -0,0 +1,39 @@
+use crate::infrastructure::tests::task_repository_tests::setup_test_db;
+use crate::infrastructure::user_repository::UserRepositoryImpl;
+use crate::models::user_account::UserAccount;
+use crate::repositories::user_repository::UserRepository;
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_create_and_find_by_email() {
+    let pool = setup_test_db().await;
+    let repo = UserRepositoryImpl::new(pool.clone());
+
+    // ユーザーを作成
+    let user = UserAccount::new("repo-test@example.com".to_string(), "hash".to_string());
+    let created = repo.create(user.clone()).await.unwrap();
+    assert_eq!(created.id, user.id);
+
+    // 大文字・小文字を区別せずに検索できる
+    let found = repo
+        .find_by_email("Repo-Test@Example.com".to_string())
+        .await
+        .unwrap();
+    assert_eq!(found.map(|u| u.id), Some(user.id));
+
+    // 大文字・小文字だけが異なるメールアドレスは登録できない
+    let duplicate = repo
+        .create(UserAccount::new(
+            "REPO-TEST@example.com".to_string(),
+            "hash".to_string(),
+        ))
+        .await;
+    assert!(matches!(duplicate, Err(sqlx::Error::Database(e)) if e.is_unique_violation()));
+
+    // 後処理：作成したユーザーを削除
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(user.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+}
@@ -0,0 +1,197 @@
+use crate::infrastructure::calendar_token_repository::CalendarTokenRepositoryImpl;
+use crate::infrastructure::grant_repository::GrantRepositoryImpl;
+use crate::infrastructure::tests::task_repository_tests::{create_test_owner, setup_test_db};
+use crate::infrastructure::workspace_repository::WorkspaceRepositoryImpl;
+use crate::models::grant::{Grant, GrantPermission, ShareTarget};
+use crate::models::workspace::{Workspace, WorkspaceRole};
+use crate::repositories::calendar_token_repository::CalendarTokenRepository;
+use crate::repositories::grant_repository::GrantRepository;
+use crate::repositories::workspace_repository::WorkspaceRepository;
+use crate::secret::{generate_token, hash_token};
+use chrono::{Duration, Utc};
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_personal_workspace_is_created_with_user() {
+    let pool = setup_test_db().await;
+    let user_id = create_test_owner(&pool).await;
+    let repo = WorkspaceRepositoryImpl::new(pool);
+
+    // ユーザー登録時に個人ワークスペースの管理者になっている
+    assert_eq!(
+        repo.find_role(user_id, user_id).await.unwrap(),
+        Some(WorkspaceRole::Admin)
+    );
+    let memberships = repo.find_memberships(user_id).await.unwrap();
+    assert_eq!(memberships.len(), 1);
+    assert_eq!(memberships[0].workspace_id, user_id);
+}
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_manage_members() {
+    let pool = setup_test_db().await;
+    let admin_id = create_test_owner(&pool).await;
+    let member_id = create_test_owner(&pool).await;
+    let repo = WorkspaceRepositoryImpl::new(pool.clone());
+
+    // ワークスペースを作成すると作成者が管理者になる
+    let workspace = repo
+        .create(Workspace::new("チーム".to_string()), admin_id)
+        .await
+        .unwrap();
+    assert_eq!(
+        repo.find_role(workspace.id, admin_id).await.unwrap(),
+        Some(WorkspaceRole::Admin)
+    );
+    assert_eq!(repo.find_role(workspace.id, member_id).await.unwrap(), None);
+
+    // メンバーを追加し、ロールを変更する
+    repo.upsert_member(workspace.id, member_id, WorkspaceRole::Member)
+        .await
+        .unwrap();
+    repo.upsert_member(workspace.id, member_id, WorkspaceRole::Viewer)
+        .await
+        .unwrap();
+    let members = repo.find_members(workspace.id).await.unwrap();
+    assert_eq!(members.len(), 2);
+    assert_eq!(
+        members
+            .iter()
+            .find(|m| m.user_id == member_id)
+            .map(|m| m.role),
+        Some(WorkspaceRole::Viewer)
+    );
+
+    // メンバーを外す（2回目は対象がない）
+    repo.remove_member(workspace.id, member_id).await.unwrap();
+    assert!(matches!(
+        repo.remove_member(workspace.id, member_id).await,
+        Err(sqlx::Error::RowNotFound)
+    ));
+
+    // 後処理：作成したワークスペースを削除
+    sqlx::query("DELETE FROM workspaces WHERE id = $1")
+        .bind(workspace.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_remove_member_revokes_calendar_tokens() {
+    let pool = setup_test_db().await;
+    let admin_id = create_test_owner(&pool).await;
+    let member_id = create_test_owner(&pool).await;
+    let repo = WorkspaceRepositoryImpl::new(pool.clone());
+    let token_repo = CalendarTokenRepositoryImpl::new(pool.clone());
+    let workspace = repo
+        .create(Workspace::new("チーム".to_string()), admin_id)
+        .await
+        .unwrap();
+    repo.upsert_member(workspace.id, member_id, WorkspaceRole::Member)
+        .await
+        .unwrap();
+
+    // メンバーがチームと個人ワークスペースでトークンを発行する
+    let team_token = hash_token(&generate_token());
+    let personal_token = hash_token(&generate_token());
+    token_repo
+        .create(member_id, workspace.id, team_token.clone())
+        .await
+        .unwrap();
+    token_repo
+        .create(member_id, member_id, personal_token.clone())
+        .await
+        .unwrap();
+
+    // メンバーを外すと、そのワークスペースで発行したトークンだけが削除される
+    repo.remove_member(workspace.id, member_id).await.unwrap();
+    assert!(token_repo.find_owner(team_token).await.unwrap().is_none());
+    assert!(token_repo
+        .find_owner(personal_token)
+        .await
+        .unwrap()
+        .is_some());
+
+    // 後処理：作成したワークスペースを削除
+    sqlx::query("DELETE FROM workspaces WHERE id = $1")
+        .bind(workspace.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[ignore = "Requires DATABASE_URL to be set"]
+async fn test_remove_member_revokes_grants() {
+    let pool = setup_test_db().await;
+    let admin_id = create_test_owner(&pool).await;
+    let member_id = create_test_owner(&pool).await;
+    let grantee_id = create_test_owner(&pool).await;
+    let repo = WorkspaceRepositoryImpl::new(pool.clone());
+    let grant_repo = GrantRepositoryImpl::new(pool.clone());
+    let workspace = repo
+        .create(Workspace::new("チーム".to_string()), admin_id)
+        .await
+        .unwrap();
+    repo.upsert_member(workspace.id, member_id, WorkspaceRole::Member)
+        .await
+        .unwrap();
+
+    // メンバーと管理者がそれぞれ共有を作成する
+    let project = ShareTarget::Project("release".to_string());
+    let by_member = grant_repo
+        .create(Grant::for_user(
+            workspace.id,
+            project.clone(),
+            grantee_id,
+            GrantPermission::Edit,
+            None,
+            member_id,
+        ))
+        .await
+        .unwrap();
+    let link_by_member = grant_repo
+        .create(Grant::for_link(
+            workspace.id,
+            project.clone(),
+            hash_token(&generate_token()),
+            Utc::now() + Duration::hours(1),
+            member_id,
+        ))
+        .await
+        .unwrap();
+    let by_admin = grant_repo
+        .create(Grant::for_user(
+            workspace.id,
+            project,
+            grantee_id,
+            GrantPermission::View,
+            None,
+            admin_id,
+        ))
+        .await
+        .unwrap();
+
+    // メンバーを外すと、そのメンバーが作成した共有だけが削除される
+    repo.remove_member(workspace.id, member_id).await.unwrap();
+    let remaining: Vec<_> = grant_repo
+        .find_by_workspace(workspace.id)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|grant| grant.id)
+        .collect();
+    assert!(!remaining.contains(&by_member.id));
+    assert!(!remaining.contains(&link_by_member.id));
+    assert!(remaining.contains(&by_admin.id));
+
+    // 後処理：作成したワークスペースを削除
+    sqlx::query("DELETE FROM workspaces WHERE id = $1")
+        .bind(workspace.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+}
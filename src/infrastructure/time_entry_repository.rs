@@ -0,0 +1,139 @@
+use crate::infrastructure::db::{DbPool, TenantPool};
+use crate::models::time_entry::{TimeEntry, TimeReportRange, TimeReportRow};
+use crate::repositories::time_entry_repository::TimeEntryRepository;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+// SELECT / RETURNING で取得するカラム
+const TIME_ENTRY_COLUMNS: &str =
+    "id, workspace_id, task_id, user_id, started_at, ended_at, note, created_at";
+
+#[derive(Clone)]
+pub struct TimeEntryRepositoryImpl {
+    pub pool: TenantPool,
+}
+
+impl TimeEntryRepositoryImpl {
+    pub fn new(pool: DbPool) -> Self {
+        Self {
+            pool: TenantPool::new(pool),
+        }
+    }
+}
+
+#[async_trait]
+impl TimeEntryRepository for TimeEntryRepositoryImpl {
+    async fn find_running(
+        &self,
+        workspace_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<TimeEntry>, sqlx::Error> {
+        let mut tx = self.pool.begin(workspace_id).await?;
+        let entry = sqlx::query_as::<_, TimeEntry>(&format!(
+            "SELECT {} FROM time_entries
+             WHERE ended_at IS NULL AND user_id = $1 AND workspace_id = $2",
+            TIME_ENTRY_COLUMNS
+        ))
+        .bind(user_id)
+        .bind(workspace_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(entry)
+    }
+
+    async fn find_by_task(
+        &self,
+        workspace_id: Uuid,
+        task_id: Uuid,
+    ) -> Result<Vec<TimeEntry>, sqlx::Error> {
+        let mut tx = self.pool.begin(workspace_id).await?;
+        let entries = sqlx::query_as::<_, TimeEntry>(&format!(
+            "SELECT {} FROM time_entries
+             WHERE task_id = $1 AND workspace_id = $2
+             ORDER BY started_at",
+            TIME_ENTRY_COLUMNS
+        ))
+        .bind(task_id)
+        .bind(workspace_id)
+        .fetch_all(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(entries)
+    }
+
+    async fn create(&self, entry: TimeEntry) -> Result<TimeEntry, sqlx::Error> {
+        let mut tx = self.pool.begin(entry.workspace_id).await?;
+        let created_entry = sqlx::query_as::<_, TimeEntry>(&format!(
+            "INSERT INTO time_entries (id, workspace_id, task_id, user_id, started_at, ended_at, note, created_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+             RETURNING {}",
+            TIME_ENTRY_COLUMNS
+        ))
+        .bind(entry.id)
+        .bind(entry.workspace_id)
+        .bind(entry.task_id)
+        .bind(entry.user_id)
+        .bind(entry.started_at)
+        .bind(entry.ended_at)
+        .bind(&entry.note)
+        .bind(entry.created_at)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(created_entry)
+    }
+
+    async fn stop(
+        &self,
+        workspace_id: Uuid,
+        id: Uuid,
+        ended_at: DateTime<Utc>,
+    ) -> Result<TimeEntry, sqlx::Error> {
+        let mut tx = self.pool.begin(workspace_id).await?;
+        let stopped_entry = sqlx::query_as::<_, TimeEntry>(&format!(
+            "UPDATE time_entries SET ended_at = $1
+             WHERE id = $2 AND workspace_id = $3 AND ended_at IS NULL
+             RETURNING {}",
+            TIME_ENTRY_COLUMNS
+        ))
+        .bind(ended_at)
+        .bind(id)
+        .bind(workspace_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(stopped_entry)
+    }
+
+    // 日付をまたぐ記録は開始日に計上する
+    async fn report(
+        &self,
+        workspace_id: Uuid,
+        user_id: Uuid,
+        range: TimeReportRange,
+    ) -> Result<Vec<TimeReportRow>, sqlx::Error> {
+        let mut tx = self.pool.begin(workspace_id).await?;
+        let rows = sqlx::query_as::<_, TimeReportRow>(
+            "SELECT (te.started_at AT TIME ZONE $3)::date AS day,
+                    t.project,
+                    sum(EXTRACT(EPOCH FROM (COALESCE(te.ended_at, NOW()) - te.started_at)))::bigint AS seconds
+             FROM time_entries te
+             JOIN tasks t ON t.id = te.task_id
+             WHERE te.workspace_id = $5 AND te.user_id = $4
+               AND te.started_at >= $1 AND te.started_at < $2
+             GROUP BY 1, 2
+             ORDER BY 1, 2 NULLS LAST",
+        )
+        .bind(range.from)
+        .bind(range.to)
+        .bind(&range.timezone)
+        .bind(user_id)
+        .bind(workspace_id)
+        .fetch_all(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(rows)
+    }
+}
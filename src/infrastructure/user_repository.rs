@@ -0,0 +1,81 @@
+use crate::infrastructure::db::DbPool;
+use crate::models::user_account::UserAccount;
+use crate::models::workspace::Workspace;
+use crate::repositories::user_repository::UserRepository;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+// SELECT / RETURNING で取得するカラム
+const USER_COLUMNS: &str = "id, email, password_hash, created_at, updated_at";
+
+#[derive(Clone)]
+pub struct UserRepositoryImpl {
+    pub pool: DbPool,
+}
+
+impl UserRepositoryImpl {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl UserRepository for UserRepositoryImpl {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<UserAccount>, sqlx::Error> {
+        let user = sqlx::query_as::<_, UserAccount>(&format!(
+            "SELECT {} FROM users WHERE id = $1",
+            USER_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(user)
+    }
+
+    async fn find_by_email(&self, email: String) -> Result<Option<UserAccount>, sqlx::Error> {
+        let user = sqlx::query_as::<_, UserAccount>(&format!(
+            "SELECT {} FROM users WHERE lower(email) = lower($1)",
+            USER_COLUMNS
+        ))
+        .bind(email)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(user)
+    }
+
+    async fn create(&self, user: UserAccount) -> Result<UserAccount, sqlx::Error> {
+        // ユーザーと個人ワークスペース（管理者として所属する）を同じトランザクションで作成する
+        let mut tx = self.pool.begin().await?;
+        let created_user = sqlx::query_as::<_, UserAccount>(&format!(
+            "INSERT INTO users (id, email, password_hash, created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING {}",
+            USER_COLUMNS
+        ))
+        .bind(user.id)
+        .bind(user.email)
+        .bind(user.password_hash)
+        .bind(user.created_at)
+        .bind(user.updated_at)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let workspace = Workspace::personal(created_user.id);
+        sqlx::query("INSERT INTO workspaces (id, name, created_at) VALUES ($1, $2, $3)")
+            .bind(workspace.id)
+            .bind(workspace.name)
+            .bind(workspace.created_at)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(
+            "INSERT INTO workspace_members (workspace_id, user_id, role) VALUES ($1, $2, 'admin')",
+        )
+        .bind(workspace.id)
+        .bind(created_user.id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(created_user)
+    }
+}
@@ -0,0 +1,29 @@
+use crate::error::AppError;
+use crate::models::user::USERS_API_URL;
+use crate::repositories::upstream_probe::UpstreamProbe;
+use async_trait::async_trait;
+use std::time::Duration;
+
+// users API にユーザーを1件だけ問い合わせて、応答するかを確認する
+#[derive(Clone)]
+pub struct UsersApiProbe {
+    http: reqwest::Client,
+    url: String,
+}
+
+impl UsersApiProbe {
+    pub fn new(timeout: Duration) -> Result<Self, reqwest::Error> {
+        Ok(Self {
+            http: reqwest::Client::builder().timeout(timeout).build()?,
+            url: format!("{}/1", USERS_API_URL),
+        })
+    }
+}
+
+#[async_trait]
+impl UpstreamProbe for UsersApiProbe {
+    async fn check(&self) -> Result<(), AppError> {
+        self.http.get(&self.url).send().await?.error_for_status()?;
+        Ok(())
+    }
+}
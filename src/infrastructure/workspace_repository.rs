@@ -0,0 +1,132 @@
+use crate::infrastructure::db::DbPool;
+use crate::models::workspace::{Workspace, WorkspaceMember, WorkspaceMembership, WorkspaceRole};
+use crate::repositories::workspace_repository::WorkspaceRepository;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct WorkspaceRepositoryImpl {
+    pub pool: DbPool,
+}
+
+impl WorkspaceRepositoryImpl {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl WorkspaceRepository for WorkspaceRepositoryImpl {
+    async fn create(&self, workspace: Workspace, admin_id: Uuid) -> Result<Workspace, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let created_workspace = sqlx::query_as::<_, Workspace>(
+            "INSERT INTO workspaces (id, name, created_at) VALUES ($1, $2, $3)
+             RETURNING id, name, created_at",
+        )
+        .bind(workspace.id)
+        .bind(workspace.name)
+        .bind(workspace.created_at)
+        .fetch_one(&mut *tx)
+        .await?;
+        sqlx::query(
+            "INSERT INTO workspace_members (workspace_id, user_id, role) VALUES ($1, $2, 'admin')",
+        )
+        .bind(created_workspace.id)
+        .bind(admin_id)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(created_workspace)
+    }
+
+    async fn find_role(
+        &self,
+        workspace_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<WorkspaceRole>, sqlx::Error> {
+        let role = sqlx::query_scalar(
+            "SELECT role FROM workspace_members WHERE workspace_id = $1 AND user_id = $2",
+        )
+        .bind(workspace_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(role)
+    }
+
+    async fn find_memberships(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<WorkspaceMembership>, sqlx::Error> {
+        let memberships = sqlx::query_as::<_, WorkspaceMembership>(
+            "SELECT w.id AS workspace_id, w.name, m.role
+             FROM workspace_members m
+             JOIN workspaces w ON w.id = m.workspace_id
+             WHERE m.user_id = $1
+             ORDER BY w.created_at, w.id",
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(memberships)
+    }
+
+    async fn find_members(&self, workspace_id: Uuid) -> Result<Vec<WorkspaceMember>, sqlx::Error> {
+        let members = sqlx::query_as::<_, WorkspaceMember>(
+            "SELECT u.id AS user_id, u.email, m.role
+             FROM workspace_members m
+             JOIN users u ON u.id = m.user_id
+             WHERE m.workspace_id = $1
+             ORDER BY m.created_at, u.id",
+        )
+        .bind(workspace_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(members)
+    }
+
+    async fn upsert_member(
+        &self,
+        workspace_id: Uuid,
+        user_id: Uuid,
+        role: WorkspaceRole,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO workspace_members (workspace_id, user_id, role) VALUES ($1, $2, $3)
+             ON CONFLICT (workspace_id, user_id) DO UPDATE SET role = EXCLUDED.role",
+        )
+        .bind(workspace_id)
+        .bind(user_id)
+        .bind(role)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn remove_member(&self, workspace_id: Uuid, user_id: Uuid) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let result =
+            sqlx::query("DELETE FROM workspace_members WHERE workspace_id = $1 AND user_id = $2")
+                .bind(workspace_id)
+                .bind(user_id)
+                .execute(&mut *tx)
+                .await?;
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        // ユーザーがこのワークスペースで発行したカレンダーのトークンを無効にする
+        sqlx::query("DELETE FROM calendar_tokens WHERE workspace_id = $1 AND user_id = $2")
+            .bind(workspace_id)
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+        // ユーザーがこのワークスペースで作成した共有（ユーザーへの共有と共有リンク）を無効にする
+        sqlx::query("DELETE FROM grants WHERE workspace_id = $1 AND created_by = $2")
+            .bind(workspace_id)
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+}
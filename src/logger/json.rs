@@ -0,0 +1,99 @@
+use chrono::{SecondsFormat, Utc};
+use serde_json::{json, Map, Value};
+use std::fmt;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::fmt::format::{JsonFields, Writer};
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormattedFields};
+use tracing_subscriber::registry::LookupSpan;
+
+// 1行1つの JSON オブジェクトで出力するログの形式（ログ基盤で解析できるよう、項目はいつも同じにする）
+// - timestamp: RFC 3339（UTC、マイクロ秒まで）
+// - level, target, message
+// - request_id: リクエストの処理中でなければ null
+// - span: ログを出力したスパンの名前（スパンの外では null）
+// - fields: message 以外のイベントのフィールド
+// スパンのフィールドを読むため、JsonFields と組み合わせて使う
+pub struct JsonLogFormat;
+
+impl<S> FormatEvent<S, JsonFields> for JsonLogFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, JsonFields>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        let mut fields = visitor.0;
+        let message = fields
+            .remove("message")
+            .unwrap_or_else(|| Value::String(String::new()));
+
+        // 内側のスパンから順に探す
+        let mut span = None;
+        let mut request_id = None;
+        if let Some(scope) = ctx.event_scope() {
+            for span_ref in scope {
+                span.get_or_insert(span_ref.name());
+                if request_id.is_none() {
+                    request_id = span_ref
+                        .extensions()
+                        .get::<FormattedFields<JsonFields>>()
+                        .and_then(|fields| serde_json::from_str::<Map<String, Value>>(fields).ok())
+                        .and_then(|mut fields| fields.remove("request_id"));
+                }
+            }
+        }
+
+        let line = json!({
+            "timestamp": Utc::now().to_rfc3339_opts(SecondsFormat::Micros, true),
+            "level": event.metadata().level().as_str(),
+            "target": event.metadata().target(),
+            "message": message,
+            "request_id": request_id,
+            "span": span,
+            "fields": fields,
+        });
+        writeln!(writer, "{}", line)
+    }
+}
+
+// イベントのフィールドを JSON の値として集める
+#[derive(Default)]
+struct FieldVisitor(Map<String, Value>);
+
+impl Visit for FieldVisitor {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), json!(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), json!(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), json!(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), json!(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), json!(value));
+    }
+
+    fn record_error(&mut self, field: &Field, value: &(dyn std::error::Error + 'static)) {
+        self.0
+            .insert(field.name().to_string(), json!(value.to_string()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), json!(format!("{:?}", value)));
+    }
+}
@@ -0,0 +1,28 @@
+pub mod json;
+#[cfg(test)]
+pub mod tests;
+
+use crate::config::settings::{LogFormat, LogSettings};
+use crate::logger::json::JsonLogFormat;
+use opentelemetry_sdk::trace::Tracer;
+use tracing_subscriber::fmt::format::JsonFields;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, EnvFilter, Layer};
+
+// filter は Config::validate で確認済み
+// ログと同じスパンを OpenTelemetry のスパンとしても記録する
+pub fn init(settings: &LogSettings, tracer: Tracer) {
+    let fmt_layer = match settings.format {
+        LogFormat::Text => fmt::layer().boxed(),
+        LogFormat::Json => fmt::layer()
+            .fmt_fields(JsonFields::new())
+            .event_format(JsonLogFormat)
+            .boxed(),
+    };
+    tracing_subscriber::registry()
+        .with(EnvFilter::new(&settings.filter))
+        .with(fmt_layer)
+        .with(tracing_opentelemetry::layer().with_tracer(tracer))
+        .init();
+}
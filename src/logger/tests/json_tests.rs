@@ -0,0 +1,61 @@
+use crate::logger::tests::log_capture::LogCapture;
+use serde_json::{json, Value};
+use tracing::field::Empty;
+use tracing::{info, info_span, warn};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_fields() {
+        let capture = LogCapture::start();
+
+        // ミドルウェアと同じく、request_id は後から記録する
+        let request = info_span!("http_request", request_id = Empty);
+        request.record("request_id", "req-1");
+        request.in_scope(|| {
+            let _task = info_span!("TaskService::create_task").entered();
+            info!(task_id = 5, created = true, "task created");
+        });
+
+        let lines = capture.lines();
+
+        assert_eq!(lines.len(), 1);
+        let line = lines[0].as_object().unwrap();
+        let mut keys: Vec<_> = line.keys().map(String::as_str).collect();
+        keys.sort();
+        assert_eq!(
+            keys,
+            [
+                "fields",
+                "level",
+                "message",
+                "request_id",
+                "span",
+                "target",
+                "timestamp"
+            ]
+        );
+        assert_eq!(line["level"], "INFO");
+        assert_eq!(line["message"], "task created");
+        assert_eq!(line["request_id"], "req-1");
+        assert_eq!(line["span"], "TaskService::create_task");
+        assert_eq!(line["fields"], json!({"task_id": 5, "created": true}));
+        assert!(chrono::DateTime::parse_from_rfc3339(line["timestamp"].as_str().unwrap()).is_ok());
+    }
+
+    #[test]
+    fn test_fields_are_null_outside_request() {
+        let capture = LogCapture::start();
+
+        warn!("no request");
+
+        let lines = capture.lines();
+
+        assert_eq!(lines[0]["level"], "WARN");
+        assert_eq!(lines[0]["request_id"], Value::Null);
+        assert_eq!(lines[0]["span"], Value::Null);
+        assert_eq!(lines[0]["fields"], json!({}));
+    }
+}
@@ -0,0 +1,62 @@
+use crate::logger::json::JsonLogFormat;
+use serde_json::Value;
+use std::io;
+use std::sync::{Arc, Mutex};
+use tracing::subscriber::DefaultGuard;
+use tracing_subscriber::fmt::format::JsonFields;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::SubscriberExt;
+
+// 出力されたログを JSON 形式でメモリに溜める（テスト以外では使わない）
+// 購読はこのスレッドだけなので、current_thread のランタイム（#[tokio::test] の既定）で使う
+pub struct LogCapture {
+    buffer: Buffer,
+    _guard: DefaultGuard,
+}
+
+impl LogCapture {
+    pub fn start() -> Self {
+        let buffer = Buffer::default();
+        let subscriber = tracing_subscriber::registry().with(
+            tracing_subscriber::fmt::layer()
+                .fmt_fields(JsonFields::new())
+                .event_format(JsonLogFormat)
+                .with_writer(buffer.clone()),
+        );
+        Self {
+            buffer: buffer.clone(),
+            _guard: tracing::subscriber::set_default(subscriber),
+        }
+    }
+
+    // 出力されたログを1行ずつ JSON として読む
+    pub fn lines(&self) -> Vec<Value> {
+        let output = String::from_utf8(self.buffer.0.lock().unwrap().clone()).unwrap();
+        output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+}
+
+#[derive(Clone, Default)]
+struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for Buffer {
+    type Writer = Buffer;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
@@ -0,0 +1,2 @@
+pub mod json_tests;
+pub mod log_capture;
@@ -1,22 +1,75 @@
 use anyhow::Result;
+use axum::http::{HeaderValue, StatusCode};
 use dotenvy::dotenv;
-use sqlx::PgPool;
-use std::env;
-use std::net::SocketAddr;
+use opentelemetry::trace::TracerProvider as _;
+use sqlx::postgres::PgPoolOptions;
+use sqlx::{Connection, PgConnection};
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::net::TcpListener;
-use tracing::info;
+use tracing::{info, warn};
 
+use crate::access_log::config::AccessLogConfig;
+use crate::app::{AppOptions, AppServices};
+use crate::auth::jwt::JwtKeys;
+use crate::auth::oidc::OidcConfig;
+use crate::config::cli::{CliArgs, Command, MigrateCommand};
+use crate::config::settings::Config;
+use crate::infrastructure::api_key_repository::ApiKeyRepositoryImpl;
+use crate::infrastructure::calendar_token_repository::CalendarTokenRepositoryImpl;
+use crate::infrastructure::db::DbPool;
+use crate::infrastructure::grant_repository::GrantRepositoryImpl;
+use crate::infrastructure::health_repository::HealthRepositoryImpl;
+use crate::infrastructure::idempotency_repository::IdempotencyRepositoryImpl;
+use crate::infrastructure::migration;
+use crate::infrastructure::oidc_provider::OidcProviderImpl;
+use crate::infrastructure::oidc_repository::OidcRepositoryImpl;
+use crate::infrastructure::rate_limit_store::{InMemoryRateLimitStore, RedisRateLimitStore};
+use crate::infrastructure::refresh_token_repository::RefreshTokenRepositoryImpl;
+use crate::infrastructure::session_repository::SessionRepositoryImpl;
 use crate::infrastructure::task_repository::TaskRepositoryImpl;
+use crate::infrastructure::time_entry_repository::TimeEntryRepositoryImpl;
+use crate::infrastructure::user_repository::UserRepositoryImpl;
+use crate::infrastructure::users_api_probe::UsersApiProbe;
+use crate::infrastructure::workspace_repository::WorkspaceRepositoryImpl;
+use crate::rate_limit::config::{RateLimitBackend, RateLimitConfig};
+use crate::rate_limit::middleware::RateLimitState;
+use crate::repositories::rate_limit_store::RateLimitStore;
+use crate::routes::metrics::MetricsState;
+use crate::server::http::DrainOutcome;
+use crate::server::shutdown::{wait_for_signal, Shutdown};
+use crate::usecase::api_key_usecase::ApiKeyUsecase;
+use crate::usecase::auth_usecase::AuthUsecase;
+use crate::usecase::calendar_usecase::CalendarUsecase;
+use crate::usecase::health_usecase::HealthUsecase;
+use crate::usecase::idempotency_usecase::IdempotencyUsecase;
+use crate::usecase::oidc_usecase::OidcUsecase;
+use crate::usecase::seed_usecase::{SeedService, SeedUsecase, SEED_EMAIL, SEED_PASSWORD};
+use crate::usecase::session_usecase::SessionUsecase;
+use crate::usecase::sharing_usecase::SharingUsecase;
 use crate::usecase::task_usecase::TaskUsecase;
+use crate::usecase::time_tracking_usecase::TimeTrackingUsecase;
+use crate::usecase::workspace_usecase::WorkspaceUsecase;
 
+mod access_log;
 mod app;
+mod auth;
+mod config;
 mod docs;
 mod error;
+mod idempotency;
 mod infrastructure;
 mod logger;
+mod metrics;
 mod models;
+mod policy;
+mod rate_limit;
 mod repositories;
+mod request_id;
 mod routes;
+mod secret;
+mod server;
+mod telemetry;
 mod usecase;
 
 #[tokio::main]
@@ -24,26 +77,280 @@ async fn main() -> Result<()> {
     // .env読み込み
     dotenv().ok();
 
-    // ロガー初期化（RUST_LOG 環境変数にも対応）
-    logger::init();
+    // 設定の読み込み（既定値 → --config または CONFIG_FILE の TOML → 環境変数）
+    let args = CliArgs::parse(std::env::args().skip(1)).map_err(anyhow::Error::msg)?;
+    let config_path = args
+        .config_path
+        .or_else(|| std::env::var_os("CONFIG_FILE").map(Into::into));
+    let config = Config::load(config_path.as_deref(), |name| std::env::var(name).ok())?;
+    if args.print_config {
+        print!("{}", config.to_redacted_toml());
+    }
+    config.validate()?;
+    if args.print_config {
+        return Ok(());
+    }
+
+    match args.command {
+        Command::Serve { migrate_on_start } => serve(config, migrate_on_start).await,
+        Command::Migrate(command) => migrate(&config, command).await,
+        Command::Seed => seed(&config).await,
+        Command::CheckConfig => {
+            println!("configuration is valid");
+            Ok(())
+        }
+    }
+}
+
+// database.url は Config::validate で必須であることを確認済み
+fn database_url(config: &Config) -> &str {
+    config
+        .database
+        .url
+        .as_ref()
+        .map(|url| url.expose())
+        .unwrap_or_default()
+}
+
+async fn connect_pool(config: &Config) -> Result<DbPool> {
+    let database = &config.database;
+    let pool = PgPoolOptions::new()
+        .max_connections(database.max_connections)
+        .min_connections(database.min_connections)
+        .acquire_timeout(Duration::from_secs(database.acquire_timeout_secs))
+        .connect(database_url(config))
+        .await?;
+    Ok(pool)
+}
+
+// マイグレーションはプールを使わず専用の接続で実行する
+// （途中で失敗しても、接続を閉じれば advisory lock が解放される）
+async fn migrate(config: &Config, command: MigrateCommand) -> Result<()> {
+    let mut conn = PgConnection::connect(database_url(config)).await?;
+    match command {
+        MigrateCommand::Up => {
+            let applied = migration::migrate_up(&mut conn).await?;
+            if applied.is_empty() {
+                println!("no pending migrations");
+            }
+            for version in applied {
+                println!("applied {}", version);
+            }
+        }
+        MigrateCommand::Down { to } => {
+            let reverted = migration::migrate_down(&mut conn, to).await?;
+            if reverted.is_empty() {
+                println!("no migrations to revert");
+            }
+            for version in reverted {
+                println!("reverted {}", version);
+            }
+        }
+        MigrateCommand::Status => {
+            println!("{:<14} {:<18} DESCRIPTION", "VERSION", "STATE");
+            for status in migration::migration_status(&mut conn).await? {
+                println!("{}", status);
+            }
+        }
+    }
+    conn.close().await?;
+    Ok(())
+}
+
+async fn seed(config: &Config) -> Result<()> {
+    let pool = connect_pool(config).await?;
+    let report = SeedUsecase::new(
+        UserRepositoryImpl::new(pool.clone()),
+        TaskRepositoryImpl::new(pool.clone()),
+    )
+    .seed()
+    .await?;
+    if report.created_user {
+        println!("created user {} (password: {})", SEED_EMAIL, SEED_PASSWORD);
+    } else {
+        println!("user {} already exists", SEED_EMAIL);
+    }
+    println!(
+        "created {} tasks in workspace {}",
+        report.created_tasks, report.user.id
+    );
+    pool.close().await;
+    Ok(())
+}
+
+async fn serve(config: Config, migrate_on_start: bool) -> Result<()> {
+    // トレーシングとロガー初期化（OTLP のバッチ送信は tokio のランタイムで動く）
+    let tracer_provider = telemetry::tracer::build_tracer_provider(&config.tracing)?;
+    logger::init(&config.log, tracer_provider.tracer("rust-on-docker"));
+
+    // 複数のレプリカが同時に起動しても、マイグレーションは advisory lock で1つずつ実行される
+    if migrate_on_start {
+        let mut conn = PgConnection::connect(database_url(&config)).await?;
+        let applied = migration::migrate_up(&mut conn).await?;
+        conn.close().await?;
+        info!("applied migrations: {:?}", applied);
+    }
 
     //  データベース接続
-    let database_url = env::var("DATABASE_URL")?;
-    let pool = PgPool::connect(&database_url).await?;
+    let pool = connect_pool(&config).await?;
+
+    // JWTの署名・検証鍵
+    let jwt_keys = JwtKeys::from_settings(&config.jwt)?;
+
+    // SIGTERM / SIGINT を受け取ったら終了を始める
+    let shutdown = Shutdown::new();
+    tokio::spawn({
+        let shutdown = shutdown.clone();
+        async move {
+            wait_for_signal().await;
+            shutdown.trigger();
+        }
+    });
 
     // 依存関係のセットアップ
     let task_repository = TaskRepositoryImpl::new(pool.clone());
-    let task_service = TaskUsecase::new(task_repository);
+    let grant_repository = GrantRepositoryImpl::new(pool.clone());
+    let task_service = TaskUsecase::new(task_repository.clone(), grant_repository.clone());
+    let time_entry_repository = TimeEntryRepositoryImpl::new(pool.clone());
+    let time_tracking_service =
+        TimeTrackingUsecase::new(time_entry_repository, task_repository.clone());
+    let user_repository = UserRepositoryImpl::new(pool.clone());
+    let refresh_token_repository = RefreshTokenRepositoryImpl::new(pool.clone());
+    let auth_service = AuthUsecase::new(
+        user_repository.clone(),
+        refresh_token_repository.clone(),
+        jwt_keys.clone(),
+    );
+    // oidc.issuer_url が設定されている場合だけ OIDC ログインを有効にする
+    let oidc_service = match OidcConfig::from_settings(&config.oidc)? {
+        Some(config) => Some(OidcUsecase::new(
+            OidcProviderImpl::new(config)?,
+            OidcRepositoryImpl::new(pool.clone()),
+            user_repository.clone(),
+            refresh_token_repository.clone(),
+            jwt_keys.clone(),
+        )),
+        None => None,
+    };
+    let session_service = SessionUsecase::new(
+        SessionRepositoryImpl::new(pool.clone()),
+        user_repository.clone(),
+        refresh_token_repository,
+        jwt_keys.clone(),
+    );
+    let workspace_repository = WorkspaceRepositoryImpl::new(pool.clone());
+    let calendar_token_repository = CalendarTokenRepositoryImpl::new(pool.clone());
+    let calendar_service = CalendarUsecase::new(
+        calendar_token_repository,
+        task_repository.clone(),
+        workspace_repository.clone(),
+    );
+    let workspace_service =
+        WorkspaceUsecase::new(workspace_repository.clone(), user_repository.clone());
+    let api_key_repository = ApiKeyRepositoryImpl::new(pool.clone());
+    let api_key_service = ApiKeyUsecase::new(api_key_repository, workspace_repository);
+    let sharing_service = SharingUsecase::new(grant_repository, task_repository, user_repository);
+    let idempotency_service = IdempotencyUsecase::new(IdempotencyRepositoryImpl::new(pool.clone()));
+    let check_timeout = Duration::from_millis(config.health.check_timeout_ms);
+    let users_api_probe = if config.health.check_users_api {
+        Some(UsersApiProbe::new(check_timeout)?)
+    } else {
+        None
+    };
+    let health_service = HealthUsecase::new(
+        HealthRepositoryImpl::new(pool.clone()),
+        users_api_probe,
+        shutdown.clone(),
+        check_timeout,
+    );
+
+    // レート制限（store = "redis" の場合はレプリカ間で上限を共有する）
+    let rate_limit_state = if config.features.rate_limit {
+        let rate_limit_config = RateLimitConfig::from_settings(&config.rate_limit)?;
+        let rate_limit_store: Arc<dyn RateLimitStore + Send + Sync> =
+            match &rate_limit_config.backend {
+                RateLimitBackend::Memory => Arc::new(InMemoryRateLimitStore::new()),
+                RateLimitBackend::Redis(url) => Arc::new(RedisRateLimitStore::connect(url).await?),
+            };
+        Some(RateLimitState {
+            config: Arc::new(rate_limit_config),
+            store: rate_limit_store,
+        })
+    } else {
+        None
+    };
+    let access_log_config = if config.features.access_log {
+        Some(Arc::new(AccessLogConfig::from_settings(
+            &config.access_log,
+        )?))
+    } else {
+        None
+    };
+    // オリジンの形式は Config::validate で確認済み
+    let cors_allowed_origins = config
+        .cors
+        .allowed_origins
+        .iter()
+        .map(|origin| HeaderValue::from_str(origin))
+        .collect::<Result<Vec<_>, _>>()?;
 
     // アプリ初期化
-    let app = app::create_app(task_service.clone());
-    // アドレス指定 & ログ出力
-    let addr = SocketAddr::from(([0, 0, 0, 0], 3000));
-    info!("🚀 Server listening on http://{}", addr);
+    let app = app::create_app(
+        AppServices {
+            task: task_service,
+            calendar: calendar_service,
+            time_tracking: time_tracking_service,
+            auth: auth_service,
+            workspace: workspace_service,
+            api_key: api_key_service,
+            sharing: sharing_service,
+            oidc: oidc_service,
+            session: session_service,
+            idempotency: idempotency_service,
+            health: health_service,
+        },
+        jwt_keys,
+        AppOptions {
+            rate_limit: rate_limit_state,
+            swagger_ui: config.features.swagger_ui,
+            metrics: config
+                .features
+                .metrics
+                .then(|| MetricsState { pool: pool.clone() }),
+            access_log: access_log_config,
+            cors_allowed_origins,
+            request_timeout: Duration::from_secs(config.server.request_timeout_secs),
+            // 408 または 503 であることは Config::validate で確認済み
+            timeout_status: StatusCode::from_u16(config.server.timeout_status)?,
+            max_body_bytes: config.server.max_body_bytes,
+            compression: config.features.compression,
+            hsts_max_age: (config.server.hsts_max_age_secs > 0)
+                .then(|| Duration::from_secs(config.server.hsts_max_age_secs)),
+        },
+    );
 
     // サーバ起動
-    let listener = TcpListener::bind(addr).await?;
-    axum::serve(listener, app.into_make_service()).await?;
+    let listener = TcpListener::bind((config.server.host.as_str(), config.server.port)).await?;
+    info!("🚀 Server listening on http://{}", listener.local_addr()?);
+    let outcome = server::http::serve(
+        listener,
+        app,
+        shutdown,
+        Duration::from_secs(config.server.shutdown_delay_secs),
+        Duration::from_secs(config.server.shutdown_timeout_secs),
+    )
+    .await?;
+
+    // 接続を返していないリクエストが残っていると close が終わらないため、待ち切れた場合だけ閉じる
+    if outcome == DrainOutcome::Drained {
+        pool.close().await;
+    }
+    info!("server stopped");
+
+    // 送信待ちのスパンを送り切る
+    if let Err(e) = tracer_provider.shutdown() {
+        warn!("failed to shut down tracer provider: {}", e);
+    }
 
     Ok(())
 }
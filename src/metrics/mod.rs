@@ -0,0 +1,5 @@
+pub mod middleware;
+pub mod registry;
+
+#[cfg(test)]
+pub mod tests;
@@ -0,0 +1,134 @@
+use once_cell::sync::Lazy;
+use prometheus::{
+    Encoder, Histogram, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, Opts,
+    Registry, TextEncoder,
+};
+
+// プロセス全体で1つのメトリクス（/metrics で出力する）
+pub static METRICS: Lazy<Metrics> = Lazy::new(Metrics::new);
+
+// HTTP リクエストの処理時間のバケット（秒）
+const HTTP_DURATION_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+// データベースの接続の取得・外部APIの呼び出しの時間のバケット（秒）
+const WAIT_DURATION_BUCKETS: &[f64] = &[0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0];
+
+pub struct Metrics {
+    registry: Registry,
+    // route はルーターに登録したパス（例: "/tasks/:id"）で、ルートがない場合は "unmatched"
+    pub http_requests_total: IntCounterVec,
+    pub http_request_duration_seconds: HistogramVec,
+    // state は idle または in_use
+    pub db_pool_connections: IntGaugeVec,
+    pub db_pool_max_connections: IntGauge,
+    pub db_pool_acquire_duration_seconds: Histogram,
+    // operation は create / update / delete / import（成功した操作だけを数える）
+    pub task_operations_total: IntCounterVec,
+    // outcome は ok または error
+    pub upstream_request_duration_seconds: HistogramVec,
+    // kind は request（接続・タイムアウト）/ status（エラーのステータス）/ decode（レスポンスの形式）
+    pub upstream_errors_total: IntCounterVec,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new();
+        let metrics = Self {
+            http_requests_total: IntCounterVec::new(
+                Opts::new("http_requests_total", "Number of HTTP requests handled"),
+                &["method", "route", "status"],
+            )
+            .unwrap(),
+            http_request_duration_seconds: HistogramVec::new(
+                HistogramOpts::new(
+                    "http_request_duration_seconds",
+                    "Time spent handling HTTP requests",
+                )
+                .buckets(HTTP_DURATION_BUCKETS.to_vec()),
+                &["method", "route", "status"],
+            )
+            .unwrap(),
+            db_pool_connections: IntGaugeVec::new(
+                Opts::new(
+                    "db_pool_connections",
+                    "Number of open database connections by state",
+                ),
+                &["state"],
+            )
+            .unwrap(),
+            db_pool_max_connections: IntGauge::new(
+                "db_pool_max_connections",
+                "Maximum number of database connections in the pool",
+            )
+            .unwrap(),
+            db_pool_acquire_duration_seconds: Histogram::with_opts(
+                HistogramOpts::new(
+                    "db_pool_acquire_duration_seconds",
+                    "Time to acquire a database connection for a tenant transaction",
+                )
+                .buckets(WAIT_DURATION_BUCKETS.to_vec()),
+            )
+            .unwrap(),
+            task_operations_total: IntCounterVec::new(
+                Opts::new(
+                    "task_operations_total",
+                    "Number of successful task operations",
+                ),
+                &["operation"],
+            )
+            .unwrap(),
+            upstream_request_duration_seconds: HistogramVec::new(
+                HistogramOpts::new(
+                    "upstream_request_duration_seconds",
+                    "Time spent calling upstream APIs",
+                )
+                .buckets(WAIT_DURATION_BUCKETS.to_vec()),
+                &["upstream", "outcome"],
+            )
+            .unwrap(),
+            upstream_errors_total: IntCounterVec::new(
+                Opts::new(
+                    "upstream_errors_total",
+                    "Number of failed upstream API calls",
+                ),
+                &["upstream", "kind"],
+            )
+            .unwrap(),
+            registry,
+        };
+
+        let collectors: [Box<dyn prometheus::core::Collector>; 8] = [
+            Box::new(metrics.http_requests_total.clone()),
+            Box::new(metrics.http_request_duration_seconds.clone()),
+            Box::new(metrics.db_pool_connections.clone()),
+            Box::new(metrics.db_pool_max_connections.clone()),
+            Box::new(metrics.db_pool_acquire_duration_seconds.clone()),
+            Box::new(metrics.task_operations_total.clone()),
+            Box::new(metrics.upstream_request_duration_seconds.clone()),
+            Box::new(metrics.upstream_errors_total.clone()),
+        ];
+        for collector in collectors {
+            // 名前はすべて異なるため、登録に失敗することはない
+            metrics.registry.register(collector).unwrap();
+        }
+        metrics
+    }
+
+    // Prometheus のテキスト形式で出力する
+    pub fn render(&self) -> String {
+        let mut buffer = Vec::new();
+        // テキスト形式への変換はメモリへの書き込みだけなので失敗しない
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .unwrap();
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+
+    pub fn record_task_operation(&self, operation: &str) {
+        self.task_operations_total
+            .with_label_values(&[operation])
+            .inc();
+    }
+}
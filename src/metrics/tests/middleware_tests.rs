@@ -0,0 +1,72 @@
+use crate::metrics::middleware::track_http_metrics;
+use crate::metrics::registry::METRICS;
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::get,
+    Router,
+};
+use tower::ServiceExt;
+
+// ほかのテストのリクエストと区別するため、このテストだけのパスを使う
+fn router() -> Router {
+    Router::new()
+        .route("/metrics-test/:id", get(|| async { "ok" }))
+        .layer(middleware::from_fn(track_http_metrics))
+}
+
+async fn get_status(router: &Router, uri: &str) -> StatusCode {
+    router
+        .clone()
+        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+        .status()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_requests_are_labeled_by_matched_route() {
+        let router = router();
+
+        assert_eq!(get_status(&router, "/metrics-test/1").await, StatusCode::OK);
+        assert_eq!(get_status(&router, "/metrics-test/2").await, StatusCode::OK);
+
+        let labels = ["GET", "/metrics-test/:id", "200"];
+        assert_eq!(
+            METRICS.http_requests_total.with_label_values(&labels).get(),
+            2
+        );
+        assert_eq!(
+            METRICS
+                .http_request_duration_seconds
+                .with_label_values(&labels)
+                .get_sample_count(),
+            2
+        );
+        // 実際のパスはラベルに使わない
+        assert!(!METRICS.render().contains("/metrics-test/1"));
+    }
+
+    #[tokio::test]
+    async fn test_unmatched_requests_share_one_label() {
+        let router = router();
+        let unmatched =
+            METRICS
+                .http_requests_total
+                .with_label_values(&["DELETE", "unmatched", "404"]);
+        let before = unmatched.get();
+
+        let request = Request::delete("/metrics-test-unknown/1")
+            .body(Body::empty())
+            .unwrap();
+        let status = router.oneshot(request).await.unwrap().status();
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(unmatched.get(), before + 1);
+    }
+}
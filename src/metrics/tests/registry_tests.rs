@@ -0,0 +1,18 @@
+use crate::metrics::registry::METRICS;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_prometheus_text() {
+        METRICS.record_task_operation("registry_test");
+        METRICS.db_pool_max_connections.set(10);
+
+        let text = METRICS.render();
+
+        assert!(text.contains("# TYPE task_operations_total counter"));
+        assert!(text.contains("task_operations_total{operation=\"registry_test\"} 1"));
+        assert!(text.contains("# TYPE db_pool_max_connections gauge"));
+    }
+}
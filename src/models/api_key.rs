@@ -0,0 +1,84 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+// 平文のAPIキーの先頭に付ける文字列（キーは tk_<prefix>_<secret> の形式）
+pub const API_KEY_PREFIX: &str = "tk_";
+
+// APIキーで許可する操作
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Eq, PartialEq, sqlx::Type, ToSchema)]
+#[sqlx(type_name = "api_key_scope")]
+pub enum ApiKeyScope {
+    // タスクの一覧・詳細・エクスポート・統計・作業時間の閲覧
+    #[sqlx(rename = "tasks:read")]
+    #[serde(rename = "tasks:read")]
+    TasksRead,
+    // タスクの作成・更新・削除・インポートと作業時間の記録
+    #[sqlx(rename = "tasks:write")]
+    #[serde(rename = "tasks:write")]
+    TasksWrite,
+}
+
+// 保存されたAPIキー（key_hash は平文のキー全体の SHA-256）
+#[derive(Clone, Debug, FromRow, Eq, PartialEq)]
+pub struct ApiKey {
+    pub id: Uuid,
+    // キーを発行したユーザー（キーはこのユーザーのロールで操作する）
+    pub user_id: Uuid,
+    pub workspace_id: Uuid,
+    pub name: String,
+    // 検索用の公開部分
+    pub prefix: String,
+    pub key_hash: String,
+    pub scopes: Vec<ApiKeyScope>,
+    pub expires_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ApiKey {
+    pub fn new(
+        user_id: Uuid,
+        workspace_id: Uuid,
+        name: String,
+        prefix: String,
+        key_hash: String,
+        scopes: Vec<ApiKeyScope>,
+        expires_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id: Uuid::now_v7(),
+            user_id,
+            workspace_id,
+            name,
+            prefix,
+            key_hash,
+            scopes,
+            expires_at,
+            last_used_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+}
+
+// 平文のキーから検索用の prefix を取り出す（形式が不正な場合は None）
+pub fn parse_prefix(key: &str) -> Option<&str> {
+    let (prefix, secret) = key.strip_prefix(API_KEY_PREFIX)?.split_once('_')?;
+    if prefix.is_empty() || secret.is_empty() {
+        return None;
+    }
+    Some(prefix)
+}
+
+// 発行時に一度だけ返す平文のキーと、保存したキー
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IssuedApiKey {
+    pub api_key: ApiKey,
+    pub key: String,
+}
@@ -0,0 +1,34 @@
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+// 保存されたリフレッシュトークン（token_hash は平文トークンの SHA-256）
+#[derive(Clone, Debug, FromRow, Eq, PartialEq)]
+pub struct RefreshToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl RefreshToken {
+    pub fn new(user_id: Uuid, token_hash: String, expires_at: DateTime<Utc>) -> Self {
+        Self {
+            id: Uuid::now_v7(),
+            user_id,
+            token_hash,
+            expires_at,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+// ログイン・リフレッシュ時に発行するトークンの組
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenPair {
+    pub access_token: String,
+    // アクセストークンの有効期間（秒）
+    pub expires_in: i64,
+    pub refresh_token: String,
+}
@@ -0,0 +1,130 @@
+use crate::models::task::Task;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+// RFC 5545 で1行に許される最大オクテット数（改行を除く）
+const MAX_LINE_OCTETS: usize = 75;
+
+// フィード購読用のトークンを発行したユーザーと、発行したワークスペース
+#[derive(Clone, Debug, Eq, PartialEq, FromRow)]
+pub struct CalendarTokenOwner {
+    pub user_id: Uuid,
+    pub workspace_id: Uuid,
+}
+
+// フィードに出力するコンポーネントの種類
+#[derive(Deserialize, Clone, Copy, Debug, Default, Eq, PartialEq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CalendarComponent {
+    // すべてのタスクを VTODO として出力する
+    #[default]
+    Vtodo,
+    // 期限のあるタスクだけを期限日時の VEVENT として出力する
+    Vevent,
+}
+
+// タスク一覧を iCalendar (RFC 5545) 形式の文字列にする
+pub fn render_calendar(tasks: &[Task], component: CalendarComponent) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//rust-on-docker//Tasks//JA".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "X-WR-CALNAME:Tasks".to_string(),
+    ];
+
+    for task in tasks {
+        match component {
+            CalendarComponent::Vtodo => {
+                lines.push("BEGIN:VTODO".to_string());
+                push_common_properties(&mut lines, task);
+                if let Some(due_at) = task.due_at {
+                    lines.push(format!("DUE:{}", format_utc(due_at)));
+                }
+                let status = if task.completed {
+                    "COMPLETED"
+                } else {
+                    "NEEDS-ACTION"
+                };
+                lines.push(format!("STATUS:{}", status));
+                if let Some(completed_at) = task.completed_at {
+                    lines.push(format!("COMPLETED:{}", format_utc(completed_at)));
+                }
+                lines.push("END:VTODO".to_string());
+            }
+            CalendarComponent::Vevent => {
+                let Some(due_at) = task.due_at else {
+                    continue;
+                };
+                lines.push("BEGIN:VEVENT".to_string());
+                push_common_properties(&mut lines, task);
+                lines.push(format!("DTSTART:{}", format_utc(due_at)));
+                lines.push("TRANSP:TRANSPARENT".to_string());
+                lines.push("END:VEVENT".to_string());
+            }
+        }
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines
+        .iter()
+        .map(|line| fold_line(line))
+        .collect::<Vec<_>>()
+        .join("")
+}
+
+// VTODO / VEVENT に共通するプロパティ
+// METHOD を指定しないカレンダーでは DTSTAMP はコンポーネントの最終更新日時を表す
+fn push_common_properties(lines: &mut Vec<String>, task: &Task) {
+    lines.push(format!("UID:{}@rust-on-docker", task.id));
+    lines.push(format!("DTSTAMP:{}", format_utc(task.updated_at)));
+    lines.push(format!("CREATED:{}", format_utc(task.created_at)));
+    lines.push(format!("LAST-MODIFIED:{}", format_utc(task.updated_at)));
+    lines.push(format!("SUMMARY:{}", escape_text(&task.title)));
+    if !task.tags.is_empty() {
+        let categories: Vec<String> = task.tags.iter().map(|t| escape_text(t)).collect();
+        lines.push(format!("CATEGORIES:{}", categories.join(",")));
+    }
+}
+
+// UTC の DATE-TIME（例: 20250512T090000Z）
+pub fn format_utc(datetime: DateTime<Utc>) -> String {
+    datetime.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+// TEXT 値のエスケープ（バックスラッシュ・セミコロン・カンマ・改行）
+pub fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// 75オクテットを超える行を折り返し、CRLF を付けて返す（マルチバイト文字の途中では切らない）
+pub fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 4);
+    let mut current_octets = 0;
+    for c in line.chars() {
+        let len = c.len_utf8();
+        if current_octets + len > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            // 継続行の先頭の空白も1オクテットとして数える
+            current_octets = 1;
+        }
+        folded.push(c);
+        current_octets += len;
+    }
+    folded.push_str("\r\n");
+    folded
+}
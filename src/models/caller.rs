@@ -0,0 +1,42 @@
+use crate::models::api_key::ApiKeyScope;
+use crate::models::workspace::WorkspaceRole;
+use uuid::Uuid;
+
+// 認証済みのリクエストの呼び出し元（ユースケースはこれを使ってデータの可視範囲と権限を決める）
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Caller {
+    pub user_id: Uuid,
+    // 操作の対象になるワークスペース
+    pub workspace_id: Uuid,
+    // workspace_id でのロール
+    pub role: WorkspaceRole,
+    // APIキーで認証した場合はキーのスコープ（ログインしたユーザーは None で、ロールの範囲ですべて操作できる）
+    pub scopes: Option<Vec<ApiKeyScope>>,
+}
+
+impl Caller {
+    pub fn new(user_id: Uuid, workspace_id: Uuid, role: WorkspaceRole) -> Self {
+        Self {
+            user_id,
+            workspace_id,
+            role,
+            scopes: None,
+        }
+    }
+
+    // APIキーで認証した呼び出し元
+    pub fn with_scopes(mut self, scopes: Vec<ApiKeyScope>) -> Self {
+        self.scopes = Some(scopes);
+        self
+    }
+
+    pub fn is_api_key(&self) -> bool {
+        self.scopes.is_some()
+    }
+
+    pub fn has_scope(&self, scope: ApiKeyScope) -> bool {
+        self.scopes
+            .as_ref()
+            .is_none_or(|scopes| scopes.contains(&scope))
+    }
+}
@@ -0,0 +1,139 @@
+use crate::models::task::Task;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+// 共有された相手ができる操作（edit は view を含む）
+#[derive(
+    Deserialize, Serialize, Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, sqlx::Type, ToSchema,
+)]
+#[sqlx(type_name = "grant_permission", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum GrantPermission {
+    // 閲覧のみ
+    View,
+    // 閲覧と更新（削除はできない）
+    Edit,
+}
+
+// 共有する対象
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ShareTarget {
+    Task(Uuid),
+    // プロジェクトのすべてのタスク（後から追加されたタスクも含む）
+    Project(String),
+}
+
+// タスクまたはプロジェクトの共有（grantee_id があればユーザーへの共有、token_hash があれば共有リンク）
+#[derive(Clone, Debug, FromRow, Eq, PartialEq)]
+pub struct Grant {
+    pub id: Uuid,
+    // 共有したタスクが属するワークスペース
+    pub workspace_id: Uuid,
+    pub task_id: Option<Uuid>,
+    pub project: Option<String>,
+    pub grantee_id: Option<Uuid>,
+    pub token_hash: Option<String>,
+    pub permission: GrantPermission,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Grant {
+    // ユーザーへの共有
+    pub fn for_user(
+        workspace_id: Uuid,
+        target: ShareTarget,
+        grantee_id: Uuid,
+        permission: GrantPermission,
+        expires_at: Option<DateTime<Utc>>,
+        created_by: Uuid,
+    ) -> Self {
+        let mut grant = Self::new(workspace_id, target, permission, expires_at, created_by);
+        grant.grantee_id = Some(grantee_id);
+        grant
+    }
+
+    // 共有リンク（閲覧のみ）
+    pub fn for_link(
+        workspace_id: Uuid,
+        target: ShareTarget,
+        token_hash: String,
+        expires_at: DateTime<Utc>,
+        created_by: Uuid,
+    ) -> Self {
+        let mut grant = Self::new(
+            workspace_id,
+            target,
+            GrantPermission::View,
+            Some(expires_at),
+            created_by,
+        );
+        grant.token_hash = Some(token_hash);
+        grant
+    }
+
+    fn new(
+        workspace_id: Uuid,
+        target: ShareTarget,
+        permission: GrantPermission,
+        expires_at: Option<DateTime<Utc>>,
+        created_by: Uuid,
+    ) -> Self {
+        let (task_id, project) = match target {
+            ShareTarget::Task(task_id) => (Some(task_id), None),
+            ShareTarget::Project(project) => (None, Some(project)),
+        };
+        Self {
+            id: Uuid::now_v7(),
+            workspace_id,
+            task_id,
+            project,
+            grantee_id: None,
+            token_hash: None,
+            permission,
+            expires_at,
+            created_by,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn target(&self) -> ShareTarget {
+        match (&self.task_id, &self.project) {
+            (Some(task_id), _) => ShareTarget::Task(*task_id),
+            (None, project) => ShareTarget::Project(project.clone().unwrap_or_default()),
+        }
+    }
+
+    pub fn is_link(&self) -> bool {
+        self.token_hash.is_some()
+    }
+
+    // タスクがこの共有の対象に含まれるか
+    pub fn covers(&self, task: &Task) -> bool {
+        if task.workspace_id != self.workspace_id {
+            return false;
+        }
+        match self.target() {
+            ShareTarget::Task(task_id) => task.id == task_id,
+            ShareTarget::Project(project) => task.project.as_deref() == Some(project.as_str()),
+        }
+    }
+}
+
+// 呼び出し元に共有されたタスクと、共有された操作
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SharedTask {
+    pub task: Task,
+    pub permission: GrantPermission,
+}
+
+// 作成時に一度だけ返す平文のトークンと、保存した共有リンク
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IssuedShareLink {
+    pub grant: Grant,
+    pub token: String,
+}
@@ -0,0 +1,63 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Ok,
+    Fail,
+}
+
+// 依存先ごとの確認結果
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, ToSchema)]
+pub struct CheckResult {
+    pub status: HealthStatus,
+    pub latency_ms: u64,
+    // 失敗した理由（内部の詳細は含めない）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl CheckResult {
+    pub fn ok(latency_ms: u64) -> Self {
+        Self {
+            status: HealthStatus::Ok,
+            latency_ms,
+            error: None,
+        }
+    }
+
+    pub fn fail(latency_ms: u64, error: impl Into<String>) -> Self {
+        Self {
+            status: HealthStatus::Fail,
+            latency_ms,
+            error: Some(error.into()),
+        }
+    }
+}
+
+// /healthz・/readyz のレスポンス（確認結果が1つでも失敗なら全体も失敗）
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, ToSchema)]
+pub struct HealthReport {
+    pub status: HealthStatus,
+    pub checks: BTreeMap<String, CheckResult>,
+}
+
+impl HealthReport {
+    pub fn new(checks: BTreeMap<String, CheckResult>) -> Self {
+        let status = if checks
+            .values()
+            .all(|check| check.status == HealthStatus::Ok)
+        {
+            HealthStatus::Ok
+        } else {
+            HealthStatus::Fail
+        };
+        Self { status, checks }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.status == HealthStatus::Ok
+    }
+}
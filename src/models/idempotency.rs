@@ -0,0 +1,103 @@
+use chrono::{DateTime, Duration, Utc};
+use sha2::{Digest, Sha256};
+use sqlx::types::Json;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+// 最初のレスポンスを保存しておく期間（この間の再送には同じレスポンスを返す）
+pub const IDEMPOTENCY_KEY_TTL: Duration = Duration::hours(24);
+
+// 処理中のまま（プロセスの停止などで）残ったキーを、同じキーの再送で引き継げるようになるまでの時間
+// 引き継いだ後も元の試行が動いていた場合、その complete / release は attempt_id が違うため無視される
+pub const IN_PROGRESS_TIMEOUT: Duration = Duration::minutes(1);
+
+// Idempotency-Key ヘッダーの値の最大長
+pub const MAX_IDEMPOTENCY_KEY_LENGTH: usize = 255;
+
+// 再送時に返すために保存したレスポンス
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StoredResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+// 保存された Idempotency-Key（response_* は処理が終わるまで None）
+#[derive(Clone, Debug, FromRow, Eq, PartialEq)]
+pub struct IdempotencyRecord {
+    pub user_id: Uuid,
+    pub key: String,
+    pub request_hash: String,
+    // キーを確保した試行（引き継ぐたびに新しくなる）
+    pub attempt_id: Uuid,
+    pub response_status: Option<i16>,
+    pub response_headers: Option<Json<Vec<(String, String)>>>,
+    pub response_body: Option<Vec<u8>>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl IdempotencyRecord {
+    // 処理中の状態で作成する
+    pub fn new(user_id: Uuid, key: String, request_hash: String, now: DateTime<Utc>) -> Self {
+        Self {
+            user_id,
+            key,
+            request_hash,
+            attempt_id: Uuid::now_v7(),
+            response_status: None,
+            response_headers: None,
+            response_body: None,
+            created_at: now,
+            expires_at: now + IDEMPOTENCY_KEY_TTL,
+        }
+    }
+
+    // 処理中のまま IN_PROGRESS_TIMEOUT が過ぎたか
+    pub fn is_abandoned(&self, now: DateTime<Utc>) -> bool {
+        self.response_status.is_none() && now - self.created_at >= IN_PROGRESS_TIMEOUT
+    }
+
+    // 保存済みのレスポンス（処理中の場合は None）
+    pub fn response(&self) -> Option<StoredResponse> {
+        let status = u16::try_from(self.response_status?).ok()?;
+        Some(StoredResponse {
+            status,
+            headers: self
+                .response_headers
+                .as_ref()
+                .map(|Json(headers)| headers.clone())
+                .unwrap_or_default(),
+            body: self.response_body.clone().unwrap_or_default(),
+        })
+    }
+}
+
+// 前回と同じ処理を行うための開始の結果
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum IdempotencyStart {
+    // 初めてのキー（または引き継いだキー）なので処理を行う
+    // 処理が終わったら attempt_id を指定して complete / release する
+    Proceed { attempt_id: Uuid },
+    // 処理済みなので保存したレスポンスを返す
+    Replay(StoredResponse),
+}
+
+// 空でなく、表示可能な ASCII 文字だけからなるキーか
+pub fn is_valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= MAX_IDEMPOTENCY_KEY_LENGTH
+        && key.bytes().all(|b| b.is_ascii_graphic())
+}
+
+// 同じキーで別のリクエストが送られたことを判定するための指紋
+pub fn request_fingerprint(method: &str, uri: &str, workspace_id: Uuid, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    for part in [method.as_bytes(), uri.as_bytes(), workspace_id.as_bytes()] {
+        // 区切りが曖昧にならないよう長さを前に付ける
+        hasher.update((part.len() as u64).to_be_bytes());
+        hasher.update(part);
+    }
+    hasher.update(body);
+    hex::encode(hasher.finalize())
+}
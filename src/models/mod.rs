@@ -1,4 +1,20 @@
+pub mod api_key;
+pub mod auth_token;
+pub mod calendar;
+pub mod caller;
+pub mod grant;
+pub mod health;
+pub mod idempotency;
+pub mod oidc;
+pub mod rate_limit;
+pub mod request_id;
+pub mod session;
+pub mod stats;
 pub mod task;
+pub mod task_transfer;
+pub mod time_entry;
 pub mod user;
+pub mod user_account;
+pub mod workspace;
 #[cfg(test)]
 pub mod tests;
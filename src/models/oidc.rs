@@ -0,0 +1,103 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+// 認可リクエストからコールバックまでに許す時間
+pub const LOGIN_STATE_TTL: Duration = Duration::minutes(10);
+
+// 認可リクエストを開始したときに保存するログイン状態（state_hash は平文の state の SHA-256）
+#[derive(Clone, Debug, FromRow, Eq, PartialEq)]
+pub struct OidcLoginState {
+    pub state_hash: String,
+    // ID トークンの nonce と照合する値
+    pub nonce: String,
+    // PKCE のコード検証子（トークンエンドポイントに送る）
+    pub code_verifier: String,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl OidcLoginState {
+    pub fn new(
+        state_hash: String,
+        nonce: String,
+        code_verifier: String,
+        now: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            state_hash,
+            nonce,
+            code_verifier,
+            expires_at: now + LOGIN_STATE_TTL,
+            created_at: now,
+        }
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+}
+
+// プロバイダーのアカウント（issuer と subject の組）とローカルユーザーの紐付け
+#[derive(Clone, Debug, FromRow, Eq, PartialEq)]
+pub struct OidcIdentity {
+    pub issuer: String,
+    pub subject: String,
+    pub user_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+impl OidcIdentity {
+    pub fn new(issuer: String, subject: String, user_id: Uuid) -> Self {
+        Self {
+            issuer,
+            subject,
+            user_id,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+// 署名・issuer・audience・有効期限を検証済みの ID トークンのクレーム
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+pub struct IdTokenClaims {
+    pub iss: String,
+    pub sub: String,
+    pub email: Option<String>,
+    pub email_verified: Option<bool>,
+    pub nonce: Option<String>,
+}
+
+impl IdTokenClaims {
+    // プロバイダーが確認済みのメールアドレス（未確認のものは既存ユーザーとの紐付けに使わない）
+    pub fn verified_email(&self) -> Option<&str> {
+        match (&self.email, self.email_verified) {
+            (Some(email), Some(true)) => Some(email.as_str()),
+            _ => None,
+        }
+    }
+}
+
+// 認可エンドポイントに渡すパラメーター
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuthorizationRequest {
+    pub state: String,
+    pub nonce: String,
+    pub code_challenge: String,
+}
+
+// ログイン開始時に返す、リダイレクト先と平文の state
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OidcLogin {
+    pub authorization_url: String,
+    pub state: String,
+}
+
+// PKCE の code_challenge（S256: コード検証子の SHA-256 を base64url でエンコードしたもの）
+pub fn code_challenge(code_verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()))
+}
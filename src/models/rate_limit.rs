@@ -0,0 +1,107 @@
+use chrono::{DateTime, Duration, Utc};
+
+// レート制限の上限（window の間に limit 回まで。トークンは window をかけて一定の速さで補充する）
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RateLimitQuota {
+    pub limit: u32,
+    pub window: Duration,
+}
+
+impl RateLimitQuota {
+    pub const fn per_minute(limit: u32) -> Self {
+        Self {
+            limit,
+            window: Duration::minutes(1),
+        }
+    }
+
+    // 1ミリ秒あたりに補充するトークンの数
+    pub fn refill_per_milli(&self) -> f64 {
+        f64::from(self.limit) / self.window.num_milliseconds() as f64
+    }
+
+    // RateLimit-Policy ヘッダーの値（例: "30;w=60"）
+    pub fn policy(&self) -> String {
+        format!("{};w={}", self.limit, self.window.num_seconds())
+    }
+}
+
+// クライアントごとのトークンバケット
+#[derive(Clone, Debug, PartialEq)]
+pub struct TokenBucket {
+    pub tokens: f64,
+    pub updated_at: DateTime<Utc>,
+}
+
+// トークンを取り出した結果（RateLimit-* ヘッダーに使う）
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    pub quota: RateLimitQuota,
+    pub remaining: u32,
+    // バケットが満杯に戻るまでの秒数
+    pub reset_after: u64,
+    // 拒否した場合に次のトークンが補充されるまでの秒数（許可した場合は 0）
+    pub retry_after: u64,
+}
+
+impl RateLimitDecision {
+    // tokens は取り出した後にバケットに残っているトークンの数
+    pub fn new(allowed: bool, quota: RateLimitQuota, tokens: f64) -> Self {
+        let rate = quota.refill_per_milli();
+        Self {
+            allowed,
+            quota,
+            remaining: tokens.floor() as u32,
+            reset_after: seconds_until(f64::from(quota.limit) - tokens, rate),
+            retry_after: if allowed {
+                0
+            } else {
+                seconds_until(1.0 - tokens, rate).max(1)
+            },
+        }
+    }
+}
+
+impl TokenBucket {
+    pub fn full(quota: RateLimitQuota, now: DateTime<Utc>) -> Self {
+        Self {
+            tokens: f64::from(quota.limit),
+            updated_at: now,
+        }
+    }
+
+    // 経過時間分を補充してからトークンを1つ取り出す
+    pub fn take(&mut self, quota: RateLimitQuota, now: DateTime<Utc>) -> RateLimitDecision {
+        self.tokens = self.refilled(quota, now);
+        self.updated_at = now;
+
+        let allowed = self.tokens >= 1.0;
+        if allowed {
+            self.tokens -= 1.0;
+        }
+        RateLimitDecision::new(allowed, quota, self.tokens)
+    }
+
+    // トークンを取り出さずに、取り出せるかどうかだけを確かめる
+    pub fn peek(&self, quota: RateLimitQuota, now: DateTime<Utc>) -> RateLimitDecision {
+        let tokens = self.refilled(quota, now);
+        RateLimitDecision::new(tokens >= 1.0, quota, tokens)
+    }
+
+    // now の時点のトークンの数（経過時間分を補充する）
+    fn refilled(&self, quota: RateLimitQuota, now: DateTime<Utc>) -> f64 {
+        let elapsed = (now - self.updated_at).num_milliseconds().max(0) as f64;
+        (self.tokens + elapsed * quota.refill_per_milli()).min(f64::from(quota.limit))
+    }
+
+    // 満杯に戻っていて、保持しておく必要がないか
+    pub fn is_idle(&self, quota: RateLimitQuota, now: DateTime<Utc>) -> bool {
+        now - self.updated_at >= quota.window
+    }
+}
+
+// missing 個のトークンが補充されるまでの秒数（切り上げ）
+fn seconds_until(missing: f64, rate: f64) -> u64 {
+    (missing.max(0.0) / rate / 1000.0).ceil() as u64
+}
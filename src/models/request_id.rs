@@ -0,0 +1,34 @@
+use std::fmt;
+use uuid::Uuid;
+
+// 受け取る X-Request-Id の長さの上限（これを超える場合は新しく発行する）
+pub const MAX_REQUEST_ID_LENGTH: usize = 128;
+
+// リクエストを識別するID（ログ・エラーレスポンス・外部APIの呼び出しを紐付ける）
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RequestId(String);
+
+impl RequestId {
+    // 時刻順に並ぶ UUIDv7 で発行する
+    pub fn generate() -> Self {
+        Self(Uuid::now_v7().to_string())
+    }
+
+    // 呼び出し元が送ってきたID（ログに書くため、空でなく表示可能な ASCII 文字だけのものに限る）
+    pub fn parse(value: &str) -> Option<Self> {
+        let valid = !value.is_empty()
+            && value.len() <= MAX_REQUEST_ID_LENGTH
+            && value.bytes().all(|b| b.is_ascii_graphic());
+        valid.then(|| Self(value.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
@@ -0,0 +1,60 @@
+use crate::secret::verify_token;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+// 最後のアクセスからこの時間が過ぎたセッションは無効にする
+pub const SESSION_IDLE_TIMEOUT: Duration = Duration::minutes(30);
+// アクセスが続いていても、ログインからこの時間が過ぎたセッションは無効にする
+pub const SESSION_ABSOLUTE_TIMEOUT: Duration = Duration::hours(12);
+
+// 保存されたセッション（token_hash・csrf_token_hash は平文トークンの SHA-256）
+#[derive(Clone, Debug, FromRow, Eq, PartialEq)]
+pub struct Session {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub token_hash: String,
+    pub csrf_token_hash: String,
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl Session {
+    pub fn new(
+        user_id: Uuid,
+        token_hash: String,
+        csrf_token_hash: String,
+        now: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id: Uuid::now_v7(),
+            user_id,
+            token_hash,
+            csrf_token_hash,
+            created_at: now,
+            last_seen_at: now,
+            expires_at: now + SESSION_ABSOLUTE_TIMEOUT,
+        }
+    }
+
+    // アイドルタイムアウトか絶対タイムアウトを過ぎているか
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now >= self.expires_at || now - self.last_seen_at >= SESSION_IDLE_TIMEOUT
+    }
+
+    // リクエストの CSRF トークンがこのセッションのものか
+    pub fn verifies_csrf(&self, csrf_token: &str) -> bool {
+        verify_token(csrf_token, &self.csrf_token_hash)
+    }
+}
+
+// ログイン時に一度だけ返す平文のトークンと、保存したセッション
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IssuedSession {
+    pub session: Session,
+    // Cookie に保存するセッショントークン
+    pub token: String,
+    // 状態を変更するリクエストの X-CSRF-Token ヘッダーに付けるトークン
+    pub csrf_token: String,
+}
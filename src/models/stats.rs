@@ -0,0 +1,125 @@
+use chrono::{DateTime, Duration, Months, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+
+// 1回の集計で返すバケット数の上限
+const MAX_BUCKETS: i64 = 366;
+
+// 集計の時間単位
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default, Eq, PartialEq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum StatsBucket {
+    Day,
+    #[default]
+    Week,
+    Month,
+}
+
+impl StatsBucket {
+    // PostgreSQL の date_trunc に渡す単位
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            StatsBucket::Day => "day",
+            StatsBucket::Week => "week",
+            StatsBucket::Month => "month",
+        }
+    }
+
+    // 期間が指定されなかった場合に遡るデフォルトの開始日時
+    fn default_from(&self, to: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            StatsBucket::Day => to - Duration::days(30),
+            StatsBucket::Week => to - Duration::weeks(12),
+            StatsBucket::Month => to.checked_sub_months(Months::new(12)).unwrap_or(to),
+        }
+    }
+
+    // 期間に含まれるバケット数のおおよその値（上限チェック用）
+    fn approx_count(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> i64 {
+        let days = (to - from).num_days();
+        match self {
+            StatsBucket::Day => days,
+            StatsBucket::Week => days / 7,
+            StatsBucket::Month => days / 28,
+        }
+    }
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct StatsQuery {
+    /// 集計の時間単位（day / week / month）
+    #[serde(default)]
+    pub bucket: StatsBucket,
+    /// 集計期間の開始日時（省略時は単位ごとのデフォルト）
+    pub from: Option<DateTime<Utc>>,
+    /// 集計期間の終了日時（省略時は現在日時）
+    pub to: Option<DateTime<Utc>>,
+    /// バケットの区切りに使うタイムゾーン（例: Asia/Tokyo）
+    pub timezone: Option<String>,
+}
+
+impl StatsQuery {
+    // デフォルト値を補完し、集計範囲を検証する
+    pub fn resolve(self, now: DateTime<Utc>) -> Result<StatsRange, String> {
+        let to = self.to.unwrap_or(now);
+        let from = self.from.unwrap_or_else(|| self.bucket.default_from(to));
+        if from >= to {
+            return Err("`from` must be earlier than `to`".to_string());
+        }
+        if self.bucket.approx_count(from, to) > MAX_BUCKETS {
+            return Err(format!(
+                "the range is too long: at most {} buckets are allowed",
+                MAX_BUCKETS
+            ));
+        }
+        Ok(StatsRange {
+            bucket: self.bucket,
+            from,
+            to,
+            timezone: self.timezone.unwrap_or_else(|| "UTC".to_string()),
+        })
+    }
+}
+
+// 検証済みの集計範囲
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StatsRange {
+    pub bucket: StatsBucket,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub timezone: String,
+}
+
+#[derive(Serialize, Clone, Debug, Default, PartialEq, FromRow, ToSchema)]
+pub struct TaskStatusCounts {
+    pub total: i64,
+    pub completed: i64,
+    pub open: i64,
+    // 未完了かつ期限切れのタスク数
+    pub overdue: i64,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, FromRow, ToSchema)]
+pub struct CompletionBucket {
+    pub bucket_start: DateTime<Utc>,
+    // バケット内に作成されたタスク数
+    pub created: i64,
+    // バケット内に完了したタスク数
+    pub completed: i64,
+    // バケット内に作成されたタスクのうち完了済みの割合（作成数が0の場合はnull）
+    pub completion_rate: Option<f64>,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, ToSchema)]
+pub struct TaskStats {
+    pub bucket: StatsBucket,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub timezone: String,
+    pub status: TaskStatusCounts,
+    // 作成から完了までの平均秒数（完了済みタスクがない場合はnull）
+    pub avg_seconds_to_complete: Option<f64>,
+    pub completion: Vec<CompletionBucket>,
+}
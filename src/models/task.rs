@@ -7,14 +7,24 @@ use uuid::Uuid;
 #[derive(Deserialize, Serialize, Clone, Debug, FromRow, Eq, PartialEq, ToSchema)]
 pub struct Task {
     pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub owner_id: Uuid,
     pub title: String,
     pub completed: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub due_at: Option<DateTime<Utc>>,
+    pub project: Option<String>,
+    pub tags: Vec<String>,
+    pub completed_at: Option<DateTime<Utc>>,
+    // 完了にしたユーザー（未完了のタスクと、記録する前に完了したタスクは None）
+    pub completed_by: Option<Uuid>,
+    // 記録された作業時間の合計秒数（time_entries から集計する読み取り専用の値）
+    pub tracked_seconds: i64,
 }
 
 impl Task {
-    pub fn new(title: String) -> Self {
+    pub fn new(workspace_id: Uuid, owner_id: Uuid, title: String) -> Self {
         // 日本時間のオフセット（UTC+9時間）
         let jst = FixedOffset::east_opt(9 * 3600).unwrap();
         // 現在の日本時間を取得し、UTCに変換
@@ -23,10 +33,60 @@ impl Task {
 
         Self {
             id: Uuid::now_v7(),
+            workspace_id,
+            owner_id,
             title,
             completed: false,
             created_at: now_utc,
             updated_at: now_utc,
+            due_at: None,
+            project: None,
+            tags: Vec::new(),
+            completed_at: None,
+            completed_by: None,
+            tracked_seconds: 0,
         }
     }
+
+    // 完了状態を変更する（未完了→完了で完了日時と完了にしたユーザーを記録し、完了→未完了で消去する）
+    pub fn set_completed(&mut self, completed: bool, by: Uuid, now: DateTime<Utc>) {
+        if completed && !self.completed {
+            self.completed_at = Some(now);
+            self.completed_by = Some(by);
+        } else if !completed {
+            self.completed_at = None;
+            self.completed_by = None;
+        }
+        self.completed = completed;
+    }
+
+    // 指定された付加情報だけを上書きする
+    pub fn apply_details(&mut self, details: TaskDetails) {
+        if let Some(due_at) = details.due_at {
+            self.due_at = Some(due_at);
+        }
+        if let Some(project) = details.project {
+            self.project = Some(project);
+        }
+        if let Some(tags) = details.tags {
+            self.tags = tags;
+        }
+    }
+}
+
+// タスクの作成・更新時に指定できる付加情報（Noneの項目は変更しない）
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TaskDetails {
+    pub due_at: Option<DateTime<Utc>>,
+    pub project: Option<String>,
+    pub tags: Option<Vec<String>>,
+}
+
+// タスク一覧の絞り込み条件
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TaskFilter {
+    pub project: Option<String>,
+    pub tag: Option<String>,
+    // この日時以降に完了したタスクに絞り込む
+    pub completed_since: Option<DateTime<Utc>>,
 }
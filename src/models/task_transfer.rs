@@ -0,0 +1,270 @@
+use crate::models::task::Task;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+// エクスポート/インポートで扱うファイル形式
+#[derive(Deserialize, Clone, Copy, Debug, Default, Eq, PartialEq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferFormat {
+    #[default]
+    Json,
+    Ndjson,
+    Csv,
+}
+
+impl TransferFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            TransferFormat::Json => "application/json",
+            TransferFormat::Ndjson => "application/x-ndjson",
+            TransferFormat::Csv => "text/csv; charset=utf-8",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            TransferFormat::Json => "json",
+            TransferFormat::Ndjson => "ndjson",
+            TransferFormat::Csv => "csv",
+        }
+    }
+
+    // ストリームの先頭に出力するバイト列
+    pub fn prologue(&self) -> Vec<u8> {
+        match self {
+            TransferFormat::Json => b"[".to_vec(),
+            TransferFormat::Ndjson => Vec::new(),
+            TransferFormat::Csv => {
+                b"id,title,completed,created_at,updated_at,due_at,project,tags,completed_at,completed_by\n"
+                    .to_vec()
+            }
+        }
+    }
+
+    // ストリームの末尾に出力するバイト列
+    pub fn epilogue(&self) -> Vec<u8> {
+        match self {
+            TransferFormat::Json => b"]".to_vec(),
+            TransferFormat::Ndjson | TransferFormat::Csv => Vec::new(),
+        }
+    }
+
+    // 1ページ分のタスクをエンコードする（JSONの区切り文字のため先頭ページかどうかを受け取る）
+    pub fn encode_page(&self, tasks: &[Task], first_page: bool) -> Vec<u8> {
+        let mut buf = Vec::new();
+        for (i, task) in tasks.iter().enumerate() {
+            let record = TaskRecord::from(task.clone());
+            match self {
+                TransferFormat::Json => {
+                    if !(first_page && i == 0) {
+                        buf.push(b',');
+                    }
+                    buf.extend(serde_json::to_vec(&record).expect("TaskRecord is serializable"));
+                }
+                TransferFormat::Ndjson => {
+                    buf.extend(serde_json::to_vec(&record).expect("TaskRecord is serializable"));
+                    buf.push(b'\n');
+                }
+                TransferFormat::Csv => {
+                    let mut writer = csv::WriterBuilder::new()
+                        .has_headers(false)
+                        .from_writer(&mut buf);
+                    writer
+                        .serialize(CsvTaskRow::from(record))
+                        .expect("TaskRecord is serializable");
+                    writer.flush().expect("writing to Vec never fails");
+                }
+            }
+        }
+        buf
+    }
+
+    // インポート用のボディを行ごとにパースする（行番号はデータ行の1始まり）
+    pub fn parse_records(&self, body: &str) -> Result<Vec<Result<TaskRecord, String>>, String> {
+        match self {
+            TransferFormat::Json => {
+                let values: Vec<serde_json::Value> =
+                    serde_json::from_str(body).map_err(|e| format!("invalid JSON array: {}", e))?;
+                Ok(values
+                    .into_iter()
+                    .map(|v| serde_json::from_value(v).map_err(|e| e.to_string()))
+                    .collect())
+            }
+            TransferFormat::Ndjson => Ok(body
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| serde_json::from_str(line).map_err(|e| e.to_string()))
+                .collect()),
+            TransferFormat::Csv => {
+                let mut reader = csv::ReaderBuilder::new()
+                    .trim(csv::Trim::All)
+                    .from_reader(body.as_bytes());
+                Ok(reader
+                    .deserialize::<CsvTaskRow>()
+                    .map(|r| r.map(TaskRecord::from).map_err(|e| e.to_string()))
+                    .collect())
+            }
+        }
+    }
+}
+
+// エクスポート/インポートの1行分
+#[derive(Deserialize, Serialize, Clone, Debug, Eq, PartialEq, ToSchema)]
+pub struct TaskRecord {
+    pub id: Uuid,
+    pub title: String,
+    #[serde(default)]
+    pub completed: bool,
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub due_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub project: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub completed_at: Option<DateTime<Utc>>,
+    // エクスポートにだけ使う（インポートではインポートしたユーザーが完了にしたものとして扱う）
+    #[serde(default)]
+    pub completed_by: Option<Uuid>,
+}
+
+impl From<Task> for TaskRecord {
+    fn from(task: Task) -> Self {
+        Self {
+            id: task.id,
+            title: task.title,
+            completed: task.completed,
+            created_at: Some(task.created_at),
+            updated_at: Some(task.updated_at),
+            due_at: task.due_at,
+            project: task.project,
+            tags: task.tags,
+            completed_at: task.completed_at,
+            completed_by: task.completed_by,
+        }
+    }
+}
+
+// CSVの1行（CSVは配列を表現できないため、タグは `|` 区切りの1カラムにする）
+#[derive(Deserialize, Serialize)]
+struct CsvTaskRow {
+    id: Uuid,
+    title: String,
+    #[serde(default)]
+    completed: bool,
+    #[serde(default)]
+    created_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    updated_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    due_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    project: Option<String>,
+    #[serde(default)]
+    tags: String,
+    #[serde(default)]
+    completed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    completed_by: Option<Uuid>,
+}
+
+impl From<TaskRecord> for CsvTaskRow {
+    fn from(record: TaskRecord) -> Self {
+        Self {
+            id: record.id,
+            title: record.title,
+            completed: record.completed,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+            due_at: record.due_at,
+            project: record.project,
+            tags: record.tags.join("|"),
+            completed_at: record.completed_at,
+            completed_by: record.completed_by,
+        }
+    }
+}
+
+impl From<CsvTaskRow> for TaskRecord {
+    fn from(row: CsvTaskRow) -> Self {
+        Self {
+            id: row.id,
+            title: row.title,
+            completed: row.completed,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+            due_at: row.due_at,
+            project: row.project.filter(|p| !p.is_empty()),
+            tags: row
+                .tags
+                .split('|')
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .map(str::to_string)
+                .collect(),
+            completed_at: row.completed_at,
+            completed_by: row.completed_by,
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Copy, Debug, Eq, PartialEq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportAction {
+    Created,
+    Updated,
+    Unchanged,
+}
+
+#[derive(Serialize, Clone, Debug, Eq, PartialEq, ToSchema)]
+pub struct ImportRowError {
+    pub row: usize,
+    pub id: Option<Uuid>,
+    pub message: String,
+}
+
+// インポート結果のレポート
+#[derive(Serialize, Clone, Debug, Default, Eq, PartialEq, ToSchema)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub total: usize,
+    pub created: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    pub failed: usize,
+    pub errors: Vec<ImportRowError>,
+}
+
+impl ImportReport {
+    pub fn new(dry_run: bool) -> Self {
+        Self {
+            dry_run,
+            ..Default::default()
+        }
+    }
+
+    pub fn record(&mut self, action: ImportAction) {
+        self.total += 1;
+        match action {
+            ImportAction::Created => self.created += 1,
+            ImportAction::Updated => self.updated += 1,
+            ImportAction::Unchanged => self.unchanged += 1,
+        }
+    }
+
+    pub fn fail(&mut self, row: usize, id: Option<Uuid>, message: impl Into<String>) {
+        self.total += 1;
+        self.failed += 1;
+        self.errors.push(ImportRowError {
+            row,
+            id,
+            message: message.into(),
+        });
+    }
+}
@@ -0,0 +1,56 @@
+use crate::models::api_key::{parse_prefix, ApiKey, ApiKeyScope};
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_prefix() {
+        assert_eq!(parse_prefix("tk_0a1b2c3d4e5f_secret"), Some("0a1b2c3d4e5f"));
+    }
+
+    #[test]
+    fn test_parse_prefix_rejects_malformed_keys() {
+        for key in [
+            "",
+            "tk_",
+            "tk_abc",
+            "tk__secret",
+            "tk_abc_",
+            "xx_abc_secret",
+        ] {
+            assert_eq!(parse_prefix(key), None, "{} should be rejected", key);
+        }
+    }
+
+    #[test]
+    fn test_is_expired() {
+        let now = Utc::now();
+        let key = ApiKey::new(
+            Uuid::from_u128(1),
+            Uuid::from_u128(1),
+            "ci".to_string(),
+            "0a1b2c3d4e5f".to_string(),
+            "hash".to_string(),
+            vec![ApiKeyScope::TasksRead],
+            now + Duration::days(1),
+        );
+
+        assert!(!key.is_expired(now));
+        assert!(key.is_expired(now + Duration::days(1)));
+    }
+
+    #[test]
+    fn test_scope_serialization() {
+        assert_eq!(
+            serde_json::to_string(&ApiKeyScope::TasksWrite).unwrap(),
+            "\"tasks:write\""
+        );
+        assert_eq!(
+            serde_json::from_str::<ApiKeyScope>("\"tasks:read\"").unwrap(),
+            ApiKeyScope::TasksRead
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use crate::models::calendar::{
+    escape_text, fold_line, format_utc, render_calendar, CalendarComponent,
+};
+use crate::models::task::Task;
+use chrono::{TimeZone, Utc};
+use uuid::Uuid;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 作成・更新・期限日時を固定したテスト用のTask
+    fn create_scheduled_task(title: &str) -> Task {
+        let mut task = Task::new(Uuid::nil(), Uuid::nil(), title.to_string());
+        task.created_at = Utc.with_ymd_and_hms(2025, 5, 1, 0, 0, 0).unwrap();
+        task.updated_at = Utc.with_ymd_and_hms(2025, 5, 2, 3, 4, 5).unwrap();
+        task.due_at = Some(Utc.with_ymd_and_hms(2025, 5, 10, 9, 0, 0).unwrap());
+        task
+    }
+
+    #[test]
+    fn test_format_utc() {
+        let datetime = Utc.with_ymd_and_hms(2025, 5, 12, 9, 30, 0).unwrap();
+        assert_eq!(format_utc(datetime), "20250512T093000Z");
+    }
+
+    #[test]
+    fn test_escape_text() {
+        assert_eq!(escape_text("a,b;c\\d\ne"), r"a\,b\;c\\d\ne");
+    }
+
+    #[test]
+    fn test_fold_line_keeps_multibyte_characters_intact() {
+        let line = format!("SUMMARY:{}", "あ".repeat(40));
+
+        let folded = fold_line(&line);
+
+        // 各物理行は75オクテット以下で、展開すると元の行に戻る
+        for physical in folded.trim_end_matches("\r\n").split("\r\n") {
+            assert!(physical.len() <= 75);
+        }
+        assert_eq!(folded.replace("\r\n ", "").trim_end(), line);
+    }
+
+    #[test]
+    fn test_render_vtodo_uses_timestamps() {
+        let task = create_scheduled_task("会議, 準備");
+
+        let ics = render_calendar(std::slice::from_ref(&task), CalendarComponent::Vtodo);
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert!(ics.contains(&format!("UID:{}@rust-on-docker\r\n", task.id)));
+        assert!(ics.contains("DTSTAMP:20250502T030405Z\r\n"));
+        assert!(ics.contains("CREATED:20250501T000000Z\r\n"));
+        assert!(ics.contains("LAST-MODIFIED:20250502T030405Z\r\n"));
+        assert!(ics.contains("DUE:20250510T090000Z\r\n"));
+        assert!(ics.contains("SUMMARY:会議\\, 準備\r\n"));
+        assert!(ics.contains("STATUS:NEEDS-ACTION\r\n"));
+    }
+
+    #[test]
+    fn test_render_vevent_skips_tasks_without_due_date() {
+        let scheduled = create_scheduled_task("期限あり");
+        let unscheduled = Task::new(Uuid::nil(), Uuid::nil(), "期限なし".to_string());
+
+        let ics = render_calendar(&[scheduled, unscheduled], CalendarComponent::Vevent);
+
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 1);
+        assert!(ics.contains("DTSTART:20250510T090000Z\r\n"));
+        assert!(!ics.contains("期限なし"));
+    }
+}
@@ -0,0 +1,62 @@
+use crate::models::grant::{Grant, GrantPermission, ShareTarget};
+use crate::models::task::Task;
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+const WORKSPACE_ID: Uuid = Uuid::from_u128(10);
+const USER_ID: Uuid = Uuid::from_u128(1);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_covers_task_and_project() {
+        let mut task = Task::new(WORKSPACE_ID, USER_ID, "タスク".to_string());
+        task.project = Some("release".to_string());
+        let other_task = Task::new(WORKSPACE_ID, USER_ID, "別のタスク".to_string());
+        let mut other_workspace_task = task.clone();
+        other_workspace_task.workspace_id = Uuid::from_u128(20);
+
+        let task_grant = Grant::for_user(
+            WORKSPACE_ID,
+            ShareTarget::Task(task.id),
+            Uuid::from_u128(2),
+            GrantPermission::View,
+            None,
+            USER_ID,
+        );
+        let project_grant = Grant::for_link(
+            WORKSPACE_ID,
+            ShareTarget::Project("release".to_string()),
+            "hash".to_string(),
+            Utc::now() + Duration::days(1),
+            USER_ID,
+        );
+
+        // 検証
+        assert!(task_grant.covers(&task));
+        assert!(!task_grant.covers(&other_task));
+        assert!(project_grant.covers(&task));
+        assert!(!project_grant.covers(&other_task));
+        // 同じ id・プロジェクトでも他のワークスペースのタスクは対象外
+        assert!(!task_grant.covers(&other_workspace_task));
+        assert!(!project_grant.covers(&other_workspace_task));
+    }
+
+    #[test]
+    fn test_share_link_is_view_only() {
+        let grant = Grant::for_link(
+            WORKSPACE_ID,
+            ShareTarget::Task(Uuid::now_v7()),
+            "hash".to_string(),
+            Utc::now() + Duration::days(1),
+            USER_ID,
+        );
+
+        // 検証
+        assert!(grant.is_link());
+        assert_eq!(grant.permission, GrantPermission::View);
+        assert!(grant.grantee_id.is_none());
+    }
+}
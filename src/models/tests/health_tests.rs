@@ -0,0 +1,37 @@
+use crate::models::health::{CheckResult, HealthReport, HealthStatus};
+use std::collections::BTreeMap;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_fails_if_any_check_fails() {
+        let ok = HealthReport::new(BTreeMap::from([(
+            "database".to_string(),
+            CheckResult::ok(3),
+        )]));
+        assert_eq!(ok.status, HealthStatus::Ok);
+
+        let failed = HealthReport::new(BTreeMap::from([
+            ("database".to_string(), CheckResult::ok(3)),
+            (
+                "users_api".to_string(),
+                CheckResult::fail(2000, "timed out"),
+            ),
+        ]));
+        assert_eq!(failed.status, HealthStatus::Fail);
+
+        // 失敗した確認だけ error を含める
+        assert_eq!(
+            serde_json::to_value(&failed).unwrap(),
+            serde_json::json!({
+                "status": "fail",
+                "checks": {
+                    "database": {"status": "ok", "latency_ms": 3},
+                    "users_api": {"status": "fail", "latency_ms": 2000, "error": "timed out"}
+                }
+            })
+        );
+    }
+}
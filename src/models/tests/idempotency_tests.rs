@@ -0,0 +1,78 @@
+use crate::models::idempotency::{
+    is_valid_key, request_fingerprint, IdempotencyRecord, IN_PROGRESS_TIMEOUT,
+    MAX_IDEMPOTENCY_KEY_LENGTH,
+};
+use chrono::{Duration, Utc};
+use sqlx::types::Json;
+use uuid::Uuid;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_key() {
+        assert!(is_valid_key("3f1c2a9e-5b7d-4e0f-9a8b-1c2d3e4f5a6b"));
+        assert!(is_valid_key(&"k".repeat(MAX_IDEMPOTENCY_KEY_LENGTH)));
+
+        assert!(!is_valid_key(""));
+        assert!(!is_valid_key("has space"));
+        assert!(!is_valid_key("キー"));
+        assert!(!is_valid_key(&"k".repeat(MAX_IDEMPOTENCY_KEY_LENGTH + 1)));
+    }
+
+    #[test]
+    fn test_request_fingerprint() {
+        let workspace_id = Uuid::from_u128(1);
+        let fingerprint = request_fingerprint("POST", "/tasks", workspace_id, b"{\"title\":\"a\"}");
+
+        assert_eq!(
+            fingerprint,
+            request_fingerprint("POST", "/tasks", workspace_id, b"{\"title\":\"a\"}")
+        );
+        // 本文・パス・ワークスペースのどれかが違えば別のリクエストになる
+        assert_ne!(
+            fingerprint,
+            request_fingerprint("POST", "/tasks", workspace_id, b"{\"title\":\"b\"}")
+        );
+        assert_ne!(
+            fingerprint,
+            request_fingerprint("POST", "/tasks/import", workspace_id, b"{\"title\":\"a\"}")
+        );
+        assert_ne!(
+            fingerprint,
+            request_fingerprint("POST", "/tasks", Uuid::from_u128(2), b"{\"title\":\"a\"}")
+        );
+        // 区切りの位置をずらしても同じにならない
+        assert_ne!(
+            request_fingerprint("POST", "/tasksa", workspace_id, b""),
+            request_fingerprint("POS", "T/tasksa", workspace_id, b"")
+        );
+    }
+
+    #[test]
+    fn test_response_is_none_while_in_progress() {
+        let now = Utc::now();
+        let mut record = IdempotencyRecord::new(
+            Uuid::from_u128(1),
+            "key".to_string(),
+            "hash".to_string(),
+            now,
+        );
+        assert!(record.response().is_none());
+        assert!(!record.is_abandoned(now + IN_PROGRESS_TIMEOUT - Duration::seconds(1)));
+        assert!(record.is_abandoned(now + IN_PROGRESS_TIMEOUT));
+
+        // 処理済みのキーは処理中として引き継がない
+        record.response_status = Some(201);
+        record.response_headers = Some(Json(vec![(
+            "content-type".to_string(),
+            "application/json".to_string(),
+        )]));
+        record.response_body = Some(b"{}".to_vec());
+        let response = record.response().unwrap();
+        assert_eq!(response.status, 201);
+        assert_eq!(response.body, b"{}".to_vec());
+        assert!(!record.is_abandoned(now + IN_PROGRESS_TIMEOUT));
+    }
+}
@@ -1 +1,13 @@
+pub mod api_key_tests;
+pub mod calendar_tests;
+pub mod grant_tests;
+pub mod health_tests;
+pub mod idempotency_tests;
+pub mod oidc_tests;
+pub mod rate_limit_tests;
+pub mod request_id_tests;
+pub mod session_tests;
+pub mod stats_tests;
 pub mod task_tests;
+pub mod task_transfer_tests;
+pub mod user_account_tests;
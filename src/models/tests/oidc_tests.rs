@@ -0,0 +1,57 @@
+use crate::models::oidc::{code_challenge, IdTokenClaims, OidcLoginState};
+use chrono::{Duration, Utc};
+
+fn claims(email: Option<&str>, email_verified: Option<bool>) -> IdTokenClaims {
+    IdTokenClaims {
+        iss: "https://idp.example.com".to_string(),
+        sub: "user-1".to_string(),
+        email: email.map(str::to_string),
+        email_verified,
+        nonce: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_challenge_matches_rfc7636_example() {
+        // RFC 7636 Appendix B の例
+        assert_eq!(
+            code_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+
+    #[test]
+    fn test_login_state_expires() {
+        let now = Utc::now();
+        let state = OidcLoginState::new(
+            "hash".to_string(),
+            "nonce".to_string(),
+            "verifier".to_string(),
+            now,
+        );
+
+        assert!(!state.is_expired(now));
+        assert!(state.is_expired(now + Duration::minutes(10)));
+    }
+
+    #[test]
+    fn test_verified_email_requires_email_verified() {
+        assert_eq!(
+            claims(Some("alice@example.com"), Some(true)).verified_email(),
+            Some("alice@example.com")
+        );
+        assert_eq!(
+            claims(Some("alice@example.com"), Some(false)).verified_email(),
+            None
+        );
+        assert_eq!(
+            claims(Some("alice@example.com"), None).verified_email(),
+            None
+        );
+        assert_eq!(claims(None, Some(true)).verified_email(), None);
+    }
+}
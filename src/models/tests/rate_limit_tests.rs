@@ -0,0 +1,80 @@
+use crate::models::rate_limit::{RateLimitQuota, TokenBucket};
+use chrono::{Duration, Utc};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_allows_burst_up_to_limit() {
+        let now = Utc::now();
+        let quota = RateLimitQuota::per_minute(3);
+        let mut bucket = TokenBucket::full(quota, now);
+
+        // 満杯のバケットからは limit 回まで続けて取り出せる
+        let remaining: Vec<u32> = (0..3).map(|_| bucket.take(quota, now).remaining).collect();
+        assert_eq!(remaining, vec![2, 1, 0]);
+
+        // 空になったら拒否し、次のトークンが補充されるまでの秒数を返す
+        let decision = bucket.take(quota, now);
+        assert!(!decision.allowed);
+        assert_eq!(decision.remaining, 0);
+        assert_eq!(decision.retry_after, 20);
+        assert_eq!(decision.reset_after, 60);
+    }
+
+    #[test]
+    fn test_bucket_refills_over_time() {
+        let now = Utc::now();
+        let quota = RateLimitQuota::per_minute(3);
+        let mut bucket = TokenBucket::full(quota, now);
+        for _ in 0..3 {
+            bucket.take(quota, now);
+        }
+
+        // 20秒ごとに1つ補充される
+        let decision = bucket.take(quota, now + Duration::seconds(20));
+        assert!(decision.allowed);
+        assert_eq!(decision.retry_after, 0);
+        assert!(!bucket.take(quota, now + Duration::seconds(30)).allowed);
+
+        // 長く空いても limit を超えては貯まらない
+        let decision = bucket.take(quota, now + Duration::hours(1));
+        assert_eq!(decision.remaining, 2);
+        assert_eq!(decision.reset_after, 20);
+    }
+
+    #[test]
+    fn test_peek_does_not_take_token() {
+        let now = Utc::now();
+        let quota = RateLimitQuota::per_minute(1);
+        let mut bucket = TokenBucket::full(quota, now);
+
+        // 何度確かめてもトークンは減らない
+        assert!(bucket.peek(quota, now).allowed);
+        assert!(bucket.peek(quota, now).allowed);
+
+        // 取り出した後は、補充されるまで拒否する
+        bucket.take(quota, now);
+        let decision = bucket.peek(quota, now);
+        assert!(!decision.allowed);
+        assert_eq!(decision.retry_after, 60);
+        assert!(bucket.peek(quota, now + Duration::minutes(1)).allowed);
+    }
+
+    #[test]
+    fn test_bucket_is_idle_after_window() {
+        let now = Utc::now();
+        let quota = RateLimitQuota::per_minute(3);
+        let mut bucket = TokenBucket::full(quota, now);
+        bucket.take(quota, now);
+
+        assert!(!bucket.is_idle(quota, now + Duration::seconds(59)));
+        assert!(bucket.is_idle(quota, now + Duration::minutes(1)));
+    }
+
+    #[test]
+    fn test_policy() {
+        assert_eq!(RateLimitQuota::per_minute(30).policy(), "30;w=60");
+    }
+}
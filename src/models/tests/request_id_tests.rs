@@ -0,0 +1,31 @@
+use crate::models::request_id::{RequestId, MAX_REQUEST_ID_LENGTH};
+use uuid::Uuid;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate() {
+        let id = RequestId::generate();
+
+        assert!(Uuid::parse_str(id.as_str()).is_ok());
+        assert_ne!(id, RequestId::generate());
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            RequestId::parse("req-123").map(|id| id.to_string()),
+            Some("req-123".to_string())
+        );
+        assert!(RequestId::parse(&"r".repeat(MAX_REQUEST_ID_LENGTH)).is_some());
+
+        // ログを壊したり偽装したりできる値は受け取らない
+        assert!(RequestId::parse("").is_none());
+        assert!(RequestId::parse("has space").is_none());
+        assert!(RequestId::parse("line\nbreak").is_none());
+        assert!(RequestId::parse("リクエスト").is_none());
+        assert!(RequestId::parse(&"r".repeat(MAX_REQUEST_ID_LENGTH + 1)).is_none());
+    }
+}
@@ -0,0 +1,56 @@
+use crate::models::session::{Session, SESSION_ABSOLUTE_TIMEOUT, SESSION_IDLE_TIMEOUT};
+use crate::secret::hash_token;
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_idle_timeout() {
+        let now = Utc::now();
+        let mut session = Session::new(
+            Uuid::from_u128(1),
+            "hash".to_string(),
+            "csrf".to_string(),
+            now,
+        );
+
+        assert!(!session.is_expired(now + SESSION_IDLE_TIMEOUT - Duration::seconds(1)));
+        assert!(session.is_expired(now + SESSION_IDLE_TIMEOUT));
+
+        // アクセスがあればアイドルタイムアウトは延長される
+        session.last_seen_at = now + Duration::minutes(20);
+        assert!(!session.is_expired(now + SESSION_IDLE_TIMEOUT));
+    }
+
+    #[test]
+    fn test_absolute_timeout() {
+        let now = Utc::now();
+        let mut session = Session::new(
+            Uuid::from_u128(1),
+            "hash".to_string(),
+            "csrf".to_string(),
+            now,
+        );
+
+        // 直前までアクセスがあっても、絶対タイムアウトは延長されない
+        session.last_seen_at = now + SESSION_ABSOLUTE_TIMEOUT - Duration::minutes(1);
+        assert!(session.is_expired(now + SESSION_ABSOLUTE_TIMEOUT));
+    }
+
+    #[test]
+    fn test_verifies_csrf() {
+        let session = Session::new(
+            Uuid::from_u128(1),
+            "hash".to_string(),
+            hash_token("csrf-token"),
+            Utc::now(),
+        );
+
+        assert!(session.verifies_csrf("csrf-token"));
+        assert!(!session.verifies_csrf("another-token"));
+        assert!(!session.verifies_csrf(""));
+    }
+}
@@ -0,0 +1,51 @@
+use crate::models::stats::{StatsBucket, StatsQuery};
+use chrono::{Duration, TimeZone, Utc};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_applies_defaults() {
+        let now = Utc.with_ymd_and_hms(2025, 5, 12, 0, 0, 0).unwrap();
+        let query = StatsQuery {
+            bucket: StatsBucket::Week,
+            from: None,
+            to: None,
+            timezone: None,
+        };
+
+        let range = query.resolve(now).unwrap();
+
+        // 週単位の場合は12週間前から現在までを集計する
+        assert_eq!(range.to, now);
+        assert_eq!(range.from, now - Duration::weeks(12));
+        assert_eq!(range.timezone, "UTC");
+    }
+
+    #[test]
+    fn test_resolve_rejects_inverted_range() {
+        let now = Utc::now();
+        let query = StatsQuery {
+            bucket: StatsBucket::Day,
+            from: Some(now),
+            to: Some(now - Duration::days(1)),
+            timezone: None,
+        };
+
+        assert!(query.resolve(now).is_err());
+    }
+
+    #[test]
+    fn test_resolve_rejects_too_many_buckets() {
+        let now = Utc::now();
+        let query = StatsQuery {
+            bucket: StatsBucket::Day,
+            from: Some(now - Duration::days(1000)),
+            to: None,
+            timezone: Some("Asia/Tokyo".to_string()),
+        };
+
+        assert!(query.resolve(now).is_err());
+    }
+}
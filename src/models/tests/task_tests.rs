@@ -1,5 +1,6 @@
 use crate::models::task::Task;
 use chrono::Utc;
+use uuid::Uuid;
 
 #[cfg(test)]
 mod tests {
@@ -9,10 +10,14 @@ mod tests {
     fn test_new_task() {
         let title = "テストタイトル";
         
-        let task = Task::new(title.to_string());
+        let workspace_id = Uuid::now_v7();
+        let owner_id = Uuid::now_v7();
+        let task = Task::new(workspace_id, owner_id, title.to_string());
         
-        // タイトルが正しく設定されていることを確認
+        // タイトル・ワークスペース・作成者が正しく設定されていることを確認
         assert_eq!(task.title, title);
+        assert_eq!(task.workspace_id, workspace_id);
+        assert_eq!(task.owner_id, owner_id);
         
         // 初期状態では完了していないことを確認
         assert!(!task.completed);
@@ -28,4 +33,32 @@ mod tests {
         let diff = now.signed_duration_since(task.created_at);
         assert!(diff.num_seconds().abs() < 1);
     }
+
+    #[test]
+    fn test_set_completed_records_and_clears_completed_at() {
+        let mut task = Task::new(Uuid::nil(), Uuid::nil(), "テストタイトル".to_string());
+        let completed_at = Utc::now();
+        let user_id = Uuid::from_u128(1);
+
+        // 未完了→完了で完了日時と完了にしたユーザーが記録される
+        task.set_completed(true, user_id, completed_at);
+        assert!(task.completed);
+        assert_eq!(task.completed_at, Some(completed_at));
+        assert_eq!(task.completed_by, Some(user_id));
+
+        // 完了済みのまま再度完了にしても完了日時と完了にしたユーザーは変わらない
+        task.set_completed(
+            true,
+            Uuid::from_u128(2),
+            completed_at + chrono::Duration::hours(1),
+        );
+        assert_eq!(task.completed_at, Some(completed_at));
+        assert_eq!(task.completed_by, Some(user_id));
+
+        // 完了→未完了で完了日時と完了にしたユーザーが消去される
+        task.set_completed(false, user_id, Utc::now());
+        assert!(!task.completed);
+        assert!(task.completed_at.is_none());
+        assert!(task.completed_by.is_none());
+    }
 }
@@ -0,0 +1,83 @@
+use crate::models::task::Task;
+use crate::models::task_transfer::{TaskRecord, TransferFormat};
+use uuid::Uuid;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // prologue + 各ページ + epilogue を連結して1つの文字列にする
+    fn encode_all(format: TransferFormat, pages: &[Vec<Task>]) -> String {
+        let mut buf = format.prologue();
+        for (i, page) in pages.iter().enumerate() {
+            buf.extend(format.encode_page(page, i == 0));
+        }
+        buf.extend(format.epilogue());
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn test_json_export_is_valid_array_across_pages() {
+        let pages = vec![
+            vec![
+                Task::new(Uuid::nil(), Uuid::nil(), "タスク1".to_string()),
+                Task::new(Uuid::nil(), Uuid::nil(), "タスク2".to_string()),
+            ],
+            vec![Task::new(Uuid::nil(), Uuid::nil(), "タスク3".to_string())],
+        ];
+
+        let output = encode_all(TransferFormat::Json, &pages);
+
+        let records: Vec<TaskRecord> = serde_json::from_str(&output).unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[2].title, "タスク3");
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let task = Task::new(
+            Uuid::nil(),
+            Uuid::nil(),
+            "カンマ, と \"引用符\" を含む".to_string(),
+        );
+        let output = encode_all(TransferFormat::Csv, &[vec![task.clone()]]);
+
+        let rows = TransferFormat::Csv.parse_records(&output).unwrap();
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0], Ok(TaskRecord::from(task)));
+    }
+
+    #[test]
+    fn test_csv_export_includes_completed_by() {
+        let user_id = Uuid::now_v7();
+        let mut task = Task::new(Uuid::nil(), user_id, "完了済み".to_string());
+        task.set_completed(true, user_id, chrono::Utc::now());
+        let output = encode_all(TransferFormat::Csv, &[vec![task.clone()]]);
+
+        let rows = TransferFormat::Csv.parse_records(&output).unwrap();
+
+        assert!(output.starts_with("id,title,completed,created_at,updated_at,due_at,project,tags,completed_at,completed_by\n"));
+        assert_eq!(rows[0].as_ref().unwrap().completed_by, Some(user_id));
+    }
+
+    #[test]
+    fn test_ndjson_reports_invalid_lines_per_row() {
+        let body = format!(
+            "{{\"id\":\"{}\",\"title\":\"OK\"}}\n\nnot json\n",
+            uuid::Uuid::now_v7()
+        );
+
+        let rows = TransferFormat::Ndjson.parse_records(&body).unwrap();
+
+        // 空行は無視され、壊れた行だけがエラーになる
+        assert_eq!(rows.len(), 2);
+        assert!(rows[0].is_ok());
+        assert!(rows[1].is_err());
+    }
+
+    #[test]
+    fn test_json_body_that_is_not_an_array_is_rejected() {
+        assert!(TransferFormat::Json.parse_records("{}").is_err());
+    }
+}
@@ -0,0 +1,51 @@
+use crate::models::user_account::{normalize_email, validate_password, UserAccount};
+use crate::secret::verify_password;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_email() {
+        // 前後の空白を除き、小文字にそろえる
+        assert_eq!(
+            normalize_email("  Alice@Example.COM ").unwrap(),
+            "alice@example.com"
+        );
+    }
+
+    #[test]
+    fn test_normalize_email_rejects_invalid_addresses() {
+        for email in [
+            "",
+            "alice",
+            "@example.com",
+            "alice@",
+            "alice@example",
+            "a b@example.com",
+            "a@b@example.com",
+        ] {
+            assert!(
+                normalize_email(email).is_err(),
+                "{} should be rejected",
+                email
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_password_length() {
+        assert!(validate_password("short").is_err());
+        assert!(validate_password("long enough").is_ok());
+        assert!(validate_password(&"a".repeat(129)).is_err());
+    }
+
+    #[test]
+    fn test_account_without_password_cannot_log_in_with_password() {
+        let user = UserAccount::without_password("alice@example.com".to_string());
+
+        for password in ["", "correct horse"] {
+            assert!(!verify_password(password, &user.password_hash));
+        }
+    }
+}
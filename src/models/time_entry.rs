@@ -0,0 +1,92 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+// 1回のレポートで集計できる最大日数
+const MAX_REPORT_DAYS: i64 = 366;
+
+#[derive(Deserialize, Serialize, Clone, Debug, FromRow, Eq, PartialEq, ToSchema)]
+pub struct TimeEntry {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub task_id: Uuid,
+    pub user_id: Option<Uuid>,
+    pub started_at: DateTime<Utc>,
+    // 計測中のタイマーは None
+    pub ended_at: Option<DateTime<Utc>>,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TimeEntry {
+    pub fn new(
+        workspace_id: Uuid,
+        task_id: Uuid,
+        user_id: Option<Uuid>,
+        started_at: DateTime<Utc>,
+        ended_at: Option<DateTime<Utc>>,
+        note: Option<String>,
+    ) -> Self {
+        Self {
+            id: Uuid::now_v7(),
+            workspace_id,
+            task_id,
+            user_id,
+            started_at,
+            ended_at,
+            note,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TimeReportQuery {
+    /// 集計期間の開始日時（省略時は30日前）
+    pub from: Option<DateTime<Utc>>,
+    /// 集計期間の終了日時（省略時は現在日時）
+    pub to: Option<DateTime<Utc>>,
+    /// 日付の区切りに使うタイムゾーン（例: Asia/Tokyo）
+    pub timezone: Option<String>,
+}
+
+impl TimeReportQuery {
+    // デフォルト値を補完し、集計範囲を検証する
+    pub fn resolve(self, now: DateTime<Utc>) -> Result<TimeReportRange, String> {
+        let to = self.to.unwrap_or(now);
+        let from = self.from.unwrap_or(to - Duration::days(30));
+        if from >= to {
+            return Err("`from` must be earlier than `to`".to_string());
+        }
+        if (to - from).num_days() > MAX_REPORT_DAYS {
+            return Err(format!(
+                "the range is too long: at most {} days are allowed",
+                MAX_REPORT_DAYS
+            ));
+        }
+        Ok(TimeReportRange {
+            from,
+            to,
+            timezone: self.timezone.unwrap_or_else(|| "UTC".to_string()),
+        })
+    }
+}
+
+// 検証済みの集計範囲
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TimeReportRange {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub timezone: String,
+}
+
+// 日付×プロジェクトごとの作業時間
+#[derive(Serialize, Clone, Debug, FromRow, Eq, PartialEq, ToSchema)]
+pub struct TimeReportRow {
+    pub day: NaiveDate,
+    pub project: Option<String>,
+    pub seconds: i64,
+}
@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+// ユーザー情報を取得する外部API
+pub const USERS_API_URL: &str = "https://jsonplaceholder.typicode.com/users";
+
 #[derive(Deserialize, Serialize)]
 pub struct User {
     id: i32,
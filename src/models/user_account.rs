@@ -0,0 +1,74 @@
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+// パスワードの最小・最大文字数（ハッシュ計算を悪用されないよう上限も設ける）
+const MIN_PASSWORD_CHARS: usize = 8;
+const MAX_PASSWORD_CHARS: usize = 128;
+// RFC 5321 で許されるメールアドレスの最大長
+const MAX_EMAIL_LEN: usize = 254;
+
+// ローカルに保存するユーザーアカウント（models::user::User は外部APIのレスポンス）
+#[derive(Clone, Debug, FromRow, Eq, PartialEq)]
+pub struct UserAccount {
+    pub id: Uuid,
+    pub email: String,
+    pub password_hash: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl UserAccount {
+    pub fn new(email: String, password_hash: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::now_v7(),
+            email,
+            password_hash,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    // OIDC で作成する、パスワードでログインできないアカウント（空のハッシュは照合に必ず失敗する）
+    pub fn without_password(email: String) -> Self {
+        Self::new(email, String::new())
+    }
+}
+
+// メールアドレスを前後の空白を除いた小文字に正規化し、形式を検証する
+pub fn normalize_email(email: &str) -> Result<String, String> {
+    let email = email.trim().to_lowercase();
+    let valid = match email.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !domain.contains('@')
+        }
+        None => false,
+    };
+    if !valid || email.len() > MAX_EMAIL_LEN || email.chars().any(char::is_whitespace) {
+        return Err("email is not a valid address".to_string());
+    }
+    Ok(email)
+}
+
+// パスワードの長さを検証する
+pub fn validate_password(password: &str) -> Result<(), String> {
+    let chars = password.chars().count();
+    if chars < MIN_PASSWORD_CHARS {
+        return Err(format!(
+            "password must be at least {} characters",
+            MIN_PASSWORD_CHARS
+        ));
+    }
+    if chars > MAX_PASSWORD_CHARS {
+        return Err(format!(
+            "password must be at most {} characters",
+            MAX_PASSWORD_CHARS
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+// ワークスペース内のロール
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Eq, PartialEq, sqlx::Type, ToSchema)]
+#[sqlx(type_name = "workspace_role", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum WorkspaceRole {
+    // すべての操作ができる
+    Admin,
+    // タスクの作成と、自分のタスクの編集ができる
+    Member,
+    // 閲覧のみ
+    Viewer,
+}
+
+#[derive(Serialize, Clone, Debug, FromRow, Eq, PartialEq, ToSchema)]
+pub struct Workspace {
+    pub id: Uuid,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Workspace {
+    pub fn new(name: String) -> Self {
+        Self {
+            id: Uuid::now_v7(),
+            name,
+            created_at: Utc::now(),
+        }
+    }
+
+    // ユーザー登録時に作成する個人ワークスペース（id はユーザーIDと同じ）
+    pub fn personal(user_id: Uuid) -> Self {
+        Self {
+            id: user_id,
+            name: "Personal".to_string(),
+            created_at: Utc::now(),
+        }
+    }
+}
+
+// ユーザーが所属するワークスペースとそのロール
+#[derive(Serialize, Clone, Debug, FromRow, Eq, PartialEq, ToSchema)]
+pub struct WorkspaceMembership {
+    pub workspace_id: Uuid,
+    pub name: String,
+    pub role: WorkspaceRole,
+}
+
+// ワークスペースのメンバー
+#[derive(Serialize, Clone, Debug, FromRow, Eq, PartialEq, ToSchema)]
+pub struct WorkspaceMember {
+    pub user_id: Uuid,
+    pub email: String,
+    pub role: WorkspaceRole,
+}
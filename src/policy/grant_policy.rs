@@ -0,0 +1,46 @@
+use crate::error::AppError;
+use crate::models::caller::Caller;
+use crate::models::grant::{Grant, GrantPermission};
+use crate::models::task::Task;
+use crate::models::workspace::WorkspaceRole;
+use crate::policy::session_policy::authorize_user_session;
+use crate::policy::task_policy::{authorize, authorize_scope, TaskAction};
+
+// 共有された権限でできる操作（view は閲覧、edit は閲覧と更新。削除や作業時間の記録はできない）
+pub fn is_allowed_shared(permission: GrantPermission, action: TaskAction) -> bool {
+    match action {
+        TaskAction::Read => true,
+        TaskAction::Update => permission == GrantPermission::Edit,
+        TaskAction::Create | TaskAction::Delete | TaskAction::TrackTime => false,
+    }
+}
+
+// 共有されたタスクへの操作ができなければ Forbidden を返す
+pub fn authorize_shared(
+    caller: &Caller,
+    action: TaskAction,
+    permission: GrantPermission,
+) -> Result<(), AppError> {
+    authorize_scope(caller, action)?;
+    if is_allowed_shared(permission, action) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden)
+    }
+}
+
+// タスクはそのタスクを更新できるユーザー、プロジェクトは admin だけが共有できる（task はタスクを共有する場合に渡す）
+pub fn authorize_share(caller: &Caller, task: Option<&Task>) -> Result<(), AppError> {
+    authorize_user_session(caller)?;
+    match task {
+        Some(task) => authorize(caller, TaskAction::Update, Some(task)),
+        None if caller.role == WorkspaceRole::Admin => Ok(()),
+        None => Err(AppError::Forbidden),
+    }
+}
+
+// 共有の一覧と取り消しは、admin はワークスペースのすべての共有、それ以外は自分が作成した共有だけ
+pub fn can_manage_grant(caller: &Caller, grant: &Grant) -> bool {
+    grant.workspace_id == caller.workspace_id
+        && (caller.role == WorkspaceRole::Admin || grant.created_by == caller.user_id)
+}
@@ -0,0 +1,6 @@
+pub mod grant_policy;
+pub mod session_policy;
+pub mod task_policy;
+#[cfg(test)]
+pub mod tests;
+pub mod workspace_policy;
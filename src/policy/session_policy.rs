@@ -0,0 +1,11 @@
+use crate::error::AppError;
+use crate::models::caller::Caller;
+
+// 認証情報の発行やワークスペースの管理は、ログインしたユーザーだけができる（APIキーではできない）
+pub fn authorize_user_session(caller: &Caller) -> Result<(), AppError> {
+    if caller.is_api_key() {
+        Err(AppError::Forbidden)
+    } else {
+        Ok(())
+    }
+}
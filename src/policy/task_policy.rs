@@ -0,0 +1,59 @@
+use crate::error::AppError;
+use crate::models::api_key::ApiKeyScope;
+use crate::models::caller::Caller;
+use crate::models::task::Task;
+use crate::models::workspace::WorkspaceRole;
+
+// タスクに対する操作
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TaskAction {
+    // 一覧・詳細・エクスポート・統計
+    Read,
+    Create,
+    Update,
+    Delete,
+    // タイマーや手動記録で作業時間を記録する
+    TrackTime,
+}
+
+// ロールと「呼び出し元が作成したタスクかどうか」から操作の可否を決める
+// viewer は閲覧のみ、member は作成と自分のタスクの編集、admin はすべて
+pub fn is_allowed(role: WorkspaceRole, action: TaskAction, is_owner: bool) -> bool {
+    match (role, action) {
+        (_, TaskAction::Read) => true,
+        (WorkspaceRole::Viewer, _) => false,
+        (WorkspaceRole::Admin, _) => true,
+        (WorkspaceRole::Member, TaskAction::Create | TaskAction::TrackTime) => true,
+        (WorkspaceRole::Member, TaskAction::Update | TaskAction::Delete) => is_owner,
+    }
+}
+
+// 操作に必要なAPIキーのスコープ
+pub fn required_scope(action: TaskAction) -> ApiKeyScope {
+    match action {
+        TaskAction::Read => ApiKeyScope::TasksRead,
+        TaskAction::Create | TaskAction::Update | TaskAction::Delete | TaskAction::TrackTime => {
+            ApiKeyScope::TasksWrite
+        }
+    }
+}
+
+// APIキーで認証した呼び出し元がスコープを持っていなければ Forbidden を返す（ロールは確認しない）
+pub fn authorize_scope(caller: &Caller, action: TaskAction) -> Result<(), AppError> {
+    if caller.has_scope(required_scope(action)) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden)
+    }
+}
+
+// 操作できなければ Forbidden を返す（task は既存のタスクへの操作の場合に渡す）
+pub fn authorize(caller: &Caller, action: TaskAction, task: Option<&Task>) -> Result<(), AppError> {
+    authorize_scope(caller, action)?;
+    let is_owner = task.is_none_or(|task| task.owner_id == caller.user_id);
+    if is_allowed(caller.role, action, is_owner) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden)
+    }
+}
@@ -0,0 +1,110 @@
+use crate::error::AppError;
+use crate::models::api_key::ApiKeyScope;
+use crate::models::caller::Caller;
+use crate::models::grant::{Grant, GrantPermission, ShareTarget};
+use crate::models::task::Task;
+use crate::models::workspace::WorkspaceRole;
+use crate::policy::grant_policy::{
+    authorize_share, authorize_shared, can_manage_grant, is_allowed_shared,
+};
+use crate::policy::task_policy::TaskAction;
+use uuid::Uuid;
+
+const WORKSPACE_ID: Uuid = Uuid::from_u128(10);
+const USER_ID: Uuid = Uuid::from_u128(1);
+const OTHER_USER_ID: Uuid = Uuid::from_u128(2);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shared_permissions() {
+        // view は閲覧のみ、edit は閲覧と更新
+        assert!(is_allowed_shared(GrantPermission::View, TaskAction::Read));
+        assert!(!is_allowed_shared(
+            GrantPermission::View,
+            TaskAction::Update
+        ));
+        assert!(is_allowed_shared(GrantPermission::Edit, TaskAction::Update));
+        // 削除や作業時間の記録は共有されてもできない
+        for action in [
+            TaskAction::Create,
+            TaskAction::Delete,
+            TaskAction::TrackTime,
+        ] {
+            assert!(!is_allowed_shared(GrantPermission::Edit, action));
+        }
+    }
+
+    #[test]
+    fn test_authorize_shared_checks_api_key_scope() {
+        let caller = Caller::new(USER_ID, WORKSPACE_ID, WorkspaceRole::Member)
+            .with_scopes(vec![ApiKeyScope::TasksRead]);
+
+        // 検証
+        assert!(authorize_shared(&caller, TaskAction::Read, GrantPermission::Edit).is_ok());
+        assert!(matches!(
+            authorize_shared(&caller, TaskAction::Update, GrantPermission::Edit),
+            Err(AppError::Forbidden)
+        ));
+    }
+
+    #[test]
+    fn test_authorize_share() {
+        let member = Caller::new(USER_ID, WORKSPACE_ID, WorkspaceRole::Member);
+        let admin = Caller::new(USER_ID, WORKSPACE_ID, WorkspaceRole::Admin);
+        let own_task = Task::new(WORKSPACE_ID, USER_ID, "自分のタスク".to_string());
+        let other_task = Task::new(WORKSPACE_ID, OTHER_USER_ID, "他人のタスク".to_string());
+
+        // タスクは更新できる人だけが共有できる
+        assert!(authorize_share(&member, Some(&own_task)).is_ok());
+        assert!(matches!(
+            authorize_share(&member, Some(&other_task)),
+            Err(AppError::Forbidden)
+        ));
+        // プロジェクトは admin だけが共有できる
+        assert!(authorize_share(&admin, None).is_ok());
+        assert!(matches!(
+            authorize_share(&member, None),
+            Err(AppError::Forbidden)
+        ));
+        // APIキーでは共有できない
+        let api_key_admin = admin.with_scopes(vec![ApiKeyScope::TasksWrite]);
+        assert!(matches!(
+            authorize_share(&api_key_admin, Some(&own_task)),
+            Err(AppError::Forbidden)
+        ));
+    }
+
+    #[test]
+    fn test_can_manage_grant() {
+        let grant = Grant::for_user(
+            WORKSPACE_ID,
+            ShareTarget::Project("release".to_string()),
+            OTHER_USER_ID,
+            GrantPermission::View,
+            None,
+            USER_ID,
+        );
+
+        // 作成者と admin は管理でき、他の member はできない
+        assert!(can_manage_grant(
+            &Caller::new(USER_ID, WORKSPACE_ID, WorkspaceRole::Viewer),
+            &grant
+        ));
+        assert!(can_manage_grant(
+            &Caller::new(OTHER_USER_ID, WORKSPACE_ID, WorkspaceRole::Admin),
+            &grant
+        ));
+        assert!(!can_manage_grant(
+            &Caller::new(OTHER_USER_ID, WORKSPACE_ID, WorkspaceRole::Member),
+            &grant
+        ));
+        // 他のワークスペースの共有は管理できない
+        assert!(!can_manage_grant(
+            &Caller::new(USER_ID, Uuid::from_u128(20), WorkspaceRole::Admin),
+            &grant
+        ));
+    }
+}
@@ -0,0 +1,4 @@
+pub mod grant_policy_tests;
+pub mod session_policy_tests;
+pub mod task_policy_tests;
+pub mod workspace_policy_tests;
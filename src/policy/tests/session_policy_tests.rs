@@ -0,0 +1,28 @@
+use crate::error::AppError;
+use crate::models::api_key::ApiKeyScope;
+use crate::models::caller::Caller;
+use crate::models::workspace::WorkspaceRole;
+use crate::policy::session_policy::authorize_user_session;
+use uuid::Uuid;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_logged_in_users_are_allowed() {
+        let caller = Caller::new(
+            Uuid::from_u128(1),
+            Uuid::from_u128(10),
+            WorkspaceRole::Admin,
+        );
+        let api_key_caller = caller.clone().with_scopes(vec![ApiKeyScope::TasksWrite]);
+
+        // 検証
+        assert!(authorize_user_session(&caller).is_ok());
+        assert!(matches!(
+            authorize_user_session(&api_key_caller),
+            Err(AppError::Forbidden)
+        ));
+    }
+}
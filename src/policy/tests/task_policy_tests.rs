@@ -0,0 +1,105 @@
+use crate::error::AppError;
+use crate::models::api_key::ApiKeyScope;
+use crate::models::caller::Caller;
+use crate::models::task::Task;
+use crate::models::workspace::WorkspaceRole;
+use crate::policy::task_policy::{authorize, is_allowed, TaskAction};
+use uuid::Uuid;
+
+const WORKSPACE_ID: Uuid = Uuid::from_u128(10);
+const USER_ID: Uuid = Uuid::from_u128(1);
+const OTHER_USER_ID: Uuid = Uuid::from_u128(2);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_ACTIONS: [TaskAction; 5] = [
+        TaskAction::Read,
+        TaskAction::Create,
+        TaskAction::Update,
+        TaskAction::Delete,
+        TaskAction::TrackTime,
+    ];
+
+    #[test]
+    fn test_admin_can_do_anything() {
+        for action in ALL_ACTIONS {
+            assert!(is_allowed(WorkspaceRole::Admin, action, true));
+            assert!(is_allowed(WorkspaceRole::Admin, action, false));
+        }
+    }
+
+    #[test]
+    fn test_member_can_edit_only_own_tasks() {
+        // 閲覧・作成・作業時間の記録はタスクの作成者に関係なくできる
+        for action in [TaskAction::Read, TaskAction::Create, TaskAction::TrackTime] {
+            assert!(is_allowed(WorkspaceRole::Member, action, false));
+        }
+        // 更新・削除は自分のタスクだけ
+        for action in [TaskAction::Update, TaskAction::Delete] {
+            assert!(is_allowed(WorkspaceRole::Member, action, true));
+            assert!(!is_allowed(WorkspaceRole::Member, action, false));
+        }
+    }
+
+    #[test]
+    fn test_viewer_can_only_read() {
+        for action in ALL_ACTIONS {
+            assert_eq!(
+                is_allowed(WorkspaceRole::Viewer, action, true),
+                action == TaskAction::Read,
+                "{:?}",
+                action
+            );
+        }
+    }
+
+    #[test]
+    fn test_authorize_checks_task_owner() {
+        let caller = Caller::new(USER_ID, WORKSPACE_ID, WorkspaceRole::Member);
+        let own_task = Task::new(WORKSPACE_ID, USER_ID, "自分のタスク".to_string());
+        let other_task = Task::new(WORKSPACE_ID, OTHER_USER_ID, "他人のタスク".to_string());
+
+        // 検証
+        assert!(authorize(&caller, TaskAction::Update, Some(&own_task)).is_ok());
+        assert!(matches!(
+            authorize(&caller, TaskAction::Update, Some(&other_task)),
+            Err(AppError::Forbidden)
+        ));
+        assert!(authorize(&caller, TaskAction::Create, None).is_ok());
+    }
+
+    #[test]
+    fn test_authorize_requires_api_key_scope() {
+        let read_only = Caller::new(USER_ID, WORKSPACE_ID, WorkspaceRole::Admin)
+            .with_scopes(vec![ApiKeyScope::TasksRead]);
+        let write_only = Caller::new(USER_ID, WORKSPACE_ID, WorkspaceRole::Admin)
+            .with_scopes(vec![ApiKeyScope::TasksWrite]);
+
+        // 検証
+        assert!(authorize(&read_only, TaskAction::Read, None).is_ok());
+        assert!(matches!(
+            authorize(&read_only, TaskAction::Create, None),
+            Err(AppError::Forbidden)
+        ));
+        assert!(authorize(&write_only, TaskAction::Create, None).is_ok());
+        assert!(matches!(
+            authorize(&write_only, TaskAction::Read, None),
+            Err(AppError::Forbidden)
+        ));
+    }
+
+    #[test]
+    fn test_api_key_scope_does_not_exceed_role() {
+        // スコープがあってもロールで許可されていない操作はできない
+        let viewer = Caller::new(USER_ID, WORKSPACE_ID, WorkspaceRole::Viewer)
+            .with_scopes(vec![ApiKeyScope::TasksRead, ApiKeyScope::TasksWrite]);
+
+        // 検証
+        assert!(matches!(
+            authorize(&viewer, TaskAction::Create, None),
+            Err(AppError::Forbidden)
+        ));
+    }
+}
@@ -0,0 +1,41 @@
+use crate::error::AppError;
+use crate::models::api_key::ApiKeyScope;
+use crate::models::caller::Caller;
+use crate::models::workspace::WorkspaceRole;
+use crate::policy::workspace_policy::authorize_manage_members;
+use uuid::Uuid;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_admin_can_manage_members() {
+        let caller = |role| Caller::new(Uuid::from_u128(1), Uuid::from_u128(10), role);
+
+        // 検証
+        assert!(authorize_manage_members(&caller(WorkspaceRole::Admin)).is_ok());
+        for role in [WorkspaceRole::Member, WorkspaceRole::Viewer] {
+            assert!(matches!(
+                authorize_manage_members(&caller(role)),
+                Err(AppError::Forbidden)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_api_key_cannot_manage_members() {
+        let caller = Caller::new(
+            Uuid::from_u128(1),
+            Uuid::from_u128(10),
+            WorkspaceRole::Admin,
+        )
+        .with_scopes(vec![ApiKeyScope::TasksRead, ApiKeyScope::TasksWrite]);
+
+        // 検証
+        assert!(matches!(
+            authorize_manage_members(&caller),
+            Err(AppError::Forbidden)
+        ));
+    }
+}
@@ -0,0 +1,14 @@
+use crate::error::AppError;
+use crate::models::caller::Caller;
+use crate::models::workspace::WorkspaceRole;
+use crate::policy::session_policy::authorize_user_session;
+
+// メンバーの追加・ロール変更・削除は admin だけができる
+pub fn authorize_manage_members(caller: &Caller) -> Result<(), AppError> {
+    authorize_user_session(caller)?;
+    if caller.role == WorkspaceRole::Admin {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden)
+    }
+}
@@ -0,0 +1,91 @@
+use crate::config::settings::{RateLimitSettings, RateLimitStoreKind};
+use crate::models::rate_limit::RateLimitQuota;
+use anyhow::Context;
+use axum::http::Method;
+
+// 認証に失敗してよい回数の上限（IPごと、1分あたり）
+const AUTH_FAILURE_PER_MINUTE: u32 = 20;
+
+// バケットの保存先
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RateLimitBackend {
+    Memory,
+    // Redis の接続URL
+    Redis(String),
+}
+
+// 特定のルートの上限（ほかのルートとは別のバケットで数える）
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RouteQuota {
+    pub method: Method,
+    // ルーターに登録したパス（例: "/tasks/:id"）
+    pub path: String,
+    pub quota: RateLimitQuota,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RateLimitConfig {
+    pub backend: RateLimitBackend,
+    // X-Forwarded-For の先頭をクライアントのIPとして使う（リバースプロキシの後ろで動かす場合だけ有効にする）
+    pub trust_forwarded_for: bool,
+    pub default_quota: RateLimitQuota,
+    pub routes: Vec<RouteQuota>,
+    // 認証が必要なルートで認証に失敗したリクエストの上限（IPごとに数える）
+    pub auth_failure_quota: RateLimitQuota,
+}
+
+// 設定の既定値（ルートごとの上限は RateLimitSettings の既定のルートの一覧）
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self::from_settings(&RateLimitSettings::default())
+            .expect("default rate limit settings are valid")
+    }
+}
+
+impl RateLimitConfig {
+    // 設定から読み込む（値は Config::validate で確認済み）
+    pub fn from_settings(settings: &RateLimitSettings) -> anyhow::Result<Self> {
+        let backend = match settings.store {
+            RateLimitStoreKind::Memory => RateLimitBackend::Memory,
+            RateLimitStoreKind::Redis => RateLimitBackend::Redis(
+                settings
+                    .redis_url
+                    .as_ref()
+                    .context("rate_limit.redis_url must be set for the redis store")?
+                    .expose()
+                    .to_string(),
+            ),
+        };
+        let routes = settings
+            .routes
+            .iter()
+            .map(|route| {
+                let method = Method::from_bytes(route.method.to_ascii_uppercase().as_bytes())
+                    .with_context(|| {
+                        format!("invalid method in rate_limit.routes: {:?}", route.method)
+                    })?;
+                Ok(RouteQuota {
+                    method,
+                    path: route.path.clone(),
+                    quota: RateLimitQuota::per_minute(route.per_minute),
+                })
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self {
+            backend,
+            trust_forwarded_for: settings.trust_forwarded_for,
+            default_quota: RateLimitQuota::per_minute(settings.per_minute),
+            routes,
+            auth_failure_quota: RateLimitQuota::per_minute(AUTH_FAILURE_PER_MINUTE),
+        })
+    }
+
+    // リクエストに適用する上限と、バケットを分けるための名前を返す
+    pub fn quota_for(&self, method: &Method, path: &str) -> (String, RateLimitQuota) {
+        self.routes
+            .iter()
+            .find(|route| route.method == *method && route.path == path)
+            .map(|route| (format!("{} {}", route.method, route.path), route.quota))
+            .unwrap_or_else(|| ("default".to_string(), self.default_quota))
+    }
+}
@@ -0,0 +1,148 @@
+use crate::auth::middleware::API_KEY_HEADER;
+use crate::error::AppError;
+use crate::models::api_key::parse_prefix;
+use crate::models::caller::Caller;
+use crate::models::rate_limit::RateLimitDecision;
+use crate::rate_limit::config::RateLimitConfig;
+use crate::repositories::rate_limit_store::RateLimitStore;
+use axum::{
+    extract::{ConnectInfo, MatchedPath, Request, State},
+    http::{HeaderMap, HeaderName, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tracing::warn;
+
+// IETF の RateLimit ヘッダー（draft-ietf-httpapi-ratelimit-headers）
+pub const RATE_LIMIT_LIMIT_HEADER: HeaderName = HeaderName::from_static("ratelimit-limit");
+pub const RATE_LIMIT_REMAINING_HEADER: HeaderName = HeaderName::from_static("ratelimit-remaining");
+pub const RATE_LIMIT_RESET_HEADER: HeaderName = HeaderName::from_static("ratelimit-reset");
+pub const RATE_LIMIT_POLICY_HEADER: HeaderName = HeaderName::from_static("ratelimit-policy");
+
+#[derive(Clone)]
+pub struct RateLimitState {
+    pub config: Arc<RateLimitConfig>,
+    // 設定に応じてメモリか Redis を使う
+    pub store: Arc<dyn RateLimitStore + Send + Sync>,
+}
+
+// クライアントごとにトークンバケットで流量を制限し、超えた場合は 429 を返す
+// 認証済みのルートでは require_auth の後に実行し、APIキーまたはユーザーごとに数える
+pub async fn enforce_rate_limit(
+    State(state): State<RateLimitState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str())
+        .unwrap_or_else(|| request.uri().path());
+    let (scope, quota) = state.config.quota_for(request.method(), path);
+    let key = format!(
+        "{}|{}",
+        scope,
+        client_key(&request, state.config.trust_forwarded_for)
+    );
+
+    // ストアが使えない場合はリクエストを止めない
+    let decision = match state.store.take(&key, quota, Utc::now()).await {
+        Ok(decision) => decision,
+        Err(e) => {
+            warn!("rate limit store is unavailable: {}", e);
+            return next.run(request).await;
+        }
+    };
+    let mut response = if decision.allowed {
+        next.run(request).await
+    } else {
+        AppError::TooManyRequests(decision.retry_after).into_response()
+    };
+    insert_rate_limit_headers(response.headers_mut(), &decision);
+    response
+}
+
+// 認証が必要なルートで認証に失敗したリクエストを IP ごとに数え、上限に達した IP には認証する前に 429 を返す
+// enforce_rate_limit は認証の後に数えるため、不正なトークンやAPIキーを試すリクエストはここで止める
+// require_auth より先に実行する
+pub async fn limit_failed_auth(
+    State(state): State<RateLimitState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let key = format!(
+        "auth_failure|{}",
+        ip_key(&request, state.config.trust_forwarded_for)
+    );
+    let quota = state.config.auth_failure_quota;
+
+    // ストアが使えない場合はリクエストを止めない
+    match state.store.peek(&key, quota, Utc::now()).await {
+        Ok(decision) if !decision.allowed => {
+            let mut response = AppError::TooManyRequests(decision.retry_after).into_response();
+            insert_rate_limit_headers(response.headers_mut(), &decision);
+            return response;
+        }
+        Ok(_) => {}
+        Err(e) => warn!("rate limit store is unavailable: {}", e),
+    }
+    let response = next.run(request).await;
+    if response.status() == StatusCode::UNAUTHORIZED {
+        if let Err(e) = state.store.take(&key, quota, Utc::now()).await {
+            warn!("rate limit store is unavailable: {}", e);
+        }
+    }
+    response
+}
+
+// バケットを分けるクライアントの識別子（APIキー、ユーザー、IPの順に使う）
+fn client_key(request: &Request, trust_forwarded_for: bool) -> String {
+    if let Some(caller) = request.extensions().get::<Caller>() {
+        let prefix = caller
+            .is_api_key()
+            .then(|| request.headers().get(API_KEY_HEADER))
+            .flatten()
+            .and_then(|value| value.to_str().ok())
+            .and_then(|key| parse_prefix(key.trim()));
+        return match prefix {
+            Some(prefix) => format!("api_key:{}", prefix),
+            None => format!("user:{}", caller.user_id),
+        };
+    }
+    ip_key(request, trust_forwarded_for)
+}
+
+// クライアントのIPの識別子
+fn ip_key(request: &Request, trust_forwarded_for: bool) -> String {
+    let forwarded_for = trust_forwarded_for
+        .then(|| request.headers().get("x-forwarded-for"))
+        .flatten()
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(',').next())
+        .map(|ip| ip.trim().to_string())
+        .filter(|ip| !ip.is_empty());
+    let ip = forwarded_for.or_else(|| {
+        request
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip().to_string())
+    });
+    format!("ip:{}", ip.as_deref().unwrap_or("unknown"))
+}
+
+fn insert_rate_limit_headers(headers: &mut HeaderMap, decision: &RateLimitDecision) {
+    let values = [
+        (RATE_LIMIT_LIMIT_HEADER, decision.quota.limit.to_string()),
+        (RATE_LIMIT_REMAINING_HEADER, decision.remaining.to_string()),
+        (RATE_LIMIT_RESET_HEADER, decision.reset_after.to_string()),
+        (RATE_LIMIT_POLICY_HEADER, decision.quota.policy()),
+    ];
+    for (name, value) in values {
+        if let Ok(value) = HeaderValue::from_str(&value) {
+            headers.insert(name, value);
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod config;
+pub mod middleware;
+#[cfg(test)]
+pub mod tests;
@@ -0,0 +1,73 @@
+use crate::config::settings::{RateLimitSettings, RateLimitStoreKind, RouteQuotaSettings};
+use crate::models::rate_limit::RateLimitQuota;
+use crate::rate_limit::config::{RateLimitBackend, RateLimitConfig, RouteQuota};
+use axum::http::Method;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quota_for_route() {
+        let config = RateLimitConfig::default();
+
+        // ルートごとの上限はメソッドとパスの両方が一致した場合だけ使う
+        assert_eq!(
+            config.quota_for(&Method::POST, "/tasks"),
+            ("POST /tasks".to_string(), RateLimitQuota::per_minute(30))
+        );
+        assert_eq!(
+            config.quota_for(&Method::GET, "/tasks"),
+            ("default".to_string(), config.default_quota)
+        );
+        assert_eq!(
+            config.quota_for(&Method::POST, "/tasks/:id/timer/start"),
+            ("default".to_string(), config.default_quota)
+        );
+    }
+
+    #[test]
+    fn test_from_settings() {
+        let settings = RateLimitSettings {
+            store: RateLimitStoreKind::Redis,
+            redis_url: Some("redis://redis:6379".parse().unwrap()),
+            per_minute: 60,
+            trust_forwarded_for: true,
+            routes: vec![RouteQuotaSettings {
+                method: "post".to_string(),
+                path: "/tasks/import".to_string(),
+                per_minute: 2,
+            }],
+        };
+
+        let config = RateLimitConfig::from_settings(&settings).unwrap();
+
+        assert_eq!(
+            config.backend,
+            RateLimitBackend::Redis("redis://redis:6379".to_string())
+        );
+        assert!(config.trust_forwarded_for);
+        assert_eq!(config.default_quota, RateLimitQuota::per_minute(60));
+        // メソッドは大文字・小文字を区別せずに読み込む
+        assert_eq!(
+            config.routes,
+            vec![RouteQuota {
+                method: Method::POST,
+                path: "/tasks/import".to_string(),
+                quota: RateLimitQuota::per_minute(2),
+            }]
+        );
+        assert_eq!(
+            config.quota_for(&Method::POST, "/tasks/import"),
+            (
+                "POST /tasks/import".to_string(),
+                RateLimitQuota::per_minute(2)
+            )
+        );
+        // 一覧にないルートは既定の上限を使う
+        assert_eq!(
+            config.quota_for(&Method::POST, "/tasks"),
+            ("default".to_string(), RateLimitQuota::per_minute(60))
+        );
+    }
+}
@@ -0,0 +1,281 @@
+use crate::error::AppError;
+use crate::infrastructure::rate_limit_store::InMemoryRateLimitStore;
+use crate::models::caller::Caller;
+use crate::models::rate_limit::RateLimitQuota;
+use crate::models::workspace::WorkspaceRole;
+use crate::rate_limit::config::{RateLimitBackend, RateLimitConfig, RouteQuota};
+use crate::rate_limit::middleware::{
+    enforce_rate_limit, limit_failed_auth, RateLimitState, RATE_LIMIT_LIMIT_HEADER,
+    RATE_LIMIT_POLICY_HEADER, RATE_LIMIT_REMAINING_HEADER, RATE_LIMIT_RESET_HEADER,
+};
+use crate::repositories::rate_limit_store::{MockRateLimitStore, RateLimitStore};
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{header, Method, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use std::sync::Arc;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+// 既定は1分あたり2回、POST /items は1回まで、認証の失敗はIPごとに2回まで
+fn config() -> RateLimitConfig {
+    RateLimitConfig {
+        backend: RateLimitBackend::Memory,
+        trust_forwarded_for: true,
+        default_quota: RateLimitQuota::per_minute(2),
+        routes: vec![RouteQuota {
+            method: Method::POST,
+            path: "/items".to_string(),
+            quota: RateLimitQuota::per_minute(1),
+        }],
+        auth_failure_quota: RateLimitQuota::per_minute(2),
+    }
+}
+
+// X-User-Id ヘッダーがあれば認証済みの呼び出し元として扱うテスト用の認証
+async fn fake_auth(mut request: Request, next: Next) -> Response {
+    let user_id = request
+        .headers()
+        .get("x-user-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| Uuid::parse_str(value).ok());
+    if let Some(user_id) = user_id {
+        request
+            .extensions_mut()
+            .insert(Caller::new(user_id, user_id, WorkspaceRole::Member));
+    }
+    next.run(request).await
+}
+
+fn router(store: Arc<dyn RateLimitStore + Send + Sync>) -> Router {
+    let state = RateLimitState {
+        config: Arc::new(config()),
+        store,
+    };
+    Router::new()
+        .route(
+            "/items",
+            get(|| async { "ok" }).post(|| async { "created" }),
+        )
+        .route_layer(middleware::from_fn_with_state(state, enforce_rate_limit))
+        .route_layer(middleware::from_fn(fake_auth))
+}
+
+// X-User-Id ヘッダーがなければ 401 を返すテスト用の認証
+async fn require_fake_auth(request: Request, next: Next) -> Response {
+    if request.headers().get("x-user-id").is_none() {
+        return AppError::Unauthorized.into_response();
+    }
+    fake_auth(request, next).await
+}
+
+// app.rs の認証が必要なルートと同じ順に実行する
+fn protected_router(store: Arc<dyn RateLimitStore + Send + Sync>) -> Router {
+    let state = RateLimitState {
+        config: Arc::new(config()),
+        store,
+    };
+    Router::new()
+        .route("/items", get(|| async { "ok" }))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            enforce_rate_limit,
+        ))
+        .route_layer(middleware::from_fn(require_fake_auth))
+        .route_layer(middleware::from_fn_with_state(state, limit_failed_auth))
+}
+
+fn request(method: Method, ip: &str, user_id: Option<Uuid>) -> Request<Body> {
+    let mut builder = Request::builder()
+        .method(method)
+        .uri("/items")
+        .header("x-forwarded-for", format!("{}, 10.0.0.1", ip));
+    if let Some(user_id) = user_id {
+        builder = builder.header("x-user-id", user_id.to_string());
+    }
+    builder.body(Body::empty()).unwrap()
+}
+
+async fn statuses(router: &Router, requests: Vec<Request<Body>>) -> Vec<StatusCode> {
+    let mut statuses = Vec::new();
+    for request in requests {
+        statuses.push(router.clone().oneshot(request).await.unwrap().status());
+    }
+    statuses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_returns_429_with_rate_limit_headers() {
+        let router = router(Arc::new(InMemoryRateLimitStore::new()));
+
+        // 1回目は残り1回
+        let response = router
+            .clone()
+            .oneshot(request(Method::GET, "192.0.2.1", None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[RATE_LIMIT_LIMIT_HEADER], "2");
+        assert_eq!(response.headers()[RATE_LIMIT_REMAINING_HEADER], "1");
+        assert_eq!(response.headers()[RATE_LIMIT_RESET_HEADER], "30");
+        assert_eq!(response.headers()[RATE_LIMIT_POLICY_HEADER], "2;w=60");
+
+        // 上限を超えたら 429 と Retry-After を返す
+        router
+            .clone()
+            .oneshot(request(Method::GET, "192.0.2.1", None))
+            .await
+            .unwrap();
+        let response = router
+            .oneshot(request(Method::GET, "192.0.2.1", None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "30");
+        assert_eq!(response.headers()[RATE_LIMIT_REMAINING_HEADER], "0");
+    }
+
+    #[tokio::test]
+    async fn test_counts_each_client_separately() {
+        let router = router(Arc::new(InMemoryRateLimitStore::new()));
+        let user_id = Uuid::now_v7();
+
+        // 同じIPでも、認証済みのリクエストはユーザーごとに数える
+        let result = statuses(
+            &router,
+            vec![
+                request(Method::GET, "192.0.2.1", None),
+                request(Method::GET, "192.0.2.1", None),
+                request(Method::GET, "192.0.2.1", None),
+                request(Method::GET, "192.0.2.2", None),
+                request(Method::GET, "192.0.2.1", Some(user_id)),
+                request(Method::GET, "192.0.2.3", Some(user_id)),
+                request(Method::GET, "192.0.2.4", Some(user_id)),
+            ],
+        )
+        .await;
+
+        assert_eq!(
+            result,
+            vec![
+                StatusCode::OK,
+                StatusCode::OK,
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::OK,
+                StatusCode::OK,
+                StatusCode::OK,
+                StatusCode::TOO_MANY_REQUESTS,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_route_quota_uses_separate_bucket() {
+        let router = router(Arc::new(InMemoryRateLimitStore::new()));
+
+        // POST /items は1回までで、GET の上限とは別に数える
+        let result = statuses(
+            &router,
+            vec![
+                request(Method::POST, "192.0.2.1", None),
+                request(Method::POST, "192.0.2.1", None),
+                request(Method::GET, "192.0.2.1", None),
+            ],
+        )
+        .await;
+
+        assert_eq!(
+            result,
+            vec![
+                StatusCode::OK,
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::OK
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_repeated_auth_failures_end_in_429() {
+        let router = protected_router(Arc::new(InMemoryRateLimitStore::new()));
+        let user_id = Uuid::now_v7();
+
+        // 認証に失敗し続けた IP からは、認証する前に 429 を返す（正しい認証情報でも同じ）
+        let result = statuses(
+            &router,
+            vec![
+                request(Method::GET, "192.0.2.1", None),
+                request(Method::GET, "192.0.2.1", None),
+                request(Method::GET, "192.0.2.1", None),
+                request(Method::GET, "192.0.2.1", Some(user_id)),
+                request(Method::GET, "192.0.2.2", Some(user_id)),
+            ],
+        )
+        .await;
+
+        assert_eq!(
+            result,
+            vec![
+                StatusCode::UNAUTHORIZED,
+                StatusCode::UNAUTHORIZED,
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::OK,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_successful_auth_is_not_charged_to_ip() {
+        let router = protected_router(Arc::new(InMemoryRateLimitStore::new()));
+
+        // 認証に成功したリクエストは、IP の失敗の回数に数えない（ユーザーごとの上限だけが適用される）
+        let result = statuses(
+            &router,
+            vec![
+                request(Method::GET, "192.0.2.1", Some(Uuid::now_v7())),
+                request(Method::GET, "192.0.2.1", Some(Uuid::now_v7())),
+                request(Method::GET, "192.0.2.1", Some(Uuid::now_v7())),
+                request(Method::GET, "192.0.2.1", None),
+            ],
+        )
+        .await;
+
+        assert_eq!(
+            result,
+            vec![
+                StatusCode::OK,
+                StatusCode::OK,
+                StatusCode::OK,
+                StatusCode::UNAUTHORIZED,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_allows_request_when_store_fails() {
+        // モックストアの作成（Redis に接続できない場合など）
+        let mut store = MockRateLimitStore::new();
+        store
+            .expect_take()
+            .times(1)
+            .returning(|_, _, _| Err(AppError::InternalError));
+
+        let response = router(Arc::new(store))
+            .oneshot(request(Method::GET, "192.0.2.1", None))
+            .await
+            .unwrap();
+
+        // 検証（ヘッダーは付けずにそのまま通す）
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().get(RATE_LIMIT_LIMIT_HEADER).is_none());
+    }
+}
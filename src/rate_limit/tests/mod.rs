@@ -0,0 +1,2 @@
+pub mod config_tests;
+pub mod middleware_tests;
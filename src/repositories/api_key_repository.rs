@@ -0,0 +1,37 @@
+use crate::models::api_key::ApiKey;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use mockall::mock;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait ApiKeyRepository {
+    async fn create(&self, api_key: ApiKey) -> Result<ApiKey, sqlx::Error>;
+    async fn find_by_prefix(&self, prefix: String) -> Result<Option<ApiKey>, sqlx::Error>;
+    // ユーザーが発行したキーの一覧（新しい順）
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<ApiKey>, sqlx::Error>;
+    // ユーザーが発行したキーでなければ RowNotFound
+    async fn delete(&self, user_id: Uuid, id: Uuid) -> Result<(), sqlx::Error>;
+    async fn touch(&self, id: Uuid, used_at: DateTime<Utc>) -> Result<(), sqlx::Error>;
+}
+
+// 非同期トレイトをモックするために mock! を使う
+mock! {
+    pub ApiKeyRepository {}
+
+    #[async_trait]
+    impl ApiKeyRepository for ApiKeyRepository {
+        async fn create(&self, api_key: ApiKey) -> Result<ApiKey, sqlx::Error>;
+        async fn find_by_prefix(&self, prefix: String) -> Result<Option<ApiKey>, sqlx::Error>;
+        async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<ApiKey>, sqlx::Error>;
+        async fn delete(&self, user_id: Uuid, id: Uuid) -> Result<(), sqlx::Error>;
+        async fn touch(&self, id: Uuid, used_at: DateTime<Utc>) -> Result<(), sqlx::Error>;
+    }
+}
+
+// MockApiKeyRepository に Clone を追加する
+impl Clone for MockApiKeyRepository {
+    fn clone(&self) -> Self {
+        MockApiKeyRepository::new()
+    }
+}
@@ -0,0 +1,48 @@
+use crate::models::calendar::CalendarTokenOwner;
+use async_trait::async_trait;
+use mockall::mock;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait CalendarTokenRepository {
+    async fn create(
+        &self,
+        user_id: Uuid,
+        workspace_id: Uuid,
+        token_hash: String,
+    ) -> Result<(), sqlx::Error>;
+    // ユーザーがワークスペースで発行したトークンを新しいトークンに置き換える
+    async fn replace(
+        &self,
+        user_id: Uuid,
+        workspace_id: Uuid,
+        token_hash: String,
+    ) -> Result<(), sqlx::Error>;
+    // ユーザーがワークスペースで発行したトークンをすべて削除し、削除した件数を返す
+    async fn delete_for_user(&self, user_id: Uuid, workspace_id: Uuid) -> Result<u64, sqlx::Error>;
+    // トークンを発行したユーザーとワークスペースを取得する
+    async fn find_owner(
+        &self,
+        token_hash: String,
+    ) -> Result<Option<CalendarTokenOwner>, sqlx::Error>;
+}
+
+// 非同期トレイトをモックするために mock! を使う
+mock! {
+    pub CalendarTokenRepository {}
+
+    #[async_trait]
+    impl CalendarTokenRepository for CalendarTokenRepository {
+        async fn create(&self, user_id: Uuid, workspace_id: Uuid, token_hash: String) -> Result<(), sqlx::Error>;
+        async fn replace(&self, user_id: Uuid, workspace_id: Uuid, token_hash: String) -> Result<(), sqlx::Error>;
+        async fn delete_for_user(&self, user_id: Uuid, workspace_id: Uuid) -> Result<u64, sqlx::Error>;
+        async fn find_owner(&self, token_hash: String) -> Result<Option<CalendarTokenOwner>, sqlx::Error>;
+    }
+}
+
+// MockCalendarTokenRepository に Clone を追加する
+impl Clone for MockCalendarTokenRepository {
+    fn clone(&self) -> Self {
+        MockCalendarTokenRepository::new()
+    }
+}
@@ -0,0 +1,49 @@
+use crate::models::grant::Grant;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use mockall::mock;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait GrantRepository {
+    async fn create(&self, grant: Grant) -> Result<Grant, sqlx::Error>;
+    async fn find_by_id(&self, workspace_id: Uuid, id: Uuid) -> Result<Option<Grant>, sqlx::Error>;
+    // ワークスペースのすべての共有（新しい順）
+    async fn find_by_workspace(&self, workspace_id: Uuid) -> Result<Vec<Grant>, sqlx::Error>;
+    // ユーザーに共有されていて、now の時点で期限切れでないもの
+    async fn find_active_by_grantee(
+        &self,
+        grantee_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<Grant>, sqlx::Error>;
+    // 共有リンクのトークンのハッシュで検索する（期限切れであれば None）
+    async fn find_active_by_token(
+        &self,
+        token_hash: String,
+        now: DateTime<Utc>,
+    ) -> Result<Option<Grant>, sqlx::Error>;
+    // 存在しなければ RowNotFound
+    async fn delete(&self, workspace_id: Uuid, id: Uuid) -> Result<(), sqlx::Error>;
+}
+
+// 非同期トレイトをモックするために mock! を使う
+mock! {
+    pub GrantRepository {}
+
+    #[async_trait]
+    impl GrantRepository for GrantRepository {
+        async fn create(&self, grant: Grant) -> Result<Grant, sqlx::Error>;
+        async fn find_by_id(&self, workspace_id: Uuid, id: Uuid) -> Result<Option<Grant>, sqlx::Error>;
+        async fn find_by_workspace(&self, workspace_id: Uuid) -> Result<Vec<Grant>, sqlx::Error>;
+        async fn find_active_by_grantee(&self, grantee_id: Uuid, now: DateTime<Utc>) -> Result<Vec<Grant>, sqlx::Error>;
+        async fn find_active_by_token(&self, token_hash: String, now: DateTime<Utc>) -> Result<Option<Grant>, sqlx::Error>;
+        async fn delete(&self, workspace_id: Uuid, id: Uuid) -> Result<(), sqlx::Error>;
+    }
+}
+
+// MockGrantRepository に Clone を追加する
+impl Clone for MockGrantRepository {
+    fn clone(&self) -> Self {
+        MockGrantRepository::new()
+    }
+}
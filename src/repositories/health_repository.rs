@@ -0,0 +1,30 @@
+use crate::error::AppError;
+use async_trait::async_trait;
+use mockall::mock;
+
+// /readyz で確認するデータベースの状態
+#[async_trait]
+pub trait HealthRepository {
+    // 接続してクエリを実行できるか（SELECT 1）
+    async fn ping(&self) -> Result<(), AppError>;
+    // バイナリに含まれるマイグレーションのうち、適用されていないもののバージョン
+    async fn pending_migrations(&self) -> Result<Vec<i64>, AppError>;
+}
+
+// 非同期トレイトをモックするために mock! を使う
+mock! {
+    pub HealthRepository {}
+
+    #[async_trait]
+    impl HealthRepository for HealthRepository {
+        async fn ping(&self) -> Result<(), AppError>;
+        async fn pending_migrations(&self) -> Result<Vec<i64>, AppError>;
+    }
+}
+
+// MockHealthRepository に Clone を追加する
+impl Clone for MockHealthRepository {
+    fn clone(&self) -> Self {
+        MockHealthRepository::new()
+    }
+}
@@ -0,0 +1,71 @@
+use crate::models::idempotency::{IdempotencyRecord, StoredResponse};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use mockall::mock;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait IdempotencyRepository {
+    // 処理中として保存する（有効なキーが既にあれば保存せず false を返す）
+    async fn reserve(&self, record: IdempotencyRecord) -> Result<bool, sqlx::Error>;
+    // abandoned_before より前に確保されたまま処理中のキーを record の試行として確保し直す
+    // 同時に引き継ごうとしても、確保し直せるのはどちらか一方だけ（できなければ false）
+    async fn take_over(
+        &self,
+        record: IdempotencyRecord,
+        abandoned_before: DateTime<Utc>,
+    ) -> Result<bool, sqlx::Error>;
+    async fn find(
+        &self,
+        user_id: Uuid,
+        key: String,
+    ) -> Result<Option<IdempotencyRecord>, sqlx::Error>;
+    // 処理が終わったレスポンスを保存する（attempt_id の試行がキーを確保している場合だけ）
+    async fn complete(
+        &self,
+        user_id: Uuid,
+        key: String,
+        attempt_id: Uuid,
+        response: StoredResponse,
+    ) -> Result<(), sqlx::Error>;
+    // 処理中のキーを削除し、同じキーで再試行できるようにする（attempt_id の試行がキーを確保している場合だけ）
+    async fn release(
+        &self,
+        user_id: Uuid,
+        key: String,
+        attempt_id: Uuid,
+    ) -> Result<(), sqlx::Error>;
+}
+
+// 非同期トレイトをモックするために mock! を使う
+mock! {
+    pub IdempotencyRepository {}
+
+    #[async_trait]
+    impl IdempotencyRepository for IdempotencyRepository {
+        async fn reserve(&self, record: IdempotencyRecord) -> Result<bool, sqlx::Error>;
+        async fn take_over(
+            &self,
+            record: IdempotencyRecord,
+            abandoned_before: DateTime<Utc>,
+        ) -> Result<bool, sqlx::Error>;
+        async fn find(&self, user_id: Uuid, key: String)
+            -> Result<Option<IdempotencyRecord>, sqlx::Error>;
+        async fn complete(
+            &self,
+            user_id: Uuid,
+            key: String,
+            attempt_id: Uuid,
+            response: StoredResponse,
+        ) -> Result<(), sqlx::Error>;
+        async fn release(&self, user_id: Uuid, key: String, attempt_id: Uuid)
+            -> Result<(), sqlx::Error>;
+    }
+}
+
+// MockIdempotencyRepository に Clone を追加する
+impl Clone for MockIdempotencyRepository {
+    fn clone(&self) -> Self {
+        MockIdempotencyRepository::new()
+    }
+}
@@ -0,0 +1,40 @@
+use crate::error::AppError;
+use crate::models::oidc::{AuthorizationRequest, IdTokenClaims};
+use async_trait::async_trait;
+use mockall::mock;
+
+// OIDCプロバイダーとの通信（認可コードフロー）
+#[async_trait]
+pub trait OidcProvider {
+    // ユーザーをリダイレクトする認可エンドポイントのURL
+    async fn authorization_url(&self, request: AuthorizationRequest) -> Result<String, AppError>;
+    // 認可コードをトークンに交換し、検証済みの ID トークンのクレームを返す
+    // （コードや ID トークンが無効な場合は Unauthorized）
+    async fn exchange_code(
+        &self,
+        code: String,
+        code_verifier: String,
+    ) -> Result<IdTokenClaims, AppError>;
+}
+
+// 非同期トレイトをモックするために mock! を使う
+mock! {
+    pub OidcProvider {}
+
+    #[async_trait]
+    impl OidcProvider for OidcProvider {
+        async fn authorization_url(&self, request: AuthorizationRequest) -> Result<String, AppError>;
+        async fn exchange_code(
+            &self,
+            code: String,
+            code_verifier: String,
+        ) -> Result<IdTokenClaims, AppError>;
+    }
+}
+
+// MockOidcProvider に Clone を追加する
+impl Clone for MockOidcProvider {
+    fn clone(&self) -> Self {
+        MockOidcProvider::new()
+    }
+}
@@ -0,0 +1,46 @@
+use crate::models::oidc::{OidcIdentity, OidcLoginState};
+use async_trait::async_trait;
+use mockall::mock;
+
+#[async_trait]
+pub trait OidcRepository {
+    async fn save_login_state(&self, state: OidcLoginState) -> Result<(), sqlx::Error>;
+    // ログイン状態を削除して返す（同じ state は一度しか使えない）
+    async fn take_login_state(
+        &self,
+        state_hash: String,
+    ) -> Result<Option<OidcLoginState>, sqlx::Error>;
+    async fn find_identity(
+        &self,
+        issuer: String,
+        subject: String,
+    ) -> Result<Option<OidcIdentity>, sqlx::Error>;
+    async fn create_identity(&self, identity: OidcIdentity) -> Result<(), sqlx::Error>;
+}
+
+// 非同期トレイトをモックするために mock! を使う
+mock! {
+    pub OidcRepository {}
+
+    #[async_trait]
+    impl OidcRepository for OidcRepository {
+        async fn save_login_state(&self, state: OidcLoginState) -> Result<(), sqlx::Error>;
+        async fn take_login_state(
+            &self,
+            state_hash: String,
+        ) -> Result<Option<OidcLoginState>, sqlx::Error>;
+        async fn find_identity(
+            &self,
+            issuer: String,
+            subject: String,
+        ) -> Result<Option<OidcIdentity>, sqlx::Error>;
+        async fn create_identity(&self, identity: OidcIdentity) -> Result<(), sqlx::Error>;
+    }
+}
+
+// MockOidcRepository に Clone を追加する
+impl Clone for MockOidcRepository {
+    fn clone(&self) -> Self {
+        MockOidcRepository::new()
+    }
+}
@@ -0,0 +1,46 @@
+use crate::error::AppError;
+use crate::models::rate_limit::{RateLimitDecision, RateLimitQuota};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use mockall::mock;
+
+// クライアントごとのトークンバケットの保存先
+// （複数のレプリカで上限を共有する場合は Redis を使う）
+#[async_trait]
+pub trait RateLimitStore {
+    // key のバケットからトークンを1つ取り出す（バケットがなければ満杯の状態から始める）
+    async fn take(
+        &self,
+        key: &str,
+        quota: RateLimitQuota,
+        now: DateTime<Utc>,
+    ) -> Result<RateLimitDecision, AppError>;
+    // key のバケットからトークンを取り出せるかどうかを確かめる（トークンは減らさない）
+    async fn peek(
+        &self,
+        key: &str,
+        quota: RateLimitQuota,
+        now: DateTime<Utc>,
+    ) -> Result<RateLimitDecision, AppError>;
+}
+
+// 非同期トレイトをモックするために mock! を使う
+mock! {
+    pub RateLimitStore {}
+
+    #[async_trait]
+    impl RateLimitStore for RateLimitStore {
+        async fn take(
+            &self,
+            key: &str,
+            quota: RateLimitQuota,
+            now: DateTime<Utc>,
+        ) -> Result<RateLimitDecision, AppError>;
+        async fn peek(
+            &self,
+            key: &str,
+            quota: RateLimitQuota,
+            now: DateTime<Utc>,
+        ) -> Result<RateLimitDecision, AppError>;
+    }
+}
@@ -0,0 +1,32 @@
+use crate::models::auth_token::RefreshToken;
+use async_trait::async_trait;
+use mockall::mock;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait RefreshTokenRepository {
+    async fn create(&self, token: RefreshToken) -> Result<(), sqlx::Error>;
+    // トークンを削除して返す（同じトークンは一度しか使えない）
+    async fn consume(&self, token_hash: String) -> Result<Option<RefreshToken>, sqlx::Error>;
+    // ユーザーのすべてのリフレッシュトークンを削除する（すべての端末からのログアウト）
+    async fn delete_by_user(&self, user_id: Uuid) -> Result<u64, sqlx::Error>;
+}
+
+// 非同期トレイトをモックするために mock! を使う
+mock! {
+    pub RefreshTokenRepository {}
+
+    #[async_trait]
+    impl RefreshTokenRepository for RefreshTokenRepository {
+        async fn create(&self, token: RefreshToken) -> Result<(), sqlx::Error>;
+        async fn consume(&self, token_hash: String) -> Result<Option<RefreshToken>, sqlx::Error>;
+        async fn delete_by_user(&self, user_id: Uuid) -> Result<u64, sqlx::Error>;
+    }
+}
+
+// MockRefreshTokenRepository に Clone を追加する
+impl Clone for MockRefreshTokenRepository {
+    fn clone(&self) -> Self {
+        MockRefreshTokenRepository::new()
+    }
+}
@@ -0,0 +1,37 @@
+use crate::models::session::Session;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use mockall::mock;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait SessionRepository {
+    async fn create(&self, session: Session) -> Result<(), sqlx::Error>;
+    async fn find_by_token_hash(&self, token_hash: String) -> Result<Option<Session>, sqlx::Error>;
+    // 最終アクセス日時を更新する（アイドルタイムアウトを延長する）
+    async fn touch(&self, id: Uuid, seen_at: DateTime<Utc>) -> Result<(), sqlx::Error>;
+    async fn delete_by_token_hash(&self, token_hash: String) -> Result<(), sqlx::Error>;
+    // ユーザーのすべてのセッションを削除し、削除した件数を返す
+    async fn delete_by_user(&self, user_id: Uuid) -> Result<u64, sqlx::Error>;
+}
+
+// 非同期トレイトをモックするために mock! を使う
+mock! {
+    pub SessionRepository {}
+
+    #[async_trait]
+    impl SessionRepository for SessionRepository {
+        async fn create(&self, session: Session) -> Result<(), sqlx::Error>;
+        async fn find_by_token_hash(&self, token_hash: String) -> Result<Option<Session>, sqlx::Error>;
+        async fn touch(&self, id: Uuid, seen_at: DateTime<Utc>) -> Result<(), sqlx::Error>;
+        async fn delete_by_token_hash(&self, token_hash: String) -> Result<(), sqlx::Error>;
+        async fn delete_by_user(&self, user_id: Uuid) -> Result<u64, sqlx::Error>;
+    }
+}
+
+// MockSessionRepository に Clone を追加する
+impl Clone for MockSessionRepository {
+    fn clone(&self) -> Self {
+        MockSessionRepository::new()
+    }
+}
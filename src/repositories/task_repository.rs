@@ -1,15 +1,39 @@
-use crate::models::task::Task;
+use crate::models::stats::{StatsRange, TaskStats};
+use crate::models::task::{Task, TaskFilter};
 use async_trait::async_trait;
 use mockall::mock;
 use uuid::Uuid;
 
 #[async_trait]
+// すべてのメソッドは workspace_id のタスクだけを対象にする（他のワークスペースのタスクは存在しないものとして扱う）
 pub trait TaskRepository {
-    async fn find_all(&self) -> Result<Vec<Task>, sqlx::Error>;
-    async fn find_by_id(&self, id: Uuid) -> Result<Option<Task>, sqlx::Error>;
+    async fn find_all(&self, workspace_id: Uuid) -> Result<Vec<Task>, sqlx::Error>;
+    async fn find_by_id(&self, workspace_id: Uuid, id: Uuid) -> Result<Option<Task>, sqlx::Error>;
+    async fn find_filtered(
+        &self,
+        workspace_id: Uuid,
+        filter: TaskFilter,
+    ) -> Result<Vec<Task>, sqlx::Error>;
+    // idの昇順でafterより後ろのタスクを最大limit件取得する（キーセットページング）
+    async fn find_page(
+        &self,
+        workspace_id: Uuid,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<Task>, sqlx::Error>;
     async fn create(&self, task: Task) -> Result<Task, sqlx::Error>;
+    // create と同じ INSERT を試してロールバックする（作成できない id を書き込まずに確かめる）
+    async fn try_create(&self, task: Task) -> Result<(), sqlx::Error>;
+    // task.workspace_id のタスクでなければ RowNotFound
     async fn update(&self, task: Task) -> Result<Task, sqlx::Error>;
-    async fn delete(&self, id: Uuid) -> Result<(), sqlx::Error>;
+    // 削除対象がなければ RowNotFound
+    async fn delete(&self, workspace_id: Uuid, id: Uuid) -> Result<(), sqlx::Error>;
+    // 状態別の件数・完了率の推移・平均完了時間をSQLで集計する
+    async fn aggregate_stats(
+        &self,
+        workspace_id: Uuid,
+        range: StatsRange,
+    ) -> Result<TaskStats, sqlx::Error>;
 }
 
 // 非同期トレイトをモックするために mock! を使う
@@ -18,11 +42,15 @@ mock! {
 
     #[async_trait]
     impl TaskRepository for TaskRepository {
-        async fn find_all(&self) -> Result<Vec<Task>, sqlx::Error>;
-        async fn find_by_id(&self, id: Uuid) -> Result<Option<Task>, sqlx::Error>;
+        async fn find_all(&self, workspace_id: Uuid) -> Result<Vec<Task>, sqlx::Error>;
+        async fn find_by_id(&self, workspace_id: Uuid, id: Uuid) -> Result<Option<Task>, sqlx::Error>;
+        async fn find_filtered(&self, workspace_id: Uuid, filter: TaskFilter) -> Result<Vec<Task>, sqlx::Error>;
+        async fn find_page(&self, workspace_id: Uuid, after: Option<Uuid>, limit: i64) -> Result<Vec<Task>, sqlx::Error>;
         async fn create(&self, task: Task) -> Result<Task, sqlx::Error>;
+        async fn try_create(&self, task: Task) -> Result<(), sqlx::Error>;
         async fn update(&self, task: Task) -> Result<Task, sqlx::Error>;
-        async fn delete(&self, id: Uuid) -> Result<(), sqlx::Error>;
+        async fn delete(&self, workspace_id: Uuid, id: Uuid) -> Result<(), sqlx::Error>;
+        async fn aggregate_stats(&self, workspace_id: Uuid, range: StatsRange) -> Result<TaskStats, sqlx::Error>;
     }
 }
 
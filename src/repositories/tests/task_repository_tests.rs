@@ -1,4 +1,5 @@
 use crate::repositories::task_repository::{MockTaskRepository, TaskRepository};
+use uuid::Uuid;
 
 #[cfg(test)]
 mod tests {
@@ -12,10 +13,10 @@ mod tests {
         mock_repo
             .expect_find_all()
             .times(1)
-            .returning(|| Ok(vec![]));
+            .returning(|_| Ok(vec![]));
 
         // テスト実行
-        let result = mock_repo.find_all().await.unwrap();
+        let result = mock_repo.find_all(Uuid::nil()).await.unwrap();
 
         // 空のベクターが返されることを確認
         assert_eq!(result.len(), 0);
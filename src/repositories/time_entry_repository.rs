@@ -0,0 +1,57 @@
+use crate::models::time_entry::{TimeEntry, TimeReportRange, TimeReportRow};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use mockall::mock;
+use uuid::Uuid;
+
+#[async_trait]
+// すべてのメソッドは workspace_id の作業時間だけを対象にする
+pub trait TimeEntryRepository {
+    // ユーザーの計測中のタイマーを取得する
+    async fn find_running(
+        &self,
+        workspace_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<TimeEntry>, sqlx::Error>;
+    async fn find_by_task(
+        &self,
+        workspace_id: Uuid,
+        task_id: Uuid,
+    ) -> Result<Vec<TimeEntry>, sqlx::Error>;
+    // entry.workspace_id のワークスペースに作成する
+    async fn create(&self, entry: TimeEntry) -> Result<TimeEntry, sqlx::Error>;
+    async fn stop(
+        &self,
+        workspace_id: Uuid,
+        id: Uuid,
+        ended_at: DateTime<Utc>,
+    ) -> Result<TimeEntry, sqlx::Error>;
+    // ユーザーの作業時間を日付×プロジェクトごとに集計する
+    async fn report(
+        &self,
+        workspace_id: Uuid,
+        user_id: Uuid,
+        range: TimeReportRange,
+    ) -> Result<Vec<TimeReportRow>, sqlx::Error>;
+}
+
+// 非同期トレイトをモックするために mock! を使う
+mock! {
+    pub TimeEntryRepository {}
+
+    #[async_trait]
+    impl TimeEntryRepository for TimeEntryRepository {
+        async fn find_running(&self, workspace_id: Uuid, user_id: Uuid) -> Result<Option<TimeEntry>, sqlx::Error>;
+        async fn find_by_task(&self, workspace_id: Uuid, task_id: Uuid) -> Result<Vec<TimeEntry>, sqlx::Error>;
+        async fn create(&self, entry: TimeEntry) -> Result<TimeEntry, sqlx::Error>;
+        async fn stop(&self, workspace_id: Uuid, id: Uuid, ended_at: DateTime<Utc>) -> Result<TimeEntry, sqlx::Error>;
+        async fn report(&self, workspace_id: Uuid, user_id: Uuid, range: TimeReportRange) -> Result<Vec<TimeReportRow>, sqlx::Error>;
+    }
+}
+
+// MockTimeEntryRepository に Clone を追加する
+impl Clone for MockTimeEntryRepository {
+    fn clone(&self) -> Self {
+        MockTimeEntryRepository::new()
+    }
+}
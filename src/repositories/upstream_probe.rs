@@ -0,0 +1,27 @@
+use crate::error::AppError;
+use async_trait::async_trait;
+use mockall::mock;
+
+// /readyz で確認する外部API
+#[async_trait]
+pub trait UpstreamProbe {
+    // リクエストに成功のステータスを返すか
+    async fn check(&self) -> Result<(), AppError>;
+}
+
+// 非同期トレイトをモックするために mock! を使う
+mock! {
+    pub UpstreamProbe {}
+
+    #[async_trait]
+    impl UpstreamProbe for UpstreamProbe {
+        async fn check(&self) -> Result<(), AppError>;
+    }
+}
+
+// MockUpstreamProbe に Clone を追加する
+impl Clone for MockUpstreamProbe {
+    fn clone(&self) -> Self {
+        MockUpstreamProbe::new()
+    }
+}
@@ -0,0 +1,32 @@
+use crate::models::user_account::UserAccount;
+use async_trait::async_trait;
+use mockall::mock;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait UserRepository {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<UserAccount>, sqlx::Error>;
+    // email は正規化済み（小文字）のものを渡す
+    async fn find_by_email(&self, email: String) -> Result<Option<UserAccount>, sqlx::Error>;
+    // 個人ワークスペース（id はユーザーIDと同じ）も作成し、その管理者にする
+    async fn create(&self, user: UserAccount) -> Result<UserAccount, sqlx::Error>;
+}
+
+// 非同期トレイトをモックするために mock! を使う
+mock! {
+    pub UserRepository {}
+
+    #[async_trait]
+    impl UserRepository for UserRepository {
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<UserAccount>, sqlx::Error>;
+        async fn find_by_email(&self, email: String) -> Result<Option<UserAccount>, sqlx::Error>;
+        async fn create(&self, user: UserAccount) -> Result<UserAccount, sqlx::Error>;
+    }
+}
+
+// MockUserRepository に Clone を追加する
+impl Clone for MockUserRepository {
+    fn clone(&self) -> Self {
+        MockUserRepository::new()
+    }
+}
@@ -0,0 +1,53 @@
+use crate::models::workspace::{Workspace, WorkspaceMember, WorkspaceMembership, WorkspaceRole};
+use async_trait::async_trait;
+use mockall::mock;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait WorkspaceRepository {
+    // ワークスペースを作成し、admin_id のユーザーを管理者として追加する
+    async fn create(&self, workspace: Workspace, admin_id: Uuid) -> Result<Workspace, sqlx::Error>;
+    // メンバーでなければ None
+    async fn find_role(
+        &self,
+        workspace_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<WorkspaceRole>, sqlx::Error>;
+    // ユーザーが所属するワークスペースの一覧
+    async fn find_memberships(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<WorkspaceMembership>, sqlx::Error>;
+    async fn find_members(&self, workspace_id: Uuid) -> Result<Vec<WorkspaceMember>, sqlx::Error>;
+    // メンバーでなければ追加し、メンバーであればロールを変更する
+    async fn upsert_member(
+        &self,
+        workspace_id: Uuid,
+        user_id: Uuid,
+        role: WorkspaceRole,
+    ) -> Result<(), sqlx::Error>;
+    // メンバーでなければ RowNotFound（ユーザーがこのワークスペースで発行したカレンダーのトークンも削除する）
+    async fn remove_member(&self, workspace_id: Uuid, user_id: Uuid) -> Result<(), sqlx::Error>;
+}
+
+// 非同期トレイトをモックするために mock! を使う
+mock! {
+    pub WorkspaceRepository {}
+
+    #[async_trait]
+    impl WorkspaceRepository for WorkspaceRepository {
+        async fn create(&self, workspace: Workspace, admin_id: Uuid) -> Result<Workspace, sqlx::Error>;
+        async fn find_role(&self, workspace_id: Uuid, user_id: Uuid) -> Result<Option<WorkspaceRole>, sqlx::Error>;
+        async fn find_memberships(&self, user_id: Uuid) -> Result<Vec<WorkspaceMembership>, sqlx::Error>;
+        async fn find_members(&self, workspace_id: Uuid) -> Result<Vec<WorkspaceMember>, sqlx::Error>;
+        async fn upsert_member(&self, workspace_id: Uuid, user_id: Uuid, role: WorkspaceRole) -> Result<(), sqlx::Error>;
+        async fn remove_member(&self, workspace_id: Uuid, user_id: Uuid) -> Result<(), sqlx::Error>;
+    }
+}
+
+// MockWorkspaceRepository に Clone を追加する
+impl Clone for MockWorkspaceRepository {
+    fn clone(&self) -> Self {
+        MockWorkspaceRepository::new()
+    }
+}
@@ -0,0 +1,43 @@
+use crate::models::request_id::RequestId;
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+
+// リクエストを識別するヘッダー（受け取ったものを使い、なければ発行する）
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+tokio::task_local! {
+    static REQUEST_ID: RequestId;
+}
+
+// 処理中のリクエストのID（リクエストの処理の外では None）
+// エラーレスポンスや外部APIの呼び出しのように、ハンドラーの引数から渡しにくい所で使う
+pub fn current_request_id() -> Option<RequestId> {
+    REQUEST_ID.try_with(RequestId::clone).ok()
+}
+
+// X-Request-Id を決めてリクエストの処理中に current_request_id で取り出せるようにし、レスポンスにも付ける
+// 不正な値が送られてきた場合は新しく発行したIDに置き換える
+pub async fn assign_request_id(mut request: Request, next: Next) -> Response {
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(RequestId::parse)
+        .unwrap_or_else(RequestId::generate);
+    // 検証済みなので HeaderValue に変換できる
+    let value = HeaderValue::from_str(request_id.as_str()).expect("valid request id");
+    request
+        .headers_mut()
+        .insert(HeaderName::from_static(REQUEST_ID_HEADER), value.clone());
+
+    let mut response = REQUEST_ID.scope(request_id, next.run(request)).await;
+
+    response
+        .headers_mut()
+        .insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+    response
+}
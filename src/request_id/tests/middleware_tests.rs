@@ -0,0 +1,112 @@
+use crate::error::AppError;
+use crate::request_id::middleware::{assign_request_id, current_request_id, REQUEST_ID_HEADER};
+use axum::{
+    body::Body,
+    http::{HeaderMap, Request, StatusCode},
+    middleware,
+    response::Response,
+    routing::get,
+    Router,
+};
+use tower::ServiceExt;
+use uuid::Uuid;
+
+// ハンドラーが受け取ったヘッダーと current_request_id を返す
+async fn echo(headers: HeaderMap) -> String {
+    format!(
+        "{} {}",
+        headers[REQUEST_ID_HEADER].to_str().unwrap(),
+        current_request_id().unwrap()
+    )
+}
+
+fn router() -> Router {
+    Router::new()
+        .route("/echo", get(echo))
+        .route(
+            "/missing",
+            get(|| async { Err::<(), _>(AppError::ResourceNotFound("Task")) }),
+        )
+        .layer(middleware::from_fn(assign_request_id))
+}
+
+async fn send(uri: &str, request_id: Option<&str>) -> Response {
+    let mut request = Request::get(uri);
+    if let Some(request_id) = request_id {
+        request = request.header(REQUEST_ID_HEADER, request_id);
+    }
+    router()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+async fn body_string(response: Response) -> String {
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+fn response_request_id(response: &Response) -> String {
+    response.headers()[REQUEST_ID_HEADER]
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_generates_request_id() {
+        let response = send("/echo", None).await;
+
+        let request_id = response_request_id(&response);
+        assert!(Uuid::parse_str(&request_id).is_ok());
+        // ハンドラーにも同じIDが見える
+        assert_eq!(
+            body_string(response).await,
+            format!("{} {}", request_id, request_id)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_keeps_incoming_request_id() {
+        let response = send("/echo", Some("req-123")).await;
+
+        assert_eq!(response_request_id(&response), "req-123");
+        assert_eq!(body_string(response).await, "req-123 req-123");
+    }
+
+    #[tokio::test]
+    async fn test_replaces_invalid_request_id() {
+        let response = send("/echo", Some(&"r".repeat(200))).await;
+
+        let request_id = response_request_id(&response);
+        assert!(Uuid::parse_str(&request_id).is_ok());
+        assert_eq!(
+            body_string(response).await,
+            format!("{} {}", request_id, request_id)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_error_response_has_request_id_header() {
+        let response = send("/missing", Some("req-404")).await;
+
+        // ヘッダーと同じIDを本文にも含める
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response_request_id(&response), "req-404");
+        assert_eq!(
+            body_string(response).await,
+            "Task not found (request_id: req-404)"
+        );
+    }
+
+    #[test]
+    fn test_no_request_id_outside_request() {
+        assert!(current_request_id().is_none());
+    }
+}
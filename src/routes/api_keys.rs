@@ -0,0 +1,167 @@
+use crate::error::AppError;
+use crate::models::api_key::{ApiKey, ApiKeyScope, IssuedApiKey};
+use crate::models::caller::Caller;
+use crate::usecase::api_key_usecase::ApiKeyService;
+use axum::{
+    extract::{Json, Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{delete, get},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct ApiKeyState<K: ApiKeyService> {
+    pub api_key_service: Arc<K>,
+}
+
+pub fn router<K: ApiKeyService + Send + Sync + 'static + Clone>(api_key_service: K) -> Router {
+    let state = ApiKeyState {
+        api_key_service: Arc::new(api_key_service),
+    };
+    Router::new()
+        .route(
+            "/api-keys",
+            get(list_api_keys::<K>).post(create_api_key::<K>),
+        )
+        .route("/api-keys/:id", delete(revoke_api_key::<K>))
+        .with_state(state)
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateApiKeyRequest {
+    name: String,
+    scopes: Vec<ApiKeyScope>,
+    /// 有効期間の日数（省略時は90日、最大365日）
+    expires_in_days: Option<i64>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ApiKeyResponse {
+    id: Uuid,
+    name: String,
+    workspace_id: Uuid,
+    prefix: String,
+    scopes: Vec<ApiKeyScope>,
+    expires_at: DateTime<Utc>,
+    last_used_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<ApiKey> for ApiKeyResponse {
+    fn from(api_key: ApiKey) -> Self {
+        Self {
+            id: api_key.id,
+            name: api_key.name,
+            workspace_id: api_key.workspace_id,
+            prefix: api_key.prefix,
+            scopes: api_key.scopes,
+            expires_at: api_key.expires_at,
+            last_used_at: api_key.last_used_at,
+            created_at: api_key.created_at,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct CreatedApiKeyResponse {
+    /// X-API-Key ヘッダーに指定するキー（再表示できないため安全な場所に保存すること）
+    key: String,
+    #[serde(flatten)]
+    api_key: ApiKeyResponse,
+}
+
+impl From<IssuedApiKey> for CreatedApiKeyResponse {
+    fn from(issued: IssuedApiKey) -> Self {
+        Self {
+            key: issued.key,
+            api_key: ApiKeyResponse::from(issued.api_key),
+        }
+    }
+}
+
+// APIキーの発行（呼び出し元のワークスペースで、呼び出し元のロールの範囲で使える）
+#[utoipa::path(
+    post,
+    path = "/api-keys",
+    request_body = CreateApiKeyRequest,
+    responses(
+        (status = 201, description = "APIキー発行成功（キーはこのレスポンスでのみ返す）", body = CreatedApiKeyResponse),
+        (status = 400, description = "名前・スコープ・有効期間が不正"),
+        (status = 403, description = "APIキーでは発行できない")
+    ),
+    security(("bearer_auth" = []), ("session_cookie" = [])),
+    tag = "ApiKeys"
+)]
+async fn create_api_key<K: ApiKeyService>(
+    State(state): State<ApiKeyState<K>>,
+    caller: Caller,
+    Json(payload): Json<CreateApiKeyRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let issued = state
+        .api_key_service
+        .create_key(
+            &caller,
+            payload.name,
+            payload.scopes,
+            payload.expires_in_days,
+        )
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(CreatedApiKeyResponse::from(issued)),
+    ))
+}
+
+// 発行したAPIキーの一覧
+#[utoipa::path(
+    get,
+    path = "/api-keys",
+    responses(
+        (status = 200, description = "APIキー一覧取得成功", body = [ApiKeyResponse]),
+        (status = 403, description = "APIキーでは取得できない")
+    ),
+    security(("bearer_auth" = []), ("session_cookie" = [])),
+    tag = "ApiKeys"
+)]
+async fn list_api_keys<K: ApiKeyService>(
+    State(state): State<ApiKeyState<K>>,
+    caller: Caller,
+) -> Result<impl IntoResponse, AppError> {
+    let api_keys = state.api_key_service.list_keys(&caller).await?;
+    Ok(Json(
+        api_keys
+            .into_iter()
+            .map(ApiKeyResponse::from)
+            .collect::<Vec<_>>(),
+    ))
+}
+
+// APIキーの失効
+#[utoipa::path(
+    delete,
+    path = "/api-keys/{id}",
+    params(
+        ("id" = Uuid, Path, description = "APIキーのUUID")
+    ),
+    responses(
+        (status = 204, description = "APIキー失効成功"),
+        (status = 403, description = "APIキーでは失効できない"),
+        (status = 404, description = "APIキーが存在しない")
+    ),
+    security(("bearer_auth" = []), ("session_cookie" = [])),
+    tag = "ApiKeys"
+)]
+async fn revoke_api_key<K: ApiKeyService>(
+    State(state): State<ApiKeyState<K>>,
+    caller: Caller,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    state.api_key_service.revoke_key(&caller, id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,177 @@
+use crate::error::AppError;
+use crate::models::auth_token::TokenPair;
+use crate::models::caller::Caller;
+use crate::models::user_account::UserAccount;
+use crate::usecase::auth_usecase::AuthService;
+use axum::{
+    extract::{Json, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, post},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct AuthState<A: AuthService> {
+    pub auth_service: Arc<A>,
+}
+
+// 認証なしで呼び出せるルート
+pub fn router<A: AuthService + Send + Sync + 'static + Clone>(auth_service: A) -> Router {
+    let state = AuthState {
+        auth_service: Arc::new(auth_service),
+    };
+    Router::new()
+        .route("/auth/register", post(register::<A>))
+        .route("/auth/login", post(login::<A>))
+        .route("/auth/refresh", post(refresh::<A>))
+        .with_state(state)
+}
+
+// アクセストークンが必要なルート
+pub fn account_router<A: AuthService + Send + Sync + 'static + Clone>(auth_service: A) -> Router {
+    let state = AuthState {
+        auth_service: Arc::new(auth_service),
+    };
+    Router::new()
+        .route("/auth/me", get(get_me::<A>))
+        .with_state(state)
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CredentialsRequest {
+    pub email: String,
+    pub password: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct RefreshRequest {
+    refresh_token: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct TokenResponse {
+    access_token: String,
+    #[schema(example = "Bearer")]
+    token_type: &'static str,
+    // アクセストークンの有効期間（秒）
+    expires_in: i64,
+    refresh_token: String,
+}
+
+impl From<TokenPair> for TokenResponse {
+    fn from(tokens: TokenPair) -> Self {
+        Self {
+            access_token: tokens.access_token,
+            token_type: "Bearer",
+            expires_in: tokens.expires_in,
+            refresh_token: tokens.refresh_token,
+        }
+    }
+}
+
+// パスワードハッシュを含めないユーザー情報
+#[derive(Serialize, ToSchema)]
+pub struct UserResponse {
+    id: Uuid,
+    email: String,
+    created_at: DateTime<Utc>,
+}
+
+impl From<UserAccount> for UserResponse {
+    fn from(user: UserAccount) -> Self {
+        Self {
+            id: user.id,
+            email: user.email,
+            created_at: user.created_at,
+        }
+    }
+}
+
+// ユーザー登録
+#[utoipa::path(
+    post,
+    path = "/auth/register",
+    request_body = CredentialsRequest,
+    responses(
+        (status = 201, description = "登録成功", body = UserResponse),
+        (status = 400, description = "メールアドレスまたはパスワードが不正"),
+        (status = 409, description = "メールアドレスが登録済み")
+    ),
+    tag = "Auth"
+)]
+async fn register<A: AuthService>(
+    State(state): State<AuthState<A>>,
+    Json(payload): Json<CredentialsRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = state
+        .auth_service
+        .register(payload.email, payload.password)
+        .await?;
+    Ok((StatusCode::CREATED, Json(UserResponse::from(user))))
+}
+
+// ログイン
+#[utoipa::path(
+    post,
+    path = "/auth/login",
+    request_body = CredentialsRequest,
+    responses(
+        (status = 200, description = "ログイン成功", body = TokenResponse),
+        (status = 401, description = "メールアドレスまたはパスワードが違う")
+    ),
+    tag = "Auth"
+)]
+async fn login<A: AuthService>(
+    State(state): State<AuthState<A>>,
+    Json(payload): Json<CredentialsRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let tokens = state
+        .auth_service
+        .login(payload.email, payload.password)
+        .await?;
+    Ok(Json(TokenResponse::from(tokens)))
+}
+
+// トークンの再発行
+#[utoipa::path(
+    post,
+    path = "/auth/refresh",
+    request_body = RefreshRequest,
+    responses(
+        (status = 200, description = "再発行成功（リクエストのリフレッシュトークンは無効になる）", body = TokenResponse),
+        (status = 401, description = "リフレッシュトークンが無効または期限切れ")
+    ),
+    tag = "Auth"
+)]
+async fn refresh<A: AuthService>(
+    State(state): State<AuthState<A>>,
+    Json(payload): Json<RefreshRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let tokens = state.auth_service.refresh(payload.refresh_token).await?;
+    Ok(Json(TokenResponse::from(tokens)))
+}
+
+// ログイン中のユーザー
+#[utoipa::path(
+    get,
+    path = "/auth/me",
+    responses(
+        (status = 200, description = "ログイン中のユーザー", body = UserResponse),
+        (status = 401, description = "認証されていない")
+    ),
+    security(("bearer_auth" = []), ("session_cookie" = [])),
+    tag = "Auth"
+)]
+async fn get_me<A: AuthService>(
+    State(state): State<AuthState<A>>,
+    caller: Caller,
+) -> Result<impl IntoResponse, AppError> {
+    let user = state.auth_service.current_user(caller.user_id).await?;
+    Ok(Json(UserResponse::from(user)))
+}
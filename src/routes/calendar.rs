@@ -0,0 +1,168 @@
+use crate::error::AppError;
+use crate::models::calendar::CalendarComponent;
+use crate::models::caller::Caller;
+use crate::models::task::TaskFilter;
+use crate::usecase::calendar_usecase::CalendarService;
+use axum::{
+    extract::{Json, Query, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
+    routing::{get, post},
+    Router,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::error;
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Clone)]
+pub struct CalendarState<C: CalendarService> {
+    pub calendar_service: Arc<C>,
+}
+
+// トークンの発行・再発行・失効（アクセストークンが必要）
+pub fn router<C: CalendarService + Send + Sync + 'static + Clone>(calendar_service: C) -> Router {
+    let state = CalendarState {
+        calendar_service: Arc::new(calendar_service),
+    };
+    Router::new()
+        .route(
+            "/calendar/tokens",
+            post(create_calendar_token::<C>).delete(revoke_calendar_tokens::<C>),
+        )
+        .route("/calendar/tokens/rotate", post(rotate_calendar_token::<C>))
+        .with_state(state)
+}
+
+// フィード本体（カレンダーアプリは Authorization ヘッダーを送れないため、URLのトークンで認証する）
+pub fn feed_router<C: CalendarService + Send + Sync + 'static + Clone>(
+    calendar_service: C,
+) -> Router {
+    let state = CalendarState {
+        calendar_service: Arc::new(calendar_service),
+    };
+    Router::new()
+        .route("/calendar.ics", get(get_calendar::<C>))
+        .with_state(state)
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CalendarQuery {
+    /// フィード購読用のトークン
+    token: String,
+    /// プロジェクトで絞り込む
+    project: Option<String>,
+    /// タグで絞り込む
+    tag: Option<String>,
+    /// 出力するコンポーネント（vtodo / vevent）
+    #[serde(default)]
+    component: CalendarComponent,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct CalendarTokenResponse {
+    token: String,
+    url: String,
+}
+
+impl CalendarTokenResponse {
+    fn new(token: String) -> Self {
+        let url = format!("/calendar.ics?token={}", token);
+        Self { token, url }
+    }
+}
+
+// iCalendarフィード
+#[utoipa::path(
+    get,
+    path = "/calendar.ics",
+    params(CalendarQuery),
+    responses(
+        (status = 200, description = "iCalendar (RFC 5545) 形式のタスク一覧", content_type = "text/calendar"),
+        (status = 404, description = "トークンが無効")
+    ),
+    tag = "Calendar"
+)]
+async fn get_calendar<C: CalendarService>(
+    State(state): State<CalendarState<C>>,
+    Query(query): Query<CalendarQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let filter = TaskFilter {
+        project: query.project,
+        tag: query.tag,
+        ..Default::default()
+    };
+    let feed = state
+        .calendar_service
+        .render_feed(&query.token, filter, query.component)
+        .await
+        .inspect_err(|e| error!("get_calendar: failed to render calendar: {:?}", e))?;
+    match feed {
+        Some(body) => Ok((
+            [(header::CONTENT_TYPE, "text/calendar; charset=utf-8")],
+            body,
+        )
+            .into_response()),
+        // トークンの存在を推測されないよう、無効なトークンは404にする
+        None => Ok((StatusCode::NOT_FOUND, "Calendar not found").into_response()),
+    }
+}
+
+// フィード購読用トークンの発行
+#[utoipa::path(
+    post,
+    path = "/calendar/tokens",
+    responses(
+        (status = 201, description = "トークン発行成功（トークンはこのレスポンスでしか確認できない）", body = CalendarTokenResponse),
+        (status = 403, description = "APIキーでは発行できない")
+    ),
+    security(("bearer_auth" = []), ("session_cookie" = [])),
+    tag = "Calendar"
+)]
+async fn create_calendar_token<C: CalendarService>(
+    State(state): State<CalendarState<C>>,
+    caller: Caller,
+) -> Result<impl IntoResponse, AppError> {
+    let token = state.calendar_service.issue_token(&caller).await?;
+    Ok((StatusCode::CREATED, Json(CalendarTokenResponse::new(token))))
+}
+
+// フィード購読用トークンの再発行（それまでに発行したトークンは使えなくなる）
+#[utoipa::path(
+    post,
+    path = "/calendar/tokens/rotate",
+    responses(
+        (status = 201, description = "トークン再発行成功（トークンはこのレスポンスでしか確認できない）", body = CalendarTokenResponse),
+        (status = 403, description = "APIキーでは再発行できない")
+    ),
+    security(("bearer_auth" = []), ("session_cookie" = [])),
+    tag = "Calendar"
+)]
+async fn rotate_calendar_token<C: CalendarService>(
+    State(state): State<CalendarState<C>>,
+    caller: Caller,
+) -> Result<impl IntoResponse, AppError> {
+    let token = state.calendar_service.rotate_token(&caller).await?;
+    Ok((StatusCode::CREATED, Json(CalendarTokenResponse::new(token))))
+}
+
+// フィード購読用トークンの失効（呼び出し元がワークスペースで発行したトークンをすべて失効させる）
+#[utoipa::path(
+    delete,
+    path = "/calendar/tokens",
+    responses(
+        (status = 204, description = "トークン失効成功"),
+        (status = 403, description = "APIキーでは失効できない"),
+        (status = 404, description = "トークンが存在しない")
+    ),
+    security(("bearer_auth" = []), ("session_cookie" = [])),
+    tag = "Calendar"
+)]
+async fn revoke_calendar_tokens<C: CalendarService>(
+    State(state): State<CalendarState<C>>,
+    caller: Caller,
+) -> Result<impl IntoResponse, AppError> {
+    state.calendar_service.revoke_tokens(&caller).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,64 @@
+use crate::models::health::HealthReport;
+use crate::usecase::health_usecase::HealthService;
+use axum::{
+    extract::{Json, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct HealthState<H: HealthService> {
+    pub health_service: Arc<H>,
+}
+
+// オーケストレーターが呼び出すルート（認証・レート制限なし）
+pub fn router<H: HealthService + Send + Sync + 'static + Clone>(health_service: H) -> Router {
+    let state = HealthState {
+        health_service: Arc::new(health_service),
+    };
+    Router::new()
+        .route("/healthz", get(liveness::<H>))
+        .route("/readyz", get(readiness::<H>))
+        .with_state(state)
+}
+
+fn report_response(report: HealthReport) -> impl IntoResponse {
+    let status = if report.is_ok() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(report))
+}
+
+#[utoipa::path(
+    get,
+    path = "/healthz",
+    tag = "Health",
+    responses(
+        (status = 200, description = "プロセスが動いている", body = HealthReport)
+    )
+)]
+pub async fn liveness<H: HealthService + Send + Sync>(
+    State(state): State<HealthState<H>>,
+) -> impl IntoResponse {
+    report_response(state.health_service.liveness())
+}
+
+#[utoipa::path(
+    get,
+    path = "/readyz",
+    tag = "Health",
+    responses(
+        (status = 200, description = "リクエストを受け付けられる", body = HealthReport),
+        (status = 503, description = "依存先の確認に失敗した、または終了中", body = HealthReport)
+    )
+)]
+pub async fn readiness<H: HealthService + Send + Sync>(
+    State(state): State<HealthState<H>>,
+) -> impl IntoResponse {
+    report_response(state.health_service.readiness().await)
+}
@@ -0,0 +1,43 @@
+use crate::infrastructure::db::DbPool;
+use crate::metrics::registry::METRICS;
+use axum::{extract::State, http::header, response::IntoResponse, routing::get, Router};
+
+// Prometheus のテキスト形式の Content-Type
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+#[derive(Clone)]
+pub struct MetricsState {
+    pub pool: DbPool,
+}
+
+// Prometheus が収集するルート（認証・レート制限なし。外部に公開しない場合は features.metrics で無効にする）
+pub fn router(state: MetricsState) -> Router {
+    Router::new()
+        .route("/metrics", get(get_metrics))
+        .with_state(state)
+}
+
+pub async fn get_metrics(State(state): State<MetricsState>) -> impl IntoResponse {
+    record_pool_metrics(&state.pool);
+    (
+        [(header::CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)],
+        METRICS.render(),
+    )
+}
+
+// 収集のたびにプールの状態を記録する（接続は取得しないので、プールが枯渇していても待たされない）
+fn record_pool_metrics(pool: &DbPool) {
+    let size = i64::from(pool.size());
+    let idle = pool.num_idle() as i64;
+    METRICS
+        .db_pool_connections
+        .with_label_values(&["idle"])
+        .set(idle);
+    METRICS
+        .db_pool_connections
+        .with_label_values(&["in_use"])
+        .set(size - idle);
+    METRICS
+        .db_pool_max_connections
+        .set(i64::from(pool.options().get_max_connections()));
+}
@@ -1,3 +1,18 @@
+pub mod api_keys;
+pub mod auth;
+pub mod calendar;
+pub mod health;
 pub mod hello;
+pub mod metrics;
+pub mod oidc;
+pub mod sessions;
+pub mod sharing;
+pub mod stats;
+pub mod task_transfer;
 pub mod tasks;
+pub mod time_tracking;
 pub mod users;
+pub mod workspaces;
+
+#[cfg(test)]
+pub mod tests;
@@ -0,0 +1,113 @@
+use crate::auth::cookie::{clear_cookie, cookie_value, set_cookie};
+use crate::error::AppError;
+use crate::models::oidc::LOGIN_STATE_TTL;
+use crate::routes::auth::TokenResponse;
+use crate::usecase::oidc_usecase::OidcService;
+use axum::{
+    extract::{Json, Query, State},
+    http::{header, HeaderMap},
+    response::{IntoResponse, Redirect},
+    routing::get,
+    Router,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::warn;
+use utoipa::IntoParams;
+
+// 認可リクエストを開始したブラウザだけがコールバックを完了できるよう、state を保存するクッキー
+const STATE_COOKIE: &str = "oidc_state";
+const STATE_COOKIE_PATH: &str = "/auth/oidc";
+
+#[derive(Clone)]
+pub struct OidcState<O: OidcService> {
+    pub oidc_service: Arc<O>,
+}
+
+// OIDC ログイン（認証なしで呼び出せるルート）
+pub fn router<O: OidcService + Send + Sync + 'static + Clone>(oidc_service: O) -> Router {
+    let state = OidcState {
+        oidc_service: Arc::new(oidc_service),
+    };
+    Router::new()
+        .route("/auth/oidc/login", get(login::<O>))
+        .route("/auth/oidc/callback", get(callback::<O>))
+        .with_state(state)
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct CallbackQuery {
+    /// 認可コード
+    code: Option<String>,
+    /// 認可リクエストで渡した state
+    state: Option<String>,
+    /// ユーザーが拒否した場合などにプロバイダーが返すエラー
+    error: Option<String>,
+}
+
+// OIDC ログインの開始
+#[utoipa::path(
+    get,
+    path = "/auth/oidc/login",
+    responses(
+        (status = 303, description = "プロバイダーのログイン画面へリダイレクト"),
+        (status = 500, description = "プロバイダーの設定を取得できない")
+    ),
+    tag = "Auth"
+)]
+async fn login<O: OidcService>(
+    State(state): State<OidcState<O>>,
+) -> Result<impl IntoResponse, AppError> {
+    let login = state.oidc_service.begin_login().await?;
+    Ok((
+        [(
+            header::SET_COOKIE,
+            set_cookie(
+                STATE_COOKIE,
+                &login.state,
+                STATE_COOKIE_PATH,
+                LOGIN_STATE_TTL.num_seconds(),
+                true,
+            ),
+        )],
+        Redirect::to(&login.authorization_url),
+    ))
+}
+
+// OIDC ログインのコールバック
+#[utoipa::path(
+    get,
+    path = "/auth/oidc/callback",
+    params(CallbackQuery),
+    responses(
+        (status = 200, description = "ログイン成功", body = TokenResponse),
+        (status = 401, description = "state・認可コード・ID トークンが無効"),
+        (status = 403, description = "プロバイダーが確認済みのメールアドレスがない")
+    ),
+    tag = "Auth"
+)]
+async fn callback<O: OidcService>(
+    State(state): State<OidcState<O>>,
+    headers: HeaderMap,
+    Query(query): Query<CallbackQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    if let Some(error) = query.error {
+        warn!("OIDC provider returned error: {}", error);
+        return Err(AppError::Unauthorized);
+    }
+    let (Some(code), Some(login_state)) = (query.code, query.state) else {
+        return Err(AppError::Unauthorized);
+    };
+    // 他人の認可コードでログインさせられる攻撃（ログイン CSRF）を防ぐ
+    if cookie_value(&headers, STATE_COOKIE) != Some(login_state.as_str()) {
+        return Err(AppError::Unauthorized);
+    }
+    let tokens = state.oidc_service.complete_login(code, login_state).await?;
+    Ok((
+        [(
+            header::SET_COOKIE,
+            clear_cookie(STATE_COOKIE, STATE_COOKIE_PATH, true),
+        )],
+        Json(TokenResponse::from(tokens)),
+    ))
+}
@@ -0,0 +1,159 @@
+use crate::auth::cookie::{clear_cookie, cookie_value, set_cookie};
+use crate::auth::middleware::SESSION_COOKIE;
+use crate::error::AppError;
+use crate::models::caller::Caller;
+use crate::models::session::{IssuedSession, SESSION_ABSOLUTE_TIMEOUT, SESSION_IDLE_TIMEOUT};
+use crate::routes::auth::CredentialsRequest;
+use crate::usecase::session_usecase::SessionService;
+use axum::{
+    extract::{Json, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{AppendHeaders, IntoResponse},
+    routing::{delete, post},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::Arc;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+// 画面のスクリプトが読み取って X-CSRF-Token ヘッダーに付ける CSRF トークンの Cookie
+const CSRF_COOKIE: &str = "csrf_token";
+
+#[derive(Clone)]
+pub struct SessionState<S: SessionService> {
+    pub session_service: Arc<S>,
+}
+
+// 認証なしで呼び出せるルート
+pub fn router<S: SessionService + Send + Sync + 'static + Clone>(session_service: S) -> Router {
+    let state = SessionState {
+        session_service: Arc::new(session_service),
+    };
+    Router::new()
+        .route("/auth/sessions", post(create_session::<S>))
+        .with_state(state)
+}
+
+// ログイン中に呼び出すルート（セッション Cookie の場合は CSRF トークンも必要）
+pub fn account_router<S: SessionService + Send + Sync + 'static + Clone>(
+    session_service: S,
+) -> Router {
+    let state = SessionState {
+        session_service: Arc::new(session_service),
+    };
+    Router::new()
+        .route("/auth/sessions", delete(delete_all_sessions::<S>))
+        .route("/auth/sessions/current", delete(delete_session::<S>))
+        .with_state(state)
+}
+
+fn clear_session_cookies() -> AppendHeaders<[(header::HeaderName, String); 2]> {
+    AppendHeaders([
+        (header::SET_COOKIE, clear_cookie(SESSION_COOKIE, "/", true)),
+        (header::SET_COOKIE, clear_cookie(CSRF_COOKIE, "/", false)),
+    ])
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct SessionResponse {
+    user_id: Uuid,
+    /// 状態を変更するリクエストの X-CSRF-Token ヘッダーに付けるトークン（csrf_token Cookie と同じ値）
+    csrf_token: String,
+    /// 絶対タイムアウト（アクセスがあっても延長しない）
+    expires_at: DateTime<Utc>,
+    /// アイドルタイムアウト（秒）
+    idle_timeout: i64,
+}
+
+impl From<IssuedSession> for SessionResponse {
+    fn from(issued: IssuedSession) -> Self {
+        Self {
+            user_id: issued.session.user_id,
+            csrf_token: issued.csrf_token,
+            expires_at: issued.session.expires_at,
+            idle_timeout: SESSION_IDLE_TIMEOUT.num_seconds(),
+        }
+    }
+}
+
+// セッションでのログイン（ブラウザ向け）
+#[utoipa::path(
+    post,
+    path = "/auth/sessions",
+    request_body = CredentialsRequest,
+    responses(
+        (status = 200, description = "ログイン成功（session・csrf_token の Cookie を設定する）", body = SessionResponse),
+        (status = 401, description = "メールアドレスまたはパスワードが違う")
+    ),
+    tag = "Auth"
+)]
+async fn create_session<S: SessionService>(
+    State(state): State<SessionState<S>>,
+    headers: HeaderMap,
+    Json(payload): Json<CredentialsRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let previous_token = cookie_value(&headers, SESSION_COOKIE).map(str::to_string);
+    let issued = state
+        .session_service
+        .login(payload.email, payload.password, previous_token)
+        .await?;
+    let max_age = SESSION_ABSOLUTE_TIMEOUT.num_seconds();
+    Ok((
+        AppendHeaders([
+            (
+                header::SET_COOKIE,
+                set_cookie(SESSION_COOKIE, &issued.token, "/", max_age, true),
+            ),
+            (
+                header::SET_COOKIE,
+                set_cookie(CSRF_COOKIE, &issued.csrf_token, "/", max_age, false),
+            ),
+        ]),
+        Json(SessionResponse::from(issued)),
+    ))
+}
+
+// このセッションからのログアウト
+#[utoipa::path(
+    delete,
+    path = "/auth/sessions/current",
+    responses(
+        (status = 204, description = "ログアウト成功（Cookie を削除する）"),
+        (status = 401, description = "認証されていない"),
+        (status = 403, description = "CSRF トークンがない・一致しない")
+    ),
+    security(("session_cookie" = [])),
+    tag = "Auth"
+)]
+async fn delete_session<S: SessionService>(
+    State(state): State<SessionState<S>>,
+    _caller: Caller,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    if let Some(token) = cookie_value(&headers, SESSION_COOKIE) {
+        state.session_service.logout(token).await?;
+    }
+    Ok((StatusCode::NO_CONTENT, clear_session_cookies()))
+}
+
+// すべての端末からのログアウト（セッションとリフレッシュトークンをすべて破棄する）
+#[utoipa::path(
+    delete,
+    path = "/auth/sessions",
+    responses(
+        (status = 204, description = "ログアウト成功（Cookie を削除する）"),
+        (status = 401, description = "認証されていない"),
+        (status = 403, description = "APIキーでは実行できない、または CSRF トークンがない・一致しない")
+    ),
+    security(("bearer_auth" = []), ("session_cookie" = [])),
+    tag = "Auth"
+)]
+async fn delete_all_sessions<S: SessionService>(
+    State(state): State<SessionState<S>>,
+    caller: Caller,
+) -> Result<impl IntoResponse, AppError> {
+    state.session_service.logout_everywhere(&caller).await?;
+    Ok((StatusCode::NO_CONTENT, clear_session_cookies()))
+}
@@ -0,0 +1,266 @@
+use crate::error::AppError;
+use crate::models::caller::Caller;
+use crate::models::grant::{Grant, GrantPermission, IssuedShareLink, ShareTarget};
+use crate::routes::tasks::TaskResponse;
+use crate::usecase::sharing_usecase::SharingService;
+use axum::{
+    extract::{Json, Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{delete, get, post},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct SharingState<S: SharingService> {
+    pub sharing_service: Arc<S>,
+}
+
+// 共有の作成・一覧・取り消し（アクセストークンが必要）
+pub fn router<S: SharingService + Send + Sync + 'static + Clone>(sharing_service: S) -> Router {
+    let state = SharingState {
+        sharing_service: Arc::new(sharing_service),
+    };
+    Router::new()
+        .route("/grants", get(list_grants::<S>).post(create_grant::<S>))
+        .route("/grants/:id", delete(revoke_grant::<S>))
+        .route("/share-links", post(create_share_link::<S>))
+        .with_state(state)
+}
+
+// 共有リンクの閲覧（リンクを知っていれば誰でも見られるため、認証しない）
+pub fn link_router<S: SharingService + Send + Sync + 'static + Clone>(
+    sharing_service: S,
+) -> Router {
+    let state = SharingState {
+        sharing_service: Arc::new(sharing_service),
+    };
+    Router::new()
+        .route("/shared/:token", get(get_shared_link::<S>))
+        .with_state(state)
+}
+
+// task_id と project のどちらか一方を指定する
+fn share_target(task_id: Option<Uuid>, project: Option<String>) -> Result<ShareTarget, AppError> {
+    match (task_id, project) {
+        (Some(task_id), None) => Ok(ShareTarget::Task(task_id)),
+        (None, Some(project)) => Ok(ShareTarget::Project(project)),
+        _ => Err(AppError::BadRequest(
+            "Specify exactly one of `task_id` or `project`".to_string(),
+        )),
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateGrantRequest {
+    /// 共有するタスク（project と同時には指定できない）
+    task_id: Option<Uuid>,
+    /// 共有するプロジェクト（task_id と同時には指定できない）
+    project: Option<String>,
+    /// 共有する相手のメールアドレス
+    email: String,
+    permission: GrantPermission,
+    /// 共有の期限（省略時は無期限）
+    expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateShareLinkRequest {
+    /// 共有するタスク（project と同時には指定できない）
+    task_id: Option<Uuid>,
+    /// 共有するプロジェクト（task_id と同時には指定できない）
+    project: Option<String>,
+    /// 有効期間の時間数（省略時は7日、最大30日）
+    expires_in_hours: Option<i64>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct GrantResponse {
+    id: Uuid,
+    task_id: Option<Uuid>,
+    project: Option<String>,
+    /// 共有された相手（共有リンクの場合は null）
+    grantee_id: Option<Uuid>,
+    is_link: bool,
+    permission: GrantPermission,
+    expires_at: Option<DateTime<Utc>>,
+    created_by: Uuid,
+    created_at: DateTime<Utc>,
+}
+
+impl From<Grant> for GrantResponse {
+    fn from(grant: Grant) -> Self {
+        Self {
+            is_link: grant.is_link(),
+            id: grant.id,
+            task_id: grant.task_id,
+            project: grant.project,
+            grantee_id: grant.grantee_id,
+            permission: grant.permission,
+            expires_at: grant.expires_at,
+            created_by: grant.created_by,
+            created_at: grant.created_at,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ShareLinkResponse {
+    /// 共有リンクのトークン（再表示できないため安全な場所に保存すること）
+    token: String,
+    url: String,
+    #[serde(flatten)]
+    grant: GrantResponse,
+}
+
+impl From<IssuedShareLink> for ShareLinkResponse {
+    fn from(issued: IssuedShareLink) -> Self {
+        Self {
+            url: format!("/shared/{}", issued.token),
+            token: issued.token,
+            grant: GrantResponse::from(issued.grant),
+        }
+    }
+}
+
+// ユーザーへの共有
+#[utoipa::path(
+    post,
+    path = "/grants",
+    request_body = CreateGrantRequest,
+    responses(
+        (status = 201, description = "共有成功", body = GrantResponse),
+        (status = 400, description = "共有対象・期限が不正"),
+        (status = 403, description = "共有する権限がない"),
+        (status = 404, description = "タスクまたはユーザーが存在しない")
+    ),
+    security(("bearer_auth" = []), ("session_cookie" = [])),
+    tag = "Sharing"
+)]
+async fn create_grant<S: SharingService>(
+    State(state): State<SharingState<S>>,
+    caller: Caller,
+    Json(payload): Json<CreateGrantRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let target = share_target(payload.task_id, payload.project)?;
+    let grant = state
+        .sharing_service
+        .share_with_user(
+            &caller,
+            target,
+            payload.email,
+            payload.permission,
+            payload.expires_at,
+        )
+        .await?;
+    Ok((StatusCode::CREATED, Json(GrantResponse::from(grant))))
+}
+
+// 共有リンクの作成（閲覧のみ）
+#[utoipa::path(
+    post,
+    path = "/share-links",
+    request_body = CreateShareLinkRequest,
+    responses(
+        (status = 201, description = "共有リンク作成成功（トークンはこのレスポンスでしか確認できない）", body = ShareLinkResponse),
+        (status = 400, description = "共有対象・有効期間が不正"),
+        (status = 403, description = "共有する権限がない"),
+        (status = 404, description = "タスクが存在しない")
+    ),
+    security(("bearer_auth" = []), ("session_cookie" = [])),
+    tag = "Sharing"
+)]
+async fn create_share_link<S: SharingService>(
+    State(state): State<SharingState<S>>,
+    caller: Caller,
+    Json(payload): Json<CreateShareLinkRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let target = share_target(payload.task_id, payload.project)?;
+    let issued = state
+        .sharing_service
+        .create_share_link(&caller, target, payload.expires_in_hours)
+        .await?;
+    Ok((StatusCode::CREATED, Json(ShareLinkResponse::from(issued))))
+}
+
+// 共有の一覧（admin はワークスペースのすべて、それ以外は自分が作成したもの）
+#[utoipa::path(
+    get,
+    path = "/grants",
+    responses(
+        (status = 200, description = "共有一覧取得成功", body = [GrantResponse])
+    ),
+    security(("bearer_auth" = []), ("session_cookie" = [])),
+    tag = "Sharing"
+)]
+async fn list_grants<S: SharingService>(
+    State(state): State<SharingState<S>>,
+    caller: Caller,
+) -> Result<impl IntoResponse, AppError> {
+    let grants = state.sharing_service.list_grants(&caller).await?;
+    Ok(Json(
+        grants
+            .into_iter()
+            .map(GrantResponse::from)
+            .collect::<Vec<_>>(),
+    ))
+}
+
+// 共有・共有リンクの取り消し
+#[utoipa::path(
+    delete,
+    path = "/grants/{id}",
+    params(
+        ("id" = Uuid, Path, description = "共有のUUID")
+    ),
+    responses(
+        (status = 204, description = "取り消し成功"),
+        (status = 403, description = "取り消す権限がない"),
+        (status = 404, description = "共有が存在しない")
+    ),
+    security(("bearer_auth" = []), ("session_cookie" = [])),
+    tag = "Sharing"
+)]
+async fn revoke_grant<S: SharingService>(
+    State(state): State<SharingState<S>>,
+    caller: Caller,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    state.sharing_service.revoke_grant(&caller, id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// 共有リンクのタスク
+#[utoipa::path(
+    get,
+    path = "/shared/{token}",
+    params(
+        ("token" = String, Path, description = "共有リンクのトークン")
+    ),
+    responses(
+        (status = 200, description = "共有されたタスク取得成功", body = [TaskResponse]),
+        (status = 404, description = "トークンが無効または期限切れ")
+    ),
+    tag = "Sharing"
+)]
+async fn get_shared_link<S: SharingService>(
+    State(state): State<SharingState<S>>,
+    Path(token): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let tasks = state
+        .sharing_service
+        .get_link_tasks(&token)
+        .await?
+        .ok_or(AppError::ResourceNotFound("Share link"))?;
+    Ok(Json(
+        tasks
+            .into_iter()
+            .map(TaskResponse::from)
+            .collect::<Vec<_>>(),
+    ))
+}
@@ -0,0 +1,38 @@
+use crate::error::AppError;
+use crate::models::caller::Caller;
+use crate::models::stats::StatsQuery;
+use crate::routes::tasks::AppState;
+use crate::usecase::task_usecase::TaskService;
+use axum::{
+    extract::{Json, Query, State},
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use chrono::Utc;
+
+pub fn routes<T: TaskService + Send + Sync + 'static + Clone>() -> Router<AppState<T>> {
+    Router::new().route("/stats/tasks", get(get_task_stats::<T>))
+}
+
+// タスクの統計
+#[utoipa::path(
+    get,
+    path = "/stats/tasks",
+    params(StatsQuery),
+    responses(
+        (status = 200, description = "タスクの統計取得成功", body = crate::models::stats::TaskStats),
+        (status = 400, description = "集計範囲またはタイムゾーンが不正")
+    ),
+    security(("bearer_auth" = []), ("session_cookie" = []), ("api_key" = [])),
+    tag = "Stats"
+)]
+async fn get_task_stats<T: TaskService>(
+    State(state): State<AppState<T>>,
+    caller: Caller,
+    Query(query): Query<StatsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let range = query.resolve(Utc::now()).map_err(AppError::BadRequest)?;
+    let stats = state.task_service.get_task_stats(&caller, range).await?;
+    Ok(Json(stats))
+}
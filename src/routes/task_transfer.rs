@@ -0,0 +1,146 @@
+use crate::error::AppError;
+use crate::models::caller::Caller;
+use crate::models::task_transfer::TransferFormat;
+use crate::routes::tasks::AppState;
+use crate::usecase::task_usecase::TaskService;
+use axum::{
+    body::Body,
+    extract::{Json, Query, State},
+    http::header,
+    response::IntoResponse,
+    routing::{get, post},
+    Router,
+};
+use futures::{future, stream, StreamExt};
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+// エクスポート時に1回のクエリで取得する件数
+const EXPORT_PAGE_SIZE: i64 = 500;
+
+pub fn routes<T: TaskService + Send + Sync + 'static + Clone>() -> Router<AppState<T>> {
+    Router::new()
+        .route("/tasks/export", get(export_tasks::<T>))
+        .route("/tasks/import", post(import_tasks::<T>))
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportQuery {
+    /// 出力形式（json / ndjson / csv）
+    #[serde(default)]
+    format: TransferFormat,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ImportQuery {
+    /// 入力形式（json / ndjson / csv）
+    #[serde(default)]
+    format: TransferFormat,
+    /// trueの場合は検証のみ行い書き込まない
+    #[serde(default)]
+    dry_run: bool,
+}
+
+// ページングの状態
+#[derive(Clone, Copy)]
+struct ExportCursor {
+    after: Option<Uuid>,
+    first_page: bool,
+    done: bool,
+}
+
+// エクスポート（全件をメモリに載せずにページ単位でストリーミングする）
+#[utoipa::path(
+    get,
+    path = "/tasks/export",
+    params(ExportQuery),
+    responses(
+        (status = 200, description = "タスクのエクスポート成功")
+    ),
+    security(("bearer_auth" = []), ("session_cookie" = []), ("api_key" = [])),
+    tag = "Tasks"
+)]
+async fn export_tasks<T: TaskService + Send + Sync + 'static>(
+    State(state): State<AppState<T>>,
+    caller: Caller,
+    Query(query): Query<ExportQuery>,
+) -> impl IntoResponse {
+    let format = query.format;
+    let service = state.task_service.clone();
+    let start = ExportCursor {
+        after: None,
+        first_page: true,
+        done: false,
+    };
+
+    let pages = stream::try_unfold(start, move |cursor| {
+        let service = service.clone();
+        let caller = caller.clone();
+        async move {
+            if cursor.done {
+                return Ok(None);
+            }
+            let tasks = service
+                .get_tasks_page(&caller, cursor.after, EXPORT_PAGE_SIZE)
+                .await?;
+            if tasks.is_empty() {
+                return Ok(None);
+            }
+            let chunk = format.encode_page(&tasks, cursor.first_page);
+            let next = ExportCursor {
+                after: tasks.last().map(|task| task.id),
+                first_page: false,
+                done: (tasks.len() as i64) < EXPORT_PAGE_SIZE,
+            };
+            Ok::<_, AppError>(Some((chunk, next)))
+        }
+    });
+    let body = stream::once(future::ready(Ok(format.prologue())))
+        .chain(pages)
+        .chain(stream::once(future::ready(Ok(format.epilogue()))));
+
+    (
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"tasks.{}\"", format.extension()),
+            ),
+        ],
+        Body::from_stream(body),
+    )
+}
+
+// インポート（idで作成または更新し、行ごとのエラーをレポートで返す）
+#[utoipa::path(
+    post,
+    path = "/tasks/import",
+    params(ImportQuery),
+    request_body(content = String, description = "format で指定した形式のタスク一覧"),
+    responses(
+        (status = 200, description = "インポート結果", body = crate::models::task_transfer::ImportReport),
+        (status = 400, description = "ボディ全体をパースできない"),
+        (status = 403, description = "タスクを作成する権限がない")
+    ),
+    security(("bearer_auth" = []), ("session_cookie" = []), ("api_key" = [])),
+    tag = "Tasks"
+)]
+async fn import_tasks<T: TaskService>(
+    State(state): State<AppState<T>>,
+    caller: Caller,
+    Query(query): Query<ImportQuery>,
+    body: String,
+) -> Result<impl IntoResponse, AppError> {
+    let rows = query
+        .format
+        .parse_records(&body)
+        .map_err(AppError::BadRequest)?;
+    let report = state
+        .task_service
+        .import_tasks(&caller, rows, query.dry_run)
+        .await?;
+    Ok(Json(report))
+}
@@ -1,16 +1,25 @@
-use crate::models::task::Task;
+use crate::error::AppError;
+use crate::models::grant::{GrantPermission, SharedTask};
+use crate::models::task::{Task, TaskDetails, TaskFilter};
 use axum::{
-    extract::{Json, Path, State},
+    extract::{Json, Path, Query, State},
+    handler::Handler,
     http::StatusCode,
+    middleware,
     response::IntoResponse,
     routing::get,
     Router,
 };
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
+use crate::idempotency::middleware::{idempotent, IdempotencyState};
+use crate::models::caller::Caller;
+use crate::routes::{stats, task_transfer};
+use crate::usecase::idempotency_usecase::IdempotencyService;
 use crate::usecase::task_usecase::TaskService;
 
 #[derive(Clone)]
@@ -18,30 +27,53 @@ pub struct AppState<T: TaskService> {
     pub task_service: Arc<T>,
 }
 
-pub fn router<T: TaskService + Send + Sync + 'static + Clone>(task_service: T) -> Router {
+pub fn router<T, I>(task_service: T, idempotency_service: I) -> Router
+where
+    T: TaskService + Send + Sync + 'static + Clone,
+    I: IdempotencyService + Send + Sync + 'static + Clone,
+{
     let state = AppState {
         task_service: Arc::new(task_service),
     };
+    // 再送で重複して作成しないよう Idempotency-Key に対応する
+    let idempotency = IdempotencyState {
+        idempotency_service: Arc::new(idempotency_service),
+    };
     Router::new()
-        .route("/tasks", get(get_tasks::<T>).post(create_task::<T>))
+        .route(
+            "/tasks",
+            get(get_tasks::<T>).post(
+                create_task::<T>
+                    .layer(middleware::from_fn_with_state(idempotency, idempotent::<I>)),
+            ),
+        )
         .route(
             "/tasks/:id",
             get(get_task::<T>)
                 .put(update_task::<T>)
                 .delete(delete_task::<T>),
         )
+        .route("/shared-with-me", get(get_shared_tasks::<T>))
+        .merge(task_transfer::routes::<T>())
+        .merge(stats::routes::<T>())
         .with_state(state)
 }
 
 #[derive(Deserialize, ToSchema)]
 pub struct CreateTaskRequest {
     title: String,
+    due_at: Option<DateTime<Utc>>,
+    project: Option<String>,
+    tags: Option<Vec<String>>,
 }
 
 #[derive(Deserialize, ToSchema)]
 pub struct UpdateTaskRequest {
     title: Option<String>,
     completed: Option<bool>,
+    due_at: Option<DateTime<Utc>>,
+    project: Option<String>,
+    tags: Option<Vec<String>>,
 }
 
 #[derive(Serialize, ToSchema)]
@@ -49,6 +81,43 @@ pub struct TaskResponse {
     id: Uuid,
     title: String,
     completed: bool,
+    due_at: Option<DateTime<Utc>>,
+    project: Option<String>,
+    tags: Vec<String>,
+    completed_at: Option<DateTime<Utc>>,
+    /// 完了にしたユーザー
+    completed_by: Option<Uuid>,
+    tracked_seconds: i64,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct SharedTaskResponse {
+    /// タスクが属するワークスペース
+    workspace_id: Uuid,
+    permission: GrantPermission,
+    #[serde(flatten)]
+    task: TaskResponse,
+}
+
+impl From<SharedTask> for SharedTaskResponse {
+    fn from(shared: SharedTask) -> Self {
+        Self {
+            workspace_id: shared.task.workspace_id,
+            permission: shared.permission,
+            task: TaskResponse::from(shared.task),
+        }
+    }
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TaskListQuery {
+    /// プロジェクトで絞り込む
+    project: Option<String>,
+    /// タグで絞り込む
+    tag: Option<String>,
+    /// この日時以降に完了したタスクに絞り込む
+    completed_since: Option<DateTime<Utc>>,
 }
 
 impl From<Task> for TaskResponse {
@@ -57,6 +126,12 @@ impl From<Task> for TaskResponse {
             id: task.id,
             title: task.title,
             completed: task.completed,
+            due_at: task.due_at,
+            project: task.project,
+            tags: task.tags,
+            completed_at: task.completed_at,
+            completed_by: task.completed_by,
+            tracked_seconds: task.tracked_seconds,
         }
     }
 }
@@ -65,22 +140,30 @@ impl From<Task> for TaskResponse {
 #[utoipa::path(
     get,
     path = "/tasks",
+    params(TaskListQuery),
     responses(
         (status = 200, description = "タスク一覧取得成功", body = [TaskResponse])
     ),
+    security(("bearer_auth" = []), ("session_cookie" = []), ("api_key" = [])),
     tag = "Tasks"
 )]
-async fn get_tasks<T: TaskService>(State(state): State<AppState<T>>) -> impl IntoResponse {
-    match state.task_service.get_all_tasks().await {
-        Ok(tasks) => Json(
-            tasks
-                .into_iter()
-                .map(TaskResponse::from)
-                .collect::<Vec<_>>(),
-        )
-        .into_response(),
-        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch todos").into_response(),
-    }
+async fn get_tasks<T: TaskService>(
+    State(state): State<AppState<T>>,
+    caller: Caller,
+    Query(query): Query<TaskListQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let filter = TaskFilter {
+        project: query.project,
+        tag: query.tag,
+        completed_since: query.completed_since,
+    };
+    let tasks = state.task_service.get_all_tasks(&caller, filter).await?;
+    Ok(Json(
+        tasks
+            .into_iter()
+            .map(TaskResponse::from)
+            .collect::<Vec<_>>(),
+    ))
 }
 
 // 単一取得
@@ -94,17 +177,20 @@ async fn get_tasks<T: TaskService>(State(state): State<AppState<T>>) -> impl Int
         (status = 200, description = "タスク取得成功", body = TaskResponse),
         (status = 404, description = "タスクが存在しない")
     ),
+    security(("bearer_auth" = []), ("session_cookie" = []), ("api_key" = [])),
     tag = "Tasks"
 )]
 async fn get_task<T: TaskService>(
     State(state): State<AppState<T>>,
+    caller: Caller,
     Path(id): Path<Uuid>,
-) -> impl IntoResponse {
-    match state.task_service.get_task_by_id(id).await {
-        Ok(Some(task)) => Json(TaskResponse::from(task)).into_response(),
-        Ok(None) => (StatusCode::NOT_FOUND, "Task not found").into_response(),
-        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch todo").into_response(),
-    }
+) -> Result<impl IntoResponse, AppError> {
+    let task = state
+        .task_service
+        .get_task_by_id(&caller, id)
+        .await?
+        .ok_or(AppError::ResourceNotFound("Task"))?;
+    Ok(Json(TaskResponse::from(task)))
 }
 
 // 作成
@@ -112,19 +198,34 @@ async fn get_task<T: TaskService>(
     post,
     path = "/tasks",
     request_body = CreateTaskRequest,
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "再送時に同じタスクを重複して作成しないためのキー（24時間有効）")
+    ),
     responses(
-        (status = 201, description = "タスク作成成功", body = TaskResponse)
+        (status = 201, description = "タスク作成成功（同じキーの再送には Idempotent-Replayed: true を付けて最初のレスポンスを返す）", body = TaskResponse),
+        (status = 400, description = "タイトルが空か、Idempotency-Key が不正"),
+        (status = 403, description = "タスクを作成する権限がない"),
+        (status = 409, description = "同じ Idempotency-Key のリクエストを処理中"),
+        (status = 422, description = "Idempotency-Key が別の内容のリクエストに使われている")
     ),
+    security(("bearer_auth" = []), ("session_cookie" = []), ("api_key" = [])),
     tag = "Tasks"
 )]
 async fn create_task<T: TaskService>(
     State(state): State<AppState<T>>,
+    caller: Caller,
     Json(payload): Json<CreateTaskRequest>,
-) -> impl IntoResponse {
-    match state.task_service.create_task(payload.title).await {
-        Ok(task) => (StatusCode::CREATED, Json(TaskResponse::from(task))).into_response(),
-        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Failed to create todo").into_response(),
-    }
+) -> Result<impl IntoResponse, AppError> {
+    let details = TaskDetails {
+        due_at: payload.due_at,
+        project: payload.project,
+        tags: payload.tags,
+    };
+    let task = state
+        .task_service
+        .create_task(&caller, payload.title, details)
+        .await?;
+    Ok((StatusCode::CREATED, Json(TaskResponse::from(task))))
 }
 
 // 更新
@@ -137,24 +238,29 @@ async fn create_task<T: TaskService>(
     ),
     responses(
         (status = 200, description = "タスク更新成功", body = TaskResponse),
+        (status = 400, description = "タイトルが空"),
+        (status = 403, description = "タスクを更新する権限がない"),
         (status = 404, description = "タスクが存在しない")
     ),
+    security(("bearer_auth" = []), ("session_cookie" = []), ("api_key" = [])),
     tag = "Tasks"
 )]
 async fn update_task<T: TaskService>(
     State(state): State<AppState<T>>,
+    caller: Caller,
     Path(id): Path<Uuid>,
     Json(payload): Json<UpdateTaskRequest>,
-) -> impl IntoResponse {
-    match state
+) -> Result<impl IntoResponse, AppError> {
+    let details = TaskDetails {
+        due_at: payload.due_at,
+        project: payload.project,
+        tags: payload.tags,
+    };
+    let task = state
         .task_service
-        .update_task(id, payload.title, payload.completed)
-        .await
-    {
-        Ok(task) => Json(TaskResponse::from(task)).into_response(),
-        Err(sqlx::Error::RowNotFound) => (StatusCode::NOT_FOUND, "Task not found").into_response(),
-        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Failed to update todo").into_response(),
-    }
+        .update_task(&caller, id, payload.title, payload.completed, details)
+        .await?;
+    Ok(Json(TaskResponse::from(task)))
 }
 
 // 削除
@@ -166,17 +272,40 @@ async fn update_task<T: TaskService>(
     ),
     responses(
         (status = 204, description = "タスク削除成功"),
+        (status = 403, description = "タスクを削除する権限がない"),
         (status = 404, description = "タスクが存在しない")
     ),
+    security(("bearer_auth" = []), ("session_cookie" = []), ("api_key" = [])),
     tag = "Tasks"
 )]
 async fn delete_task<T: TaskService>(
     State(state): State<AppState<T>>,
+    caller: Caller,
     Path(id): Path<Uuid>,
-) -> impl IntoResponse {
-    match state.task_service.delete_task(id).await {
-        Ok(()) => StatusCode::NO_CONTENT.into_response(),
-        Err(sqlx::Error::RowNotFound) => (StatusCode::NOT_FOUND, "Task not found").into_response(),
-        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Failed to delete todo").into_response(),
-    }
+) -> Result<impl IntoResponse, AppError> {
+    state.task_service.delete_task(&caller, id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// 自分に共有されたタスクの一覧
+#[utoipa::path(
+    get,
+    path = "/shared-with-me",
+    responses(
+        (status = 200, description = "共有されたタスク一覧取得成功", body = [SharedTaskResponse])
+    ),
+    security(("bearer_auth" = []), ("session_cookie" = [])),
+    tag = "Sharing"
+)]
+async fn get_shared_tasks<T: TaskService>(
+    State(state): State<AppState<T>>,
+    caller: Caller,
+) -> Result<impl IntoResponse, AppError> {
+    let shared = state.task_service.get_shared_tasks(&caller).await?;
+    Ok(Json(
+        shared
+            .into_iter()
+            .map(SharedTaskResponse::from)
+            .collect::<Vec<_>>(),
+    ))
 }
@@ -0,0 +1,159 @@
+use crate::models::caller::Caller;
+use crate::models::workspace::WorkspaceRole;
+use crate::repositories::calendar_token_repository::MockCalendarTokenRepository;
+use crate::repositories::task_repository::MockTaskRepository;
+use crate::repositories::workspace_repository::MockWorkspaceRepository;
+use crate::routes::calendar;
+use crate::usecase::calendar_usecase::CalendarUsecase;
+use axum::{
+    body::Body,
+    http::{Method, Request, StatusCode},
+    Extension, Router,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+const USER_ID: Uuid = Uuid::from_u128(1);
+const WORKSPACE_ID: Uuid = Uuid::from_u128(10);
+
+type Usecase =
+    CalendarUsecase<MockCalendarTokenRepository, MockTaskRepository, MockWorkspaceRepository>;
+
+fn usecase(token_repo: MockCalendarTokenRepository) -> Usecase {
+    CalendarUsecase::new(
+        token_repo,
+        MockTaskRepository::new(),
+        MockWorkspaceRepository::new(),
+    )
+}
+
+// 認証済みの呼び出し元（認証ミドルウェアの代わりに Caller を設定する）
+fn token_router(token_repo: MockCalendarTokenRepository) -> Router {
+    calendar::router(usecase(token_repo)).layer(Extension(Caller::new(
+        USER_ID,
+        WORKSPACE_ID,
+        WorkspaceRole::Member,
+    )))
+}
+
+fn request(method: Method, uri: &str) -> Request<Body> {
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .body(Body::empty())
+        .unwrap()
+}
+
+async fn body_string(response: axum::response::Response) -> String {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_rotate_token_returns_new_feed_url() {
+        let mut token_repo = MockCalendarTokenRepository::new();
+        token_repo
+            .expect_replace()
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        let response = token_router(token_repo)
+            .oneshot(request(Method::POST, "/calendar/tokens/rotate"))
+            .await
+            .unwrap();
+
+        // 新しいトークンとフィードのURLを返す
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body: Value = serde_json::from_str(&body_string(response).await).unwrap();
+        let token = body["token"].as_str().unwrap();
+        assert_eq!(token.len(), 64);
+        assert_eq!(body["url"], format!("/calendar.ics?token={}", token));
+    }
+
+    #[tokio::test]
+    async fn test_revoke_tokens_returns_no_content() {
+        let mut token_repo = MockCalendarTokenRepository::new();
+        token_repo
+            .expect_delete_for_user()
+            .times(1)
+            .returning(|_, _| Ok(1));
+
+        let response = token_router(token_repo)
+            .oneshot(request(Method::DELETE, "/calendar/tokens"))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn test_revoke_tokens_without_tokens_returns_not_found() {
+        let mut token_repo = MockCalendarTokenRepository::new();
+        token_repo
+            .expect_delete_for_user()
+            .times(1)
+            .returning(|_, _| Ok(0));
+
+        let response = token_router(token_repo)
+            .oneshot(request(Method::DELETE, "/calendar/tokens"))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_token_routes_require_caller() {
+        let mut token_repo = MockCalendarTokenRepository::new();
+        token_repo.expect_delete_for_user().never();
+
+        // 認証ミドルウェアを通っていないリクエストは拒否する
+        let response = calendar::router(usecase(token_repo))
+            .oneshot(request(Method::DELETE, "/calendar/tokens"))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_feed_database_error_returns_app_error() {
+        let mut token_repo = MockCalendarTokenRepository::new();
+        token_repo
+            .expect_find_owner()
+            .times(1)
+            .returning(|_| Err(sqlx::Error::PoolTimedOut));
+
+        let response = calendar::feed_router(usecase(token_repo))
+            .oneshot(request(Method::GET, "/calendar.ics?token=token"))
+            .await
+            .unwrap();
+
+        // データベースのエラーの内容は返さない
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body_string(response).await, "Database error");
+    }
+
+    #[tokio::test]
+    async fn test_feed_with_invalid_token_returns_not_found() {
+        let mut token_repo = MockCalendarTokenRepository::new();
+        token_repo
+            .expect_find_owner()
+            .times(1)
+            .returning(|_| Ok(None));
+
+        let response = calendar::feed_router(usecase(token_repo))
+            .oneshot(request(Method::GET, "/calendar.ics?token=invalid"))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1 @@
+pub mod calendar_tests;
@@ -0,0 +1,178 @@
+use crate::error::AppError;
+use crate::models::caller::Caller;
+use crate::models::time_entry::TimeReportQuery;
+use crate::usecase::time_tracking_usecase::TimeTrackingService;
+use axum::{
+    extract::{Json, Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, post},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::sync::Arc;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct TimeTrackingState<S: TimeTrackingService> {
+    pub time_tracking_service: Arc<S>,
+}
+
+pub fn router<S: TimeTrackingService + Send + Sync + 'static + Clone>(
+    time_tracking_service: S,
+) -> Router {
+    let state = TimeTrackingState {
+        time_tracking_service: Arc::new(time_tracking_service),
+    };
+    Router::new()
+        .route("/tasks/:id/timer/start", post(start_timer::<S>))
+        .route("/tasks/:id/timer/stop", post(stop_timer::<S>))
+        .route(
+            "/tasks/:id/time-entries",
+            get(list_time_entries::<S>).post(create_time_entry::<S>),
+        )
+        .route("/reports/time", get(get_time_report::<S>))
+        .with_state(state)
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateTimeEntryRequest {
+    started_at: DateTime<Utc>,
+    ended_at: DateTime<Utc>,
+    note: Option<String>,
+}
+
+// タイマー開始
+#[utoipa::path(
+    post,
+    path = "/tasks/{id}/timer/start",
+    params(
+        ("id" = Uuid, Path, description = "タスクのUUID")
+    ),
+    responses(
+        (status = 201, description = "タイマー開始成功", body = crate::models::time_entry::TimeEntry),
+        (status = 404, description = "タスクが存在しない"),
+        (status = 409, description = "既にタイマーが動いている")
+    ),
+    security(("bearer_auth" = []), ("session_cookie" = []), ("api_key" = [])),
+    tag = "TimeTracking"
+)]
+async fn start_timer<S: TimeTrackingService>(
+    State(state): State<TimeTrackingState<S>>,
+    caller: Caller,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let entry = state.time_tracking_service.start_timer(&caller, id).await?;
+    Ok((StatusCode::CREATED, Json(entry)))
+}
+
+// タイマー停止
+#[utoipa::path(
+    post,
+    path = "/tasks/{id}/timer/stop",
+    params(
+        ("id" = Uuid, Path, description = "タスクのUUID")
+    ),
+    responses(
+        (status = 200, description = "タイマー停止成功", body = crate::models::time_entry::TimeEntry),
+        (status = 404, description = "タスクで動いているタイマーがない")
+    ),
+    security(("bearer_auth" = []), ("session_cookie" = []), ("api_key" = [])),
+    tag = "TimeTracking"
+)]
+async fn stop_timer<S: TimeTrackingService>(
+    State(state): State<TimeTrackingState<S>>,
+    caller: Caller,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let entry = state.time_tracking_service.stop_timer(&caller, id).await?;
+    Ok(Json(entry))
+}
+
+// 作業時間の一覧
+#[utoipa::path(
+    get,
+    path = "/tasks/{id}/time-entries",
+    params(
+        ("id" = Uuid, Path, description = "タスクのUUID")
+    ),
+    responses(
+        (status = 200, description = "作業時間一覧取得成功", body = [crate::models::time_entry::TimeEntry]),
+        (status = 404, description = "タスクが存在しない")
+    ),
+    security(("bearer_auth" = []), ("session_cookie" = []), ("api_key" = [])),
+    tag = "TimeTracking"
+)]
+async fn list_time_entries<S: TimeTrackingService>(
+    State(state): State<TimeTrackingState<S>>,
+    caller: Caller,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let entries = state
+        .time_tracking_service
+        .list_time_entries(&caller, id)
+        .await?;
+    Ok(Json(entries))
+}
+
+// 作業時間の手動記録
+#[utoipa::path(
+    post,
+    path = "/tasks/{id}/time-entries",
+    request_body = CreateTimeEntryRequest,
+    params(
+        ("id" = Uuid, Path, description = "タスクのUUID")
+    ),
+    responses(
+        (status = 201, description = "作業時間記録成功", body = crate::models::time_entry::TimeEntry),
+        (status = 400, description = "終了日時が開始日時以前"),
+        (status = 404, description = "タスクが存在しない")
+    ),
+    security(("bearer_auth" = []), ("session_cookie" = []), ("api_key" = [])),
+    tag = "TimeTracking"
+)]
+async fn create_time_entry<S: TimeTrackingService>(
+    State(state): State<TimeTrackingState<S>>,
+    caller: Caller,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CreateTimeEntryRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let entry = state
+        .time_tracking_service
+        .add_time_entry(
+            &caller,
+            id,
+            payload.started_at,
+            payload.ended_at,
+            payload.note,
+        )
+        .await?;
+    Ok((StatusCode::CREATED, Json(entry)))
+}
+
+// 日付×プロジェクトごとの作業時間レポート
+#[utoipa::path(
+    get,
+    path = "/reports/time",
+    params(TimeReportQuery),
+    responses(
+        (status = 200, description = "作業時間レポート取得成功", body = [crate::models::time_entry::TimeReportRow]),
+        (status = 400, description = "集計範囲またはタイムゾーンが不正")
+    ),
+    security(("bearer_auth" = []), ("session_cookie" = []), ("api_key" = [])),
+    tag = "TimeTracking"
+)]
+async fn get_time_report<S: TimeTrackingService>(
+    State(state): State<TimeTrackingState<S>>,
+    caller: Caller,
+    Query(query): Query<TimeReportQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let range = query.resolve(Utc::now()).map_err(AppError::BadRequest)?;
+    let rows = state
+        .time_tracking_service
+        .time_report(&caller, range)
+        .await?;
+    Ok(Json(rows))
+}
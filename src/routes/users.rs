@@ -1,25 +1,76 @@
 use crate::error::AppError;
-use crate::models::user::User;
+use crate::metrics::registry::METRICS;
+use crate::models::user::{User, USERS_API_URL};
+use crate::request_id::middleware::{current_request_id, REQUEST_ID_HEADER};
+use crate::telemetry::propagation::inject_context;
 use axum::{
     extract::Path,
     response::{IntoResponse, Json},
     routing::get,
     Router,
 };
+use once_cell::sync::Lazy;
+use reqwest::header::{HeaderMap, HeaderValue};
+use std::time::Instant;
+use tracing::instrument;
+
+// upstream_* メトリクスの upstream ラベル
+const USERS_UPSTREAM: &str = "users";
+
+// 接続を使い回すため、users API へのリクエストで共有する
+static USERS_API_CLIENT: Lazy<reqwest::Client> = Lazy::new(reqwest::Client::new);
 
 pub fn router() -> Router {
     Router::new().route("/users/:id", get(get_user))
 }
 
 pub async fn get_user(Path(user_id): Path<u32>) -> Result<impl IntoResponse, AppError> {
-    let url = format!("https://jsonplaceholder.typicode.com/users/{}", user_id);
+    let url = format!("{}/{}", USERS_API_URL, user_id);
 
-    let resp = reqwest::get(&url).await?;
+    let started = Instant::now();
+    let result = fetch_user(&url).await;
+    let outcome = if result.is_ok() { "ok" } else { "error" };
+    METRICS
+        .upstream_request_duration_seconds
+        .with_label_values(&[USERS_UPSTREAM, outcome])
+        .observe(started.elapsed().as_secs_f64());
 
-    if resp.status().as_u16() == 404 {
-        return Err(AppError::NotFound);
+    match result {
+        Ok(Some(user)) => Ok(Json(user)),
+        Ok(None) => Err(AppError::NotFound),
+        Err((kind, e)) => {
+            METRICS
+                .upstream_errors_total
+                .with_label_values(&[USERS_UPSTREAM, kind])
+                .inc();
+            Err(e.into())
+        }
     }
+}
 
-    let user: User = resp.json().await?;
-    Ok(Json(user))
+// ユーザーがいない場合は None（エラーの場合は upstream_errors_total の kind とともに返す）
+// クライアント側のスパンを作り、traceparent と X-Request-Id で users API に伝える
+#[instrument(
+    name = "GET users",
+    skip_all,
+    fields(otel.kind = "client", http.request.method = "GET", url.full = %url)
+)]
+async fn fetch_user(url: &str) -> Result<Option<User>, (&'static str, reqwest::Error)> {
+    let mut headers = HeaderMap::new();
+    inject_context(&mut headers);
+    if let Some(value) = current_request_id().and_then(|id| HeaderValue::from_str(id.as_str()).ok())
+    {
+        headers.insert(REQUEST_ID_HEADER, value);
+    }
+    let resp = USERS_API_CLIENT
+        .get(url)
+        .headers(headers)
+        .send()
+        .await
+        .map_err(|e| ("request", e))?;
+    if resp.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    let resp = resp.error_for_status().map_err(|e| ("status", e))?;
+    resp.json().await.map(Some).map_err(|e| ("decode", e))
 }
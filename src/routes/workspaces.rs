@@ -0,0 +1,175 @@
+use crate::error::AppError;
+use crate::models::caller::Caller;
+use crate::models::workspace::WorkspaceRole;
+use crate::usecase::workspace_usecase::WorkspaceService;
+use axum::{
+    extract::{Json, Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{delete, get},
+    Router,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct WorkspaceState<W: WorkspaceService> {
+    pub workspace_service: Arc<W>,
+}
+
+pub fn router<W: WorkspaceService + Send + Sync + 'static + Clone>(workspace_service: W) -> Router {
+    let state = WorkspaceState {
+        workspace_service: Arc::new(workspace_service),
+    };
+    Router::new()
+        .route(
+            "/workspaces",
+            get(list_workspaces::<W>).post(create_workspace::<W>),
+        )
+        .route(
+            "/workspaces/:id/members",
+            get(list_members::<W>).put(set_member_role::<W>),
+        )
+        .route(
+            "/workspaces/:id/members/:user_id",
+            delete(remove_member::<W>),
+        )
+        .with_state(state)
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateWorkspaceRequest {
+    name: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct SetMemberRoleRequest {
+    email: String,
+    role: WorkspaceRole,
+}
+
+// 所属するワークスペースの一覧
+#[utoipa::path(
+    get,
+    path = "/workspaces",
+    responses(
+        (status = 200, description = "ワークスペース一覧取得成功", body = [crate::models::workspace::WorkspaceMembership])
+    ),
+    security(("bearer_auth" = []), ("session_cookie" = [])),
+    tag = "Workspaces"
+)]
+async fn list_workspaces<W: WorkspaceService>(
+    State(state): State<WorkspaceState<W>>,
+    caller: Caller,
+) -> Result<impl IntoResponse, AppError> {
+    let workspaces = state.workspace_service.list_workspaces(&caller).await?;
+    Ok(Json(workspaces))
+}
+
+// ワークスペースの作成（作成したユーザーが管理者になる）
+#[utoipa::path(
+    post,
+    path = "/workspaces",
+    request_body = CreateWorkspaceRequest,
+    responses(
+        (status = 201, description = "ワークスペース作成成功", body = crate::models::workspace::Workspace),
+        (status = 400, description = "名前が空")
+    ),
+    security(("bearer_auth" = []), ("session_cookie" = [])),
+    tag = "Workspaces"
+)]
+async fn create_workspace<W: WorkspaceService>(
+    State(state): State<WorkspaceState<W>>,
+    caller: Caller,
+    Json(payload): Json<CreateWorkspaceRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let workspace = state
+        .workspace_service
+        .create_workspace(&caller, payload.name)
+        .await?;
+    Ok((StatusCode::CREATED, Json(workspace)))
+}
+
+// メンバーの一覧
+#[utoipa::path(
+    get,
+    path = "/workspaces/{id}/members",
+    params(
+        ("id" = Uuid, Path, description = "ワークスペースのUUID")
+    ),
+    responses(
+        (status = 200, description = "メンバー一覧取得成功", body = [crate::models::workspace::WorkspaceMember]),
+        (status = 404, description = "ワークスペースが存在しないかメンバーでない")
+    ),
+    security(("bearer_auth" = []), ("session_cookie" = [])),
+    tag = "Workspaces"
+)]
+async fn list_members<W: WorkspaceService>(
+    State(state): State<WorkspaceState<W>>,
+    caller: Caller,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let members = state.workspace_service.list_members(&caller, id).await?;
+    Ok(Json(members))
+}
+
+// メンバーの追加・ロール変更
+#[utoipa::path(
+    put,
+    path = "/workspaces/{id}/members",
+    request_body = SetMemberRoleRequest,
+    params(
+        ("id" = Uuid, Path, description = "ワークスペースのUUID")
+    ),
+    responses(
+        (status = 200, description = "メンバー追加・ロール変更成功", body = crate::models::workspace::WorkspaceMember),
+        (status = 400, description = "自分のロールは変更できない"),
+        (status = 403, description = "管理者ではない"),
+        (status = 404, description = "ワークスペースまたはユーザーが存在しない")
+    ),
+    security(("bearer_auth" = []), ("session_cookie" = [])),
+    tag = "Workspaces"
+)]
+async fn set_member_role<W: WorkspaceService>(
+    State(state): State<WorkspaceState<W>>,
+    caller: Caller,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<SetMemberRoleRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let member = state
+        .workspace_service
+        .set_member_role(&caller, id, payload.email, payload.role)
+        .await?;
+    Ok(Json(member))
+}
+
+// メンバーの削除
+#[utoipa::path(
+    delete,
+    path = "/workspaces/{id}/members/{user_id}",
+    params(
+        ("id" = Uuid, Path, description = "ワークスペースのUUID"),
+        ("user_id" = Uuid, Path, description = "外すユーザーのUUID")
+    ),
+    responses(
+        (status = 204, description = "メンバー削除成功"),
+        (status = 400, description = "自分自身は外せない"),
+        (status = 403, description = "管理者ではない"),
+        (status = 404, description = "ワークスペースまたはメンバーが存在しない")
+    ),
+    security(("bearer_auth" = []), ("session_cookie" = [])),
+    tag = "Workspaces"
+)]
+async fn remove_member<W: WorkspaceService>(
+    State(state): State<WorkspaceState<W>>,
+    caller: Caller,
+    Path((id, user_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, AppError> {
+    state
+        .workspace_service
+        .remove_member(&caller, id, user_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,56 @@
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+// URLに埋め込んで使う推測不能なトークンを生成する（32バイトの乱数を16進文字列にしたもの）
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+// APIキーの検索用に公開する短い識別子を生成する（6バイトの乱数を16進文字列にしたもの）
+pub fn generate_key_prefix() -> String {
+    let mut bytes = [0u8; 6];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+// トークンはDBに平文で保存せず、SHA-256のハッシュで照合する
+pub fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+// トークンと保存したハッシュを照合する（一致した桁数が処理時間から分からないよう、定数時間で比較する）
+pub fn verify_token(token: &str, token_hash: &str) -> bool {
+    let Ok(expected) = hex::decode(token_hash) else {
+        return false;
+    };
+    Sha256::digest(token.as_bytes())
+        .as_slice()
+        .ct_eq(&expected)
+        .into()
+}
+
+// パスワードを Argon2id でハッシュ化する（PHC文字列形式）
+pub fn hash_password(password: &str) -> Result<String, argon2::password_hash::Error> {
+    let mut salt = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut salt);
+    let salt = SaltString::encode_b64(&salt)?;
+    Ok(Argon2::default()
+        .hash_password(password.as_bytes(), &salt)?
+        .to_string())
+}
+
+// PHC文字列形式のハッシュとパスワードを照合する
+pub fn verify_password(password: &str, password_hash: &str) -> bool {
+    PasswordHash::new(password_hash)
+        .map(|hash| {
+            Argon2::default()
+                .verify_password(password.as_bytes(), &hash)
+                .is_ok()
+        })
+        .unwrap_or(false)
+}
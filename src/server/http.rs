@@ -0,0 +1,62 @@
+use crate::server::shutdown::Shutdown;
+use axum::Router;
+use std::future::IntoFuture;
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tracing::{info, warn};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DrainOutcome {
+    // 処理中のリクエストがすべて終わった
+    Drained,
+    // 期限を過ぎた（残りのリクエストはランタイムの終了とともに打ち切られる）
+    TimedOut,
+}
+
+// 終了が始まっても drain_delay の間はリクエストを受け付ける（その間 /readyz は 503 を返す）
+// その後は新しい接続を受け付けず、処理中のリクエストが終わるまで drain_timeout を上限に待つ
+pub async fn serve(
+    listener: TcpListener,
+    app: Router,
+    shutdown: Shutdown,
+    drain_delay: Duration,
+    drain_timeout: Duration,
+) -> std::io::Result<DrainOutcome> {
+    let signal = shutdown.clone();
+    let drain_started = async move {
+        signal.wait().await;
+        if !drain_delay.is_zero() {
+            info!(
+                "shutting down, accepting requests for {}s before draining",
+                drain_delay.as_secs()
+            );
+            tokio::time::sleep(drain_delay).await;
+        }
+    };
+    // レート制限でクライアントのIPを使うため、接続元のアドレスをリクエストに追加する
+    let server = axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(drain_started)
+    .into_future();
+
+    let deadline = async {
+        shutdown.wait().await;
+        tokio::time::sleep(drain_delay).await;
+        info!(
+            "shutting down, waiting up to {}s for in-flight requests",
+            drain_timeout.as_secs()
+        );
+        tokio::time::sleep(drain_timeout).await;
+    };
+
+    tokio::select! {
+        result = server => result.map(|_| DrainOutcome::Drained),
+        _ = deadline => {
+            warn!("in-flight requests did not finish in time");
+            Ok(DrainOutcome::TimedOut)
+        }
+    }
+}
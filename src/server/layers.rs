@@ -0,0 +1,121 @@
+use crate::app::AppOptions;
+use crate::auth::middleware::{API_KEY_HEADER, CSRF_TOKEN_HEADER, WORKSPACE_ID_HEADER};
+use crate::idempotency::middleware::{IDEMPOTENCY_KEY_HEADER, IDEMPOTENT_REPLAYED_HEADER};
+use crate::rate_limit::middleware::{
+    RATE_LIMIT_LIMIT_HEADER, RATE_LIMIT_POLICY_HEADER, RATE_LIMIT_REMAINING_HEADER,
+    RATE_LIMIT_RESET_HEADER,
+};
+use crate::request_id::middleware::REQUEST_ID_HEADER;
+use axum::extract::DefaultBodyLimit;
+use axum::http::{header, HeaderName, HeaderValue, Method};
+use axum::Router;
+use std::time::Duration;
+use tower_http::compression::CompressionLayer;
+use tower_http::cors::CorsLayer;
+use tower_http::decompression::RequestDecompressionLayer;
+use tower_http::limit::RequestBodyLimitLayer;
+use tower_http::set_header::SetResponseHeaderLayer;
+use tower_http::timeout::TimeoutLayer;
+
+// API のレスポンスの CSP（HTML を返さないため、何も読み込ませず、フレームにも埋め込ませない）
+pub const API_CONTENT_SECURITY_POLICY: &str = "default-src 'none'; frame-ancestors 'none'";
+
+// Swagger UI の CSP（同じオリジンのスクリプトだけを読み込む。スタイルは Swagger UI がインラインで付ける）
+pub const SWAGGER_UI_CONTENT_SECURITY_POLICY: &str = "default-src 'self'; style-src 'self' 'unsafe-inline'; img-src 'self' data:; frame-ancestors 'none'";
+
+// 別オリジンのブラウザから Cookie 付きで呼び出せるようにする（オリジンは許可リストに限る）
+fn cors_layer(allowed_origins: Vec<HeaderValue>) -> CorsLayer {
+    CorsLayer::new()
+        .allow_origin(allowed_origins)
+        .allow_credentials(true)
+        .allow_methods([
+            Method::GET,
+            Method::POST,
+            Method::PUT,
+            Method::PATCH,
+            Method::DELETE,
+        ])
+        .allow_headers([
+            header::AUTHORIZATION,
+            header::CONTENT_TYPE,
+            header::CONTENT_ENCODING,
+            HeaderName::from_static(API_KEY_HEADER),
+            HeaderName::from_static(CSRF_TOKEN_HEADER),
+            HeaderName::from_static(WORKSPACE_ID_HEADER),
+            HeaderName::from_static(IDEMPOTENCY_KEY_HEADER),
+            HeaderName::from_static(REQUEST_ID_HEADER),
+        ])
+        .expose_headers([
+            header::RETRY_AFTER,
+            header::LOCATION,
+            RATE_LIMIT_LIMIT_HEADER,
+            RATE_LIMIT_REMAINING_HEADER,
+            RATE_LIMIT_RESET_HEADER,
+            RATE_LIMIT_POLICY_HEADER,
+            HeaderName::from_static(IDEMPOTENT_REPLAYED_HEADER),
+            HeaderName::from_static(REQUEST_ID_HEADER),
+        ])
+        .max_age(Duration::from_secs(600))
+}
+
+// リクエストの処理時間と本文の大きさを制限する（メトリクスやアクセスログより内側に付ける）
+pub fn with_request_limits(router: Router, options: &AppOptions) -> Router {
+    // 処理が長引いたリクエストは timeout_status で打ち切る
+    let router = router.layer(TimeoutLayer::with_status_code(
+        options.timeout_status,
+        options.request_timeout,
+    ));
+    // 本文の大きさは展開した後に数える（小さく圧縮した巨大な本文を受け付けないため）
+    // 本文を読むエクストラクタの既定の上限（2MB）は外し、max_body_bytes だけで制限する
+    let router = router
+        .layer(DefaultBodyLimit::disable())
+        .layer(RequestBodyLimitLayer::new(options.max_body_bytes));
+    if options.compression {
+        router.layer(RequestDecompressionLayer::new())
+    } else {
+        router
+    }
+}
+
+// レスポンスの圧縮・セキュリティ関連のヘッダー・CORS を付ける（メトリクスやアクセスログより外側に付ける）
+pub fn with_response_layers(router: Router, options: &AppOptions) -> Router {
+    let mut router = router;
+    if options.compression {
+        router = router.layer(CompressionLayer::new());
+    }
+    router = with_security_headers(router, options.hsts_max_age);
+    if !options.cors_allowed_origins.is_empty() {
+        router = router.layer(cors_layer(options.cors_allowed_origins.clone()));
+    }
+    router
+}
+
+// すべてのレスポンスにセキュリティ関連のヘッダーを付ける（ルートが付けたものはそのまま）
+// HSTS は HTTPS で公開する場合だけ付ける（hsts_max_age が None の場合は付けない）
+fn with_security_headers(router: Router, hsts_max_age: Option<Duration>) -> Router {
+    let router = router
+        .layer(SetResponseHeaderLayer::if_not_present(
+            header::X_CONTENT_TYPE_OPTIONS,
+            HeaderValue::from_static("nosniff"),
+        ))
+        .layer(SetResponseHeaderLayer::if_not_present(
+            header::X_FRAME_OPTIONS,
+            HeaderValue::from_static("DENY"),
+        ))
+        .layer(SetResponseHeaderLayer::if_not_present(
+            header::REFERRER_POLICY,
+            HeaderValue::from_static("no-referrer"),
+        ))
+        .layer(SetResponseHeaderLayer::if_not_present(
+            header::CONTENT_SECURITY_POLICY,
+            HeaderValue::from_static(API_CONTENT_SECURITY_POLICY),
+        ));
+    match hsts_max_age {
+        Some(max_age) => router.layer(SetResponseHeaderLayer::if_not_present(
+            header::STRICT_TRANSPORT_SECURITY,
+            HeaderValue::from_str(&format!("max-age={}; includeSubDomains", max_age.as_secs()))
+                .expect("valid header value"),
+        )),
+        None => router,
+    }
+}
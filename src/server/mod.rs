@@ -0,0 +1,6 @@
+pub mod http;
+pub mod layers;
+pub mod shutdown;
+
+#[cfg(test)]
+pub mod tests;
@@ -0,0 +1,154 @@
+use crate::repositories::health_repository::MockHealthRepository;
+use crate::repositories::upstream_probe::MockUpstreamProbe;
+use crate::routes::health;
+use crate::server::http::{serve, DrainOutcome};
+use crate::server::shutdown::Shutdown;
+use crate::usecase::health_usecase::HealthUsecase;
+use axum::{routing::get, Router};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use tokio::time::timeout;
+
+// 呼ばれたことを started で知らせてから、delay の間処理を続けるハンドラー
+fn slow_router(started: Arc<Notify>, delay: Duration) -> Router {
+    Router::new().route(
+        "/slow",
+        get(move || async move {
+            started.notify_one();
+            tokio::time::sleep(delay).await;
+            "done"
+        }),
+    )
+}
+
+// 依存先が正常で、終了中かどうかだけで /readyz の結果が変わるルート
+fn health_router(shutdown: Shutdown) -> Router {
+    let mut repo = MockHealthRepository::new();
+    repo.expect_ping().returning(|| Ok(()));
+    repo.expect_pending_migrations().returning(|| Ok(vec![]));
+    health::router(HealthUsecase::<_, MockUpstreamProbe>::new(
+        repo,
+        None,
+        shutdown,
+        Duration::from_secs(1),
+    ))
+}
+
+// 実際のソケットでサーバーを起動する
+async fn start_server(
+    app: Router,
+    shutdown: Shutdown,
+    drain_delay: Duration,
+    drain_timeout: Duration,
+) -> (SocketAddr, JoinHandle<std::io::Result<DrainOutcome>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(serve(listener, app, shutdown, drain_delay, drain_timeout));
+    (addr, server)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_in_flight_request_completes_after_shutdown() {
+        let started = Arc::new(Notify::new());
+        let shutdown = Shutdown::new();
+        let (addr, server) = start_server(
+            slow_router(started.clone(), Duration::from_millis(300)),
+            shutdown.clone(),
+            Duration::ZERO,
+            Duration::from_secs(5),
+        )
+        .await;
+
+        let request = tokio::spawn(reqwest::get(format!("http://{}/slow", addr)));
+        started.notified().await;
+        shutdown.trigger();
+
+        // 処理中のリクエストは最後まで処理される
+        let response = request.await.unwrap().unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+        assert_eq!(response.text().await.unwrap(), "done");
+
+        // 処理中のリクエストがなくなったらサーバーが止まり、新しい接続は受け付けない
+        let outcome = timeout(Duration::from_secs(2), server)
+            .await
+            .expect("server should stop after draining")
+            .unwrap()
+            .unwrap();
+        assert_eq!(outcome, DrainOutcome::Drained);
+        assert!(reqwest::get(format!("http://{}/slow", addr)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_drain_timeout_stops_server() {
+        let started = Arc::new(Notify::new());
+        let shutdown = Shutdown::new();
+        let (addr, server) = start_server(
+            slow_router(started.clone(), Duration::from_secs(30)),
+            shutdown.clone(),
+            Duration::ZERO,
+            Duration::from_millis(100),
+        )
+        .await;
+
+        let request = tokio::spawn(reqwest::get(format!("http://{}/slow", addr)));
+        started.notified().await;
+        shutdown.trigger();
+
+        // 期限を過ぎたら処理中のリクエストを待たずに止まる
+        let outcome = timeout(Duration::from_secs(2), server)
+            .await
+            .expect("server should stop at the drain deadline")
+            .unwrap()
+            .unwrap();
+        assert_eq!(outcome, DrainOutcome::TimedOut);
+        request.abort();
+    }
+
+    #[tokio::test]
+    async fn test_readiness_fails_while_requests_are_still_served() {
+        let shutdown = Shutdown::new();
+        let app = health_router(shutdown.clone())
+            .merge(slow_router(Arc::new(Notify::new()), Duration::ZERO));
+        let (addr, server) = start_server(
+            app,
+            shutdown.clone(),
+            Duration::from_millis(500),
+            Duration::from_secs(5),
+        )
+        .await;
+
+        let ready = reqwest::get(format!("http://{}/readyz", addr))
+            .await
+            .unwrap();
+        assert_eq!(ready.status(), reqwest::StatusCode::OK);
+
+        shutdown.trigger();
+
+        // 待っている間は /readyz だけが 503 になり、新しい接続のリクエストも処理される
+        let ready = reqwest::get(format!("http://{}/readyz", addr))
+            .await
+            .unwrap();
+        assert_eq!(ready.status(), reqwest::StatusCode::SERVICE_UNAVAILABLE);
+        let response = reqwest::get(format!("http://{}/slow", addr)).await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+        assert_eq!(response.text().await.unwrap(), "done");
+        assert!(!server.is_finished());
+
+        // 待ち終わったら接続を止めて終了する
+        let outcome = timeout(Duration::from_secs(2), server)
+            .await
+            .expect("server should stop after the delay")
+            .unwrap()
+            .unwrap();
+        assert_eq!(outcome, DrainOutcome::Drained);
+        assert!(reqwest::get(format!("http://{}/slow", addr)).await.is_err());
+    }
+}
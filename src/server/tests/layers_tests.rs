@@ -0,0 +1,271 @@
+use crate::app::AppOptions;
+use crate::server::layers::{with_request_limits, with_response_layers};
+use axum::{
+    body::Body,
+    http::{header, HeaderValue, Request, StatusCode},
+    response::Response,
+    routing::{get, post},
+    Router,
+};
+use std::time::Duration;
+use tower::ServiceExt;
+
+const ALLOWED_ORIGIN: &str = "http://localhost:5173";
+
+fn options() -> AppOptions {
+    AppOptions {
+        rate_limit: None,
+        swagger_ui: false,
+        metrics: None,
+        access_log: None,
+        cors_allowed_origins: vec![HeaderValue::from_static(ALLOWED_ORIGIN)],
+        request_timeout: Duration::from_millis(100),
+        timeout_status: StatusCode::SERVICE_UNAVAILABLE,
+        max_body_bytes: 1024,
+        compression: true,
+        hsts_max_age: None,
+    }
+}
+
+// 圧縮される大きさのレスポンス・本文のエコー・タイムアウトするハンドラー
+fn router(options: &AppOptions) -> Router {
+    let app = Router::new()
+        .route("/large", get(|| async { "a".repeat(4096) }))
+        .route("/echo", post(|body: String| async move { body }))
+        .route(
+            "/slow",
+            get(|| async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                "done"
+            }),
+        )
+        .route(
+            "/framed",
+            get(|| async {
+                (
+                    [(header::CONTENT_SECURITY_POLICY, "frame-ancestors 'self'")],
+                    "framed",
+                )
+            }),
+        );
+    with_response_layers(with_request_limits(app, options), options)
+}
+
+async fn send(options: &AppOptions, request: Request<Body>) -> Response {
+    router(options).oneshot(request).await.unwrap()
+}
+
+async fn body_bytes(response: Response) -> Vec<u8> {
+    axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap()
+        .to_vec()
+}
+
+// 圧縮 layer 自身で /large のレスポンスを gzip にしたもの（展開すると 4096 バイト）
+async fn gzip_of_large(options: &AppOptions) -> Vec<u8> {
+    let response = send(
+        options,
+        Request::get("/large")
+            .header(header::ACCEPT_ENCODING, "gzip")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    body_bytes(response).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_compresses_response_with_accepted_encoding() {
+        for encoding in ["gzip", "br", "zstd"] {
+            let response = send(
+                &options(),
+                Request::get("/large")
+                    .header(header::ACCEPT_ENCODING, encoding)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await;
+
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(response.headers()[header::CONTENT_ENCODING], encoding);
+            assert!(body_bytes(response).await.len() < 4096);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_does_not_compress_when_disabled() {
+        let options = AppOptions {
+            compression: false,
+            ..options()
+        };
+        let response = send(
+            &options,
+            Request::get("/large")
+                .header(header::ACCEPT_ENCODING, "gzip")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+        assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+        assert_eq!(body_bytes(response).await.len(), 4096);
+    }
+
+    #[tokio::test]
+    async fn test_decompresses_request_body() {
+        let options = AppOptions {
+            max_body_bytes: 8192,
+            ..options()
+        };
+        let compressed = gzip_of_large(&options).await;
+
+        let response = send(
+            &options,
+            Request::post("/echo")
+                .header(header::CONTENT_ENCODING, "gzip")
+                .body(Body::from(compressed))
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_bytes(response).await, "a".repeat(4096).into_bytes());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_body_larger_than_limit() {
+        let response = send(
+            &options(),
+            Request::post("/echo")
+                .body(Body::from("a".repeat(2048)))
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn test_limits_decompressed_size_of_body() {
+        let options = options();
+        // 圧縮後は上限より小さいが、展開すると上限を超える
+        let compressed = gzip_of_large(&options).await;
+        assert!(compressed.len() < options.max_body_bytes);
+
+        let response = send(
+            &options,
+            Request::post("/echo")
+                .header(header::CONTENT_ENCODING, "gzip")
+                .body(Body::from(compressed))
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn test_times_out_with_configured_status() {
+        for status in [StatusCode::REQUEST_TIMEOUT, StatusCode::SERVICE_UNAVAILABLE] {
+            let options = AppOptions {
+                timeout_status: status,
+                ..options()
+            };
+            let response = send(&options, Request::get("/slow").body(Body::empty()).unwrap()).await;
+
+            assert_eq!(response.status(), status);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_adds_security_headers() {
+        let response = send(
+            &options(),
+            Request::get("/large").body(Body::empty()).unwrap(),
+        )
+        .await;
+
+        let headers = response.headers();
+        assert_eq!(headers[header::X_CONTENT_TYPE_OPTIONS], "nosniff");
+        assert_eq!(headers[header::X_FRAME_OPTIONS], "DENY");
+        assert_eq!(headers[header::REFERRER_POLICY], "no-referrer");
+        assert_eq!(
+            headers[header::CONTENT_SECURITY_POLICY],
+            "default-src 'none'; frame-ancestors 'none'"
+        );
+        // hsts_max_age が None の場合は付けない
+        assert!(headers.get(header::STRICT_TRANSPORT_SECURITY).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_adds_hsts_when_configured() {
+        let options = AppOptions {
+            hsts_max_age: Some(Duration::from_secs(31_536_000)),
+            ..options()
+        };
+        let response = send(
+            &options,
+            Request::get("/large").body(Body::empty()).unwrap(),
+        )
+        .await;
+
+        assert_eq!(
+            response.headers()[header::STRICT_TRANSPORT_SECURITY],
+            "max-age=31536000; includeSubDomains"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_keeps_csp_set_by_route() {
+        let response = send(
+            &options(),
+            Request::get("/framed").body(Body::empty()).unwrap(),
+        )
+        .await;
+
+        assert_eq!(
+            response.headers()[header::CONTENT_SECURITY_POLICY],
+            "frame-ancestors 'self'"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_allows_preflight_from_allowed_origin() {
+        let response = send(
+            &options(),
+            Request::options("/echo")
+                .header(header::ORIGIN, ALLOWED_ORIGIN)
+                .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+        let headers = response.headers();
+        assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_ORIGIN], ALLOWED_ORIGIN);
+        assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_CREDENTIALS], "true");
+    }
+
+    #[tokio::test]
+    async fn test_does_not_allow_other_origin() {
+        let response = send(
+            &options(),
+            Request::options("/echo")
+                .header(header::ORIGIN, "https://evil.example.com")
+                .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+        assert!(response
+            .headers()
+            .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            .is_none());
+    }
+}
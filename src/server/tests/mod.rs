@@ -0,0 +1,3 @@
+pub mod http_tests;
+pub mod layers_tests;
+pub mod shutdown_tests;
@@ -0,0 +1,32 @@
+use crate::server::shutdown::Shutdown;
+use std::time::Duration;
+use tokio::time::timeout;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_wait_returns_after_trigger() {
+        let shutdown = Shutdown::new();
+        let waiter = tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move { shutdown.wait().await }
+        });
+
+        // 終了が始まるまでは待ち続ける
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!waiter.is_finished());
+
+        shutdown.trigger();
+        timeout(Duration::from_secs(1), waiter)
+            .await
+            .expect("wait should return after trigger")
+            .unwrap();
+
+        // 既に始まっている場合はすぐに戻る
+        timeout(Duration::from_secs(1), shutdown.wait())
+            .await
+            .expect("wait should return immediately");
+    }
+}
@@ -0,0 +1,55 @@
+use crate::request_id::middleware::current_request_id;
+use crate::telemetry::propagation::extract_context;
+use axum::{
+    extract::{MatchedPath, Request},
+    middleware::Next,
+    response::Response,
+};
+use tracing::field::Empty;
+use tracing::{info_span, Instrument};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+// リクエストごとにサーバー側のスパンを作る（traceparent がある場合は呼び出し元のトレースに繋げる）
+// スパン名はパスではなくルートにする（ルートが見つからなかった場合はメソッドだけ）
+pub async fn trace_requests(request: Request, next: Next) -> Response {
+    let method = request.method().clone();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string());
+    let name = match &route {
+        Some(route) => format!("{} {}", method, route),
+        None => method.to_string(),
+    };
+    let span = info_span!(
+        "http_request",
+        otel.name = %name,
+        otel.kind = "server",
+        http.request.method = %method,
+        http.route = Empty,
+        http.response.status_code = Empty,
+        otel.status_code = Empty,
+        request_id = Empty,
+    );
+    if let Some(route) = &route {
+        span.record("http.route", route.as_str());
+    }
+    // リクエスト中のログに request_id を付けるため、スパンに記録する
+    if let Some(request_id) = current_request_id() {
+        span.record("request_id", request_id.as_str());
+    }
+    span.set_parent(extract_context(request.headers()));
+
+    let response = next.run(request).instrument(span.clone()).await;
+
+    // u16 のまま記録すると文字列の属性になるため i64 にする
+    span.record(
+        "http.response.status_code",
+        i64::from(response.status().as_u16()),
+    );
+    // 4xx は呼び出し元の誤りなので、サーバーのエラーとして扱うのは 5xx だけ
+    if response.status().is_server_error() {
+        span.record("otel.status_code", "ERROR");
+    }
+    response
+}
@@ -0,0 +1,6 @@
+pub mod middleware;
+pub mod propagation;
+pub mod tracer;
+
+#[cfg(test)]
+pub mod tests;
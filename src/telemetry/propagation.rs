@@ -0,0 +1,45 @@
+use opentelemetry::propagation::{Extractor, Injector, TextMapPropagator};
+use opentelemetry::Context;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+// 受け取ったリクエストの traceparent / tracestate からトレースのコンテキストを取り出す
+// ヘッダーがない場合や不正な場合は空のコンテキストになる（新しいトレースを始める）
+pub fn extract_context(headers: &axum::http::HeaderMap) -> Context {
+    TraceContextPropagator::new().extract(&AxumHeaderExtractor(headers))
+}
+
+// 外部APIへのリクエストに現在のスパンの traceparent / tracestate を付ける
+// reqwest 0.11 は axum と別の http クレートのバージョンの HeaderMap を使う
+pub fn inject_context(headers: &mut reqwest::header::HeaderMap) {
+    TraceContextPropagator::new().inject_context(
+        &Span::current().context(),
+        &mut ReqwestHeaderInjector(headers),
+    );
+}
+
+struct AxumHeaderExtractor<'a>(&'a axum::http::HeaderMap);
+
+impl Extractor for AxumHeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|name| name.as_str()).collect()
+    }
+}
+
+struct ReqwestHeaderInjector<'a>(&'a mut reqwest::header::HeaderMap);
+
+impl Injector for ReqwestHeaderInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        if let (Ok(name), Ok(value)) = (
+            reqwest::header::HeaderName::from_bytes(key.as_bytes()),
+            reqwest::header::HeaderValue::from_str(&value),
+        ) {
+            self.0.insert(name, value);
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use crate::telemetry::middleware::trace_requests;
+use crate::telemetry::tests::span_recorder::{attribute, SpanRecorder};
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::get,
+    Router,
+};
+use opentelemetry::trace::{SpanId, SpanKind, Status, TraceId};
+use opentelemetry::Value;
+use tower::ServiceExt;
+
+// W3C Trace Context の仕様の例
+const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+fn router() -> Router {
+    Router::new()
+        .route("/trace-test/:id", get(|| async { "ok" }))
+        .route(
+            "/trace-test/:id/fail",
+            get(|| async { StatusCode::INTERNAL_SERVER_ERROR }),
+        )
+        .layer(middleware::from_fn(trace_requests))
+}
+
+async fn get_status(uri: &str, traceparent: Option<&str>) -> StatusCode {
+    let mut request = Request::get(uri);
+    if let Some(traceparent) = traceparent {
+        request = request.header("traceparent", traceparent);
+    }
+    router()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+        .status()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_request_span_continues_incoming_trace() {
+        let recorder = SpanRecorder::start();
+
+        assert_eq!(
+            get_status("/trace-test/1", Some(TRACEPARENT)).await,
+            StatusCode::OK
+        );
+
+        // スパン名と http.route は実際のパスではなくルートにする
+        let span = recorder.span("GET /trace-test/:id");
+        assert_eq!(span.span_kind, SpanKind::Server);
+        assert_eq!(
+            span.span_context.trace_id(),
+            TraceId::from_hex("4bf92f3577b34da6a3ce929d0e0e4736").unwrap()
+        );
+        assert_eq!(
+            span.parent_span_id,
+            SpanId::from_hex("00f067aa0ba902b7").unwrap()
+        );
+        assert_eq!(
+            attribute(&span, "http.route"),
+            Some(Value::from("/trace-test/:id"))
+        );
+        assert_eq!(
+            attribute(&span, "http.response.status_code"),
+            Some(Value::I64(200))
+        );
+        assert_eq!(span.status, Status::Unset);
+    }
+
+    #[tokio::test]
+    async fn test_request_span_starts_new_trace_without_traceparent() {
+        let recorder = SpanRecorder::start();
+
+        // 不正な traceparent は無視する
+        get_status("/trace-test/1", Some("not-a-traceparent")).await;
+
+        let span = recorder.span("GET /trace-test/:id");
+        assert_eq!(span.parent_span_id, SpanId::INVALID);
+        assert_ne!(
+            span.span_context.trace_id(),
+            TraceId::from_hex("4bf92f3577b34da6a3ce929d0e0e4736").unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_server_errors_mark_span_as_error() {
+        let recorder = SpanRecorder::start();
+
+        assert_eq!(
+            get_status("/trace-test/1/fail", None).await,
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+
+        let span = recorder.span("GET /trace-test/:id/fail");
+        assert!(matches!(span.status, Status::Error { .. }));
+    }
+}
@@ -0,0 +1,3 @@
+pub mod middleware_tests;
+pub mod propagation_tests;
+pub mod span_recorder;
@@ -0,0 +1,60 @@
+use crate::telemetry::propagation::{extract_context, inject_context};
+use crate::telemetry::tests::span_recorder::SpanRecorder;
+use axum::http::HeaderValue;
+use opentelemetry::trace::TraceContextExt;
+use tracing::info_span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_inject_context_writes_current_span() {
+        let _recorder = SpanRecorder::start();
+        let span = info_span!("outgoing");
+        let _entered = span.enter();
+        let span_context = span.context().span().span_context().clone();
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        inject_context(&mut headers);
+
+        assert_eq!(
+            headers["traceparent"],
+            format!(
+                "00-{}-{}-01",
+                span_context.trace_id(),
+                span_context.span_id()
+            )
+        );
+    }
+
+    #[test]
+    fn test_inject_context_without_span_adds_nothing() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        inject_context(&mut headers);
+
+        assert!(headers.is_empty());
+    }
+
+    #[test]
+    fn test_extract_context() {
+        let mut headers = axum::http::HeaderMap::new();
+        headers.insert(
+            "traceparent",
+            HeaderValue::from_static("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-00"),
+        );
+
+        let context = extract_context(&headers);
+        let span = context.span();
+        let span_context = span.span_context();
+
+        assert!(span_context.is_remote());
+        assert_eq!(
+            span_context.trace_id().to_string(),
+            "4bf92f3577b34da6a3ce929d0e0e4736"
+        );
+        // サンプリングしないという呼び出し元の判断も引き継ぐ
+        assert!(!span_context.is_sampled());
+    }
+}
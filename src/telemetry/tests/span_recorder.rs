@@ -0,0 +1,60 @@
+use crate::telemetry::tracer::sampler;
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry::Value;
+use opentelemetry_sdk::export::trace::SpanData;
+use opentelemetry_sdk::testing::trace::InMemorySpanExporter;
+use opentelemetry_sdk::trace::TracerProvider;
+use tracing::subscriber::DefaultGuard;
+use tracing_subscriber::layer::SubscriberExt;
+
+// 記録したスパンをメモリに書き出す（テスト以外では使わない）
+// 購読はこのスレッドだけなので、current_thread のランタイム（#[tokio::test] の既定）で使う
+pub struct SpanRecorder {
+    exporter: InMemorySpanExporter,
+    provider: TracerProvider,
+    _guard: DefaultGuard,
+}
+
+impl SpanRecorder {
+    pub fn start() -> Self {
+        let exporter = InMemorySpanExporter::default();
+        let provider = TracerProvider::builder()
+            .with_sampler(sampler(1.0))
+            .with_simple_exporter(exporter.clone())
+            .build();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+        Self {
+            exporter,
+            provider,
+            _guard: tracing::subscriber::set_default(subscriber),
+        }
+    }
+
+    // 終了したスパン
+    pub fn finished_spans(&self) -> Vec<SpanData> {
+        self.provider.force_flush();
+        self.exporter.get_finished_spans().unwrap()
+    }
+
+    // 名前が一致する終了したスパン（見つからない場合は panic する）
+    pub fn span(&self, name: &str) -> SpanData {
+        let spans = self.finished_spans();
+        spans
+            .iter()
+            .find(|span| span.name == name)
+            .cloned()
+            .unwrap_or_else(|| {
+                let names: Vec<_> = spans.iter().map(|span| span.name.to_string()).collect();
+                panic!("span {:?} not found in {:?}", name, names)
+            })
+    }
+}
+
+// スパンの属性の値
+pub fn attribute(span: &SpanData, key: &str) -> Option<Value> {
+    span.attributes
+        .iter()
+        .find(|kv| kv.key.as_str() == key)
+        .map(|kv| kv.value.clone())
+}
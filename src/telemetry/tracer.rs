@@ -0,0 +1,33 @@
+use crate::config::settings::TracingSettings;
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::runtime;
+use opentelemetry_sdk::trace::{Sampler, TracerProvider};
+use opentelemetry_sdk::Resource;
+
+// スパンを作る TracerProvider
+// otlp_endpoint がない場合もスパンは作る（ログへのトレースIDの付与と traceparent の伝播に使う）
+pub fn build_tracer_provider(settings: &TracingSettings) -> anyhow::Result<TracerProvider> {
+    let builder = TracerProvider::builder()
+        .with_sampler(sampler(settings.sampling_ratio))
+        .with_resource(Resource::new([KeyValue::new(
+            "service.name",
+            settings.service_name.clone(),
+        )]));
+    let builder = match &settings.otlp_endpoint {
+        Some(endpoint) => {
+            let exporter = SpanExporter::builder()
+                .with_http()
+                .with_endpoint(endpoint)
+                .build()?;
+            builder.with_batch_exporter(exporter, runtime::Tokio)
+        }
+        None => builder,
+    };
+    Ok(builder.build())
+}
+
+// traceparent で届いた親のサンプリングの判断に従い、親がない場合だけ割合でサンプリングする
+pub fn sampler(ratio: f64) -> Sampler {
+    Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(ratio)))
+}
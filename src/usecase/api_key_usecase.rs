@@ -0,0 +1,139 @@
+use crate::error::AppError;
+use crate::models::api_key::{parse_prefix, ApiKey, ApiKeyScope, IssuedApiKey, API_KEY_PREFIX};
+use crate::models::caller::Caller;
+use crate::policy::session_policy::authorize_user_session;
+use crate::repositories::api_key_repository::ApiKeyRepository;
+use crate::repositories::workspace_repository::WorkspaceRepository;
+use crate::secret::{generate_key_prefix, generate_token, hash_token, verify_token};
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+// 有効期間を指定しなかった場合の日数と、指定できる最大の日数
+const DEFAULT_EXPIRES_IN_DAYS: i64 = 90;
+const MAX_EXPIRES_IN_DAYS: i64 = 365;
+
+// 最終使用日時の更新間隔（リクエストごとに書き込まないようにする）
+const LAST_USED_UPDATE_INTERVAL: Duration = Duration::minutes(1);
+
+#[derive(Clone)]
+pub struct ApiKeyUsecase<K: ApiKeyRepository + Clone, W: WorkspaceRepository + Clone> {
+    api_key_repository: K,
+    workspace_repository: W,
+}
+
+impl<K: ApiKeyRepository + Clone, W: WorkspaceRepository + Clone> ApiKeyUsecase<K, W> {
+    pub fn new(api_key_repository: K, workspace_repository: W) -> Self {
+        Self {
+            api_key_repository,
+            workspace_repository,
+        }
+    }
+}
+
+#[async_trait]
+pub trait ApiKeyService {
+    // 呼び出し元のワークスペースで使うキーを発行する（平文のキーを返すのはこの時だけ）
+    async fn create_key(
+        &self,
+        caller: &Caller,
+        name: String,
+        scopes: Vec<ApiKeyScope>,
+        expires_in_days: Option<i64>,
+    ) -> Result<IssuedApiKey, AppError>;
+    // 呼び出し元が発行したキーの一覧
+    async fn list_keys(&self, caller: &Caller) -> Result<Vec<ApiKey>, AppError>;
+    async fn revoke_key(&self, caller: &Caller, id: Uuid) -> Result<(), AppError>;
+    // X-API-Key の値から呼び出し元を作る（無効・期限切れのキーや、発行したユーザーがメンバーでなくなった場合は Unauthorized）
+    async fn authenticate(&self, key: &str) -> Result<Caller, AppError>;
+}
+
+#[async_trait]
+impl<K, W> ApiKeyService for ApiKeyUsecase<K, W>
+where
+    K: ApiKeyRepository + Send + Sync + Clone,
+    W: WorkspaceRepository + Send + Sync + Clone,
+{
+    async fn create_key(
+        &self,
+        caller: &Caller,
+        name: String,
+        scopes: Vec<ApiKeyScope>,
+        expires_in_days: Option<i64>,
+    ) -> Result<IssuedApiKey, AppError> {
+        authorize_user_session(caller)?;
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(AppError::BadRequest("name must not be empty".to_string()));
+        }
+        if scopes.is_empty() {
+            return Err(AppError::BadRequest("scopes must not be empty".to_string()));
+        }
+        let expires_in_days = expires_in_days.unwrap_or(DEFAULT_EXPIRES_IN_DAYS);
+        if !(1..=MAX_EXPIRES_IN_DAYS).contains(&expires_in_days) {
+            return Err(AppError::BadRequest(format!(
+                "expires_in_days must be between 1 and {}",
+                MAX_EXPIRES_IN_DAYS
+            )));
+        }
+        let mut scopes = scopes;
+        scopes.sort_by_key(|scope| *scope as u8);
+        scopes.dedup();
+
+        let prefix = generate_key_prefix();
+        let key = format!("{}{}_{}", API_KEY_PREFIX, prefix, generate_token());
+        let api_key = ApiKey::new(
+            caller.user_id,
+            caller.workspace_id,
+            name,
+            prefix,
+            hash_token(&key),
+            scopes,
+            Utc::now() + Duration::days(expires_in_days),
+        );
+        let api_key = self.api_key_repository.create(api_key).await?;
+        Ok(IssuedApiKey { api_key, key })
+    }
+
+    async fn list_keys(&self, caller: &Caller) -> Result<Vec<ApiKey>, AppError> {
+        authorize_user_session(caller)?;
+        Ok(self.api_key_repository.find_by_user(caller.user_id).await?)
+    }
+
+    async fn revoke_key(&self, caller: &Caller, id: Uuid) -> Result<(), AppError> {
+        authorize_user_session(caller)?;
+        match self.api_key_repository.delete(caller.user_id, id).await {
+            Ok(()) => Ok(()),
+            Err(sqlx::Error::RowNotFound) => Err(AppError::ResourceNotFound("API key")),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn authenticate(&self, key: &str) -> Result<Caller, AppError> {
+        let prefix = parse_prefix(key).ok_or(AppError::Unauthorized)?;
+        let api_key = self
+            .api_key_repository
+            .find_by_prefix(prefix.to_string())
+            .await?
+            .filter(|api_key| verify_token(key, &api_key.key_hash))
+            .ok_or(AppError::Unauthorized)?;
+        let now = Utc::now();
+        if api_key.is_expired(now) {
+            return Err(AppError::Unauthorized);
+        }
+        // ロールはキーの発行時ではなく現在のものを使う
+        let role = self
+            .workspace_repository
+            .find_role(api_key.workspace_id, api_key.user_id)
+            .await?
+            .ok_or(AppError::Unauthorized)?;
+
+        if api_key
+            .last_used_at
+            .is_none_or(|last_used_at| now - last_used_at >= LAST_USED_UPDATE_INTERVAL)
+        {
+            self.api_key_repository.touch(api_key.id, now).await?;
+        }
+        Ok(Caller::new(api_key.user_id, api_key.workspace_id, role).with_scopes(api_key.scopes))
+    }
+}
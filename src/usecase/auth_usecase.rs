@@ -0,0 +1,167 @@
+use crate::auth::jwt::{JwtKeys, ACCESS_TOKEN_TTL};
+use crate::error::AppError;
+use crate::models::auth_token::{RefreshToken, TokenPair};
+use crate::models::user_account::{normalize_email, validate_password, UserAccount};
+use crate::repositories::refresh_token_repository::RefreshTokenRepository;
+use crate::repositories::user_repository::UserRepository;
+use crate::secret::{generate_token, hash_password, hash_token, verify_password};
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use once_cell::sync::Lazy;
+use uuid::Uuid;
+
+// リフレッシュトークンの有効期間
+const REFRESH_TOKEN_TTL: Duration = Duration::days(30);
+
+// 存在しないメールアドレスでも照合を行い、応答時間からアカウントの有無を推測されないようにする
+static DUMMY_PASSWORD_HASH: Lazy<String> =
+    Lazy::new(|| hash_password("dummy-password").expect("failed to hash dummy password"));
+
+#[derive(Clone)]
+pub struct AuthUsecase<U: UserRepository + Clone, R: RefreshTokenRepository + Clone> {
+    user_repository: U,
+    refresh_token_repository: R,
+    jwt_keys: JwtKeys,
+}
+
+impl<U: UserRepository + Clone, R: RefreshTokenRepository + Clone> AuthUsecase<U, R> {
+    pub fn new(user_repository: U, refresh_token_repository: R, jwt_keys: JwtKeys) -> Self {
+        Self {
+            user_repository,
+            refresh_token_repository,
+            jwt_keys,
+        }
+    }
+}
+
+impl<U, R> AuthUsecase<U, R>
+where
+    U: UserRepository + Send + Sync + Clone,
+    R: RefreshTokenRepository + Send + Sync + Clone,
+{
+    // メールアドレスとパスワードを照合する（一致しない場合は InvalidCredentials）
+    pub async fn verify_credentials(
+        &self,
+        email: String,
+        password: String,
+    ) -> Result<UserAccount, AppError> {
+        let user = match normalize_email(&email) {
+            Ok(email) => self.user_repository.find_by_email(email).await?,
+            Err(_) => None,
+        };
+        // パスワードを持たない（OIDC だけで作成した）アカウントも、存在しない場合と同じ時間をかけて失敗させる
+        let stored_hash = user
+            .as_ref()
+            .map(|u| u.password_hash.clone())
+            .filter(|hash| !hash.is_empty());
+        let has_password = stored_hash.is_some();
+        let password_hash = stored_hash.unwrap_or_else(|| DUMMY_PASSWORD_HASH.clone());
+
+        let verified =
+            tokio::task::spawn_blocking(move || verify_password(&password, &password_hash))
+                .await
+                .map_err(|_| AppError::InternalError)?;
+
+        match user {
+            Some(user) if has_password && verified => Ok(user),
+            _ => Err(AppError::InvalidCredentials),
+        }
+    }
+
+    // アクセストークンと新しいリフレッシュトークンを発行する（OIDC ログインでも使う）
+    pub async fn issue_tokens(&self, user_id: Uuid) -> Result<TokenPair, AppError> {
+        let now = Utc::now();
+        let access_token = self
+            .jwt_keys
+            .issue_access_token(user_id, now)
+            .map_err(|_| AppError::InternalError)?;
+        let refresh_token = generate_token();
+        self.refresh_token_repository
+            .create(RefreshToken::new(
+                user_id,
+                hash_token(&refresh_token),
+                now + REFRESH_TOKEN_TTL,
+            ))
+            .await?;
+        Ok(TokenPair {
+            access_token,
+            expires_in: ACCESS_TOKEN_TTL.num_seconds(),
+            refresh_token,
+        })
+    }
+}
+
+#[async_trait]
+pub trait AuthService {
+    // アカウントを作成する（メールアドレスが登録済みの場合は Conflict）
+    async fn register(&self, email: String, password: String) -> Result<UserAccount, AppError>;
+    // メールアドレスとパスワードを照合してトークンを発行する（一致しない場合は InvalidCredentials）
+    async fn login(&self, email: String, password: String) -> Result<TokenPair, AppError>;
+    // リフレッシュトークンを使ってトークンを再発行する（使ったリフレッシュトークンは無効になる）
+    async fn refresh(&self, refresh_token: String) -> Result<TokenPair, AppError>;
+    async fn current_user(&self, user_id: Uuid) -> Result<UserAccount, AppError>;
+}
+
+#[async_trait]
+impl<U, R> AuthService for AuthUsecase<U, R>
+where
+    U: UserRepository + Send + Sync + Clone,
+    R: RefreshTokenRepository + Send + Sync + Clone,
+{
+    async fn register(&self, email: String, password: String) -> Result<UserAccount, AppError> {
+        let email = normalize_email(&email).map_err(AppError::BadRequest)?;
+        validate_password(&password).map_err(AppError::BadRequest)?;
+        if self
+            .user_repository
+            .find_by_email(email.clone())
+            .await?
+            .is_some()
+        {
+            return Err(AppError::Conflict(
+                "Email is already registered".to_string(),
+            ));
+        }
+
+        // Argon2 はCPUを占有するため、非同期ランタイムのスレッドを塞がないようにする
+        let password_hash = tokio::task::spawn_blocking(move || hash_password(&password))
+            .await
+            .map_err(|_| AppError::InternalError)?
+            .map_err(|_| AppError::InternalError)?;
+
+        match self
+            .user_repository
+            .create(UserAccount::new(email, password_hash))
+            .await
+        {
+            Ok(user) => Ok(user),
+            // 同時に登録された場合は一意インデックスで弾かれる
+            Err(sqlx::Error::Database(e)) if e.is_unique_violation() => Err(AppError::Conflict(
+                "Email is already registered".to_string(),
+            )),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn login(&self, email: String, password: String) -> Result<TokenPair, AppError> {
+        let user = self.verify_credentials(email, password).await?;
+        self.issue_tokens(user.id).await
+    }
+
+    async fn refresh(&self, refresh_token: String) -> Result<TokenPair, AppError> {
+        let token = self
+            .refresh_token_repository
+            .consume(hash_token(&refresh_token))
+            .await?
+            .filter(|token| token.expires_at > Utc::now())
+            .ok_or(AppError::Unauthorized)?;
+        self.issue_tokens(token.user_id).await
+    }
+
+    async fn current_user(&self, user_id: Uuid) -> Result<UserAccount, AppError> {
+        // トークンの発行後にユーザーが削除された場合
+        self.user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or(AppError::Unauthorized)
+    }
+}
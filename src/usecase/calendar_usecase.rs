@@ -0,0 +1,119 @@
+use crate::error::AppError;
+use crate::models::calendar::{render_calendar, CalendarComponent};
+use crate::models::caller::Caller;
+use crate::models::task::TaskFilter;
+use crate::policy::session_policy::authorize_user_session;
+use crate::policy::task_policy::{authorize, TaskAction};
+use crate::repositories::calendar_token_repository::CalendarTokenRepository;
+use crate::repositories::task_repository::TaskRepository;
+use crate::repositories::workspace_repository::WorkspaceRepository;
+use crate::secret::{generate_token, hash_token};
+use async_trait::async_trait;
+
+#[derive(Clone)]
+pub struct CalendarUsecase<
+    C: CalendarTokenRepository + Clone,
+    T: TaskRepository + Clone,
+    W: WorkspaceRepository + Clone,
+> {
+    token_repository: C,
+    task_repository: T,
+    workspace_repository: W,
+}
+
+impl<C, T, W> CalendarUsecase<C, T, W>
+where
+    C: CalendarTokenRepository + Clone,
+    T: TaskRepository + Clone,
+    W: WorkspaceRepository + Clone,
+{
+    pub fn new(token_repository: C, task_repository: T, workspace_repository: W) -> Self {
+        Self {
+            token_repository,
+            task_repository,
+            workspace_repository,
+        }
+    }
+}
+
+#[async_trait]
+pub trait CalendarService {
+    // フィード購読用のトークンを発行する（平文のトークンを返すのはこの時だけ、APIキーでは発行できない）
+    async fn issue_token(&self, caller: &Caller) -> Result<String, AppError>;
+    // 呼び出し元がワークスペースで発行したトークンを無効にし、新しいトークンを発行する
+    async fn rotate_token(&self, caller: &Caller) -> Result<String, AppError>;
+    // 呼び出し元がワークスペースで発行したトークンをすべて無効にする
+    async fn revoke_tokens(&self, caller: &Caller) -> Result<(), AppError>;
+    // トークンを発行したユーザーを呼び出し元として、発行したワークスペースのタスクを出力する
+    // （トークンが無効か、発行したユーザーがワークスペースのメンバーでなくなっていれば None を返す）
+    async fn render_feed(
+        &self,
+        token: &str,
+        filter: TaskFilter,
+        component: CalendarComponent,
+    ) -> Result<Option<String>, AppError>;
+}
+
+#[async_trait]
+impl<C, T, W> CalendarService for CalendarUsecase<C, T, W>
+where
+    C: CalendarTokenRepository + Send + Sync + Clone,
+    T: TaskRepository + Send + Sync + Clone,
+    W: WorkspaceRepository + Send + Sync + Clone,
+{
+    async fn issue_token(&self, caller: &Caller) -> Result<String, AppError> {
+        authorize_user_session(caller)?;
+        let token = generate_token();
+        self.token_repository
+            .create(caller.user_id, caller.workspace_id, hash_token(&token))
+            .await?;
+        Ok(token)
+    }
+
+    async fn rotate_token(&self, caller: &Caller) -> Result<String, AppError> {
+        authorize_user_session(caller)?;
+        let token = generate_token();
+        self.token_repository
+            .replace(caller.user_id, caller.workspace_id, hash_token(&token))
+            .await?;
+        Ok(token)
+    }
+
+    async fn revoke_tokens(&self, caller: &Caller) -> Result<(), AppError> {
+        authorize_user_session(caller)?;
+        let deleted = self
+            .token_repository
+            .delete_for_user(caller.user_id, caller.workspace_id)
+            .await?;
+        if deleted == 0 {
+            return Err(AppError::ResourceNotFound("Calendar token"));
+        }
+        Ok(())
+    }
+
+    async fn render_feed(
+        &self,
+        token: &str,
+        filter: TaskFilter,
+        component: CalendarComponent,
+    ) -> Result<Option<String>, AppError> {
+        let Some(owner) = self.token_repository.find_owner(hash_token(token)).await? else {
+            return Ok(None);
+        };
+        // ロールはトークンの発行時ではなく現在のものを使う
+        let Some(role) = self
+            .workspace_repository
+            .find_role(owner.workspace_id, owner.user_id)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let caller = Caller::new(owner.user_id, owner.workspace_id, role);
+        authorize(&caller, TaskAction::Read, None)?;
+        let tasks = self
+            .task_repository
+            .find_filtered(caller.workspace_id, filter)
+            .await?;
+        Ok(Some(render_calendar(&tasks, component)))
+    }
+}
@@ -0,0 +1,140 @@
+use crate::models::health::{CheckResult, HealthReport};
+use crate::repositories::health_repository::HealthRepository;
+use crate::repositories::upstream_probe::UpstreamProbe;
+use crate::server::shutdown::Shutdown;
+use async_trait::async_trait;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+#[derive(Clone)]
+pub struct HealthUsecase<H, U>
+where
+    H: HealthRepository + Clone,
+    U: UpstreamProbe + Clone,
+{
+    health_repository: H,
+    // None の場合は users API を確認しない
+    users_api: Option<U>,
+    shutdown: Shutdown,
+    // 確認ごとの待ち時間の上限
+    check_timeout: Duration,
+}
+
+impl<H, U> HealthUsecase<H, U>
+where
+    H: HealthRepository + Clone,
+    U: UpstreamProbe + Clone,
+{
+    pub fn new(
+        health_repository: H,
+        users_api: Option<U>,
+        shutdown: Shutdown,
+        check_timeout: Duration,
+    ) -> Self {
+        Self {
+            health_repository,
+            users_api,
+            shutdown,
+            check_timeout,
+        }
+    }
+}
+
+#[async_trait]
+pub trait HealthService {
+    // プロセスが動いているか（依存先は確認しない）
+    fn liveness(&self) -> HealthReport;
+    // リクエストを受け付けられるか（データベース・マイグレーション・users API と終了中でないことを確認する）
+    async fn readiness(&self) -> HealthReport;
+}
+
+#[async_trait]
+impl<H, U> HealthService for HealthUsecase<H, U>
+where
+    H: HealthRepository + Send + Sync + Clone,
+    U: UpstreamProbe + Send + Sync + Clone,
+{
+    fn liveness(&self) -> HealthReport {
+        HealthReport::new(BTreeMap::new())
+    }
+
+    async fn readiness(&self) -> HealthReport {
+        let database = timed(self.check_timeout, async {
+            self.health_repository.ping().await.map_err(|e| {
+                warn!("readiness: database check failed: {}", e);
+                "database is unavailable".to_string()
+            })
+        });
+        let migrations = timed(self.check_timeout, async {
+            let pending = self
+                .health_repository
+                .pending_migrations()
+                .await
+                .map_err(|e| {
+                    warn!("readiness: migration check failed: {}", e);
+                    "failed to read applied migrations".to_string()
+                })?;
+            match pending.as_slice() {
+                [] => Ok(()),
+                versions => Err(format!(
+                    "pending migrations: {}",
+                    versions
+                        .iter()
+                        .map(|version| version.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )),
+            }
+        });
+        let users_api = async {
+            match &self.users_api {
+                Some(users_api) => Some(
+                    timed(self.check_timeout, async {
+                        users_api.check().await.map_err(|e| {
+                            warn!("readiness: users API check failed: {}", e);
+                            "users API is unavailable".to_string()
+                        })
+                    })
+                    .await,
+                ),
+                None => None,
+            }
+        };
+        let (database, migrations, users_api) = tokio::join!(database, migrations, users_api);
+
+        let mut checks = BTreeMap::from([
+            ("database".to_string(), database),
+            ("migrations".to_string(), migrations),
+            // 終了が始まったら新しいリクエストを振り分けないようにする
+            (
+                "shutdown".to_string(),
+                if self.shutdown.is_triggered() {
+                    CheckResult::fail(0, "server is shutting down")
+                } else {
+                    CheckResult::ok(0)
+                },
+            ),
+        ]);
+        if let Some(users_api) = users_api {
+            checks.insert("users_api".to_string(), users_api);
+        }
+        HealthReport::new(checks)
+    }
+}
+
+// check を timeout まで待ち、かかった時間とともに結果を返す
+async fn timed(timeout: Duration, check: impl Future<Output = Result<(), String>>) -> CheckResult {
+    let started = Instant::now();
+    let result = tokio::time::timeout(timeout, check).await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+    match result {
+        Ok(Ok(())) => CheckResult::ok(latency_ms),
+        Ok(Err(error)) => CheckResult::fail(latency_ms, error),
+        Err(_) => CheckResult::fail(
+            latency_ms,
+            format!("timed out after {}ms", timeout.as_millis()),
+        ),
+    }
+}
@@ -0,0 +1,124 @@
+use crate::error::AppError;
+use crate::models::caller::Caller;
+use crate::models::idempotency::{
+    IdempotencyRecord, IdempotencyStart, StoredResponse, IN_PROGRESS_TIMEOUT,
+};
+use crate::repositories::idempotency_repository::IdempotencyRepository;
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct IdempotencyUsecase<I>
+where
+    I: IdempotencyRepository + Clone,
+{
+    idempotency_repository: I,
+}
+
+impl<I> IdempotencyUsecase<I>
+where
+    I: IdempotencyRepository + Clone,
+{
+    pub fn new(idempotency_repository: I) -> Self {
+        Self {
+            idempotency_repository,
+        }
+    }
+}
+
+#[async_trait]
+pub trait IdempotencyService {
+    // キーを処理中として確保する（処理済みのキーは保存したレスポンスを返す）
+    // 別の内容のリクエストに使われたキーは UnprocessableEntity、処理中のキーは Conflict
+    async fn begin(
+        &self,
+        caller: &Caller,
+        key: String,
+        request_hash: String,
+    ) -> Result<IdempotencyStart, AppError>;
+    // 処理が終わったレスポンスを保存する（attempt_id は begin が返した Proceed のもの）
+    async fn complete(
+        &self,
+        caller: &Caller,
+        key: String,
+        attempt_id: Uuid,
+        response: StoredResponse,
+    ) -> Result<(), AppError>;
+    // 処理が失敗したキーを解放し、同じキーで再試行できるようにする
+    async fn release(&self, caller: &Caller, key: String, attempt_id: Uuid)
+        -> Result<(), AppError>;
+}
+
+#[async_trait]
+impl<I> IdempotencyService for IdempotencyUsecase<I>
+where
+    I: IdempotencyRepository + Send + Sync + Clone,
+{
+    async fn begin(
+        &self,
+        caller: &Caller,
+        key: String,
+        request_hash: String,
+    ) -> Result<IdempotencyStart, AppError> {
+        let now = Utc::now();
+        let record = IdempotencyRecord::new(caller.user_id, key.clone(), request_hash.clone(), now);
+        let proceed = IdempotencyStart::Proceed {
+            attempt_id: record.attempt_id,
+        };
+        if self.idempotency_repository.reserve(record.clone()).await? {
+            return Ok(proceed);
+        }
+
+        let in_progress =
+            || AppError::Conflict("A request with this Idempotency-Key is in progress".to_string());
+        let existing = self
+            .idempotency_repository
+            .find(caller.user_id, key.clone())
+            .await?
+            .ok_or_else(in_progress)?;
+        if existing.request_hash != request_hash {
+            return Err(AppError::UnprocessableEntity(
+                "Idempotency-Key has already been used for a different request".to_string(),
+            ));
+        }
+        if let Some(response) = existing.response() {
+            return Ok(IdempotencyStart::Replay(response));
+        }
+        // 処理中のまま残ったキーは引き継ぐ（1つの UPDATE で確保し直すため、同時に引き継げるのは1件だけ）
+        if existing.is_abandoned(now)
+            && self
+                .idempotency_repository
+                .take_over(record, now - IN_PROGRESS_TIMEOUT)
+                .await?
+        {
+            return Ok(proceed);
+        }
+        Err(in_progress())
+    }
+
+    async fn complete(
+        &self,
+        caller: &Caller,
+        key: String,
+        attempt_id: Uuid,
+        response: StoredResponse,
+    ) -> Result<(), AppError> {
+        self.idempotency_repository
+            .complete(caller.user_id, key, attempt_id, response)
+            .await?;
+        Ok(())
+    }
+
+    async fn release(
+        &self,
+        caller: &Caller,
+        key: String,
+        attempt_id: Uuid,
+    ) -> Result<(), AppError> {
+        self.idempotency_repository
+            .release(caller.user_id, key, attempt_id)
+            .await?;
+        Ok(())
+    }
+}
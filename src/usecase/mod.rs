@@ -1,3 +1,14 @@
+pub mod api_key_usecase;
+pub mod auth_usecase;
+pub mod calendar_usecase;
+pub mod health_usecase;
+pub mod idempotency_usecase;
+pub mod oidc_usecase;
+pub mod seed_usecase;
+pub mod session_usecase;
+pub mod sharing_usecase;
 pub mod task_usecase;
+pub mod time_tracking_usecase;
+pub mod workspace_usecase;
 #[cfg(test)]
 pub mod tests;
\ No newline at end of file
@@ -0,0 +1,180 @@
+use crate::auth::jwt::JwtKeys;
+use crate::error::AppError;
+use crate::models::auth_token::TokenPair;
+use crate::models::oidc::{
+    code_challenge, AuthorizationRequest, IdTokenClaims, OidcIdentity, OidcLogin, OidcLoginState,
+};
+use crate::models::user_account::{normalize_email, UserAccount};
+use crate::repositories::oidc_provider::OidcProvider;
+use crate::repositories::oidc_repository::OidcRepository;
+use crate::repositories::refresh_token_repository::RefreshTokenRepository;
+use crate::repositories::user_repository::UserRepository;
+use crate::secret::{generate_token, hash_token};
+use crate::usecase::auth_usecase::AuthUsecase;
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct OidcUsecase<P, O, U, R>
+where
+    P: OidcProvider + Clone,
+    O: OidcRepository + Clone,
+    U: UserRepository + Clone,
+    R: RefreshTokenRepository + Clone,
+{
+    provider: P,
+    oidc_repository: O,
+    user_repository: U,
+    // トークンの発行はパスワードでのログインと共通にする
+    auth: AuthUsecase<U, R>,
+}
+
+impl<P, O, U, R> OidcUsecase<P, O, U, R>
+where
+    P: OidcProvider + Clone,
+    O: OidcRepository + Clone,
+    U: UserRepository + Clone,
+    R: RefreshTokenRepository + Clone,
+{
+    pub fn new(
+        provider: P,
+        oidc_repository: O,
+        user_repository: U,
+        refresh_token_repository: R,
+        jwt_keys: JwtKeys,
+    ) -> Self {
+        Self {
+            provider,
+            oidc_repository,
+            auth: AuthUsecase::new(user_repository.clone(), refresh_token_repository, jwt_keys),
+            user_repository,
+        }
+    }
+}
+
+impl<P, O, U, R> OidcUsecase<P, O, U, R>
+where
+    P: OidcProvider + Send + Sync + Clone,
+    O: OidcRepository + Send + Sync + Clone,
+    U: UserRepository + Send + Sync + Clone,
+    R: RefreshTokenRepository + Send + Sync + Clone,
+{
+    // プロバイダーのアカウントに紐付いたローカルユーザーを返す
+    // 初回ログインでは、プロバイダーが確認済みのメールアドレスで既存ユーザーに紐付けるか、新しいユーザーを作成する
+    async fn link_account(&self, claims: IdTokenClaims) -> Result<Uuid, AppError> {
+        if let Some(identity) = self
+            .oidc_repository
+            .find_identity(claims.iss.clone(), claims.sub.clone())
+            .await?
+        {
+            return Ok(identity.user_id);
+        }
+
+        // 未確認のメールアドレスで紐付けると、他人のアカウントを乗っ取れてしまう
+        let email = claims
+            .verified_email()
+            .and_then(|email| normalize_email(email).ok())
+            .ok_or(AppError::Forbidden)?;
+        let user = match self.user_repository.find_by_email(email.clone()).await? {
+            Some(user) => user,
+            None => match self
+                .user_repository
+                .create(UserAccount::without_password(email.clone()))
+                .await
+            {
+                Ok(user) => user,
+                // 同時に登録された場合は、登録されたユーザーに紐付ける
+                Err(sqlx::Error::Database(e)) if e.is_unique_violation() => self
+                    .user_repository
+                    .find_by_email(email)
+                    .await?
+                    .ok_or(AppError::InternalError)?,
+                Err(e) => return Err(e.into()),
+            },
+        };
+
+        match self
+            .oidc_repository
+            .create_identity(OidcIdentity::new(
+                claims.iss.clone(),
+                claims.sub.clone(),
+                user.id,
+            ))
+            .await
+        {
+            Ok(()) => Ok(user.id),
+            // 同じアカウントで同時にログインした場合は、先に作成された紐付けを使う
+            Err(sqlx::Error::Database(e)) if e.is_unique_violation() => self
+                .oidc_repository
+                .find_identity(claims.iss, claims.sub)
+                .await?
+                .map(|identity| identity.user_id)
+                .ok_or(AppError::InternalError),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+#[async_trait]
+pub trait OidcService {
+    // 認可リクエストを開始し、プロバイダーのログイン画面のURLと state を返す
+    async fn begin_login(&self) -> Result<OidcLogin, AppError>;
+    // コールバックで受け取った認可コードを検証してトークンを発行する
+    // （state が無効・期限切れ、または ID トークンが無効な場合は Unauthorized）
+    async fn complete_login(&self, code: String, state: String) -> Result<TokenPair, AppError>;
+}
+
+#[async_trait]
+impl<P, O, U, R> OidcService for OidcUsecase<P, O, U, R>
+where
+    P: OidcProvider + Send + Sync + Clone,
+    O: OidcRepository + Send + Sync + Clone,
+    U: UserRepository + Send + Sync + Clone,
+    R: RefreshTokenRepository + Send + Sync + Clone,
+{
+    async fn begin_login(&self) -> Result<OidcLogin, AppError> {
+        let state = generate_token();
+        let nonce = generate_token();
+        let code_verifier = generate_token();
+        let authorization_url = self
+            .provider
+            .authorization_url(AuthorizationRequest {
+                state: state.clone(),
+                nonce: nonce.clone(),
+                code_challenge: code_challenge(&code_verifier),
+            })
+            .await?;
+        self.oidc_repository
+            .save_login_state(OidcLoginState::new(
+                hash_token(&state),
+                nonce,
+                code_verifier,
+                Utc::now(),
+            ))
+            .await?;
+        Ok(OidcLogin {
+            authorization_url,
+            state,
+        })
+    }
+
+    async fn complete_login(&self, code: String, state: String) -> Result<TokenPair, AppError> {
+        let login_state = self
+            .oidc_repository
+            .take_login_state(hash_token(&state))
+            .await?
+            .filter(|login_state| !login_state.is_expired(Utc::now()))
+            .ok_or(AppError::Unauthorized)?;
+        let claims = self
+            .provider
+            .exchange_code(code, login_state.code_verifier)
+            .await?;
+        // 別の認可リクエストで発行された ID トークンの使い回しを防ぐ
+        if claims.nonce.as_deref() != Some(login_state.nonce.as_str()) {
+            return Err(AppError::Unauthorized);
+        }
+        let user_id = self.link_account(claims).await?;
+        self.auth.issue_tokens(user_id).await
+    }
+}
@@ -0,0 +1,106 @@
+use crate::error::AppError;
+use crate::models::task::Task;
+use crate::models::user_account::UserAccount;
+use crate::repositories::task_repository::TaskRepository;
+use crate::repositories::user_repository::UserRepository;
+use crate::secret::hash_password;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+
+// 開発用のユーザー（本番のデータベースでは seed を実行しない）
+pub const SEED_EMAIL: &str = "demo@example.com";
+pub const SEED_PASSWORD: &str = "demo-password";
+
+// seed で作成したもの（実行済みの場合は作成しない）
+#[derive(Clone, Debug)]
+pub struct SeedReport {
+    pub user: UserAccount,
+    pub created_user: bool,
+    pub created_tasks: usize,
+}
+
+#[derive(Clone)]
+pub struct SeedUsecase<U: UserRepository + Clone, T: TaskRepository + Clone> {
+    user_repository: U,
+    task_repository: T,
+}
+
+impl<U: UserRepository + Clone, T: TaskRepository + Clone> SeedUsecase<U, T> {
+    pub fn new(user_repository: U, task_repository: T) -> Self {
+        Self {
+            user_repository,
+            task_repository,
+        }
+    }
+}
+
+#[async_trait]
+pub trait SeedService {
+    // 開発用のユーザーと、その個人ワークスペースのタスクを作成する（何度実行しても重複しない）
+    async fn seed(&self) -> Result<SeedReport, AppError>;
+}
+
+#[async_trait]
+impl<U, T> SeedService for SeedUsecase<U, T>
+where
+    U: UserRepository + Send + Sync + Clone,
+    T: TaskRepository + Send + Sync + Clone,
+{
+    async fn seed(&self) -> Result<SeedReport, AppError> {
+        let (user, created_user) = match self
+            .user_repository
+            .find_by_email(SEED_EMAIL.to_string())
+            .await?
+        {
+            Some(user) => (user, false),
+            None => {
+                // Argon2 はCPUを占有するため、非同期ランタイムのスレッドを塞がないようにする
+                let password_hash = tokio::task::spawn_blocking(|| hash_password(SEED_PASSWORD))
+                    .await
+                    .map_err(|_| AppError::InternalError)?
+                    .map_err(|_| AppError::InternalError)?;
+                let user = self
+                    .user_repository
+                    .create(UserAccount::new(SEED_EMAIL.to_string(), password_hash))
+                    .await?;
+                (user, true)
+            }
+        };
+
+        // 個人ワークスペースの id はユーザーIDと同じ
+        let mut created_tasks = 0;
+        if self.task_repository.find_all(user.id).await?.is_empty() {
+            for task in sample_tasks(&user) {
+                self.task_repository.create(task).await?;
+                created_tasks += 1;
+            }
+        }
+
+        Ok(SeedReport {
+            user,
+            created_user,
+            created_tasks,
+        })
+    }
+}
+
+// 期限・プロジェクト・タグ・完了状態の表示を確認できるタスク
+fn sample_tasks(user: &UserAccount) -> Vec<Task> {
+    let now = Utc::now();
+    let task = |title: &str, project: &str, tags: &[&str]| {
+        let mut task = Task::new(user.id, user.id, title.to_string());
+        task.project = Some(project.to_string());
+        task.tags = tags.iter().map(|tag| tag.to_string()).collect();
+        task
+    };
+
+    let mut write_spec = task("仕様書を書く", "website", &["docs"]);
+    write_spec.due_at = Some(now + Duration::days(3));
+    let mut fix_login = task("ログイン画面の不具合を直す", "website", &["bug", "urgent"]);
+    fix_login.due_at = Some(now + Duration::days(1));
+    let mut setup_ci = task("CI を設定する", "infra", &[]);
+    setup_ci.set_completed(true, user.id, now);
+    let buy_coffee = task("コーヒー豆を買う", "personal", &["errand"]);
+
+    vec![write_spec, fix_login, setup_ci, buy_coffee]
+}
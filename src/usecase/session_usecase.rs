@@ -0,0 +1,142 @@
+use crate::auth::jwt::JwtKeys;
+use crate::error::AppError;
+use crate::models::caller::Caller;
+use crate::models::session::{IssuedSession, Session};
+use crate::policy::session_policy::authorize_user_session;
+use crate::repositories::refresh_token_repository::RefreshTokenRepository;
+use crate::repositories::session_repository::SessionRepository;
+use crate::repositories::user_repository::UserRepository;
+use crate::secret::{generate_token, hash_token};
+use crate::usecase::auth_usecase::AuthUsecase;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+
+// 最終アクセス日時の更新間隔（リクエストごとに書き込まないようにする）
+const LAST_SEEN_UPDATE_INTERVAL: Duration = Duration::minutes(1);
+
+#[derive(Clone)]
+pub struct SessionUsecase<S, U, R>
+where
+    S: SessionRepository + Clone,
+    U: UserRepository + Clone,
+    R: RefreshTokenRepository + Clone,
+{
+    session_repository: S,
+    refresh_token_repository: R,
+    // パスワードの照合はトークンでのログインと共通にする
+    auth: AuthUsecase<U, R>,
+}
+
+impl<S, U, R> SessionUsecase<S, U, R>
+where
+    S: SessionRepository + Clone,
+    U: UserRepository + Clone,
+    R: RefreshTokenRepository + Clone,
+{
+    pub fn new(
+        session_repository: S,
+        user_repository: U,
+        refresh_token_repository: R,
+        jwt_keys: JwtKeys,
+    ) -> Self {
+        Self {
+            session_repository,
+            auth: AuthUsecase::new(user_repository, refresh_token_repository.clone(), jwt_keys),
+            refresh_token_repository,
+        }
+    }
+}
+
+#[async_trait]
+pub trait SessionService {
+    // パスワードを照合して新しいセッションを作成する
+    // ログイン前のセッションがあれば破棄し、セッション固定攻撃を防ぐ
+    async fn login(
+        &self,
+        email: String,
+        password: String,
+        previous_token: Option<String>,
+    ) -> Result<IssuedSession, AppError>;
+    // Cookie のセッショントークンからセッションを返す（存在しない・期限切れの場合は Unauthorized）
+    async fn authenticate(&self, token: &str) -> Result<Session, AppError>;
+    // このセッションだけを破棄する
+    async fn logout(&self, token: &str) -> Result<(), AppError>;
+    // 呼び出し元のすべてのセッションとリフレッシュトークンを破棄する
+    // （発行済みのアクセストークンは有効期限まで使えるため、有効期間を短くしている）
+    async fn logout_everywhere(&self, caller: &Caller) -> Result<(), AppError>;
+}
+
+#[async_trait]
+impl<S, U, R> SessionService for SessionUsecase<S, U, R>
+where
+    S: SessionRepository + Send + Sync + Clone,
+    U: UserRepository + Send + Sync + Clone,
+    R: RefreshTokenRepository + Send + Sync + Clone,
+{
+    async fn login(
+        &self,
+        email: String,
+        password: String,
+        previous_token: Option<String>,
+    ) -> Result<IssuedSession, AppError> {
+        let user = self.auth.verify_credentials(email, password).await?;
+        if let Some(previous_token) = previous_token {
+            self.session_repository
+                .delete_by_token_hash(hash_token(&previous_token))
+                .await?;
+        }
+
+        let token = generate_token();
+        let csrf_token = generate_token();
+        let session = Session::new(
+            user.id,
+            hash_token(&token),
+            hash_token(&csrf_token),
+            Utc::now(),
+        );
+        self.session_repository.create(session.clone()).await?;
+        Ok(IssuedSession {
+            session,
+            token,
+            csrf_token,
+        })
+    }
+
+    async fn authenticate(&self, token: &str) -> Result<Session, AppError> {
+        let token_hash = hash_token(token);
+        let session = self
+            .session_repository
+            .find_by_token_hash(token_hash.clone())
+            .await?
+            .ok_or(AppError::Unauthorized)?;
+        let now = Utc::now();
+        if session.is_expired(now) {
+            self.session_repository
+                .delete_by_token_hash(token_hash)
+                .await?;
+            return Err(AppError::Unauthorized);
+        }
+        if now - session.last_seen_at >= LAST_SEEN_UPDATE_INTERVAL {
+            self.session_repository.touch(session.id, now).await?;
+        }
+        Ok(session)
+    }
+
+    async fn logout(&self, token: &str) -> Result<(), AppError> {
+        self.session_repository
+            .delete_by_token_hash(hash_token(token))
+            .await?;
+        Ok(())
+    }
+
+    async fn logout_everywhere(&self, caller: &Caller) -> Result<(), AppError> {
+        authorize_user_session(caller)?;
+        self.session_repository
+            .delete_by_user(caller.user_id)
+            .await?;
+        self.refresh_token_repository
+            .delete_by_user(caller.user_id)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,229 @@
+use crate::error::AppError;
+use crate::models::caller::Caller;
+use crate::models::grant::{Grant, GrantPermission, IssuedShareLink, ShareTarget};
+use crate::models::task::{Task, TaskFilter};
+use crate::models::user_account::normalize_email;
+use crate::policy::grant_policy::{authorize_share, can_manage_grant};
+use crate::repositories::grant_repository::GrantRepository;
+use crate::repositories::task_repository::TaskRepository;
+use crate::repositories::user_repository::UserRepository;
+use crate::secret::{generate_token, hash_token};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+// 共有リンクの有効期間を指定しなかった場合の時間数と、指定できる最大の時間数
+const DEFAULT_LINK_EXPIRES_IN_HOURS: i64 = 24 * 7;
+const MAX_LINK_EXPIRES_IN_HOURS: i64 = 24 * 30;
+
+#[derive(Clone)]
+pub struct SharingUsecase<G, T, U>
+where
+    G: GrantRepository + Clone,
+    T: TaskRepository + Clone,
+    U: UserRepository + Clone,
+{
+    grant_repository: G,
+    task_repository: T,
+    user_repository: U,
+}
+
+impl<G, T, U> SharingUsecase<G, T, U>
+where
+    G: GrantRepository + Clone,
+    T: TaskRepository + Clone,
+    U: UserRepository + Clone,
+{
+    pub fn new(grant_repository: G, task_repository: T, user_repository: U) -> Self {
+        Self {
+            grant_repository,
+            task_repository,
+            user_repository,
+        }
+    }
+}
+
+impl<G, T, U> SharingUsecase<G, T, U>
+where
+    G: GrantRepository + Send + Sync + Clone,
+    T: TaskRepository + Send + Sync + Clone,
+    U: UserRepository + Send + Sync + Clone,
+{
+    // 呼び出し元のワークスペースの共有対象を確認する（タスクが存在しなければ ResourceNotFound、共有できなければ Forbidden）
+    async fn authorize_target(
+        &self,
+        caller: &Caller,
+        target: &ShareTarget,
+    ) -> Result<(), AppError> {
+        match target {
+            ShareTarget::Task(task_id) => {
+                let task = self
+                    .task_repository
+                    .find_by_id(caller.workspace_id, *task_id)
+                    .await?
+                    .ok_or(AppError::ResourceNotFound("Task"))?;
+                authorize_share(caller, Some(&task))
+            }
+            ShareTarget::Project(project) => {
+                if project.trim().is_empty() {
+                    return Err(AppError::BadRequest(
+                        "project must not be empty".to_string(),
+                    ));
+                }
+                authorize_share(caller, None)
+            }
+        }
+    }
+}
+
+#[async_trait]
+pub trait SharingService {
+    // メールアドレスで指定したユーザーにタスクまたはプロジェクトを共有する
+    async fn share_with_user(
+        &self,
+        caller: &Caller,
+        target: ShareTarget,
+        email: String,
+        permission: GrantPermission,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<Grant, AppError>;
+    // 閲覧用の共有リンクを作成する（平文のトークンを返すのはこの時だけ）
+    async fn create_share_link(
+        &self,
+        caller: &Caller,
+        target: ShareTarget,
+        expires_in_hours: Option<i64>,
+    ) -> Result<IssuedShareLink, AppError>;
+    // 呼び出し元が管理できる共有の一覧
+    async fn list_grants(&self, caller: &Caller) -> Result<Vec<Grant>, AppError>;
+    async fn revoke_grant(&self, caller: &Caller, id: Uuid) -> Result<(), AppError>;
+    // 共有リンクのタスクを取得する（トークンが無効・期限切れの場合は None）
+    async fn get_link_tasks(&self, token: &str) -> Result<Option<Vec<Task>>, AppError>;
+}
+
+#[async_trait]
+impl<G, T, U> SharingService for SharingUsecase<G, T, U>
+where
+    G: GrantRepository + Send + Sync + Clone,
+    T: TaskRepository + Send + Sync + Clone,
+    U: UserRepository + Send + Sync + Clone,
+{
+    async fn share_with_user(
+        &self,
+        caller: &Caller,
+        target: ShareTarget,
+        email: String,
+        permission: GrantPermission,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<Grant, AppError> {
+        self.authorize_target(caller, &target).await?;
+        if expires_at.is_some_and(|expires_at| expires_at <= Utc::now()) {
+            return Err(AppError::BadRequest(
+                "`expires_at` must be in the future".to_string(),
+            ));
+        }
+        let email = normalize_email(&email).map_err(AppError::BadRequest)?;
+        let grantee = self
+            .user_repository
+            .find_by_email(email)
+            .await?
+            .ok_or(AppError::ResourceNotFound("User"))?;
+        if grantee.id == caller.user_id {
+            return Err(AppError::BadRequest(
+                "You cannot share with yourself".to_string(),
+            ));
+        }
+
+        let grant = Grant::for_user(
+            caller.workspace_id,
+            target,
+            grantee.id,
+            permission,
+            expires_at,
+            caller.user_id,
+        );
+        Ok(self.grant_repository.create(grant).await?)
+    }
+
+    async fn create_share_link(
+        &self,
+        caller: &Caller,
+        target: ShareTarget,
+        expires_in_hours: Option<i64>,
+    ) -> Result<IssuedShareLink, AppError> {
+        self.authorize_target(caller, &target).await?;
+        let expires_in_hours = expires_in_hours.unwrap_or(DEFAULT_LINK_EXPIRES_IN_HOURS);
+        if !(1..=MAX_LINK_EXPIRES_IN_HOURS).contains(&expires_in_hours) {
+            return Err(AppError::BadRequest(format!(
+                "expires_in_hours must be between 1 and {}",
+                MAX_LINK_EXPIRES_IN_HOURS
+            )));
+        }
+
+        let token = generate_token();
+        let grant = Grant::for_link(
+            caller.workspace_id,
+            target,
+            hash_token(&token),
+            Utc::now() + Duration::hours(expires_in_hours),
+            caller.user_id,
+        );
+        let grant = self.grant_repository.create(grant).await?;
+        Ok(IssuedShareLink { grant, token })
+    }
+
+    async fn list_grants(&self, caller: &Caller) -> Result<Vec<Grant>, AppError> {
+        let grants = self
+            .grant_repository
+            .find_by_workspace(caller.workspace_id)
+            .await?;
+        Ok(grants
+            .into_iter()
+            .filter(|grant| can_manage_grant(caller, grant))
+            .collect())
+    }
+
+    async fn revoke_grant(&self, caller: &Caller, id: Uuid) -> Result<(), AppError> {
+        let grant = self
+            .grant_repository
+            .find_by_id(caller.workspace_id, id)
+            .await?
+            .ok_or(AppError::ResourceNotFound("Grant"))?;
+        if !can_manage_grant(caller, &grant) {
+            return Err(AppError::Forbidden);
+        }
+        match self.grant_repository.delete(caller.workspace_id, id).await {
+            Ok(()) => Ok(()),
+            Err(sqlx::Error::RowNotFound) => Err(AppError::ResourceNotFound("Grant")),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn get_link_tasks(&self, token: &str) -> Result<Option<Vec<Task>>, AppError> {
+        let Some(grant) = self
+            .grant_repository
+            .find_active_by_token(hash_token(token), Utc::now())
+            .await?
+        else {
+            return Ok(None);
+        };
+        let tasks = match grant.target() {
+            ShareTarget::Task(task_id) => self
+                .task_repository
+                .find_by_id(grant.workspace_id, task_id)
+                .await?
+                .into_iter()
+                .collect(),
+            ShareTarget::Project(project) => {
+                let filter = TaskFilter {
+                    project: Some(project),
+                    ..TaskFilter::default()
+                };
+                self.task_repository
+                    .find_filtered(grant.workspace_id, filter)
+                    .await?
+            }
+        };
+        Ok(Some(tasks))
+    }
+}
@@ -1,68 +1,523 @@
-use crate::models::task::Task;
+use crate::error::AppError;
+use crate::metrics::registry::METRICS;
+use crate::models::caller::Caller;
+use crate::models::grant::{ShareTarget, SharedTask};
+use crate::models::stats::{StatsRange, TaskStats};
+use crate::models::task::{Task, TaskDetails, TaskFilter};
+use crate::models::task_transfer::{ImportAction, ImportReport, TaskRecord};
+use crate::policy::grant_policy::authorize_shared;
+use crate::policy::task_policy::{authorize, TaskAction};
+use crate::repositories::grant_repository::GrantRepository;
 use crate::repositories::task_repository::TaskRepository;
 use async_trait::async_trait;
+use chrono::Utc;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use tracing::{error, instrument};
 use uuid::Uuid;
 
 #[derive(Clone)]
-pub struct TaskUsecase<T: TaskRepository + Clone> {
+pub struct TaskUsecase<T: TaskRepository + Clone, G: GrantRepository + Clone> {
     repository: T,
+    grant_repository: G,
 }
 
-impl<T: TaskRepository + Clone> TaskUsecase<T> {
-    pub fn new(repository: T) -> Self {
-        Self { repository }
+impl<T: TaskRepository + Clone, G: GrantRepository + Clone> TaskUsecase<T, G> {
+    pub fn new(repository: T, grant_repository: G) -> Self {
+        Self {
+            repository,
+            grant_repository,
+        }
+    }
+}
+
+impl<T, G> TaskUsecase<T, G>
+where
+    T: TaskRepository + Send + Sync + Clone,
+    G: GrantRepository + Send + Sync + Clone,
+{
+    // 呼び出し元に共有されているタスクを id で探す（他のワークスペースのタスクも含む）
+    // APIキーは発行したワークスペースの外には届かないため、共有は使わない
+    #[instrument(
+        name = "TaskUsecase::find_shared_task",
+        skip_all,
+        fields(user_id = %caller.user_id, task_id = %id)
+    )]
+    async fn find_shared_task(
+        &self,
+        caller: &Caller,
+        id: Uuid,
+    ) -> Result<Option<SharedTask>, AppError> {
+        if caller.is_api_key() {
+            return Ok(None);
+        }
+        let grants: Vec<_> = self
+            .grant_repository
+            .find_active_by_grantee(caller.user_id, Utc::now())
+            .await?
+            .into_iter()
+            .filter(|grant| grant.task_id.is_none_or(|task_id| task_id == id))
+            .collect();
+        let workspace_ids: BTreeSet<Uuid> = grants.iter().map(|grant| grant.workspace_id).collect();
+        for workspace_id in workspace_ids {
+            let Some(task) = self.repository.find_by_id(workspace_id, id).await? else {
+                continue;
+            };
+            // 複数の共有が当てはまる場合は強い方の権限を使う
+            if let Some(permission) = grants
+                .iter()
+                .filter(|grant| grant.covers(&task))
+                .map(|grant| grant.permission)
+                .max()
+            {
+                return Ok(Some(SharedTask { task, permission }));
+            }
+        }
+        Ok(None)
+    }
+
+    // 呼び出し元のワークスペースのタスクか、呼び出し元に共有されたタスクを取得して操作の権限を確認する
+    // ロールで許可されない操作も、共有された権限で許可されていればできる
+    #[instrument(
+        name = "TaskUsecase::find_authorized_task",
+        skip_all,
+        fields(workspace_id = %caller.workspace_id, user_id = %caller.user_id, task_id = %id)
+    )]
+    async fn find_authorized_task(
+        &self,
+        caller: &Caller,
+        id: Uuid,
+        action: TaskAction,
+    ) -> Result<Task, AppError> {
+        let by_role = match self.repository.find_by_id(caller.workspace_id, id).await? {
+            Some(task) => match authorize(caller, action, Some(&task)) {
+                Ok(()) => return Ok(task),
+                Err(e) => e,
+            },
+            None => AppError::ResourceNotFound("Task"),
+        };
+        match self.find_shared_task(caller, id).await? {
+            Some(shared) => {
+                authorize_shared(caller, action, shared.permission)?;
+                Ok(shared.task)
+            }
+            // 共有されていなければロールでの確認結果を返す
+            None => Err(by_role),
+        }
+    }
+
+    // 新しいタスクを検証して作成する（作成とインポートで同じ検証を通す）
+    async fn insert_task(&self, task: Task, operation: &'static str) -> Result<Task, AppError> {
+        validate_title(&task.title)?;
+        let task = self.repository.create(task).await?;
+        METRICS.record_task_operation(operation);
+        Ok(task)
+    }
+
+    // 変更したタスクを検証して保存する（更新とインポートで同じ検証を通す）
+    async fn save_task(&self, task: Task, operation: &'static str) -> Result<Task, AppError> {
+        validate_title(&task.title)?;
+        match self.repository.update(task).await {
+            Ok(task) => {
+                METRICS.record_task_operation(operation);
+                Ok(task)
+            }
+            // 確認後に削除された
+            Err(sqlx::Error::RowNotFound) => Err(AppError::ResourceNotFound("Task")),
+            Err(e) => Err(e.into()),
+        }
     }
 }
 
+// すべてのメソッドは呼び出し元のワークスペースのタスクだけを対象にし、操作の前にポリシーで権限を確認する
 #[async_trait]
 pub trait TaskService {
-    async fn get_all_tasks(&self) -> Result<Vec<Task>, sqlx::Error>;
-    async fn get_task_by_id(&self, id: Uuid) -> Result<Option<Task>, sqlx::Error>;
-    async fn create_task(&self, title: String) -> Result<Task, sqlx::Error>;
+    async fn get_all_tasks(
+        &self,
+        caller: &Caller,
+        filter: TaskFilter,
+    ) -> Result<Vec<Task>, AppError>;
+    async fn get_task_by_id(&self, caller: &Caller, id: Uuid) -> Result<Option<Task>, AppError>;
+    async fn get_tasks_page(
+        &self,
+        caller: &Caller,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<Task>, AppError>;
+    async fn create_task(
+        &self,
+        caller: &Caller,
+        title: String,
+        details: TaskDetails,
+    ) -> Result<Task, AppError>;
     async fn update_task(
         &self,
+        caller: &Caller,
         id: Uuid,
         title: Option<String>,
         completed: Option<bool>,
-    ) -> Result<Task, sqlx::Error>;
-    async fn delete_task(&self, id: Uuid) -> Result<(), sqlx::Error>;
+        details: TaskDetails,
+    ) -> Result<Task, AppError>;
+    async fn delete_task(&self, caller: &Caller, id: Uuid) -> Result<(), AppError>;
+    // パース済みの行をidで作成または更新する（dry_runの場合は検証のみ行い書き込まない）
+    async fn import_tasks(
+        &self,
+        caller: &Caller,
+        rows: Vec<Result<TaskRecord, String>>,
+        dry_run: bool,
+    ) -> Result<ImportReport, AppError>;
+    async fn get_task_stats(
+        &self,
+        caller: &Caller,
+        range: StatsRange,
+    ) -> Result<TaskStats, AppError>;
+    // 呼び出し元に共有されたタスクの一覧（プロジェクトの共有は、そのプロジェクトのタスクに展開する）
+    async fn get_shared_tasks(&self, caller: &Caller) -> Result<Vec<SharedTask>, AppError>;
+}
+
+// タスクのタイトルの検証ルール（作成・更新・インポートのすべてで使う）
+fn validate_title(title: &str) -> Result<(), AppError> {
+    if title.trim().is_empty() {
+        return Err(AppError::BadRequest("title must not be empty".to_string()));
+    }
+    Ok(())
 }
 
 #[async_trait]
-impl<T: TaskRepository + Send + Sync + Clone> TaskService for TaskUsecase<T> {
-    async fn get_all_tasks(&self) -> Result<Vec<Task>, sqlx::Error> {
-        self.repository.find_all().await
+impl<T, G> TaskService for TaskUsecase<T, G>
+where
+    T: TaskRepository + Send + Sync + Clone,
+    G: GrantRepository + Send + Sync + Clone,
+{
+    #[instrument(
+        name = "TaskService::get_all_tasks",
+        skip_all,
+        fields(workspace_id = %caller.workspace_id, user_id = %caller.user_id)
+    )]
+    async fn get_all_tasks(
+        &self,
+        caller: &Caller,
+        filter: TaskFilter,
+    ) -> Result<Vec<Task>, AppError> {
+        authorize(caller, TaskAction::Read, None)?;
+        if filter == TaskFilter::default() {
+            return Ok(self.repository.find_all(caller.workspace_id).await?);
+        }
+        Ok(self
+            .repository
+            .find_filtered(caller.workspace_id, filter)
+            .await?)
     }
 
-    async fn get_task_by_id(&self, id: Uuid) -> Result<Option<Task>, sqlx::Error> {
-        self.repository.find_by_id(id).await
+    #[instrument(
+        name = "TaskService::get_task_by_id",
+        skip_all,
+        fields(workspace_id = %caller.workspace_id, user_id = %caller.user_id, task_id = %id)
+    )]
+    async fn get_task_by_id(&self, caller: &Caller, id: Uuid) -> Result<Option<Task>, AppError> {
+        authorize(caller, TaskAction::Read, None)?;
+        if let Some(task) = self.repository.find_by_id(caller.workspace_id, id).await? {
+            return Ok(Some(task));
+        }
+        Ok(self
+            .find_shared_task(caller, id)
+            .await?
+            .map(|shared| shared.task))
+    }
+
+    #[instrument(
+        name = "TaskService::get_tasks_page",
+        skip_all,
+        fields(workspace_id = %caller.workspace_id, user_id = %caller.user_id)
+    )]
+    async fn get_tasks_page(
+        &self,
+        caller: &Caller,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<Task>, AppError> {
+        authorize(caller, TaskAction::Read, None)?;
+        Ok(self
+            .repository
+            .find_page(caller.workspace_id, after, limit)
+            .await?)
     }
 
-    async fn create_task(&self, title: String) -> Result<Task, sqlx::Error> {
-        let new_task = Task::new(title);
-        self.repository.create(new_task).await
+    #[instrument(
+        name = "TaskService::create_task",
+        skip_all,
+        fields(workspace_id = %caller.workspace_id, user_id = %caller.user_id)
+    )]
+    async fn create_task(
+        &self,
+        caller: &Caller,
+        title: String,
+        details: TaskDetails,
+    ) -> Result<Task, AppError> {
+        authorize(caller, TaskAction::Create, None)?;
+        let mut new_task = Task::new(caller.workspace_id, caller.user_id, title);
+        new_task.apply_details(details);
+        self.insert_task(new_task, "create").await
     }
 
+    #[instrument(
+        name = "TaskService::update_task",
+        skip_all,
+        fields(workspace_id = %caller.workspace_id, user_id = %caller.user_id, task_id = %id)
+    )]
     async fn update_task(
         &self,
+        caller: &Caller,
         id: Uuid,
         title: Option<String>,
         completed: Option<bool>,
-    ) -> Result<Task, sqlx::Error> {
-        let existing_task = self.repository.find_by_id(id).await?;
-        if let Some(mut task) = existing_task {
-            if let Some(t) = title {
-                task.title = t;
+        details: TaskDetails,
+    ) -> Result<Task, AppError> {
+        let mut task = self
+            .find_authorized_task(caller, id, TaskAction::Update)
+            .await?;
+        if let Some(t) = title {
+            task.title = t;
+        }
+        if let Some(c) = completed {
+            task.set_completed(c, caller.user_id, Utc::now());
+        }
+        task.apply_details(details);
+        self.save_task(task, "update").await
+    }
+
+    #[instrument(
+        name = "TaskService::delete_task",
+        skip_all,
+        fields(workspace_id = %caller.workspace_id, user_id = %caller.user_id, task_id = %id)
+    )]
+    async fn delete_task(&self, caller: &Caller, id: Uuid) -> Result<(), AppError> {
+        let task = self
+            .repository
+            .find_by_id(caller.workspace_id, id)
+            .await?
+            .ok_or(AppError::ResourceNotFound("Task"))?;
+        authorize(caller, TaskAction::Delete, Some(&task))?;
+        match self.repository.delete(caller.workspace_id, id).await {
+            Ok(()) => {
+                METRICS.record_task_operation("delete");
+                Ok(())
+            }
+            Err(sqlx::Error::RowNotFound) => Err(AppError::ResourceNotFound("Task")),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    #[instrument(
+        name = "TaskService::import_tasks",
+        skip_all,
+        fields(
+            workspace_id = %caller.workspace_id,
+            user_id = %caller.user_id,
+            rows = rows.len(),
+            dry_run = dry_run
+        )
+    )]
+    async fn import_tasks(
+        &self,
+        caller: &Caller,
+        rows: Vec<Result<TaskRecord, String>>,
+        dry_run: bool,
+    ) -> Result<ImportReport, AppError> {
+        // viewer はインポートできない（既存のタスクの更新権限は行ごとに確認する）
+        authorize(caller, TaskAction::Create, None)?;
+        let mut report = ImportReport::new(dry_run);
+        let mut seen_ids = HashSet::new();
+
+        for (index, row) in rows.into_iter().enumerate() {
+            let row_number = index + 1;
+            let record = match row {
+                Ok(record) => record,
+                Err(message) => {
+                    report.fail(row_number, None, message);
+                    continue;
+                }
+            };
+            if let Err(e) = validate_title(&record.title) {
+                report.fail(row_number, Some(record.id), e.to_string());
+                continue;
             }
-            if let Some(c) = completed {
-                task.completed = c;
+            if !seen_ids.insert(record.id) {
+                report.fail(row_number, Some(record.id), "duplicate id in import");
+                continue;
+            }
+
+            let (action, task) = match self
+                .repository
+                .find_by_id(caller.workspace_id, record.id)
+                .await?
+            {
+                Some(task)
+                    if task.title == record.title
+                        && task.completed == record.completed
+                        && (record.completed_at.is_none()
+                            || task.completed_at == record.completed_at)
+                        && task.due_at == record.due_at
+                        && task.project == record.project
+                        && task.tags == record.tags =>
+                {
+                    (ImportAction::Unchanged, None)
+                }
+                Some(task) if authorize(caller, TaskAction::Update, Some(&task)).is_err() => {
+                    report.fail(row_number, Some(record.id), "permission denied");
+                    continue;
+                }
+                Some(mut task) => {
+                    let now = Utc::now();
+                    task.title = record.title;
+                    task.set_completed(
+                        record.completed,
+                        caller.user_id,
+                        record.completed_at.unwrap_or(now),
+                    );
+                    if record.completed && record.completed_at.is_some() {
+                        task.completed_at = record.completed_at;
+                    }
+                    task.due_at = record.due_at;
+                    task.project = record.project;
+                    task.tags = record.tags;
+                    task.updated_at = now;
+                    (ImportAction::Updated, Some(task))
+                }
+                None => {
+                    let mut task = Task::new(caller.workspace_id, caller.user_id, record.title);
+                    task.id = record.id;
+                    task.completed = record.completed;
+                    task.due_at = record.due_at;
+                    task.project = record.project;
+                    task.tags = record.tags;
+                    if let Some(created_at) = record.created_at {
+                        task.created_at = created_at;
+                    }
+                    task.updated_at = record.updated_at.unwrap_or(task.created_at);
+                    if record.completed {
+                        task.completed_at = record.completed_at.or(Some(task.updated_at));
+                        task.completed_by = Some(caller.user_id);
+                    }
+                    (ImportAction::Created, Some(task))
+                }
+            };
+
+            if let Some(task) = task {
+                // 書き込んだ行ごとに "import" として数える
+                // dry run では作成を試してロールバックし、実際のインポートと同じ行をエラーにする
+                let written = match (action, dry_run) {
+                    (ImportAction::Created, false) => {
+                        self.insert_task(task, "import").await.map(|_| ())
+                    }
+                    (ImportAction::Created, true) => self
+                        .repository
+                        .try_create(task)
+                        .await
+                        .map_err(AppError::from),
+                    (_, false) => self.save_task(task, "import").await.map(|_| ()),
+                    // 呼び出し元のワークスペースで見つかったタスクの更新は確かめない
+                    (_, true) => Ok(()),
+                };
+                match written {
+                    Ok(_) => {}
+                    // 他のワークスペースのタスクと id が重複しているか、確認後に削除された
+                    // （他のワークスペースにタスクがあることを知らせないよう、見つからない場合と同じにする）
+                    Err(AppError::DatabaseError(sqlx::Error::Database(e)))
+                        if e.is_unique_violation() =>
+                    {
+                        report.fail(
+                            row_number,
+                            Some(record.id),
+                            AppError::ResourceNotFound("Task").to_string(),
+                        );
+                        continue;
+                    }
+                    Err(e @ (AppError::ResourceNotFound(_) | AppError::BadRequest(_))) => {
+                        report.fail(row_number, Some(record.id), e.to_string());
+                        continue;
+                    }
+                    // データベースのエラーの内容は返さず、ログにだけ残す
+                    Err(e) => {
+                        error!("import_tasks: failed to write row {}: {}", row_number, e);
+                        report.fail(row_number, Some(record.id), "failed to import row");
+                        continue;
+                    }
+                }
             }
-            return self.repository.update(task).await;
+            report.record(action);
         }
-        Err(sqlx::Error::RowNotFound)
+
+        Ok(report)
+    }
+
+    #[instrument(
+        name = "TaskService::get_task_stats",
+        skip_all,
+        fields(workspace_id = %caller.workspace_id, user_id = %caller.user_id)
+    )]
+    async fn get_task_stats(
+        &self,
+        caller: &Caller,
+        range: StatsRange,
+    ) -> Result<TaskStats, AppError> {
+        authorize(caller, TaskAction::Read, None)?;
+        self.repository
+            .aggregate_stats(caller.workspace_id, range)
+            .await
+            .map_err(AppError::from_timezone_query)
     }
 
-    async fn delete_task(&self, id: Uuid) -> Result<(), sqlx::Error> {
-        self.repository.delete(id).await
+    #[instrument(
+        name = "TaskService::get_shared_tasks",
+        skip_all,
+        fields(workspace_id = %caller.workspace_id, user_id = %caller.user_id)
+    )]
+    async fn get_shared_tasks(&self, caller: &Caller) -> Result<Vec<SharedTask>, AppError> {
+        if caller.is_api_key() {
+            return Ok(Vec::new());
+        }
+        let grants = self
+            .grant_repository
+            .find_active_by_grantee(caller.user_id, Utc::now())
+            .await?;
+        let mut shared: Vec<SharedTask> = Vec::new();
+        // タスクの ID から shared での位置
+        let mut positions: HashMap<Uuid, usize> = HashMap::new();
+        for grant in &grants {
+            let tasks = match grant.target() {
+                ShareTarget::Task(task_id) => self
+                    .repository
+                    .find_by_id(grant.workspace_id, task_id)
+                    .await?
+                    .into_iter()
+                    .collect(),
+                ShareTarget::Project(project) => {
+                    let filter = TaskFilter {
+                        project: Some(project),
+                        ..TaskFilter::default()
+                    };
+                    self.repository
+                        .find_filtered(grant.workspace_id, filter)
+                        .await?
+                }
+            };
+            for task in tasks {
+                // タスクとプロジェクトの両方で共有されている場合は強い方の権限にまとめる
+                match positions.get(&task.id) {
+                    Some(&position) => {
+                        let existing = &mut shared[position];
+                        existing.permission = existing.permission.max(grant.permission)
+                    }
+                    None => {
+                        positions.insert(task.id, shared.len());
+                        shared.push(SharedTask {
+                            task,
+                            permission: grant.permission,
+                        })
+                    }
+                }
+            }
+        }
+        Ok(shared)
     }
 }
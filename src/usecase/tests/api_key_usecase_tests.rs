@@ -0,0 +1,255 @@
+use crate::error::AppError;
+use crate::models::api_key::{parse_prefix, ApiKey, ApiKeyScope};
+use crate::models::caller::Caller;
+use crate::models::workspace::WorkspaceRole;
+use crate::repositories::api_key_repository::MockApiKeyRepository;
+use crate::repositories::workspace_repository::MockWorkspaceRepository;
+use crate::secret::hash_token;
+use crate::usecase::api_key_usecase::{ApiKeyService, ApiKeyUsecase};
+use chrono::{Duration, Utc};
+use mockall::predicate::*;
+use uuid::Uuid;
+
+// キーを発行するユーザーと、キーを使うワークスペース
+const USER_ID: Uuid = Uuid::from_u128(1);
+const WORKSPACE_ID: Uuid = Uuid::from_u128(10);
+const KEY: &str = "tk_0a1b2c3d4e5f_secret";
+
+fn caller() -> Caller {
+    Caller::new(USER_ID, WORKSPACE_ID, WorkspaceRole::Member)
+}
+
+// KEY に対応する保存済みのキー
+fn stored_key(expires_at: chrono::DateTime<Utc>) -> ApiKey {
+    ApiKey::new(
+        USER_ID,
+        WORKSPACE_ID,
+        "ci".to_string(),
+        "0a1b2c3d4e5f".to_string(),
+        hash_token(KEY),
+        vec![ApiKeyScope::TasksWrite],
+        expires_at,
+    )
+}
+
+// prefix で検索すると key を返すようにモックを設定するヘルパー関数
+fn api_key_repo_with(key: ApiKey) -> MockApiKeyRepository {
+    let mut api_key_repo = MockApiKeyRepository::new();
+    api_key_repo
+        .expect_find_by_prefix()
+        .with(eq("0a1b2c3d4e5f".to_string()))
+        .times(1)
+        .returning(move |_| Ok(Some(key.clone())));
+    api_key_repo
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_create_key_returns_plaintext_once() {
+        // モックリポジトリの作成（保存されるのはハッシュだけ）
+        let mut api_key_repo = MockApiKeyRepository::new();
+        api_key_repo
+            .expect_create()
+            .withf(|api_key| {
+                api_key.user_id == USER_ID
+                    && api_key.workspace_id == WORKSPACE_ID
+                    && api_key.key_hash.len() == 64
+                    && api_key.scopes == vec![ApiKeyScope::TasksRead, ApiKeyScope::TasksWrite]
+            })
+            .times(1)
+            .returning(Ok);
+
+        // ユースケースの作成
+        let usecase = ApiKeyUsecase::new(api_key_repo, MockWorkspaceRepository::new());
+
+        // テスト実行（重複したスコープはまとめられる）
+        let issued = usecase
+            .create_key(
+                &caller(),
+                " ci ".to_string(),
+                vec![
+                    ApiKeyScope::TasksWrite,
+                    ApiKeyScope::TasksRead,
+                    ApiKeyScope::TasksWrite,
+                ],
+                None,
+            )
+            .await
+            .unwrap();
+
+        // 検証
+        assert_eq!(issued.api_key.name, "ci");
+        assert_eq!(
+            parse_prefix(&issued.key),
+            Some(issued.api_key.prefix.as_str())
+        );
+        assert_eq!(issued.api_key.key_hash, hash_token(&issued.key));
+        let expires_in = issued.api_key.expires_at - issued.api_key.created_at;
+        assert!((expires_in - Duration::days(90)).num_seconds().abs() <= 1);
+    }
+
+    #[tokio::test]
+    async fn test_create_key_validates_input() {
+        // ユースケースの作成
+        let usecase =
+            ApiKeyUsecase::new(MockApiKeyRepository::new(), MockWorkspaceRepository::new());
+
+        // テスト実行
+        for (name, scopes, expires_in_days) in [
+            ("", vec![ApiKeyScope::TasksRead], None),
+            ("ci", vec![], None),
+            ("ci", vec![ApiKeyScope::TasksRead], Some(0)),
+            ("ci", vec![ApiKeyScope::TasksRead], Some(366)),
+        ] {
+            let result = usecase
+                .create_key(&caller(), name.to_string(), scopes, expires_in_days)
+                .await;
+
+            // 検証
+            assert!(matches!(result, Err(AppError::BadRequest(_))));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_api_key_cannot_create_keys() {
+        // ユースケースの作成
+        let usecase =
+            ApiKeyUsecase::new(MockApiKeyRepository::new(), MockWorkspaceRepository::new());
+        let api_key_caller = caller().with_scopes(vec![ApiKeyScope::TasksWrite]);
+
+        // テスト実行
+        let result = usecase
+            .create_key(
+                &api_key_caller,
+                "ci".to_string(),
+                vec![ApiKeyScope::TasksWrite],
+                None,
+            )
+            .await;
+
+        // 検証
+        assert!(matches!(result, Err(AppError::Forbidden)));
+    }
+
+    #[tokio::test]
+    async fn test_revoke_unknown_key() {
+        // モックリポジトリの作成
+        let mut api_key_repo = MockApiKeyRepository::new();
+        api_key_repo
+            .expect_delete()
+            .times(1)
+            .returning(|_, _| Err(sqlx::Error::RowNotFound));
+
+        // ユースケースの作成
+        let usecase = ApiKeyUsecase::new(api_key_repo, MockWorkspaceRepository::new());
+
+        // テスト実行
+        let result = usecase.revoke_key(&caller(), Uuid::now_v7()).await;
+
+        // 検証
+        assert!(matches!(result, Err(AppError::ResourceNotFound("API key"))));
+    }
+
+    #[tokio::test]
+    async fn test_authenticate_valid_key() {
+        // モックリポジトリの作成
+        let mut api_key_repo = api_key_repo_with(stored_key(Utc::now() + Duration::days(1)));
+        api_key_repo
+            .expect_touch()
+            .times(1)
+            .returning(|_, _| Ok(()));
+        let mut workspace_repo = MockWorkspaceRepository::new();
+        workspace_repo
+            .expect_find_role()
+            .with(eq(WORKSPACE_ID), eq(USER_ID))
+            .times(1)
+            .returning(|_, _| Ok(Some(WorkspaceRole::Member)));
+
+        // ユースケースの作成
+        let usecase = ApiKeyUsecase::new(api_key_repo, workspace_repo);
+
+        // テスト実行
+        let authenticated = usecase.authenticate(KEY).await.unwrap();
+
+        // 検証
+        assert_eq!(
+            authenticated,
+            caller().with_scopes(vec![ApiKeyScope::TasksWrite])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_authenticate_skips_recent_last_used_update() {
+        // 直前に使われたキーは最終使用日時を更新しない
+        let mut key = stored_key(Utc::now() + Duration::days(1));
+        key.last_used_at = Some(Utc::now());
+        let mut api_key_repo = api_key_repo_with(key);
+        api_key_repo.expect_touch().never();
+        let mut workspace_repo = MockWorkspaceRepository::new();
+        workspace_repo
+            .expect_find_role()
+            .returning(|_, _| Ok(Some(WorkspaceRole::Member)));
+
+        // ユースケースの作成
+        let usecase = ApiKeyUsecase::new(api_key_repo, workspace_repo);
+
+        // テスト実行・検証
+        assert!(usecase.authenticate(KEY).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_authenticate_rejects_invalid_keys() {
+        // 形式が不正なキーはDBを検索しない
+        let usecase =
+            ApiKeyUsecase::new(MockApiKeyRepository::new(), MockWorkspaceRepository::new());
+        assert!(matches!(
+            usecase.authenticate("not-a-key").await,
+            Err(AppError::Unauthorized)
+        ));
+
+        // prefix が一致してもシークレットが違えば無効
+        let usecase = ApiKeyUsecase::new(
+            api_key_repo_with(stored_key(Utc::now() + Duration::days(1))),
+            MockWorkspaceRepository::new(),
+        );
+        assert!(matches!(
+            usecase.authenticate("tk_0a1b2c3d4e5f_wrong").await,
+            Err(AppError::Unauthorized)
+        ));
+
+        // 期限切れ
+        let usecase = ApiKeyUsecase::new(
+            api_key_repo_with(stored_key(Utc::now() - Duration::seconds(1))),
+            MockWorkspaceRepository::new(),
+        );
+        assert!(matches!(
+            usecase.authenticate(KEY).await,
+            Err(AppError::Unauthorized)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_authenticate_rejects_removed_member() {
+        // 発行したユーザーがワークスペースのメンバーでなくなったキーは使えない
+        let mut workspace_repo = MockWorkspaceRepository::new();
+        workspace_repo
+            .expect_find_role()
+            .times(1)
+            .returning(|_, _| Ok(None));
+
+        // ユースケースの作成
+        let usecase = ApiKeyUsecase::new(
+            api_key_repo_with(stored_key(Utc::now() + Duration::days(1))),
+            workspace_repo,
+        );
+
+        // テスト実行・検証
+        assert!(matches!(
+            usecase.authenticate(KEY).await,
+            Err(AppError::Unauthorized)
+        ));
+    }
+}
@@ -0,0 +1,260 @@
+use crate::auth::jwt::JwtKeys;
+use crate::error::AppError;
+use crate::models::auth_token::RefreshToken;
+use crate::models::user_account::UserAccount;
+use crate::repositories::refresh_token_repository::MockRefreshTokenRepository;
+use crate::repositories::user_repository::MockUserRepository;
+use crate::secret::{hash_password, hash_token, verify_password};
+use crate::usecase::auth_usecase::{AuthService, AuthUsecase};
+use chrono::{Duration, Utc};
+use mockall::predicate::*;
+use uuid::Uuid;
+
+const JWT_SECRET: &[u8] = b"secret";
+
+// リフレッシュトークンを使わないテスト用のユースケースを作成するヘルパー関数
+fn usecase_with(
+    user_repo: MockUserRepository,
+) -> AuthUsecase<MockUserRepository, MockRefreshTokenRepository> {
+    AuthUsecase::new(
+        user_repo,
+        MockRefreshTokenRepository::new(),
+        JwtKeys::hs256(JWT_SECRET),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_register_hashes_password() {
+        // モックリポジトリの作成
+        let mut user_repo = MockUserRepository::new();
+
+        // メールアドレスは正規化してから検索・保存される
+        user_repo
+            .expect_find_by_email()
+            .with(eq("alice@example.com".to_string()))
+            .times(1)
+            .returning(|_| Ok(None));
+        user_repo
+            .expect_create()
+            .withf(|u| {
+                u.email == "alice@example.com"
+                    && u.password_hash.starts_with("$argon2id$")
+                    && verify_password("correct horse", &u.password_hash)
+            })
+            .times(1)
+            .returning(Ok);
+
+        // ユースケースの作成
+        let usecase = usecase_with(user_repo);
+
+        // テスト実行
+        let user = usecase
+            .register(
+                " Alice@Example.com".to_string(),
+                "correct horse".to_string(),
+            )
+            .await
+            .unwrap();
+
+        // 検証
+        assert_eq!(user.email, "alice@example.com");
+    }
+
+    #[tokio::test]
+    async fn test_register_conflicts_with_existing_email() {
+        // モックリポジトリの作成
+        let mut user_repo = MockUserRepository::new();
+
+        // 既に登録済み
+        user_repo
+            .expect_find_by_email()
+            .times(1)
+            .returning(|email| Ok(Some(UserAccount::new(email, "hash".to_string()))));
+        user_repo.expect_create().never();
+
+        // ユースケースの作成
+        let usecase = usecase_with(user_repo);
+
+        // テスト実行
+        let result = usecase
+            .register("alice@example.com".to_string(), "correct horse".to_string())
+            .await;
+
+        // 検証
+        assert!(matches!(result, Err(AppError::Conflict(_))));
+    }
+
+    #[tokio::test]
+    async fn test_register_rejects_short_password() {
+        // モックリポジトリの作成（リポジトリは呼ばれない）
+        let user_repo = MockUserRepository::new();
+
+        // ユースケースの作成
+        let usecase = usecase_with(user_repo);
+
+        // テスト実行
+        let result = usecase
+            .register("alice@example.com".to_string(), "short".to_string())
+            .await;
+
+        // 検証
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_login() {
+        // モックリポジトリの作成
+        let mut user_repo = MockUserRepository::new();
+        let mut refresh_repo = MockRefreshTokenRepository::new();
+        let user = UserAccount::new(
+            "alice@example.com".to_string(),
+            hash_password("correct horse").unwrap(),
+        );
+        let user_id = user.id;
+        user_repo
+            .expect_find_by_email()
+            .returning(move |_| Ok(Some(user.clone())));
+
+        // ログインに成功した場合だけリフレッシュトークンが保存される
+        refresh_repo
+            .expect_create()
+            .withf(move |t| t.user_id == user_id && t.expires_at > Utc::now())
+            .times(1)
+            .returning(|_| Ok(()));
+
+        // ユースケースの作成
+        let usecase = AuthUsecase::new(user_repo, refresh_repo, JwtKeys::hs256(JWT_SECRET));
+
+        // テスト実行・検証（正しいパスワードのみ成功する）
+        let tokens = usecase
+            .login("ALICE@example.com".to_string(), "correct horse".to_string())
+            .await
+            .unwrap();
+        let claims = JwtKeys::hs256(JWT_SECRET)
+            .verify_access_token(&tokens.access_token)
+            .await
+            .unwrap();
+        assert_eq!(claims.sub, user_id);
+
+        let result = usecase
+            .login(
+                "alice@example.com".to_string(),
+                "wrong password".to_string(),
+            )
+            .await;
+        assert!(matches!(result, Err(AppError::InvalidCredentials)));
+    }
+
+    #[tokio::test]
+    async fn test_login_with_unknown_email() {
+        // モックリポジトリの作成
+        let mut user_repo = MockUserRepository::new();
+        user_repo
+            .expect_find_by_email()
+            .times(1)
+            .returning(|_| Ok(None));
+
+        // ユースケースの作成
+        let usecase = usecase_with(user_repo);
+
+        // テスト実行
+        let result = usecase
+            .login(
+                "nobody@example.com".to_string(),
+                "correct horse".to_string(),
+            )
+            .await;
+
+        // 検証（パスワード違いと区別できないエラーになる）
+        assert!(matches!(result, Err(AppError::InvalidCredentials)));
+    }
+
+    #[tokio::test]
+    async fn test_login_without_password() {
+        // OIDC だけで作成したアカウント
+        let mut user_repo = MockUserRepository::new();
+        user_repo
+            .expect_find_by_email()
+            .times(1)
+            .returning(|email| Ok(Some(UserAccount::without_password(email))));
+
+        let usecase = usecase_with(user_repo);
+
+        // ダミーのハッシュと一致するパスワードでもログインできない
+        let result = usecase
+            .login("oidc@example.com".to_string(), "dummy-password".to_string())
+            .await;
+
+        assert!(matches!(result, Err(AppError::InvalidCredentials)));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_rotates_token() {
+        // モックリポジトリの作成
+        let user_repo = MockUserRepository::new();
+        let mut refresh_repo = MockRefreshTokenRepository::new();
+        let user_id = Uuid::now_v7();
+
+        // 使ったリフレッシュトークンは削除され、新しいトークンが保存される
+        refresh_repo
+            .expect_consume()
+            .with(eq(hash_token("old-token")))
+            .times(1)
+            .returning(move |hash| {
+                Ok(Some(RefreshToken::new(
+                    user_id,
+                    hash,
+                    Utc::now() + Duration::days(1),
+                )))
+            });
+        refresh_repo
+            .expect_create()
+            .withf(move |t| t.user_id == user_id && t.token_hash != hash_token("old-token"))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        // ユースケースの作成
+        let usecase = AuthUsecase::new(user_repo, refresh_repo, JwtKeys::hs256(JWT_SECRET));
+
+        // テスト実行
+        let tokens = usecase.refresh("old-token".to_string()).await.unwrap();
+
+        // 検証
+        assert_ne!(tokens.refresh_token, "old-token");
+        let claims = JwtKeys::hs256(JWT_SECRET)
+            .verify_access_token(&tokens.access_token)
+            .await
+            .unwrap();
+        assert_eq!(claims.sub, user_id);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_rejects_expired_token() {
+        // モックリポジトリの作成
+        let user_repo = MockUserRepository::new();
+        let mut refresh_repo = MockRefreshTokenRepository::new();
+
+        // 期限切れのトークン
+        refresh_repo.expect_consume().times(1).returning(|hash| {
+            Ok(Some(RefreshToken::new(
+                Uuid::now_v7(),
+                hash,
+                Utc::now() - Duration::seconds(1),
+            )))
+        });
+        refresh_repo.expect_create().never();
+
+        // ユースケースの作成
+        let usecase = AuthUsecase::new(user_repo, refresh_repo, JwtKeys::hs256(JWT_SECRET));
+
+        // テスト実行
+        let result = usecase.refresh("expired-token".to_string()).await;
+
+        // 検証
+        assert!(matches!(result, Err(AppError::Unauthorized)));
+    }
+}
@@ -0,0 +1,305 @@
+use crate::error::AppError;
+use crate::models::api_key::ApiKeyScope;
+use crate::models::calendar::{CalendarComponent, CalendarTokenOwner};
+use crate::models::caller::Caller;
+use crate::models::task::{Task, TaskFilter};
+use crate::models::workspace::WorkspaceRole;
+use crate::repositories::calendar_token_repository::MockCalendarTokenRepository;
+use crate::repositories::task_repository::MockTaskRepository;
+use crate::repositories::workspace_repository::MockWorkspaceRepository;
+use crate::secret::hash_token;
+use crate::usecase::calendar_usecase::{CalendarService, CalendarUsecase};
+use mockall::predicate::*;
+use uuid::Uuid;
+
+// トークンを発行したユーザーと、発行したワークスペース
+const USER_ID: Uuid = Uuid::from_u128(1);
+const WORKSPACE_ID: Uuid = Uuid::from_u128(10);
+
+fn owner() -> CalendarTokenOwner {
+    CalendarTokenOwner {
+        user_id: USER_ID,
+        workspace_id: WORKSPACE_ID,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_issue_token_stores_only_hash() {
+        // モックリポジトリの作成
+        let mut token_repo = MockCalendarTokenRepository::new();
+        let task_repo = MockTaskRepository::new();
+
+        // createメソッドのモック設定（呼び出し元のユーザーとワークスペースに紐付けて64文字の16進ハッシュが保存される）
+        token_repo
+            .expect_create()
+            .withf(|user_id, workspace_id, hash| {
+                *user_id == USER_ID && *workspace_id == WORKSPACE_ID && hash.len() == 64
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        // ユースケースの作成
+        let usecase = CalendarUsecase::new(token_repo, task_repo, MockWorkspaceRepository::new());
+
+        // テスト実行
+        let token = usecase
+            .issue_token(&Caller::new(USER_ID, WORKSPACE_ID, WorkspaceRole::Viewer))
+            .await
+            .unwrap();
+
+        // 検証
+        assert_eq!(token.len(), 64);
+    }
+
+    #[tokio::test]
+    async fn test_api_key_cannot_issue_token() {
+        // モックリポジトリの作成
+        let mut token_repo = MockCalendarTokenRepository::new();
+        token_repo.expect_create().never();
+
+        // ユースケースの作成
+        let usecase = CalendarUsecase::new(
+            token_repo,
+            MockTaskRepository::new(),
+            MockWorkspaceRepository::new(),
+        );
+
+        // テスト実行
+        let caller = Caller::new(USER_ID, WORKSPACE_ID, WorkspaceRole::Admin)
+            .with_scopes(vec![ApiKeyScope::TasksRead, ApiKeyScope::TasksWrite]);
+        let result = usecase.issue_token(&caller).await;
+
+        // 検証
+        assert!(matches!(result, Err(AppError::Forbidden)));
+    }
+
+    #[tokio::test]
+    async fn test_rotate_token_replaces_callers_tokens() {
+        // モックリポジトリの作成
+        let mut token_repo = MockCalendarTokenRepository::new();
+
+        // replaceメソッドのモック設定（呼び出し元のユーザーとワークスペースのトークンを新しいハッシュで置き換える）
+        token_repo.expect_create().never();
+        token_repo
+            .expect_replace()
+            .withf(|user_id, workspace_id, hash| {
+                *user_id == USER_ID && *workspace_id == WORKSPACE_ID && hash.len() == 64
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        // ユースケースの作成
+        let usecase = CalendarUsecase::new(
+            token_repo,
+            MockTaskRepository::new(),
+            MockWorkspaceRepository::new(),
+        );
+
+        // テスト実行
+        let token = usecase
+            .rotate_token(&Caller::new(USER_ID, WORKSPACE_ID, WorkspaceRole::Viewer))
+            .await
+            .unwrap();
+
+        // 検証
+        assert_eq!(token.len(), 64);
+    }
+
+    #[tokio::test]
+    async fn test_api_key_cannot_rotate_or_revoke_tokens() {
+        // モックリポジトリの作成
+        let mut token_repo = MockCalendarTokenRepository::new();
+        token_repo.expect_replace().never();
+        token_repo.expect_delete_for_user().never();
+
+        // ユースケースの作成
+        let usecase = CalendarUsecase::new(
+            token_repo,
+            MockTaskRepository::new(),
+            MockWorkspaceRepository::new(),
+        );
+
+        // テスト実行
+        let caller = Caller::new(USER_ID, WORKSPACE_ID, WorkspaceRole::Admin)
+            .with_scopes(vec![ApiKeyScope::TasksRead, ApiKeyScope::TasksWrite]);
+
+        // 検証
+        assert!(matches!(
+            usecase.rotate_token(&caller).await,
+            Err(AppError::Forbidden)
+        ));
+        assert!(matches!(
+            usecase.revoke_tokens(&caller).await,
+            Err(AppError::Forbidden)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_revoke_tokens_deletes_callers_tokens() {
+        // モックリポジトリの作成
+        let mut token_repo = MockCalendarTokenRepository::new();
+
+        // delete_for_userメソッドのモック設定（呼び出し元のユーザーとワークスペースのトークンを削除する）
+        token_repo
+            .expect_delete_for_user()
+            .with(eq(USER_ID), eq(WORKSPACE_ID))
+            .times(1)
+            .returning(|_, _| Ok(2));
+
+        // ユースケースの作成
+        let usecase = CalendarUsecase::new(
+            token_repo,
+            MockTaskRepository::new(),
+            MockWorkspaceRepository::new(),
+        );
+
+        // テスト実行
+        let result = usecase
+            .revoke_tokens(&Caller::new(USER_ID, WORKSPACE_ID, WorkspaceRole::Viewer))
+            .await;
+
+        // 検証
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_revoke_tokens_without_tokens() {
+        // モックリポジトリの作成
+        let mut token_repo = MockCalendarTokenRepository::new();
+
+        // 削除するトークンがない
+        token_repo
+            .expect_delete_for_user()
+            .times(1)
+            .returning(|_, _| Ok(0));
+
+        // ユースケースの作成
+        let usecase = CalendarUsecase::new(
+            token_repo,
+            MockTaskRepository::new(),
+            MockWorkspaceRepository::new(),
+        );
+
+        // テスト実行
+        let result = usecase
+            .revoke_tokens(&Caller::new(USER_ID, WORKSPACE_ID, WorkspaceRole::Viewer))
+            .await;
+
+        // 検証
+        assert!(matches!(
+            result,
+            Err(AppError::ResourceNotFound("Calendar token"))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_render_feed_with_invalid_token() {
+        // モックリポジトリの作成
+        let mut token_repo = MockCalendarTokenRepository::new();
+        let mut task_repo = MockTaskRepository::new();
+
+        // 無効なトークンではタスクを取得しない
+        token_repo
+            .expect_find_owner()
+            .with(eq(hash_token("invalid")))
+            .times(1)
+            .returning(|_| Ok(None));
+        task_repo.expect_find_filtered().never();
+
+        // ユースケースの作成
+        let usecase = CalendarUsecase::new(token_repo, task_repo, MockWorkspaceRepository::new());
+
+        // テスト実行
+        let result = usecase
+            .render_feed("invalid", TaskFilter::default(), CalendarComponent::Vtodo)
+            .await
+            .unwrap();
+
+        // 検証
+        assert!(result.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_render_feed_passes_filter() {
+        // モックリポジトリの作成
+        let mut token_repo = MockCalendarTokenRepository::new();
+        let mut task_repo = MockTaskRepository::new();
+        let filter = TaskFilter {
+            project: Some("website".to_string()),
+            tag: Some("urgent".to_string()),
+            completed_since: None,
+        };
+
+        // find_owner / find_role / find_filteredメソッドのモック設定（トークンを発行したワークスペースのタスクを取得する）
+        token_repo
+            .expect_find_owner()
+            .times(1)
+            .returning(|_| Ok(Some(owner())));
+        let mut workspace_repo = MockWorkspaceRepository::new();
+        workspace_repo
+            .expect_find_role()
+            .with(eq(WORKSPACE_ID), eq(USER_ID))
+            .times(1)
+            .returning(|_, _| Ok(Some(WorkspaceRole::Viewer)));
+        task_repo
+            .expect_find_filtered()
+            .with(eq(WORKSPACE_ID), eq(filter.clone()))
+            .times(1)
+            .returning(|workspace_id, _| {
+                Ok(vec![Task::new(
+                    workspace_id,
+                    USER_ID,
+                    "タスク1".to_string(),
+                )])
+            });
+
+        // ユースケースの作成
+        let usecase = CalendarUsecase::new(token_repo, task_repo, workspace_repo);
+
+        // テスト実行
+        let ics = usecase
+            .render_feed("token", filter, CalendarComponent::Vtodo)
+            .await
+            .unwrap()
+            .unwrap();
+
+        // 検証
+        assert!(ics.contains("SUMMARY:タスク1"));
+    }
+
+    #[tokio::test]
+    async fn test_render_feed_after_owner_left_workspace() {
+        // モックリポジトリの作成
+        let mut token_repo = MockCalendarTokenRepository::new();
+        let mut task_repo = MockTaskRepository::new();
+        let mut workspace_repo = MockWorkspaceRepository::new();
+
+        // トークンを発行したユーザーがメンバーでなくなっていればタスクを取得しない
+        token_repo
+            .expect_find_owner()
+            .times(1)
+            .returning(|_| Ok(Some(owner())));
+        workspace_repo
+            .expect_find_role()
+            .with(eq(WORKSPACE_ID), eq(USER_ID))
+            .times(1)
+            .returning(|_, _| Ok(None));
+        task_repo.expect_find_filtered().never();
+
+        // ユースケースの作成
+        let usecase = CalendarUsecase::new(token_repo, task_repo, workspace_repo);
+
+        // テスト実行
+        let result = usecase
+            .render_feed("token", TaskFilter::default(), CalendarComponent::Vtodo)
+            .await
+            .unwrap();
+
+        // 検証
+        assert!(result.is_none());
+    }
+}
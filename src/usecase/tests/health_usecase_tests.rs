@@ -0,0 +1,130 @@
+use crate::error::AppError;
+use crate::models::health::{CheckResult, HealthStatus};
+use crate::repositories::health_repository::{HealthRepository, MockHealthRepository};
+use crate::repositories::upstream_probe::MockUpstreamProbe;
+use crate::server::shutdown::Shutdown;
+use crate::usecase::health_usecase::{HealthService, HealthUsecase};
+use async_trait::async_trait;
+use std::time::Duration;
+
+const CHECK_TIMEOUT: Duration = Duration::from_secs(1);
+
+fn healthy_repo() -> MockHealthRepository {
+    let mut repo = MockHealthRepository::new();
+    repo.expect_ping().returning(|| Ok(()));
+    repo.expect_pending_migrations().returning(|| Ok(vec![]));
+    repo
+}
+
+fn usecase(
+    repo: MockHealthRepository,
+    users_api: Option<MockUpstreamProbe>,
+    shutdown: Shutdown,
+) -> HealthUsecase<MockHealthRepository, MockUpstreamProbe> {
+    HealthUsecase::new(repo, users_api, shutdown, CHECK_TIMEOUT)
+}
+
+// 応答しないデータベースの代わり
+#[derive(Clone)]
+struct SlowHealthRepository;
+
+#[async_trait]
+impl HealthRepository for SlowHealthRepository {
+    async fn ping(&self) -> Result<(), AppError> {
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        Ok(())
+    }
+
+    async fn pending_migrations(&self) -> Result<Vec<i64>, AppError> {
+        Ok(vec![])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_liveness_has_no_checks() {
+        let usecase = usecase(MockHealthRepository::new(), None, Shutdown::new());
+
+        let report = usecase.liveness();
+
+        assert!(report.is_ok());
+        assert!(report.checks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_readiness_ok() {
+        let usecase = usecase(healthy_repo(), None, Shutdown::new());
+
+        let report = usecase.readiness().await;
+
+        assert!(report.is_ok());
+        // users API は設定した場合だけ確認する
+        assert_eq!(
+            report.checks.keys().collect::<Vec<_>>(),
+            ["database", "migrations", "shutdown"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_readiness_reports_failed_checks() {
+        let mut repo = MockHealthRepository::new();
+        repo.expect_ping()
+            .returning(|| Err(AppError::DatabaseError(sqlx::Error::PoolTimedOut)));
+        repo.expect_pending_migrations()
+            .returning(|| Ok(vec![20250728090000, 20250804090000]));
+        let mut users_api = MockUpstreamProbe::new();
+        users_api.expect_check().returning(|| Ok(()));
+        let usecase = usecase(repo, Some(users_api), Shutdown::new());
+
+        let report = usecase.readiness().await;
+
+        assert_eq!(report.status, HealthStatus::Fail);
+        // 内部のエラーの詳細はレスポンスに含めない
+        assert_eq!(
+            report.checks["database"].error.as_deref(),
+            Some("database is unavailable")
+        );
+        assert_eq!(
+            report.checks["migrations"].error.as_deref(),
+            Some("pending migrations: 20250728090000, 20250804090000")
+        );
+        assert_eq!(report.checks["users_api"].status, HealthStatus::Ok);
+    }
+
+    #[tokio::test]
+    async fn test_readiness_fails_during_shutdown() {
+        let shutdown = Shutdown::new();
+        let usecase = usecase(healthy_repo(), None, shutdown.clone());
+        shutdown.trigger();
+
+        let report = usecase.readiness().await;
+
+        assert_eq!(report.status, HealthStatus::Fail);
+        assert_eq!(
+            report.checks["shutdown"],
+            CheckResult::fail(0, "server is shutting down")
+        );
+        assert_eq!(report.checks["database"].status, HealthStatus::Ok);
+    }
+
+    #[tokio::test]
+    async fn test_readiness_times_out_slow_checks() {
+        let usecase = HealthUsecase::new(
+            SlowHealthRepository,
+            None::<MockUpstreamProbe>,
+            Shutdown::new(),
+            Duration::from_millis(50),
+        );
+
+        let report = usecase.readiness().await;
+
+        let database = &report.checks["database"];
+        assert_eq!(database.status, HealthStatus::Fail);
+        assert_eq!(database.error.as_deref(), Some("timed out after 50ms"));
+        assert!(database.latency_ms < 1000);
+        assert_eq!(report.checks["migrations"].status, HealthStatus::Ok);
+    }
+}
@@ -0,0 +1,159 @@
+use crate::error::AppError;
+use crate::models::caller::Caller;
+use crate::models::idempotency::{IdempotencyRecord, IdempotencyStart, IN_PROGRESS_TIMEOUT};
+use crate::models::workspace::WorkspaceRole;
+use crate::repositories::idempotency_repository::MockIdempotencyRepository;
+use crate::usecase::idempotency_usecase::{IdempotencyService, IdempotencyUsecase};
+use chrono::{Duration, Utc};
+use mockall::predicate::*;
+use sqlx::types::Json;
+use uuid::Uuid;
+
+const USER_ID: Uuid = Uuid::from_u128(1);
+
+fn caller() -> Caller {
+    Caller::new(USER_ID, USER_ID, WorkspaceRole::Member)
+}
+
+// 確保に失敗し、既存のキーとして record を返すモック
+fn repo_with_existing(record: IdempotencyRecord) -> MockIdempotencyRepository {
+    let mut repo = MockIdempotencyRepository::new();
+    repo.expect_reserve().times(1).returning(|_| Ok(false));
+    repo.expect_find()
+        .with(eq(USER_ID), eq("key".to_string()))
+        .times(1)
+        .returning(move |_, _| Ok(Some(record.clone())));
+    repo
+}
+
+fn record(request_hash: &str, created_ago: Duration) -> IdempotencyRecord {
+    IdempotencyRecord::new(
+        USER_ID,
+        "key".to_string(),
+        request_hash.to_string(),
+        Utc::now() - created_ago,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_begin_proceeds_with_new_key() {
+        // モックリポジトリの作成
+        let mut repo = MockIdempotencyRepository::new();
+        repo.expect_reserve()
+            .withf(|record| {
+                record.user_id == USER_ID
+                    && record.key == "key"
+                    && record.request_hash == "hash"
+                    && record.response_status.is_none()
+            })
+            .times(1)
+            .returning(|_| Ok(true));
+
+        // テスト実行
+        let result = IdempotencyUsecase::new(repo)
+            .begin(&caller(), "key".to_string(), "hash".to_string())
+            .await
+            .unwrap();
+
+        // 検証
+        assert!(matches!(result, IdempotencyStart::Proceed { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_begin_replays_completed_key() {
+        let mut completed = record("hash", Duration::minutes(5));
+        completed.response_status = Some(201);
+        completed.response_headers = Some(Json(vec![(
+            "content-type".to_string(),
+            "application/json".to_string(),
+        )]));
+        completed.response_body = Some(b"{\"id\":1}".to_vec());
+
+        // テスト実行
+        let result = IdempotencyUsecase::new(repo_with_existing(completed.clone()))
+            .begin(&caller(), "key".to_string(), "hash".to_string())
+            .await
+            .unwrap();
+
+        // 検証
+        assert_eq!(
+            result,
+            IdempotencyStart::Replay(completed.response().unwrap())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_begin_rejects_key_used_for_different_request() {
+        let mut completed = record("hash", Duration::minutes(5));
+        completed.response_status = Some(201);
+
+        // テスト実行
+        let result = IdempotencyUsecase::new(repo_with_existing(completed))
+            .begin(&caller(), "key".to_string(), "other-hash".to_string())
+            .await;
+
+        // 検証
+        assert!(matches!(result, Err(AppError::UnprocessableEntity(_))));
+    }
+
+    #[tokio::test]
+    async fn test_begin_rejects_key_in_progress() {
+        // テスト実行
+        let result = IdempotencyUsecase::new(repo_with_existing(record("hash", Duration::zero())))
+            .begin(&caller(), "key".to_string(), "hash".to_string())
+            .await;
+
+        // 検証
+        assert!(matches!(result, Err(AppError::Conflict(_))));
+    }
+
+    #[tokio::test]
+    async fn test_begin_takes_over_abandoned_key() {
+        // モックリポジトリの作成（処理中のまま残ったキー）
+        let abandoned = record("hash", IN_PROGRESS_TIMEOUT + Duration::seconds(1));
+        let abandoned_attempt = abandoned.attempt_id;
+        let mut repo = repo_with_existing(abandoned);
+        // 削除して確保し直すのではなく、新しい試行として1回で確保し直す
+        repo.expect_release().never();
+        repo.expect_take_over()
+            .withf(move |record, abandoned_before| {
+                record.attempt_id != abandoned_attempt
+                    && record.request_hash == "hash"
+                    && *abandoned_before <= Utc::now() - IN_PROGRESS_TIMEOUT
+            })
+            .times(1)
+            .returning(|_, _| Ok(true));
+
+        // テスト実行
+        let result = IdempotencyUsecase::new(repo)
+            .begin(&caller(), "key".to_string(), "hash".to_string())
+            .await
+            .unwrap();
+
+        // 検証
+        assert!(matches!(
+            result,
+            IdempotencyStart::Proceed { attempt_id } if attempt_id != abandoned_attempt
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_begin_rejects_key_taken_over_by_another_retry() {
+        // モックリポジトリの作成（同時に届いた別の再送が先に引き継いだ）
+        let mut repo =
+            repo_with_existing(record("hash", IN_PROGRESS_TIMEOUT + Duration::seconds(1)));
+        repo.expect_take_over().times(1).returning(|_, _| Ok(false));
+
+        // テスト実行
+        let result = IdempotencyUsecase::new(repo)
+            .begin(&caller(), "key".to_string(), "hash".to_string())
+            .await;
+
+        // 検証
+        assert!(matches!(result, Err(AppError::Conflict(_))));
+    }
+}
@@ -1 +1,12 @@
-pub mod task_usecase_tests;
\ No newline at end of file
+pub mod api_key_usecase_tests;
+pub mod auth_usecase_tests;
+pub mod calendar_usecase_tests;
+pub mod health_usecase_tests;
+pub mod idempotency_usecase_tests;
+pub mod oidc_usecase_tests;
+pub mod seed_usecase_tests;
+pub mod session_usecase_tests;
+pub mod sharing_usecase_tests;
+pub mod task_usecase_tests;
+pub mod time_tracking_usecase_tests;
+pub mod workspace_usecase_tests;
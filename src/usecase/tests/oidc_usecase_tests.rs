@@ -0,0 +1,335 @@
+use crate::auth::jwt::JwtKeys;
+use crate::error::AppError;
+use crate::models::oidc::{code_challenge, IdTokenClaims, OidcIdentity, OidcLoginState};
+use crate::models::user_account::UserAccount;
+use crate::repositories::oidc_provider::MockOidcProvider;
+use crate::repositories::oidc_repository::MockOidcRepository;
+use crate::repositories::refresh_token_repository::MockRefreshTokenRepository;
+use crate::repositories::user_repository::MockUserRepository;
+use crate::secret::hash_token;
+use crate::usecase::oidc_usecase::{OidcService, OidcUsecase};
+use chrono::{Duration, Utc};
+use mockall::predicate::*;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+const JWT_SECRET: &[u8] = b"secret";
+const ISSUER: &str = "https://idp.example.com";
+const STATE: &str = "state-1";
+const NONCE: &str = "nonce-1";
+const CODE_VERIFIER: &str = "verifier-1";
+
+type TestOidcUsecase = OidcUsecase<
+    MockOidcProvider,
+    MockOidcRepository,
+    MockUserRepository,
+    MockRefreshTokenRepository,
+>;
+
+fn usecase(
+    provider: MockOidcProvider,
+    oidc_repo: MockOidcRepository,
+    user_repo: MockUserRepository,
+) -> TestOidcUsecase {
+    // トークンを発行するテストでは、リフレッシュトークンを保存できるようにしておく
+    let mut refresh_token_repo = MockRefreshTokenRepository::new();
+    refresh_token_repo.expect_create().returning(|_| Ok(()));
+    OidcUsecase::new(
+        provider,
+        oidc_repo,
+        user_repo,
+        refresh_token_repo,
+        JwtKeys::hs256(JWT_SECRET),
+    )
+}
+
+// STATE で開始したログイン状態を1回だけ返すモック
+fn oidc_repo_with_state(created_at: chrono::DateTime<Utc>) -> MockOidcRepository {
+    let mut oidc_repo = MockOidcRepository::new();
+    oidc_repo
+        .expect_take_login_state()
+        .with(eq(hash_token(STATE)))
+        .times(1)
+        .returning(move |state_hash| {
+            Ok(Some(OidcLoginState::new(
+                state_hash,
+                NONCE.to_string(),
+                CODE_VERIFIER.to_string(),
+                created_at,
+            )))
+        });
+    oidc_repo
+}
+
+// 認可コードを claims の ID トークンに交換するモック
+fn provider_returning(claims: IdTokenClaims) -> MockOidcProvider {
+    let mut provider = MockOidcProvider::new();
+    provider
+        .expect_exchange_code()
+        .with(eq("code-1".to_string()), eq(CODE_VERIFIER.to_string()))
+        .times(1)
+        .returning(move |_, _| Ok(claims.clone()));
+    provider
+}
+
+fn claims(email: Option<&str>, email_verified: bool) -> IdTokenClaims {
+    IdTokenClaims {
+        iss: ISSUER.to_string(),
+        sub: "user-1".to_string(),
+        email: email.map(str::to_string),
+        email_verified: Some(email_verified),
+        nonce: Some(NONCE.to_string()),
+    }
+}
+
+async fn access_token_user(access_token: &str) -> Uuid {
+    JwtKeys::hs256(JWT_SECRET)
+        .verify_access_token(access_token)
+        .await
+        .unwrap()
+        .sub
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_begin_login_saves_state_for_callback() {
+        // モックリポジトリの作成
+        let requested = Arc::new(Mutex::new(None));
+        let saved = Arc::new(Mutex::new(None));
+        let mut provider = MockOidcProvider::new();
+        let requested_clone = requested.clone();
+        provider
+            .expect_authorization_url()
+            .times(1)
+            .returning(move |request| {
+                *requested_clone.lock().unwrap() = Some(request);
+                Ok("https://idp.example.com/authorize?client_id=task-api".to_string())
+            });
+        let mut oidc_repo = MockOidcRepository::new();
+        let saved_clone = saved.clone();
+        oidc_repo
+            .expect_save_login_state()
+            .times(1)
+            .returning(move |state| {
+                *saved_clone.lock().unwrap() = Some(state);
+                Ok(())
+            });
+
+        // ユースケースの作成
+        let usecase = usecase(provider, oidc_repo, MockUserRepository::new());
+
+        // テスト実行
+        let login = usecase.begin_login().await.unwrap();
+
+        // 検証
+        let requested = requested.lock().unwrap().clone().unwrap();
+        let saved = saved.lock().unwrap().clone().unwrap();
+        assert_eq!(
+            login.authorization_url,
+            "https://idp.example.com/authorize?client_id=task-api"
+        );
+        assert_eq!(requested.state, login.state);
+        // state は平文を保存せず、PKCE の code_challenge はコード検証子から計算する
+        assert_eq!(saved.state_hash, hash_token(&login.state));
+        assert_eq!(saved.nonce, requested.nonce);
+        assert_eq!(
+            requested.code_challenge,
+            code_challenge(&saved.code_verifier)
+        );
+        assert_ne!(saved.nonce, login.state);
+    }
+
+    #[tokio::test]
+    async fn test_complete_login_with_linked_identity() {
+        let user_id = Uuid::now_v7();
+
+        // モックリポジトリの作成
+        let mut oidc_repo = oidc_repo_with_state(Utc::now());
+        oidc_repo
+            .expect_find_identity()
+            .with(eq(ISSUER.to_string()), eq("user-1".to_string()))
+            .times(1)
+            .returning(move |issuer, subject| {
+                Ok(Some(OidcIdentity::new(issuer, subject, user_id)))
+            });
+
+        // ユースケースの作成
+        let usecase = usecase(
+            provider_returning(claims(None, false)),
+            oidc_repo,
+            MockUserRepository::new(),
+        );
+
+        // テスト実行
+        let tokens = usecase
+            .complete_login("code-1".to_string(), STATE.to_string())
+            .await
+            .unwrap();
+
+        // 検証（紐付け済みのアカウントはメールアドレスがなくてもログインできる）
+        assert_eq!(access_token_user(&tokens.access_token).await, user_id);
+    }
+
+    #[tokio::test]
+    async fn test_complete_login_links_existing_user_by_verified_email() {
+        let user = UserAccount::new("alice@example.com".to_string(), "hash".to_string());
+        let user_id = user.id;
+
+        // モックリポジトリの作成
+        let mut oidc_repo = oidc_repo_with_state(Utc::now());
+        oidc_repo
+            .expect_find_identity()
+            .times(1)
+            .returning(|_, _| Ok(None));
+        oidc_repo
+            .expect_create_identity()
+            .withf(move |identity| {
+                identity.issuer == ISSUER
+                    && identity.subject == "user-1"
+                    && identity.user_id == user_id
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+        let mut user_repo = MockUserRepository::new();
+        user_repo
+            .expect_find_by_email()
+            .with(eq("alice@example.com".to_string()))
+            .times(1)
+            .returning(move |_| Ok(Some(user.clone())));
+
+        // ユースケースの作成
+        let usecase = usecase(
+            provider_returning(claims(Some("Alice@Example.com"), true)),
+            oidc_repo,
+            user_repo,
+        );
+
+        // テスト実行
+        let tokens = usecase
+            .complete_login("code-1".to_string(), STATE.to_string())
+            .await
+            .unwrap();
+
+        // 検証
+        assert_eq!(access_token_user(&tokens.access_token).await, user_id);
+    }
+
+    #[tokio::test]
+    async fn test_complete_login_creates_user_without_password() {
+        // モックリポジトリの作成
+        let mut oidc_repo = oidc_repo_with_state(Utc::now());
+        oidc_repo
+            .expect_find_identity()
+            .times(1)
+            .returning(|_, _| Ok(None));
+        oidc_repo
+            .expect_create_identity()
+            .times(1)
+            .returning(|_| Ok(()));
+        let mut user_repo = MockUserRepository::new();
+        user_repo
+            .expect_find_by_email()
+            .times(1)
+            .returning(|_| Ok(None));
+        user_repo
+            .expect_create()
+            .withf(|user| user.email == "alice@example.com" && user.password_hash.is_empty())
+            .times(1)
+            .returning(Ok);
+
+        // ユースケースの作成
+        let usecase = usecase(
+            provider_returning(claims(Some("alice@example.com"), true)),
+            oidc_repo,
+            user_repo,
+        );
+
+        // テスト実行
+        let result = usecase
+            .complete_login("code-1".to_string(), STATE.to_string())
+            .await;
+
+        // 検証
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_complete_login_requires_verified_email_for_new_identity() {
+        for claims in [claims(Some("alice@example.com"), false), claims(None, true)] {
+            // モックリポジトリの作成
+            let mut oidc_repo = oidc_repo_with_state(Utc::now());
+            oidc_repo
+                .expect_find_identity()
+                .times(1)
+                .returning(|_, _| Ok(None));
+
+            // ユースケースの作成（ユーザーの検索・作成は行わない）
+            let usecase = usecase(
+                provider_returning(claims),
+                oidc_repo,
+                MockUserRepository::new(),
+            );
+
+            // テスト実行
+            let result = usecase
+                .complete_login("code-1".to_string(), STATE.to_string())
+                .await;
+
+            // 検証
+            assert!(matches!(result, Err(AppError::Forbidden)));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_complete_login_rejects_unknown_or_expired_state() {
+        // 存在しない state
+        let mut oidc_repo = MockOidcRepository::new();
+        oidc_repo
+            .expect_take_login_state()
+            .times(1)
+            .returning(|_| Ok(None));
+        let result = usecase(
+            MockOidcProvider::new(),
+            oidc_repo,
+            MockUserRepository::new(),
+        )
+        .complete_login("code-1".to_string(), STATE.to_string())
+        .await;
+        assert!(matches!(result, Err(AppError::Unauthorized)));
+
+        // 期限切れの state（プロバイダーに認可コードを送らない）
+        let oidc_repo = oidc_repo_with_state(Utc::now() - Duration::hours(1));
+        let result = usecase(
+            MockOidcProvider::new(),
+            oidc_repo,
+            MockUserRepository::new(),
+        )
+        .complete_login("code-1".to_string(), STATE.to_string())
+        .await;
+        assert!(matches!(result, Err(AppError::Unauthorized)));
+    }
+
+    #[tokio::test]
+    async fn test_complete_login_rejects_nonce_mismatch() {
+        let mut claims = claims(Some("alice@example.com"), true);
+        claims.nonce = Some("another-nonce".to_string());
+
+        // ユースケースの作成（アカウントの検索は行わない）
+        let usecase = usecase(
+            provider_returning(claims),
+            oidc_repo_with_state(Utc::now()),
+            MockUserRepository::new(),
+        );
+
+        // テスト実行
+        let result = usecase
+            .complete_login("code-1".to_string(), STATE.to_string())
+            .await;
+
+        // 検証
+        assert!(matches!(result, Err(AppError::Unauthorized)));
+    }
+}
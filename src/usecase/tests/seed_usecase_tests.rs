@@ -0,0 +1,69 @@
+use crate::models::task::Task;
+use crate::models::user_account::UserAccount;
+use crate::repositories::task_repository::MockTaskRepository;
+use crate::repositories::user_repository::MockUserRepository;
+use crate::secret::verify_password;
+use crate::usecase::seed_usecase::{SeedService, SeedUsecase, SEED_EMAIL, SEED_PASSWORD};
+use mockall::predicate::*;
+
+fn seed_user() -> UserAccount {
+    UserAccount::new(SEED_EMAIL.to_string(), "hash".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_seed_creates_user_and_tasks() {
+        let mut user_repo = MockUserRepository::new();
+        user_repo
+            .expect_find_by_email()
+            .with(eq(SEED_EMAIL.to_string()))
+            .times(1)
+            .returning(|_| Ok(None));
+        user_repo
+            .expect_create()
+            .withf(|u| u.email == SEED_EMAIL && verify_password(SEED_PASSWORD, &u.password_hash))
+            .times(1)
+            .returning(Ok);
+        let mut task_repo = MockTaskRepository::new();
+        task_repo.expect_find_all().returning(|_| Ok(vec![]));
+        // タスクは作成したユーザーの個人ワークスペースに作成される
+        task_repo
+            .expect_create()
+            .withf(|task| task.workspace_id == task.owner_id)
+            .returning(Ok);
+        let usecase = SeedUsecase::new(user_repo, task_repo);
+
+        let report = usecase.seed().await.unwrap();
+
+        assert!(report.created_user);
+        assert_eq!(report.user.email, SEED_EMAIL);
+        assert!(report.created_tasks > 0);
+    }
+
+    #[tokio::test]
+    async fn test_seed_skips_existing_data() {
+        let user = seed_user();
+        let user_id = user.id;
+        let mut user_repo = MockUserRepository::new();
+        user_repo
+            .expect_find_by_email()
+            .returning(move |_| Ok(Some(user.clone())));
+        user_repo.expect_create().never();
+        let mut task_repo = MockTaskRepository::new();
+        task_repo
+            .expect_find_all()
+            .with(eq(user_id))
+            .returning(move |_| Ok(vec![Task::new(user_id, user_id, "既存".to_string())]));
+        task_repo.expect_create().never();
+        let usecase = SeedUsecase::new(user_repo, task_repo);
+
+        let report = usecase.seed().await.unwrap();
+
+        assert!(!report.created_user);
+        assert_eq!(report.user.id, user_id);
+        assert_eq!(report.created_tasks, 0);
+    }
+}
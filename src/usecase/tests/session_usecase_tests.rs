@@ -0,0 +1,266 @@
+use crate::auth::jwt::JwtKeys;
+use crate::error::AppError;
+use crate::models::api_key::ApiKeyScope;
+use crate::models::caller::Caller;
+use crate::models::session::{Session, SESSION_IDLE_TIMEOUT};
+use crate::models::user_account::UserAccount;
+use crate::models::workspace::WorkspaceRole;
+use crate::repositories::refresh_token_repository::MockRefreshTokenRepository;
+use crate::repositories::session_repository::MockSessionRepository;
+use crate::repositories::user_repository::MockUserRepository;
+use crate::secret::{hash_password, hash_token};
+use crate::usecase::session_usecase::{SessionService, SessionUsecase};
+use chrono::{DateTime, Duration, Utc};
+use mockall::predicate::*;
+use uuid::Uuid;
+
+const USER_ID: Uuid = Uuid::from_u128(1);
+const TOKEN: &str = "session-token";
+
+type TestSessionUsecase =
+    SessionUsecase<MockSessionRepository, MockUserRepository, MockRefreshTokenRepository>;
+
+fn usecase(
+    session_repo: MockSessionRepository,
+    user_repo: MockUserRepository,
+    refresh_token_repo: MockRefreshTokenRepository,
+) -> TestSessionUsecase {
+    SessionUsecase::new(
+        session_repo,
+        user_repo,
+        refresh_token_repo,
+        JwtKeys::hs256(b"secret"),
+    )
+}
+
+// alice@example.com / "correct horse" のユーザーを返すモック
+fn user_repo_with_alice() -> MockUserRepository {
+    let mut user = UserAccount::new(
+        "alice@example.com".to_string(),
+        hash_password("correct horse").unwrap(),
+    );
+    user.id = USER_ID;
+    let mut user_repo = MockUserRepository::new();
+    user_repo
+        .expect_find_by_email()
+        .returning(move |_| Ok(Some(user.clone())));
+    user_repo
+}
+
+// TOKEN のセッション（last_seen_at 以降アクセスがないもの）を返すモック
+fn session_repo_with(
+    created_at: DateTime<Utc>,
+    last_seen_at: DateTime<Utc>,
+) -> MockSessionRepository {
+    let mut session = Session::new(
+        USER_ID,
+        hash_token(TOKEN),
+        hash_token("csrf-token"),
+        created_at,
+    );
+    session.last_seen_at = last_seen_at;
+    let mut session_repo = MockSessionRepository::new();
+    session_repo
+        .expect_find_by_token_hash()
+        .with(eq(hash_token(TOKEN)))
+        .times(1)
+        .returning(move |_| Ok(Some(session.clone())));
+    session_repo
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_login_rotates_previous_session() {
+        // モックリポジトリの作成
+        let mut session_repo = MockSessionRepository::new();
+        // ログイン前のセッションは破棄する
+        session_repo
+            .expect_delete_by_token_hash()
+            .with(eq(hash_token("previous-token")))
+            .times(1)
+            .returning(|_| Ok(()));
+        session_repo
+            .expect_create()
+            .withf(|session| session.user_id == USER_ID)
+            .times(1)
+            .returning(|_| Ok(()));
+
+        // ユースケースの作成
+        let usecase = usecase(
+            session_repo,
+            user_repo_with_alice(),
+            MockRefreshTokenRepository::new(),
+        );
+
+        // テスト実行
+        let issued = usecase
+            .login(
+                "alice@example.com".to_string(),
+                "correct horse".to_string(),
+                Some("previous-token".to_string()),
+            )
+            .await
+            .unwrap();
+
+        // 検証（平文のトークンは保存しない）
+        assert_ne!(issued.token, "previous-token");
+        assert_eq!(issued.session.token_hash, hash_token(&issued.token));
+        assert!(issued.session.verifies_csrf(&issued.csrf_token));
+        assert_ne!(issued.token, issued.csrf_token);
+    }
+
+    #[tokio::test]
+    async fn test_login_with_wrong_password_creates_no_session() {
+        // ユースケースの作成（セッションの作成・破棄は行わない）
+        let usecase = usecase(
+            MockSessionRepository::new(),
+            user_repo_with_alice(),
+            MockRefreshTokenRepository::new(),
+        );
+
+        // テスト実行
+        let result = usecase
+            .login(
+                "alice@example.com".to_string(),
+                "wrong password".to_string(),
+                Some("previous-token".to_string()),
+            )
+            .await;
+
+        // 検証
+        assert!(matches!(result, Err(AppError::InvalidCredentials)));
+    }
+
+    #[tokio::test]
+    async fn test_authenticate_touches_session_at_most_once_a_minute() {
+        let now = Utc::now();
+
+        // 直前にアクセスがあった場合は更新しない
+        let session_repo = session_repo_with(now - Duration::hours(1), now);
+        let session = usecase(
+            session_repo,
+            MockUserRepository::new(),
+            MockRefreshTokenRepository::new(),
+        )
+        .authenticate(TOKEN)
+        .await
+        .unwrap();
+        assert_eq!(session.user_id, USER_ID);
+
+        // 1分以上経っていれば最終アクセス日時を更新する
+        let mut session_repo =
+            session_repo_with(now - Duration::hours(1), now - Duration::minutes(5));
+        session_repo
+            .expect_touch()
+            .times(1)
+            .returning(|_, _| Ok(()));
+        let result = usecase(
+            session_repo,
+            MockUserRepository::new(),
+            MockRefreshTokenRepository::new(),
+        )
+        .authenticate(TOKEN)
+        .await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_authenticate_rejects_and_deletes_expired_session() {
+        let now = Utc::now();
+
+        // モックリポジトリの作成（アイドルタイムアウトを過ぎたセッション）
+        let mut session_repo = session_repo_with(
+            now - Duration::hours(1),
+            now - SESSION_IDLE_TIMEOUT - Duration::minutes(1),
+        );
+        session_repo
+            .expect_delete_by_token_hash()
+            .with(eq(hash_token(TOKEN)))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        // ユースケースの作成
+        let usecase = usecase(
+            session_repo,
+            MockUserRepository::new(),
+            MockRefreshTokenRepository::new(),
+        );
+
+        // テスト実行
+        let result = usecase.authenticate(TOKEN).await;
+
+        // 検証
+        assert!(matches!(result, Err(AppError::Unauthorized)));
+    }
+
+    #[tokio::test]
+    async fn test_authenticate_rejects_unknown_session() {
+        // モックリポジトリの作成
+        let mut session_repo = MockSessionRepository::new();
+        session_repo
+            .expect_find_by_token_hash()
+            .times(1)
+            .returning(|_| Ok(None));
+
+        // テスト実行
+        let result = usecase(
+            session_repo,
+            MockUserRepository::new(),
+            MockRefreshTokenRepository::new(),
+        )
+        .authenticate(TOKEN)
+        .await;
+
+        // 検証
+        assert!(matches!(result, Err(AppError::Unauthorized)));
+    }
+
+    #[tokio::test]
+    async fn test_logout_everywhere_revokes_sessions_and_refresh_tokens() {
+        // モックリポジトリの作成
+        let mut session_repo = MockSessionRepository::new();
+        session_repo
+            .expect_delete_by_user()
+            .with(eq(USER_ID))
+            .times(1)
+            .returning(|_| Ok(2));
+        let mut refresh_token_repo = MockRefreshTokenRepository::new();
+        refresh_token_repo
+            .expect_delete_by_user()
+            .with(eq(USER_ID))
+            .times(1)
+            .returning(|_| Ok(3));
+
+        // ユースケースの作成
+        let usecase = usecase(session_repo, MockUserRepository::new(), refresh_token_repo);
+
+        // テスト実行
+        let result = usecase
+            .logout_everywhere(&Caller::new(USER_ID, USER_ID, WorkspaceRole::Admin))
+            .await;
+
+        // 検証
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_api_key_cannot_logout_everywhere() {
+        // ユースケースの作成（セッションは破棄しない）
+        let usecase = usecase(
+            MockSessionRepository::new(),
+            MockUserRepository::new(),
+            MockRefreshTokenRepository::new(),
+        );
+
+        // テスト実行
+        let caller = Caller::new(USER_ID, USER_ID, WorkspaceRole::Admin)
+            .with_scopes(vec![ApiKeyScope::TasksWrite]);
+        let result = usecase.logout_everywhere(&caller).await;
+
+        // 検証
+        assert!(matches!(result, Err(AppError::Forbidden)));
+    }
+}
@@ -0,0 +1,238 @@
+use crate::error::AppError;
+use crate::models::caller::Caller;
+use crate::models::grant::{Grant, GrantPermission, ShareTarget};
+use crate::models::task::Task;
+use crate::models::user_account::UserAccount;
+use crate::models::workspace::WorkspaceRole;
+use crate::repositories::grant_repository::MockGrantRepository;
+use crate::repositories::task_repository::MockTaskRepository;
+use crate::repositories::user_repository::MockUserRepository;
+use crate::secret::hash_token;
+use crate::usecase::sharing_usecase::{SharingService, SharingUsecase};
+use chrono::{Duration, Utc};
+use mockall::predicate::*;
+use uuid::Uuid;
+
+// 共有するユーザーと、そのワークスペース
+const USER_ID: Uuid = Uuid::from_u128(1);
+const WORKSPACE_ID: Uuid = Uuid::from_u128(10);
+
+fn member() -> Caller {
+    Caller::new(USER_ID, WORKSPACE_ID, WorkspaceRole::Member)
+}
+
+// 呼び出し元が作成したタスクを返すようにモックを設定するヘルパー関数
+fn task_repo_with_own_task() -> (MockTaskRepository, Uuid) {
+    let task = Task::new(WORKSPACE_ID, USER_ID, "共有するタスク".to_string());
+    let task_id = task.id;
+    let mut task_repo = MockTaskRepository::new();
+    task_repo
+        .expect_find_by_id()
+        .with(eq(WORKSPACE_ID), eq(task_id))
+        .returning(move |_, _| Ok(Some(task.clone())));
+    (task_repo, task_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_share_task_with_user() {
+        // モックリポジトリの作成
+        let (task_repo, task_id) = task_repo_with_own_task();
+        let mut user_repo = MockUserRepository::new();
+        let mut grant_repo = MockGrantRepository::new();
+        let grantee = UserAccount::new("bob@example.com".to_string(), "hash".to_string());
+        let grantee_id = grantee.id;
+
+        // メールアドレスは正規化してから検索する
+        user_repo
+            .expect_find_by_email()
+            .with(eq("bob@example.com".to_string()))
+            .times(1)
+            .returning(move |_| Ok(Some(grantee.clone())));
+        grant_repo
+            .expect_create()
+            .withf(move |grant| {
+                grant.workspace_id == WORKSPACE_ID
+                    && grant.task_id == Some(task_id)
+                    && grant.grantee_id == Some(grantee_id)
+                    && grant.permission == GrantPermission::Edit
+                    && grant.created_by == USER_ID
+            })
+            .times(1)
+            .returning(Ok);
+
+        // ユースケースの作成
+        let usecase = SharingUsecase::new(grant_repo, task_repo, user_repo);
+
+        // テスト実行
+        let result = usecase
+            .share_with_user(
+                &member(),
+                ShareTarget::Task(task_id),
+                " Bob@Example.com ".to_string(),
+                GrantPermission::Edit,
+                None,
+            )
+            .await;
+
+        // 検証
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_member_cannot_share_project() {
+        // モックリポジトリの作成
+        let mut grant_repo = MockGrantRepository::new();
+        grant_repo.expect_create().never();
+
+        // ユースケースの作成
+        let usecase = SharingUsecase::new(
+            grant_repo,
+            MockTaskRepository::new(),
+            MockUserRepository::new(),
+        );
+
+        // テスト実行
+        let result = usecase
+            .create_share_link(&member(), ShareTarget::Project("release".to_string()), None)
+            .await;
+
+        // 検証
+        assert!(matches!(result, Err(AppError::Forbidden)));
+    }
+
+    #[tokio::test]
+    async fn test_create_share_link_stores_only_hash() {
+        // モックリポジトリの作成
+        let (task_repo, task_id) = task_repo_with_own_task();
+        let mut grant_repo = MockGrantRepository::new();
+        grant_repo
+            .expect_create()
+            .withf(|grant| {
+                grant.is_link()
+                    && grant.permission == GrantPermission::View
+                    && grant
+                        .token_hash
+                        .as_ref()
+                        .is_some_and(|hash| hash.len() == 64)
+            })
+            .times(1)
+            .returning(Ok);
+
+        // ユースケースの作成
+        let usecase = SharingUsecase::new(grant_repo, task_repo, MockUserRepository::new());
+
+        // テスト実行
+        let issued = usecase
+            .create_share_link(&member(), ShareTarget::Task(task_id), Some(24))
+            .await
+            .unwrap();
+
+        // 検証
+        assert_eq!(issued.grant.token_hash, Some(hash_token(&issued.token)));
+        let expires_in = issued.grant.expires_at.unwrap() - issued.grant.created_at;
+        assert!((expires_in - Duration::hours(24)).num_seconds().abs() <= 1);
+    }
+
+    #[tokio::test]
+    async fn test_create_share_link_validates_expiry() {
+        // ユースケースの作成
+        let (task_repo, task_id) = task_repo_with_own_task();
+        let usecase = SharingUsecase::new(
+            MockGrantRepository::new(),
+            task_repo,
+            MockUserRepository::new(),
+        );
+
+        // テスト実行・検証
+        for expires_in_hours in [0, 24 * 30 + 1] {
+            let result = usecase
+                .create_share_link(
+                    &member(),
+                    ShareTarget::Task(task_id),
+                    Some(expires_in_hours),
+                )
+                .await;
+            assert!(matches!(result, Err(AppError::BadRequest(_))));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_member_cannot_revoke_others_grant() {
+        // モックリポジトリの作成（他の member が作成した共有）
+        let mut grant_repo = MockGrantRepository::new();
+        let grant = Grant::for_user(
+            WORKSPACE_ID,
+            ShareTarget::Project("release".to_string()),
+            Uuid::from_u128(3),
+            GrantPermission::View,
+            None,
+            Uuid::from_u128(2),
+        );
+        let grant_id = grant.id;
+        grant_repo
+            .expect_find_by_id()
+            .with(eq(WORKSPACE_ID), eq(grant_id))
+            .times(1)
+            .returning(move |_, _| Ok(Some(grant.clone())));
+        grant_repo.expect_delete().never();
+
+        // ユースケースの作成
+        let usecase = SharingUsecase::new(
+            grant_repo,
+            MockTaskRepository::new(),
+            MockUserRepository::new(),
+        );
+
+        // テスト実行
+        let result = usecase.revoke_grant(&member(), grant_id).await;
+
+        // 検証
+        assert!(matches!(result, Err(AppError::Forbidden)));
+    }
+
+    #[tokio::test]
+    async fn test_get_link_tasks() {
+        // モックリポジトリの作成
+        let mut grant_repo = MockGrantRepository::new();
+        let mut task_repo = MockTaskRepository::new();
+        let mut task = Task::new(WORKSPACE_ID, USER_ID, "リリース作業".to_string());
+        task.project = Some("release".to_string());
+        let grant = Grant::for_link(
+            WORKSPACE_ID,
+            ShareTarget::Project("release".to_string()),
+            hash_token("valid"),
+            Utc::now() + Duration::days(1),
+            USER_ID,
+        );
+
+        // 無効・期限切れのトークンは見つからない
+        grant_repo
+            .expect_find_active_by_token()
+            .with(eq(hash_token("invalid")), always())
+            .returning(|_, _| Ok(None));
+        grant_repo
+            .expect_find_active_by_token()
+            .with(eq(hash_token("valid")), always())
+            .returning(move |_, _| Ok(Some(grant.clone())));
+        // リンクを作成したワークスペースのプロジェクトのタスクを返す
+        task_repo
+            .expect_find_filtered()
+            .withf(|workspace_id, filter| {
+                *workspace_id == WORKSPACE_ID && filter.project.as_deref() == Some("release")
+            })
+            .times(1)
+            .returning(move |_, _| Ok(vec![task.clone()]));
+
+        // ユースケースの作成
+        let usecase = SharingUsecase::new(grant_repo, task_repo, MockUserRepository::new());
+
+        // テスト実行・検証
+        assert!(usecase.get_link_tasks("invalid").await.unwrap().is_none());
+        let tasks = usecase.get_link_tasks("valid").await.unwrap().unwrap();
+        assert_eq!(tasks.len(), 1);
+    }
+}
@@ -0,0 +1,182 @@
+use crate::error::AppError;
+use crate::models::caller::Caller;
+use crate::models::task::Task;
+use crate::models::time_entry::TimeEntry;
+use crate::models::workspace::WorkspaceRole;
+use crate::repositories::task_repository::MockTaskRepository;
+use crate::repositories::time_entry_repository::MockTimeEntryRepository;
+use crate::usecase::time_tracking_usecase::{TimeTrackingService, TimeTrackingUsecase};
+use chrono::{Duration, Utc};
+use mockall::predicate::*;
+use uuid::Uuid;
+
+// テストで呼び出し元になるユーザーと、そのワークスペース
+const USER_ID: Uuid = Uuid::from_u128(1);
+const WORKSPACE_ID: Uuid = Uuid::from_u128(10);
+
+fn caller() -> Caller {
+    Caller::new(USER_ID, WORKSPACE_ID, WorkspaceRole::Member)
+}
+
+// 呼び出し元のワークスペースにタスクが存在するようにモックを設定するヘルパー関数
+fn task_repo_with(task: Task) -> MockTaskRepository {
+    let mut task_repo = MockTaskRepository::new();
+    task_repo
+        .expect_find_by_id()
+        .with(eq(WORKSPACE_ID), eq(task.id))
+        .returning(move |_, _| Ok(Some(task.clone())));
+    task_repo
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_start_timer() {
+        // モックリポジトリの作成
+        let task = Task::new(WORKSPACE_ID, USER_ID, "タスク1".to_string());
+        let task_id = task.id;
+        let task_repo = task_repo_with(task);
+        let mut entry_repo = MockTimeEntryRepository::new();
+
+        // 計測中のタイマーがなければ新しいタイマーが作成される
+        entry_repo
+            .expect_find_running()
+            .with(eq(WORKSPACE_ID), eq(USER_ID))
+            .times(1)
+            .returning(|_, _| Ok(None));
+        entry_repo
+            .expect_create()
+            .withf(move |e| {
+                e.workspace_id == WORKSPACE_ID
+                    && e.task_id == task_id
+                    && e.user_id == Some(USER_ID)
+                    && e.ended_at.is_none()
+            })
+            .times(1)
+            .returning(Ok);
+
+        // ユースケースの作成
+        let usecase = TimeTrackingUsecase::new(entry_repo, task_repo);
+
+        // テスト実行
+        let entry = usecase.start_timer(&caller(), task_id).await.unwrap();
+
+        // 検証
+        assert_eq!(entry.task_id, task_id);
+        assert!(entry.ended_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_start_timer_conflicts_with_running_timer() {
+        // モックリポジトリの作成
+        let task = Task::new(WORKSPACE_ID, USER_ID, "タスク1".to_string());
+        let task_id = task.id;
+        let task_repo = task_repo_with(task);
+        let mut entry_repo = MockTimeEntryRepository::new();
+
+        // 別のタスクでタイマーが動いている
+        entry_repo.expect_find_running().times(1).returning(|_, _| {
+            Ok(Some(TimeEntry::new(
+                WORKSPACE_ID,
+                Uuid::now_v7(),
+                Some(USER_ID),
+                Utc::now(),
+                None,
+                None,
+            )))
+        });
+        entry_repo.expect_create().never();
+
+        // ユースケースの作成
+        let usecase = TimeTrackingUsecase::new(entry_repo, task_repo);
+
+        // テスト実行
+        let result = usecase.start_timer(&caller(), task_id).await;
+
+        // 検証
+        assert!(matches!(result, Err(AppError::Conflict(_))));
+    }
+
+    #[tokio::test]
+    async fn test_start_timer_for_missing_task() {
+        // モックリポジトリの作成
+        let mut task_repo = MockTaskRepository::new();
+        let mut entry_repo = MockTimeEntryRepository::new();
+
+        // 呼び出し元のタスクとしては存在しない（他のユーザーのタスクの場合も同じ）
+        task_repo
+            .expect_find_by_id()
+            .with(eq(WORKSPACE_ID), always())
+            .times(1)
+            .returning(|_, _| Ok(None));
+        entry_repo.expect_create().never();
+
+        // ユースケースの作成
+        let usecase = TimeTrackingUsecase::new(entry_repo, task_repo);
+
+        // テスト実行
+        let result = usecase.start_timer(&caller(), Uuid::now_v7()).await;
+
+        // 検証
+        assert!(matches!(result, Err(AppError::ResourceNotFound("Task"))));
+    }
+
+    #[tokio::test]
+    async fn test_stop_timer_of_other_task() {
+        // モックリポジトリの作成
+        let task_repo = MockTaskRepository::new();
+        let mut entry_repo = MockTimeEntryRepository::new();
+
+        // 別のタスクでタイマーが動いている
+        entry_repo.expect_find_running().times(1).returning(|_, _| {
+            Ok(Some(TimeEntry::new(
+                WORKSPACE_ID,
+                Uuid::now_v7(),
+                Some(USER_ID),
+                Utc::now(),
+                None,
+                None,
+            )))
+        });
+        entry_repo.expect_stop().never();
+
+        // ユースケースの作成
+        let usecase = TimeTrackingUsecase::new(entry_repo, task_repo);
+
+        // テスト実行
+        let result = usecase.stop_timer(&caller(), Uuid::now_v7()).await;
+
+        // 検証
+        assert!(matches!(
+            result,
+            Err(AppError::ResourceNotFound("Running timer"))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_add_time_entry_rejects_inverted_range() {
+        // モックリポジトリの作成（どちらのリポジトリも呼ばれない）
+        let task_repo = MockTaskRepository::new();
+        let entry_repo = MockTimeEntryRepository::new();
+
+        // ユースケースの作成
+        let usecase = TimeTrackingUsecase::new(entry_repo, task_repo);
+
+        // テスト実行
+        let now = Utc::now();
+        let result = usecase
+            .add_time_entry(
+                &caller(),
+                Uuid::now_v7(),
+                now,
+                now - Duration::hours(1),
+                None,
+            )
+            .await;
+
+        // 検証
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+}
@@ -0,0 +1,175 @@
+use crate::error::AppError;
+use crate::models::api_key::ApiKeyScope;
+use crate::models::caller::Caller;
+use crate::models::user_account::UserAccount;
+use crate::models::workspace::WorkspaceRole;
+use crate::repositories::user_repository::MockUserRepository;
+use crate::repositories::workspace_repository::MockWorkspaceRepository;
+use crate::usecase::workspace_usecase::{WorkspaceService, WorkspaceUsecase};
+use mockall::predicate::*;
+use uuid::Uuid;
+
+// テストで呼び出し元になるユーザーと、対象のワークスペース
+const USER_ID: Uuid = Uuid::from_u128(1);
+const WORKSPACE_ID: Uuid = Uuid::from_u128(10);
+
+// 個人ワークスペースで認証された呼び出し元
+fn caller() -> Caller {
+    Caller::new(USER_ID, USER_ID, WorkspaceRole::Admin)
+}
+
+// 対象のワークスペースでのロールを返すようにモックを設定するヘルパー関数
+fn workspace_repo_with(role: Option<WorkspaceRole>) -> MockWorkspaceRepository {
+    let mut workspace_repo = MockWorkspaceRepository::new();
+    workspace_repo
+        .expect_find_role()
+        .with(eq(WORKSPACE_ID), eq(USER_ID))
+        .returning(move |_, _| Ok(role));
+    workspace_repo
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_resolve_caller_defaults_to_personal_workspace() {
+        // モックリポジトリの作成
+        let mut workspace_repo = MockWorkspaceRepository::new();
+        workspace_repo
+            .expect_find_role()
+            .with(eq(USER_ID), eq(USER_ID))
+            .times(1)
+            .returning(|_, _| Ok(Some(WorkspaceRole::Admin)));
+
+        // ユースケースの作成
+        let usecase = WorkspaceUsecase::new(workspace_repo, MockUserRepository::new());
+
+        // テスト実行
+        let caller = usecase.resolve_caller(USER_ID, None).await.unwrap();
+
+        // 検証
+        assert_eq!(caller, Caller::new(USER_ID, USER_ID, WorkspaceRole::Admin));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_caller_for_non_member() {
+        // ユースケースの作成
+        let usecase = WorkspaceUsecase::new(workspace_repo_with(None), MockUserRepository::new());
+
+        // テスト実行
+        let result = usecase.resolve_caller(USER_ID, Some(WORKSPACE_ID)).await;
+
+        // 検証
+        assert!(matches!(
+            result,
+            Err(AppError::ResourceNotFound("Workspace"))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_set_member_role_adds_user_by_email() {
+        // モックリポジトリの作成
+        let mut workspace_repo = workspace_repo_with(Some(WorkspaceRole::Admin));
+        let mut user_repo = MockUserRepository::new();
+        let user = UserAccount::new("bob@example.com".to_string(), "hash".to_string());
+        let user_id = user.id;
+
+        // メールアドレスは正規化して検索する
+        user_repo
+            .expect_find_by_email()
+            .with(eq("bob@example.com".to_string()))
+            .times(1)
+            .returning(move |_| Ok(Some(user.clone())));
+        workspace_repo
+            .expect_upsert_member()
+            .with(eq(WORKSPACE_ID), eq(user_id), eq(WorkspaceRole::Viewer))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        // ユースケースの作成
+        let usecase = WorkspaceUsecase::new(workspace_repo, user_repo);
+
+        // テスト実行
+        let member = usecase
+            .set_member_role(
+                &caller(),
+                WORKSPACE_ID,
+                " Bob@Example.com ".to_string(),
+                WorkspaceRole::Viewer,
+            )
+            .await
+            .unwrap();
+
+        // 検証
+        assert_eq!(member.user_id, user_id);
+        assert_eq!(member.role, WorkspaceRole::Viewer);
+    }
+
+    #[tokio::test]
+    async fn test_member_cannot_manage_members() {
+        // モックリポジトリの作成（メンバーは変更されない）
+        let mut workspace_repo = workspace_repo_with(Some(WorkspaceRole::Member));
+        workspace_repo.expect_upsert_member().never();
+        workspace_repo.expect_remove_member().never();
+
+        // ユースケースの作成
+        let usecase = WorkspaceUsecase::new(workspace_repo, MockUserRepository::new());
+
+        // テスト実行
+        let set_result = usecase
+            .set_member_role(
+                &caller(),
+                WORKSPACE_ID,
+                "bob@example.com".to_string(),
+                WorkspaceRole::Admin,
+            )
+            .await;
+        let remove_result = usecase
+            .remove_member(&caller(), WORKSPACE_ID, Uuid::from_u128(2))
+            .await;
+
+        // 検証
+        assert!(matches!(set_result, Err(AppError::Forbidden)));
+        assert!(matches!(remove_result, Err(AppError::Forbidden)));
+    }
+
+    #[tokio::test]
+    async fn test_admin_cannot_remove_self() {
+        // モックリポジトリの作成
+        let mut workspace_repo = workspace_repo_with(Some(WorkspaceRole::Admin));
+        workspace_repo.expect_remove_member().never();
+
+        // ユースケースの作成
+        let usecase = WorkspaceUsecase::new(workspace_repo, MockUserRepository::new());
+
+        // テスト実行
+        let result = usecase
+            .remove_member(&caller(), WORKSPACE_ID, USER_ID)
+            .await;
+
+        // 検証
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_api_key_is_limited_to_its_workspace() {
+        // モックリポジトリの作成（キーのワークスペース以外ではロールを調べない）
+        let mut workspace_repo = MockWorkspaceRepository::new();
+        workspace_repo.expect_find_role().never();
+        workspace_repo.expect_find_members().never();
+
+        // ユースケースの作成
+        let usecase = WorkspaceUsecase::new(workspace_repo, MockUserRepository::new());
+
+        // テスト実行（個人ワークスペースで発行したキーで、他のワークスペースのメンバーを取得する）
+        let api_key_caller = caller().with_scopes(vec![ApiKeyScope::TasksRead]);
+        let result = usecase.list_members(&api_key_caller, WORKSPACE_ID).await;
+
+        // 検証
+        assert!(matches!(
+            result,
+            Err(AppError::ResourceNotFound("Workspace"))
+        ));
+    }
+}
@@ -0,0 +1,177 @@
+use crate::error::AppError;
+use crate::models::caller::Caller;
+use crate::models::time_entry::{TimeEntry, TimeReportRange, TimeReportRow};
+use crate::policy::task_policy::{authorize, authorize_scope, TaskAction};
+use crate::repositories::task_repository::TaskRepository;
+use crate::repositories::time_entry_repository::TimeEntryRepository;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct TimeTrackingUsecase<E: TimeEntryRepository + Clone, T: TaskRepository + Clone> {
+    time_entry_repository: E,
+    task_repository: T,
+}
+
+impl<E: TimeEntryRepository + Clone, T: TaskRepository + Clone> TimeTrackingUsecase<E, T> {
+    pub fn new(time_entry_repository: E, task_repository: T) -> Self {
+        Self {
+            time_entry_repository,
+            task_repository,
+        }
+    }
+}
+
+impl<E, T> TimeTrackingUsecase<E, T>
+where
+    E: TimeEntryRepository + Send + Sync + Clone,
+    T: TaskRepository + Send + Sync + Clone,
+{
+    // 呼び出し元のワークスペースのタスクでなければ ResourceNotFound、操作できなければ Forbidden
+    async fn ensure_task_access(
+        &self,
+        caller: &Caller,
+        task_id: Uuid,
+        action: TaskAction,
+    ) -> Result<(), AppError> {
+        let task = self
+            .task_repository
+            .find_by_id(caller.workspace_id, task_id)
+            .await?
+            .ok_or(AppError::ResourceNotFound("Task"))?;
+        authorize(caller, action, Some(&task))
+    }
+}
+
+#[async_trait]
+pub trait TimeTrackingService {
+    // タイマーを開始する（計測中のタイマーがある場合は Conflict）
+    async fn start_timer(&self, caller: &Caller, task_id: Uuid) -> Result<TimeEntry, AppError>;
+    // タスクの計測中のタイマーを停止する
+    async fn stop_timer(&self, caller: &Caller, task_id: Uuid) -> Result<TimeEntry, AppError>;
+    // 開始・終了日時を指定して作業時間を記録する
+    async fn add_time_entry(
+        &self,
+        caller: &Caller,
+        task_id: Uuid,
+        started_at: DateTime<Utc>,
+        ended_at: DateTime<Utc>,
+        note: Option<String>,
+    ) -> Result<TimeEntry, AppError>;
+    async fn list_time_entries(
+        &self,
+        caller: &Caller,
+        task_id: Uuid,
+    ) -> Result<Vec<TimeEntry>, AppError>;
+    // 呼び出し元がワークスペースで記録した作業時間を集計する
+    async fn time_report(
+        &self,
+        caller: &Caller,
+        range: TimeReportRange,
+    ) -> Result<Vec<TimeReportRow>, AppError>;
+}
+
+#[async_trait]
+impl<E, T> TimeTrackingService for TimeTrackingUsecase<E, T>
+where
+    E: TimeEntryRepository + Send + Sync + Clone,
+    T: TaskRepository + Send + Sync + Clone,
+{
+    async fn start_timer(&self, caller: &Caller, task_id: Uuid) -> Result<TimeEntry, AppError> {
+        self.ensure_task_access(caller, task_id, TaskAction::TrackTime)
+            .await?;
+        if self
+            .time_entry_repository
+            .find_running(caller.workspace_id, caller.user_id)
+            .await?
+            .is_some()
+        {
+            return Err(AppError::Conflict("A timer is already running".to_string()));
+        }
+
+        let entry = TimeEntry::new(
+            caller.workspace_id,
+            task_id,
+            Some(caller.user_id),
+            Utc::now(),
+            None,
+            None,
+        );
+        match self.time_entry_repository.create(entry).await {
+            Ok(entry) => Ok(entry),
+            // 同時に開始された場合や、他のワークスペースで計測中の場合は一意インデックスで弾かれる
+            Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+                Err(AppError::Conflict("A timer is already running".to_string()))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn stop_timer(&self, caller: &Caller, task_id: Uuid) -> Result<TimeEntry, AppError> {
+        // 計測中のタイマーは呼び出し元のものだけなので、ロールではなくAPIキーのスコープだけを確認する
+        authorize_scope(caller, TaskAction::TrackTime)?;
+        let running = self
+            .time_entry_repository
+            .find_running(caller.workspace_id, caller.user_id)
+            .await?
+            .filter(|entry| entry.task_id == task_id)
+            .ok_or(AppError::ResourceNotFound("Running timer"))?;
+        Ok(self
+            .time_entry_repository
+            .stop(caller.workspace_id, running.id, Utc::now())
+            .await?)
+    }
+
+    async fn add_time_entry(
+        &self,
+        caller: &Caller,
+        task_id: Uuid,
+        started_at: DateTime<Utc>,
+        ended_at: DateTime<Utc>,
+        note: Option<String>,
+    ) -> Result<TimeEntry, AppError> {
+        if ended_at <= started_at {
+            return Err(AppError::BadRequest(
+                "`ended_at` must be later than `started_at`".to_string(),
+            ));
+        }
+        self.ensure_task_access(caller, task_id, TaskAction::TrackTime)
+            .await?;
+
+        let entry = TimeEntry::new(
+            caller.workspace_id,
+            task_id,
+            Some(caller.user_id),
+            started_at,
+            Some(ended_at),
+            note,
+        );
+        Ok(self.time_entry_repository.create(entry).await?)
+    }
+
+    async fn list_time_entries(
+        &self,
+        caller: &Caller,
+        task_id: Uuid,
+    ) -> Result<Vec<TimeEntry>, AppError> {
+        self.ensure_task_access(caller, task_id, TaskAction::Read)
+            .await?;
+        Ok(self
+            .time_entry_repository
+            .find_by_task(caller.workspace_id, task_id)
+            .await?)
+    }
+
+    async fn time_report(
+        &self,
+        caller: &Caller,
+        range: TimeReportRange,
+    ) -> Result<Vec<TimeReportRow>, AppError> {
+        authorize_scope(caller, TaskAction::Read)?;
+        self.time_entry_repository
+            .report(caller.workspace_id, caller.user_id, range)
+            .await
+            .map_err(AppError::from_timezone_query)
+    }
+}
@@ -0,0 +1,197 @@
+use crate::error::AppError;
+use crate::models::caller::Caller;
+use crate::models::user_account::normalize_email;
+use crate::models::workspace::{Workspace, WorkspaceMember, WorkspaceMembership, WorkspaceRole};
+use crate::policy::session_policy::authorize_user_session;
+use crate::policy::workspace_policy::authorize_manage_members;
+use crate::repositories::user_repository::UserRepository;
+use crate::repositories::workspace_repository::WorkspaceRepository;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct WorkspaceUsecase<W: WorkspaceRepository + Clone, U: UserRepository + Clone> {
+    workspace_repository: W,
+    user_repository: U,
+}
+
+impl<W: WorkspaceRepository + Clone, U: UserRepository + Clone> WorkspaceUsecase<W, U> {
+    pub fn new(workspace_repository: W, user_repository: U) -> Self {
+        Self {
+            workspace_repository,
+            user_repository,
+        }
+    }
+}
+
+impl<W, U> WorkspaceUsecase<W, U>
+where
+    W: WorkspaceRepository + Send + Sync + Clone,
+    U: UserRepository + Send + Sync + Clone,
+{
+    // 指定したワークスペースでのロール（メンバーでなければ ResourceNotFound）
+    async fn role_in(&self, user_id: Uuid, workspace_id: Uuid) -> Result<WorkspaceRole, AppError> {
+        self.workspace_repository
+            .find_role(workspace_id, user_id)
+            .await?
+            .ok_or(AppError::ResourceNotFound("Workspace"))
+    }
+
+    // 指定したワークスペースでのロールを持つ呼び出し元にする
+    // APIキーは発行したワークスペースの外には届かず、スコープもそのまま引き継ぐ
+    async fn caller_in(&self, caller: &Caller, workspace_id: Uuid) -> Result<Caller, AppError> {
+        if caller.is_api_key() && caller.workspace_id != workspace_id {
+            return Err(AppError::ResourceNotFound("Workspace"));
+        }
+        let role = self.role_in(caller.user_id, workspace_id).await?;
+        Ok(Caller {
+            workspace_id,
+            role,
+            ..caller.clone()
+        })
+    }
+}
+
+#[async_trait]
+pub trait WorkspaceService {
+    // 認証済みのユーザーの呼び出し元を作る（workspace_id が None の場合は個人ワークスペース）
+    async fn resolve_caller(
+        &self,
+        user_id: Uuid,
+        workspace_id: Option<Uuid>,
+    ) -> Result<Caller, AppError>;
+    // ワークスペースを作成し、呼び出し元を管理者にする
+    async fn create_workspace(&self, caller: &Caller, name: String) -> Result<Workspace, AppError>;
+    // 呼び出し元が所属するワークスペースの一覧
+    async fn list_workspaces(&self, caller: &Caller) -> Result<Vec<WorkspaceMembership>, AppError>;
+    async fn list_members(
+        &self,
+        caller: &Caller,
+        workspace_id: Uuid,
+    ) -> Result<Vec<WorkspaceMember>, AppError>;
+    // メールアドレスで指定したユーザーを追加するか、ロールを変更する（admin のみ）
+    async fn set_member_role(
+        &self,
+        caller: &Caller,
+        workspace_id: Uuid,
+        email: String,
+        role: WorkspaceRole,
+    ) -> Result<WorkspaceMember, AppError>;
+    // メンバーを外す（admin のみ）
+    async fn remove_member(
+        &self,
+        caller: &Caller,
+        workspace_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<(), AppError>;
+}
+
+#[async_trait]
+impl<W, U> WorkspaceService for WorkspaceUsecase<W, U>
+where
+    W: WorkspaceRepository + Send + Sync + Clone,
+    U: UserRepository + Send + Sync + Clone,
+{
+    async fn resolve_caller(
+        &self,
+        user_id: Uuid,
+        workspace_id: Option<Uuid>,
+    ) -> Result<Caller, AppError> {
+        // 個人ワークスペースの id はユーザーIDと同じ
+        let workspace_id = workspace_id.unwrap_or(user_id);
+        let role = self.role_in(user_id, workspace_id).await?;
+        Ok(Caller::new(user_id, workspace_id, role))
+    }
+
+    async fn create_workspace(&self, caller: &Caller, name: String) -> Result<Workspace, AppError> {
+        authorize_user_session(caller)?;
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(AppError::BadRequest("name must not be empty".to_string()));
+        }
+        Ok(self
+            .workspace_repository
+            .create(Workspace::new(name), caller.user_id)
+            .await?)
+    }
+
+    async fn list_workspaces(&self, caller: &Caller) -> Result<Vec<WorkspaceMembership>, AppError> {
+        let memberships = self
+            .workspace_repository
+            .find_memberships(caller.user_id)
+            .await?;
+        // APIキーからは発行したワークスペースだけが見える
+        Ok(memberships
+            .into_iter()
+            .filter(|membership| {
+                !caller.is_api_key() || membership.workspace_id == caller.workspace_id
+            })
+            .collect())
+    }
+
+    async fn list_members(
+        &self,
+        caller: &Caller,
+        workspace_id: Uuid,
+    ) -> Result<Vec<WorkspaceMember>, AppError> {
+        // メンバーであれば誰でも一覧を見られる
+        self.caller_in(caller, workspace_id).await?;
+        Ok(self.workspace_repository.find_members(workspace_id).await?)
+    }
+
+    async fn set_member_role(
+        &self,
+        caller: &Caller,
+        workspace_id: Uuid,
+        email: String,
+        role: WorkspaceRole,
+    ) -> Result<WorkspaceMember, AppError> {
+        let admin = self.caller_in(caller, workspace_id).await?;
+        authorize_manage_members(&admin)?;
+
+        let email = normalize_email(&email).map_err(AppError::BadRequest)?;
+        let user = self
+            .user_repository
+            .find_by_email(email)
+            .await?
+            .ok_or(AppError::ResourceNotFound("User"))?;
+        // 管理者がいなくなるのを防ぐため、自分のロールは変更できない
+        if user.id == admin.user_id {
+            return Err(AppError::BadRequest(
+                "You cannot change your own role".to_string(),
+            ));
+        }
+        self.workspace_repository
+            .upsert_member(workspace_id, user.id, role)
+            .await?;
+        Ok(WorkspaceMember {
+            user_id: user.id,
+            email: user.email,
+            role,
+        })
+    }
+
+    async fn remove_member(
+        &self,
+        caller: &Caller,
+        workspace_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<(), AppError> {
+        let admin = self.caller_in(caller, workspace_id).await?;
+        authorize_manage_members(&admin)?;
+        if user_id == admin.user_id {
+            return Err(AppError::BadRequest(
+                "You cannot remove yourself".to_string(),
+            ));
+        }
+        match self
+            .workspace_repository
+            .remove_member(workspace_id, user_id)
+            .await
+        {
+            Ok(()) => Ok(()),
+            Err(sqlx::Error::RowNotFound) => Err(AppError::ResourceNotFound("Member")),
+            Err(e) => Err(e.into()),
+        }
+    }
+}